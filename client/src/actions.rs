@@ -0,0 +1,345 @@
+//! Solana Actions (Blink) transaction construction.
+//!
+//! An Actions endpoint answers a POST with an unsigned, base64-serialized
+//! transaction that the user's wallet signs and sends. This module builds the
+//! atomic Solana → EVM send path — `send_greeting` followed by
+//! `request_relay` in the same transaction — so a Blink can publish and pay
+//! for delivery of a greeting with a single signature.
+//!
+//! The endpoint is expected to read the emitter's Wormhole sequence tracker
+//! and a recent blockhash right before building. If another greeting lands
+//! first, the message PDA no longer matches and the transaction fails
+//! without side effects; the wallet can simply fetch a fresh one.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hello_executor::{
+    accounts, instruction, pda,
+    relay_instructions::{GasDropOffInstruction, GasInstruction},
+    RequestRelayArgs,
+};
+use solana_program::{
+    hash::Hash, instruction::Instruction, keccak, message::Message, pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+/// Everything needed to build a greeting + relay transaction.
+#[derive(Clone, Debug)]
+pub struct GreetingRelayParams {
+    /// Wormhole Core Bridge program ID.
+    pub wormhole_program: Pubkey,
+    /// Executor program ID.
+    pub executor_program: Pubkey,
+    /// Wormhole chain ID of this deployment (`config.chain_id`).
+    pub chain_id: u16,
+    /// PDA salt of this deployment (`config.salt`).
+    pub salt: [u8; 8],
+    /// Wallet that signs and pays. Supplied by the Actions request's `account`.
+    pub payer: Pubkey,
+    /// Greeting to send.
+    pub greeting: String,
+    /// Consistency level for this greeting; `None` uses `config.finality`.
+    pub finality: Option<u8>,
+    /// Wormhole chain ID of the destination.
+    pub dst_chain: u16,
+    /// Current value of the emitter's sequence tracker, i.e. the sequence
+    /// this greeting will be assigned.
+    pub vaa_sequence: u64,
+    /// Amount to pay the Executor (lamports), from the quote.
+    pub exec_amount: u64,
+    /// Executor payee, from the signed quote.
+    pub payee: Pubkey,
+    /// Signed quote bytes from the Executor API.
+    pub signed_quote_bytes: Vec<u8>,
+    /// Gas limit and msgValue for the destination.
+    pub gas: GasInstruction,
+    /// Native tokens to deliver to a destination account.
+    pub gas_drop_off: Option<GasDropOffInstruction>,
+    /// Current send quota epoch (`config.quota_epoch(now)`) while a send
+    /// quota is set; `None` omits the payer's quota account.
+    pub quota_epoch: Option<u64>,
+    /// Whether a rate limit is set (`config.rate_limit_capacity` non-zero);
+    /// `false` omits the payer's rate limit account.
+    pub rate_limited: bool,
+}
+
+impl GreetingRelayParams {
+    fn wormhole_sequence(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"Sequence", pda::emitter().0.as_ref()],
+            &self.wormhole_program,
+        )
+        .0
+    }
+
+    /// Executor request ID: `keccak256(request_bytes)`.
+    pub fn request_id(&self) -> [u8; 32] {
+        let mut request_bytes = Vec::with_capacity(4 + 2 + 32 + 8);
+        request_bytes.extend_from_slice(b"ERV1");
+        request_bytes.extend_from_slice(&self.chain_id.to_be_bytes());
+        request_bytes.extend_from_slice(pda::emitter().0.as_ref());
+        request_bytes.extend_from_slice(&self.vaa_sequence.to_be_bytes());
+        keccak::hash(&request_bytes).to_bytes()
+    }
+
+    /// Relay instructions `request_relay` will encode from `gas` and
+    /// `gas_drop_off`. Request the quote with these.
+    pub fn relay_instructions(&self) -> Vec<u8> {
+        let mut out = self.gas.encode();
+        if let Some(drop_off) = &self.gas_drop_off {
+            out.extend(drop_off.encode());
+        }
+        out
+    }
+
+    /// Trace ID `send_greeting` will embed in this greeting, for correlating
+    /// it across chains before the transaction lands.
+    pub fn trace_id(&self) -> [u8; 16] {
+        hello_executor::trace_id(self.chain_id, &pda::emitter().0.to_bytes(), self.vaa_sequence)
+    }
+}
+
+/// Build the `send_greeting` instruction.
+pub fn send_greeting_instruction(params: &GreetingRelayParams) -> Instruction {
+    // The message PDA is keyed by `vaa_sequence + 1` (see send_greeting).
+    let (wormhole_message, _) = pda::sent(params.vaa_sequence + 1);
+
+    Instruction {
+        program_id: hello_executor::ID,
+        accounts: accounts::SendGreeting {
+            payer: params.payer,
+            config: pda::config().0,
+            wormhole_program: params.wormhole_program,
+            wormhole_bridge: Pubkey::find_program_address(&[b"Bridge"], &params.wormhole_program)
+                .0,
+            wormhole_fee_collector: Pubkey::find_program_address(
+                &[b"fee_collector"],
+                &params.wormhole_program,
+            )
+            .0,
+            wormhole_emitter: pda::emitter().0,
+            wormhole_sequence: params.wormhole_sequence(),
+            wormhole_message,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            sent: pda::sent_record(params.vaa_sequence).0,
+            treasury: pda::treasury().0,
+            spend_cap: pda::spend_cap().0,
+            cpi_guard: pda::cpi_guard().0,
+            instructions: sysvar::instructions::ID,
+            send_quota: params
+                .quota_epoch
+                .map(|epoch| pda::send_quota(&params.payer, epoch).0),
+            quota_bypass: None,
+            rate_limit: params
+                .rate_limited
+                .then(|| pda::rate_limit(&params.payer).0),
+            peer: Some(pda::peer(&params.salt, params.dst_chain).0),
+        }
+        .to_account_metas(None),
+        data: instruction::SendGreeting {
+            greeting: params.greeting.clone(),
+            finality: params.finality,
+        }
+        .data(),
+    }
+}
+
+/// Build the `request_relay` instruction for the greeting sent above.
+pub fn request_relay_instruction(params: &GreetingRelayParams) -> Instruction {
+    Instruction {
+        program_id: hello_executor::ID,
+        accounts: accounts::RequestRelay {
+            payer: params.payer,
+            payee: params.payee,
+            config: pda::config().0,
+            peer: pda::peer(&params.salt, params.dst_chain).0,
+            wormhole_emitter: pda::emitter().0,
+            wormhole_sequence: params.wormhole_sequence(),
+            relay_request: pda::relay_request(params.vaa_sequence, params.dst_chain).0,
+            relay_request_index: pda::relay_request_index(&params.request_id(), params.dst_chain).0,
+            executor_program: params.executor_program,
+            system_program: system_program::ID,
+            spend_cap: pda::spend_cap().0,
+            wormhole_message: Some(pda::sent(params.vaa_sequence + 1).0),
+            sent: Some(pda::sent_record(params.vaa_sequence).0),
+            instructions: None,
+        }
+        .to_account_metas(None),
+        data: instruction::RequestRelay {
+            args: RequestRelayArgs {
+                dst_chain: params.dst_chain,
+                exec_amount: params.exec_amount,
+                signed_quote_bytes: params.signed_quote_bytes.clone(),
+                gas: params.gas,
+                // Pin the sequence: within this transaction the tracker has
+                // already advanced past the greeting.
+                sequence: Some(params.vaa_sequence),
+                gas_drop_off: params.gas_drop_off,
+                refund_addr: None,
+            },
+        }
+        .data(),
+    }
+}
+
+/// Build the unsigned `send_greeting` + `request_relay` transaction and
+/// serialize it as base64, as returned in an Actions POST response.
+pub fn greeting_relay_transaction(params: &GreetingRelayParams, recent_blockhash: Hash) -> String {
+    let instructions = [
+        send_greeting_instruction(params),
+        request_relay_instruction(params),
+    ];
+    let message =
+        Message::new_with_blockhash(&instructions, Some(&params.payer), &recent_blockhash);
+    STANDARD.encode(serialize_unsigned(&message))
+}
+
+/// Wire format of a transaction with all signature slots zeroed:
+/// `compact-u16 signature count | 64-byte signatures | message`.
+fn serialize_unsigned(message: &Message) -> Vec<u8> {
+    let num_signatures = usize::from(message.header.num_required_signatures);
+    let mut out = Vec::new();
+
+    // compact-u16 length prefix
+    let mut len = num_signatures;
+    loop {
+        let mut byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            break;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+
+    out.resize(out.len() + 64 * num_signatures, 0);
+    out.extend_from_slice(&message.serialize());
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params() -> GreetingRelayParams {
+        GreetingRelayParams {
+            wormhole_program: Pubkey::new_unique(),
+            executor_program: Pubkey::new_unique(),
+            chain_id: 1,
+            salt: [0; 8],
+            payer: Pubkey::new_unique(),
+            greeting: "gm from a Blink".to_string(),
+            finality: None,
+            dst_chain: 10002,
+            vaa_sequence: 7,
+            exec_amount: 1_000,
+            payee: Pubkey::new_unique(),
+            signed_quote_bytes: vec![1, 2, 3],
+            gas: GasInstruction {
+                gas_limit: 200_000,
+                msg_value: 0,
+            },
+            gas_drop_off: None,
+            quota_epoch: None,
+            rate_limited: false,
+        }
+    }
+
+    #[test]
+    fn test_payer_is_first_signer() {
+        let params = params();
+        for ix in [send_greeting_instruction(&params), request_relay_instruction(&params)] {
+            assert_eq!(ix.accounts[0].pubkey, params.payer);
+            assert!(ix.accounts[0].is_signer);
+            assert!(ix.accounts.iter().skip(1).all(|meta| !meta.is_signer));
+        }
+    }
+
+    #[test]
+    fn test_trace_id_is_request_id_prefix() {
+        let params = params();
+        assert_eq!(params.trace_id()[..], params.request_id()[..16]);
+    }
+
+    #[test]
+    fn test_relay_instructions() {
+        let mut params = params();
+        assert_eq!(params.relay_instructions().len(), 33);
+
+        params.gas_drop_off = Some(GasDropOffInstruction {
+            amount: 1_000,
+            recipient: [7; 32],
+        });
+        let encoded = params.relay_instructions();
+        assert_eq!(encoded.len(), 33 + 49);
+        assert_eq!(encoded[33], 2);
+    }
+
+    /// `idls/hello_executor.json` is written by hand for CPI callers; keep
+    /// it in step with the program.
+    #[test]
+    fn test_idl_matches_program() {
+        use anchor_lang::Discriminator;
+
+        let idl: serde_json::Value =
+            serde_json::from_str(include_str!("../../idls/hello_executor.json")).unwrap();
+        assert_eq!(idl["address"], hello_executor::ID.to_string());
+
+        let params = params();
+        let cases = [
+            (
+                "send_greeting",
+                instruction::SendGreeting::DISCRIMINATOR,
+                send_greeting_instruction(&params),
+            ),
+            (
+                "request_relay",
+                instruction::RequestRelay::DISCRIMINATOR,
+                request_relay_instruction(&params),
+            ),
+        ];
+        for (name, discriminator, ix) in cases {
+            let entry = idl["instructions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|entry| entry["name"] == name)
+                .unwrap();
+            let listed: Vec<u8> = serde_json::from_value(entry["discriminator"].clone()).unwrap();
+            assert_eq!(listed, discriminator, "{name}");
+
+            let accounts = entry["accounts"].as_array().unwrap();
+            assert_eq!(accounts.len(), ix.accounts.len(), "{name}");
+            for (account, meta) in accounts.iter().zip(&ix.accounts) {
+                if account["optional"] == true && meta.pubkey == hello_executor::ID {
+                    // Omitted optional account.
+                    continue;
+                }
+                assert_eq!(account["writable"] == true, meta.is_writable, "{name}: {account}");
+                assert_eq!(account["signer"] == true, meta.is_signer, "{name}: {account}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsigned_transaction_layout() {
+        let params = params();
+        let encoded = greeting_relay_transaction(&params, Hash::new_unique());
+        let bytes = STANDARD.decode(encoded).unwrap();
+
+        // One signer (the payer), with an empty signature slot.
+        assert_eq!(bytes[0], 1);
+        assert!(bytes[1..65].iter().all(|&b| b == 0));
+
+        let message = Message::new_with_blockhash(
+            &[send_greeting_instruction(&params), request_relay_instruction(&params)],
+            Some(&params.payer),
+            &Hash::default(),
+        );
+        assert_eq!(message.header.num_required_signatures, 1);
+        assert_eq!(message.account_keys[0], params.payer);
+    }
+}
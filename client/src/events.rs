@@ -0,0 +1,261 @@
+//! Decoder for every Hello Executor event.
+//!
+//! Events reach clients two ways: as `Program data: <base64>` log lines
+//! (`emit!`) and as the data of a self-CPI instruction (`emit_cpi!`). Both
+//! carry the event's 8-byte discriminator followed by its Borsh encoding;
+//! the CPI form is additionally prefixed with Anchor's event instruction tag.
+//!
+//! [HelloExecutorEvent] lists every event the program emits, so adding one
+//! to the program and not here is caught by `test_all_events_listed`.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hello_executor::{
+    AckSent, AdminActionCancelled, AdminActionExecuted, AdminActionQueued, BackfillRequested,
+    CctpRelayRequested, DeliveryAcknowledged, DeliveryReported, DuplicateDeliveryIgnored,
+    GovernanceActionApplied, GreetingDuplicate, GreetingExpired, GreetingFailed, GreetingReceived,
+    GreetingSent, HistoryCheckpointPublished, ModerationAttested, NttRelayRequested,
+    OwnershipTransferred, PayloadPublished, PayloadReceived, PeerHandshakeReceived,
+    PeerHandshakeSent, PeerVersionReported, PendingPeerConfirmed, PendingPeerRejected,
+    ProtocolFeeCollected, QueryResponseSubmitted, ReceivedPruned, RegisterAllApplied,
+    RelayRequested, RelayRetried, ReplySent, SequenceGapDetected, SlaBreached, SpendCapWarning,
+    StatsPublished, TokenGreetingReceived, TranslationRequested, TreasuryWithdrawn,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Prefix of `emit_cpi!` instruction data (Anchor's `EVENT_IX_TAG`, LE).
+pub const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9a_cb51_2ea5_45e4u64.to_le_bytes();
+
+/// Log line prefix of `emit!` events.
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+macro_rules! events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the Hello Executor program.
+        ///
+        /// Serializes as `{"name": "<Event>", "data": {...}}`.
+        #[derive(Serialize, Deserialize)]
+        #[serde(tag = "name", content = "data")]
+        pub enum HelloExecutorEvent {
+            $(
+                #[allow(missing_docs)]
+                $name($name),
+            )*
+        }
+
+        impl HelloExecutorEvent {
+            /// Names of all events, in declaration order.
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($name)),*];
+
+            /// Decode `discriminator | borsh` event data. `Ok(None)` if the
+            /// discriminator is not a Hello Executor event.
+            pub fn decode(data: &[u8]) -> io::Result<Option<Self>> {
+                if data.len() < 8 {
+                    return Ok(None);
+                }
+                let (discriminator, mut body) = data.split_at(8);
+                $(
+                    if discriminator == $name::DISCRIMINATOR {
+                        return $name::deserialize(&mut body).map(|event| Some(Self::$name(event)));
+                    }
+                )*
+                Ok(None)
+            }
+
+            /// Name of the event.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                }
+            }
+
+            #[cfg(test)]
+            fn discriminators() -> Vec<[u8; 8]> {
+                vec![$($name::DISCRIMINATOR),*]
+            }
+        }
+    };
+}
+
+events!(
+    AckSent,
+    AdminActionCancelled,
+    AdminActionExecuted,
+    AdminActionQueued,
+    BackfillRequested,
+    CctpRelayRequested,
+    DeliveryAcknowledged,
+    DeliveryReported,
+    DuplicateDeliveryIgnored,
+    GovernanceActionApplied,
+    GreetingDuplicate,
+    GreetingExpired,
+    GreetingFailed,
+    GreetingReceived,
+    GreetingSent,
+    HistoryCheckpointPublished,
+    ModerationAttested,
+    NttRelayRequested,
+    OwnershipTransferred,
+    PayloadPublished,
+    PayloadReceived,
+    PeerHandshakeReceived,
+    PeerHandshakeSent,
+    PeerVersionReported,
+    PendingPeerConfirmed,
+    PendingPeerRejected,
+    ProtocolFeeCollected,
+    QueryResponseSubmitted,
+    ReceivedPruned,
+    RegisterAllApplied,
+    RelayRequested,
+    RelayRetried,
+    ReplySent,
+    SequenceGapDetected,
+    SlaBreached,
+    SpendCapWarning,
+    StatsPublished,
+    TokenGreetingReceived,
+    TranslationRequested,
+    TreasuryWithdrawn,
+);
+
+impl HelloExecutorEvent {
+    /// Decode a `Program data: <base64>` log line. `Ok(None)` for other
+    /// lines and other programs' events.
+    pub fn from_log(line: &str) -> io::Result<Option<Self>> {
+        let Some(encoded) = line.strip_prefix(PROGRAM_DATA_PREFIX) else {
+            return Ok(None);
+        };
+        let data = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Self::decode(&data)
+    }
+
+    /// Decode the instruction data of an `emit_cpi!` self-CPI. `Ok(None)`
+    /// if it is not an event instruction.
+    pub fn from_cpi_data(data: &[u8]) -> io::Result<Option<Self>> {
+        match data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+            Some(event) => Self::decode(event),
+            None => Ok(None),
+        }
+    }
+
+    /// Decode every event in a transaction's log messages.
+    ///
+    /// Log lines are not attributed to programs here; pass only the lines
+    /// logged by Hello Executor when other programs in the transaction may
+    /// emit events with colliding discriminators.
+    pub fn from_logs<'a>(logs: impl IntoIterator<Item = &'a str>) -> io::Result<Vec<Self>> {
+        logs.into_iter()
+            .filter_map(|line| Self::from_log(line).transpose())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::Event;
+
+    fn greeting_sent() -> GreetingSent {
+        GreetingSent {
+            greeting: "gm".to_string(),
+            sequence: 7,
+            timestamp: 1_700_000_000,
+            trace_id: [3; 16],
+            greeting_hash: [4; 32],
+            greeting_len: 2,
+        }
+    }
+
+    #[test]
+    fn test_all_events_listed() {
+        let mut discriminators = HelloExecutorEvent::discriminators();
+        assert_eq!(discriminators.len(), HelloExecutorEvent::NAMES.len());
+        discriminators.sort();
+        discriminators.dedup();
+        assert_eq!(discriminators.len(), HelloExecutorEvent::NAMES.len());
+
+        // Every `#[event]` in the program source must be listed.
+        let sources = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../programs/hello-executor/src"
+        );
+        let mut emitted = Vec::new();
+        let mut dirs = vec![std::path::PathBuf::from(sources)];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                let mut lines = source.lines();
+                while let Some(line) = lines.next() {
+                    if line.trim() != "#[event]" {
+                        continue;
+                    }
+                    let name = lines
+                        .by_ref()
+                        .find_map(|line| line.strip_prefix("pub struct "))
+                        .unwrap();
+                    emitted.push(name.trim_end_matches(" {").to_string());
+                }
+            }
+        }
+        for name in &emitted {
+            assert!(
+                HelloExecutorEvent::NAMES.contains(&name.as_str()),
+                "{name} not listed"
+            );
+        }
+        assert_eq!(emitted.len(), HelloExecutorEvent::NAMES.len());
+    }
+
+    #[test]
+    fn test_from_log() {
+        let line = format!("Program data: {}", STANDARD.encode(greeting_sent().data()));
+        let logs = ["Program log: Instruction: SendGreeting", line.as_str()];
+
+        let events = HelloExecutorEvent::from_logs(logs).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            HelloExecutorEvent::GreetingSent(event) if event.sequence == 7 && event.greeting == "gm"
+        ));
+
+        // Unknown discriminators are skipped, malformed bodies are errors.
+        let unknown = format!("Program data: {}", STANDARD.encode([0u8; 16]));
+        assert!(HelloExecutorEvent::from_log(&unknown).unwrap().is_none());
+        let truncated = &greeting_sent().data()[..10];
+        let truncated = format!("Program data: {}", STANDARD.encode(truncated));
+        assert!(HelloExecutorEvent::from_log(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_from_cpi_data() {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(greeting_sent().data());
+        let event = HelloExecutorEvent::from_cpi_data(&data).unwrap().unwrap();
+        assert_eq!(event.name(), "GreetingSent");
+
+        assert!(HelloExecutorEvent::from_cpi_data(&greeting_sent().data())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_serde() {
+        let event = HelloExecutorEvent::GreetingSent(greeting_sent());
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["name"], "GreetingSent");
+        assert_eq!(json["data"]["sequence"], 7);
+
+        let decoded: HelloExecutorEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.name(), "GreetingSent");
+    }
+}
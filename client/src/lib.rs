@@ -0,0 +1,8 @@
+//! Off-chain helpers for building Hello Executor transactions.
+//!
+//! Instruction account lists come from the Anchor-generated
+//! `hello_executor::accounts` structs, so ordering always matches the
+//! program's `#[derive(Accounts)]` definitions.
+
+pub mod actions;
+pub mod events;
@@ -0,0 +1,92 @@
+//! Checked readers for raw Wormhole Core Bridge accounts.
+//!
+//! The Core Bridge accounts Hello Executor reads (bridge data, sequence
+//! trackers, posted messages) have no Anchor discriminator and no published
+//! Rust types at the versions this workspace pins. Their byte layouts are
+//! described once here, per layout version, and read with bounds checks
+//! instead of slicing at call sites.
+//!
+//! The crate root re-exports the layout version currently deployed. A Core
+//! Bridge upgrade adds a module next to [v1] and moves the re-export.
+
+use std::fmt;
+
+pub mod v1;
+
+pub use v1::*;
+
+/// An account's data does not match the expected layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    /// Data is shorter than the layout.
+    TooShort {
+        /// Account kind being read.
+        account: &'static str,
+        /// Minimum length of the layout.
+        expected: usize,
+        /// Length of the data.
+        actual: usize,
+    },
+    /// Data does not start with the layout's prefix.
+    WrongPrefix {
+        /// Account kind being read.
+        account: &'static str,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::TooShort {
+                account,
+                expected,
+                actual,
+            } => write!(f, "{account} is {actual} bytes, expected at least {expected}"),
+            LayoutError::WrongPrefix { account } => write!(f, "{account} has the wrong prefix"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Little-endian field reader over account data.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Start reading `data` as `account`, which must hold `len` bytes.
+    fn new(account: &'static str, data: &'a [u8], len: usize) -> Result<Self, LayoutError> {
+        if data.len() < len {
+            return Err(LayoutError::TooShort {
+                account,
+                expected: len,
+                actual: data.len(),
+            });
+        }
+        Ok(Self { data, offset: 0 })
+    }
+
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        let out = self.data[self.offset..self.offset + N].try_into().unwrap();
+        self.offset += N;
+        out
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.bytes::<1>()[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.bytes())
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.bytes())
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.bytes())
+    }
+}
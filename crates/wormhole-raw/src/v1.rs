@@ -0,0 +1,189 @@
+//! Core Bridge account layouts, version 1 (all integers little-endian).
+
+use crate::{LayoutError, Reader};
+
+/// Core Bridge configuration (`["Bridge"]` PDA).
+///
+/// `guardian_set_index(4) | last_lamports(8) | guardian_set_expiration_time(4) |
+///  fee(8)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BridgeData {
+    /// Index of the current guardian set.
+    pub guardian_set_index: u32,
+    /// Fee collector balance at the last fee transfer.
+    pub last_lamports: u64,
+    /// Seconds an expired guardian set stays valid.
+    pub guardian_set_expiration_time: u32,
+    /// Lamports charged per posted message.
+    pub fee: u64,
+}
+
+impl BridgeData {
+    /// Length of the layout.
+    pub const LEN: usize = 4 + 8 + 4 + 8;
+
+    /// Read bridge data from account data.
+    pub fn read(data: &[u8]) -> Result<Self, LayoutError> {
+        let mut reader = Reader::new("BridgeData", data, Self::LEN)?;
+        Ok(Self {
+            guardian_set_index: reader.u32(),
+            last_lamports: reader.u64(),
+            guardian_set_expiration_time: reader.u32(),
+            fee: reader.u64(),
+        })
+    }
+}
+
+/// Per-emitter sequence tracker (`["Sequence", emitter]` PDA).
+///
+/// `sequence(8)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceTracker {
+    /// Sequence the next posted message will get, i.e. the number of
+    /// messages the emitter has posted.
+    pub sequence: u64,
+}
+
+impl SequenceTracker {
+    /// Length of the layout.
+    pub const LEN: usize = 8;
+
+    /// Read a sequence tracker from account data.
+    pub fn read(data: &[u8]) -> Result<Self, LayoutError> {
+        let mut reader = Reader::new("SequenceTracker", data, Self::LEN)?;
+        Ok(Self {
+            sequence: reader.u64(),
+        })
+    }
+}
+
+/// Header of a posted (not yet signed) message account.
+///
+/// `"msg"(3) | vaa_version(1) | consistency_level(1) | vaa_time(4) |
+///  vaa_signature_account(32) | submission_time(4) | nonce(4) | sequence(8) |
+///  emitter_chain(2) | emitter_address(32) | payload_len(4) | payload`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PostedMessageHeader {
+    /// VAA version.
+    pub vaa_version: u8,
+    /// Consistency level the message was posted with.
+    pub consistency_level: u8,
+    /// Time the VAA was signed, zero until then.
+    pub vaa_time: u32,
+    /// Signature set account of the VAA, zero until signed.
+    pub vaa_signature_account: [u8; 32],
+    /// Time the message was posted.
+    pub submission_time: u32,
+    /// Batch ID (nonce).
+    pub nonce: u32,
+    /// Sequence of the message.
+    pub sequence: u64,
+    /// Wormhole chain ID of the emitter.
+    pub emitter_chain: u16,
+    /// Emitter address.
+    pub emitter_address: [u8; 32],
+    /// Length of the payload following the header.
+    pub payload_len: u32,
+}
+
+impl PostedMessageHeader {
+    /// Account data prefix.
+    pub const PREFIX: &'static [u8; 3] = b"msg";
+
+    /// Length of the layout, excluding the payload.
+    pub const LEN: usize = 3 + 1 + 1 + 4 + 32 + 4 + 4 + 8 + 2 + 32 + 4;
+
+    /// Read a posted message header from account data.
+    pub fn read(data: &[u8]) -> Result<Self, LayoutError> {
+        let mut reader = Reader::new("PostedMessage", data, Self::LEN)?;
+        if reader.bytes::<3>() != *Self::PREFIX {
+            return Err(LayoutError::WrongPrefix {
+                account: "PostedMessage",
+            });
+        }
+        Ok(Self {
+            vaa_version: reader.u8(),
+            consistency_level: reader.u8(),
+            vaa_time: reader.u32(),
+            vaa_signature_account: reader.bytes(),
+            submission_time: reader.u32(),
+            nonce: reader.u32(),
+            sequence: reader.u64(),
+            emitter_chain: reader.u16(),
+            emitter_address: reader.bytes(),
+            payload_len: reader.u32(),
+        })
+    }
+
+    /// Size of a posted message account carrying `payload_len` bytes.
+    pub fn account_size(payload_len: usize) -> usize {
+        Self::LEN + payload_len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bridge_data() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&86_400u32.to_le_bytes());
+        data.extend_from_slice(&100u64.to_le_bytes());
+
+        let bridge = BridgeData::read(&data).unwrap();
+        assert_eq!(bridge.guardian_set_index, 4);
+        assert_eq!(bridge.guardian_set_expiration_time, 86_400);
+        assert_eq!(bridge.fee, 100);
+
+        assert_eq!(
+            BridgeData::read(&data[..23]),
+            Err(LayoutError::TooShort {
+                account: "BridgeData",
+                expected: 24,
+                actual: 23,
+            })
+        );
+    }
+
+    #[test]
+    fn test_sequence_tracker() {
+        assert_eq!(SequenceTracker::read(&7u64.to_le_bytes()).unwrap().sequence, 7);
+        assert!(SequenceTracker::read(&[]).is_err());
+    }
+
+    #[test]
+    fn test_posted_message_header() {
+        let mut data = Vec::new();
+        data.extend_from_slice(PostedMessageHeader::PREFIX);
+        data.extend_from_slice(&[0, 1]);
+        data.extend_from_slice(&[0; 4 + 32]);
+        data.extend_from_slice(&1_700_000_000u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&[7; 32]);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        assert_eq!(data.len(), PostedMessageHeader::LEN);
+        data.extend_from_slice(b"gm");
+
+        let header = PostedMessageHeader::read(&data).unwrap();
+        assert_eq!(header.consistency_level, 1);
+        assert_eq!(header.submission_time, 1_700_000_000);
+        assert_eq!(header.sequence, 42);
+        assert_eq!(header.emitter_chain, 1);
+        assert_eq!(header.emitter_address, [7; 32]);
+        assert_eq!(header.payload_len, 2);
+        assert_eq!(PostedMessageHeader::account_size(2), data.len());
+
+        data[0] = b'v';
+        assert_eq!(
+            PostedMessageHeader::read(&data),
+            Err(LayoutError::WrongPrefix {
+                account: "PostedMessage",
+            })
+        );
+    }
+}
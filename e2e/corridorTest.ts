@@ -0,0 +1,153 @@
+#!/usr/bin/env tsx
+/**
+ * Corridor smoke test: send a marker greeting from Solana Devnet to
+ * `--dst`, relay it through the Executor and wait for delivery, reporting
+ * pass/fail with the time each stage took. Meant for scheduled health
+ * checks of a corridor.
+ *
+ * Stages:
+ *   1. send    — send_greeting + quote + request_relay confirmed
+ *   2. vaa     — VAA signed (Wormholescan)
+ *   3. deliver — Executor reports the relay submitted on the destination
+ *
+ * Usage:
+ *   npx tsx e2e/corridorTest.ts --dst <chain> [--output table|json]
+ *
+ * <chain> is a Wormhole chain ID or `sepolia`. Exits 0 when the greeting is
+ * delivered, 1 when any stage fails or times out and 2 on invalid
+ * arguments.
+ */
+
+import { randomBytes } from 'crypto';
+
+import { CHAIN_ID_SEPOLIA, CHAIN_ID_SOLANA } from './config.js';
+import { pollExecutorStatus, pollForVAA, sendAndRelay } from './sendToSepolia.js';
+
+const EXIT_PASS = 0;
+const EXIT_FAIL = 1;
+const EXIT_USAGE = 2;
+
+const CHAINS_BY_NAME: Record<string, number> = { sepolia: CHAIN_ID_SEPOLIA };
+
+interface Stage {
+    name: 'send' | 'vaa' | 'deliver';
+    ok: boolean;
+    ms: number;
+    detail?: string;
+}
+
+function usage(error: string): never {
+    console.error(`error: ${error}`);
+    console.error('usage: corridorTest.ts --dst <chain> [--output table|json]');
+    process.exit(EXIT_USAGE);
+}
+
+function parseArgs(argv: string[]): { dstChain: number; output: 'table' | 'json' } {
+    let dst: string | undefined;
+    let output = 'table';
+    for (let i = 0; i < argv.length; i++) {
+        const arg = argv[i];
+        if (arg === '--dst') dst = argv[++i];
+        else if (arg === '--output' || arg === '-o') output = argv[++i];
+        else usage(`unknown argument ${arg}`);
+    }
+    if (dst === undefined) usage('--dst is required');
+    const dstChain = CHAINS_BY_NAME[dst.toLowerCase()] ?? Number(dst);
+    if (!Number.isInteger(dstChain) || dstChain <= 0 || dstChain > 0xffff) {
+        usage(`unknown chain ${dst}`);
+    }
+    if (output !== 'table' && output !== 'json') usage(`unknown output format ${output}`);
+    return { dstChain, output };
+}
+
+/** Run `fn`, recording how long it took and whether it succeeded. */
+async function timed<T>(
+    stages: Stage[],
+    name: Stage['name'],
+    fn: () => Promise<T>,
+    check: (result: T) => string | undefined
+): Promise<T | undefined> {
+    const start = Date.now();
+    try {
+        const result = await fn();
+        const failure = check(result);
+        stages.push({ name, ok: failure === undefined, ms: Date.now() - start, detail: failure });
+        return failure === undefined ? result : undefined;
+    } catch (error: any) {
+        stages.push({ name, ok: false, ms: Date.now() - start, detail: error.message || `${error}` });
+        return undefined;
+    }
+}
+
+async function main() {
+    const { dstChain, output } = parseArgs(process.argv.slice(2));
+    const marker = `corridor-test ${new Date().toISOString()} ${randomBytes(4).toString('hex')}`;
+    const stages: Stage[] = [];
+
+    const stdout = process.stdout.write.bind(process.stdout);
+    if (output === 'json') {
+        // Progress goes to stderr, leaving stdout to the report.
+        console.log = console.error;
+        process.stdout.write = process.stderr.write.bind(process.stderr) as typeof stdout;
+    }
+
+    const sent = await timed(
+        stages,
+        'send',
+        () => sendAndRelay(marker, null, dstChain),
+        () => undefined
+    );
+    if (sent) {
+        const vaa = await timed(
+            stages,
+            'vaa',
+            () => pollForVAA(CHAIN_ID_SOLANA, sent.emitterHex, Number(sent.vaaSequence)),
+            (data) => (data ? undefined : 'VAA not signed within timeout')
+        );
+        if (vaa) {
+            await timed(
+                stages,
+                'deliver',
+                () => pollExecutorStatus(sent.relaySig),
+                (status) => {
+                    if (status?.status === 'submitted' && status.txs?.length > 0) return undefined;
+                    if (status) return `${status.status}: ${status.failureCause || 'no detail'}`;
+                    return 'delivery not confirmed within timeout';
+                }
+            );
+        }
+    }
+
+    const pass = stages.length === 3 && stages.every((stage) => stage.ok);
+    const totalMs = stages.reduce((sum, stage) => sum + stage.ms, 0);
+
+    if (output === 'json') {
+        const report = {
+            srcChain: CHAIN_ID_SOLANA,
+            dstChain,
+            marker,
+            pass,
+            totalMs,
+            relaySig: sent?.relaySig ?? null,
+            stages,
+        };
+        stdout(JSON.stringify(report, null, 2) + '\n');
+    } else {
+        console.log('\n' + '─'.repeat(60));
+        console.log(`Corridor ${CHAIN_ID_SOLANA} → ${dstChain}: ${pass ? 'PASS' : 'FAIL'}`);
+        console.log(`Marker: "${marker}"`);
+        for (const stage of stages) {
+            const status = stage.ok ? 'ok  ' : 'FAIL';
+            const detail = stage.detail ? `  ${stage.detail}` : '';
+            console.log(`  ${status} ${stage.name.padEnd(8)} ${(stage.ms / 1000).toFixed(1)}s${detail}`);
+        }
+        console.log(`  total ${(totalMs / 1000).toFixed(1)}s`);
+    }
+
+    process.exit(pass ? EXIT_PASS : EXIT_FAIL);
+}
+
+main().catch((error) => {
+    console.error('\n❌ Error:', error.message || error);
+    process.exit(EXIT_FAIL);
+});
@@ -73,15 +73,26 @@ async function main() {
     const [wormholeMessage] = PublicKey.findProgramAddressSync([Buffer.from('sent'), seqBuf], programId);
 
     // ── Step 1: Initialize ──────────────────────────────────────────────────
-    const configExists = await connection.getAccountInfo(configPda);
-    if (configExists) {
+    // An interrupted deployment can leave some of these without the others;
+    // repair_initialize completes it with the same accounts and arguments.
+    const [configExists, emitterExists, sequenceExists] = await Promise.all(
+        [configPda, emitterPda, wormholeSequence].map((pda) => connection.getAccountInfo(pda))
+    );
+    const existing = [configExists, emitterExists].filter(Boolean).length;
+    if (existing === 2) {
         console.log('⚠️  Config already exists — skipping initialize\n');
     } else {
-        console.log('📋 Step 1: Initializing program...');
-        // Args: chain_id (u16 LE)
-        const initArgs = Buffer.alloc(2);
-        initArgs.writeUInt16LE(CHAIN_ID_SOLANA);
-        const initData = Buffer.concat([discriminator('initialize'), initArgs]);
+        const instruction = existing === 0 ? 'initialize' : 'repair_initialize';
+        console.log(`📋 Step 1: Initializing program (${instruction})...`);
+        // Args: chain_id (u16 LE) + finality (Option<u8>: 0x00 = network default)
+        //       + salt (Option<[u8; 8]>: 0x00 = unsalted PDAs)
+        //       + executor_program (Option<Pubkey>: 0x00 = canonical Executor)
+        const initArgs = Buffer.alloc(2 + 1 + 1 + 1);
+        initArgs.writeUInt16LE(CHAIN_ID_SOLANA, 0);
+        initArgs.writeUInt8(0, 2);
+        initArgs.writeUInt8(0, 3);
+        initArgs.writeUInt8(0, 4);
+        const initData = Buffer.concat([discriminator(instruction), initArgs]);
 
         const initIx = new TransactionInstruction({
             programId,
@@ -89,19 +100,44 @@ async function main() {
                 { pubkey: keypair.publicKey,      isSigner: true,  isWritable: true  },
                 { pubkey: configPda,              isSigner: false, isWritable: true  },
                 { pubkey: wormholeProgram,         isSigner: false, isWritable: false },
+                { pubkey: wormholeBridge,          isSigner: false, isWritable: false },
+                { pubkey: wormholeFeeCollector,    isSigner: false, isWritable: false },
+                { pubkey: emitterPda,              isSigner: false, isWritable: true  },
+                { pubkey: wormholeSequence,        isSigner: false, isWritable: false },
+                { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            ],
+            data: initData,
+        });
+
+        const tx = await sendAndConfirmTransaction(connection, new Transaction().add(initIx), [keypair], { commitment: 'confirmed' });
+        console.log(`  ✅ TX: https://explorer.solana.com/tx/${tx}?cluster=devnet\n`);
+    }
+
+    // ── Step 1b: Announce ───────────────────────────────────────────────────
+    // Posts the Alive message (sequence 0), which creates the sequence tracker.
+    if (sequenceExists) {
+        console.log('⚠️  Already announced — skipping announce\n');
+    } else {
+        console.log('📋 Step 1b: Announcing (Alive message)...');
+        const announceIx = new TransactionInstruction({
+            programId,
+            keys: [
+                { pubkey: keypair.publicKey,      isSigner: true,  isWritable: true  },
+                { pubkey: configPda,              isSigner: false, isWritable: false },
+                { pubkey: wormholeProgram,         isSigner: false, isWritable: false },
                 { pubkey: wormholeBridge,          isSigner: false, isWritable: true  },
                 { pubkey: wormholeFeeCollector,    isSigner: false, isWritable: true  },
-                { pubkey: emitterPda,              isSigner: false, isWritable: true  },
+                { pubkey: emitterPda,              isSigner: false, isWritable: false },
                 { pubkey: wormholeSequence,        isSigner: false, isWritable: true  },
                 { pubkey: wormholeMessage,         isSigner: false, isWritable: true  },
+                { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
                 { pubkey: SYSVAR_CLOCK_PUBKEY,     isSigner: false, isWritable: false },
                 { pubkey: SYSVAR_RENT_PUBKEY,      isSigner: false, isWritable: false },
-                { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
             ],
-            data: initData,
+            data: discriminator('announce'),
         });
 
-        const tx = await sendAndConfirmTransaction(connection, new Transaction().add(initIx), [keypair], { commitment: 'confirmed' });
+        const tx = await sendAndConfirmTransaction(connection, new Transaction().add(announceIx), [keypair], { commitment: 'confirmed' });
         console.log(`  ✅ TX: https://explorer.solana.com/tx/${tx}?cluster=devnet\n`);
     }
 
@@ -17,3 +17,18 @@ export function createRelayInstructions(gasLimit: bigint, msgValue: bigint): str
     const msgValueHex = msgValue.toString(16).padStart(32, '0');
     return '0x' + version + gasLimitHex + msgValueHex;
 }
+
+/**
+ * Create a gas drop-off relay instruction, to append to the gas instruction.
+ *
+ * Format: 0x02 + uint128 dropOff (16 bytes) + bytes32 recipient
+ *
+ * Pass the combined instructions to the quote request so the drop-off is priced.
+ * `request_relay` rejects drop-offs above the peer's `max_gas_drop_off`.
+ */
+export function createGasDropOffInstruction(dropOff: bigint, recipientBytes32: string): string {
+    const type = '02';
+    const dropOffHex = dropOff.toString(16).padStart(32, '0');
+    const recipientHex = recipientBytes32.replace(/^0x/, '').padStart(64, '0');
+    return type + dropOffHex + recipientHex;
+}
@@ -7,7 +7,10 @@
  *   2. request_relay   — pay the Executor to relay the VAA to Sepolia
  *
  * Usage:
- *   npx tsx e2e/sendToSepolia.ts "Hello from Solana!"
+ *   npx tsx e2e/sendToSepolia.ts "Hello from Solana!" [finality]
+ *
+ *   finality (optional) overrides the config's consistency level for this
+ *   message only: 0 = Confirmed, 1 = Finalized.
  */
 
 import {
@@ -15,12 +18,15 @@ import {
     PublicKey,
     SystemProgram,
     SYSVAR_CLOCK_PUBKEY,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
     SYSVAR_RENT_PUBKEY,
     Transaction,
     TransactionInstruction,
     sendAndConfirmTransaction,
 } from '@solana/web3.js';
 import { createHash } from 'crypto';
+import { pathToFileURL } from 'url';
+import { ethers } from 'ethers';
 
 import {
     config,
@@ -66,6 +72,31 @@ function deriveWormholeSequence(wormholeProgram: PublicKey, emitter: PublicKey):
     return pda;
 }
 
+function deriveSentRecordPda(programId: PublicKey, sequence: bigint): PublicKey {
+    const sequenceBuffer = Buffer.alloc(8);
+    sequenceBuffer.writeBigUInt64LE(sequence);
+    const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('sent_record'), sequenceBuffer],
+        programId
+    );
+    return pda;
+}
+
+function deriveTreasuryPda(programId: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from('treasury')], programId);
+    return pda;
+}
+
+function deriveSpendCapPda(programId: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from('spend_cap')], programId);
+    return pda;
+}
+
+function deriveCpiGuardPda(programId: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from('cpi_guard')], programId);
+    return pda;
+}
+
 function derivePeerPda(programId: PublicKey, chainId: number): PublicKey {
     const chainBuffer = Buffer.alloc(2);
     chainBuffer.writeUInt16LE(chainId);
@@ -86,6 +117,40 @@ function deriveMessagePda(programId: PublicKey, sequence: bigint): PublicKey {
     return pda;
 }
 
+/**
+ * RelayRequest PDA, keyed by the sequence (u64 LE) and destination chain
+ * (u16 LE), and its RelayRequestIndex PDA, keyed by the request ID and
+ * destination chain (u16 LE). The request ID =
+ * keccak256(request_bytes) where
+ * request_bytes = "ERV1" | u16 chain (BE) | 32-byte emitter | u64 sequence (BE).
+ */
+function deriveRelayRequestPda(
+    programId: PublicKey,
+    emitter: PublicKey,
+    sequence: bigint,
+    dstChain: number
+): { pda: PublicKey; indexPda: PublicKey; requestId: Buffer } {
+    const requestBytes = Buffer.alloc(4 + 2 + 32 + 8);
+    requestBytes.write('ERV1', 0, 'ascii');
+    requestBytes.writeUInt16BE(CHAIN_ID_SOLANA, 4);
+    emitter.toBuffer().copy(requestBytes, 6);
+    requestBytes.writeBigUInt64BE(sequence, 38);
+    const requestId = Buffer.from(ethers.getBytes(ethers.keccak256(requestBytes)));
+    const sequenceSeed = Buffer.alloc(8);
+    sequenceSeed.writeBigUInt64LE(sequence);
+    const dstChainSeed = Buffer.alloc(2);
+    dstChainSeed.writeUInt16LE(dstChain);
+    const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('relay_request'), sequenceSeed, dstChainSeed],
+        programId
+    );
+    const [indexPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('relay_request_index'), requestId, dstChainSeed],
+        programId
+    );
+    return { pda, indexPda, requestId };
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -117,7 +182,7 @@ async function getCurrentSequence(
     return BigInt(accountInfo.data.readBigUInt64LE(0));
 }
 
-async function pollForVAA(
+export async function pollForVAA(
     emitterChain: number,
     emitterAddress: string,
     sequence: number
@@ -155,7 +220,7 @@ async function pollForVAA(
  *   - "underpaid": insufficient payment to the Executor.
  * Non-terminal: "pending" (waiting for VAA), "processing" (in flight).
  */
-async function pollExecutorStatus(txHash: string): Promise<any> {
+export async function pollExecutorStatus(txHash: string): Promise<any> {
     console.log(`\nPolling executor status...`);
 
     for (let i = 0; i < 36; i++) {
@@ -231,17 +296,19 @@ async function getExecutorQuote(
 }
 
 // ============================================================================
-// Main
+// Send + Relay
 // ============================================================================
 
-async function main() {
-    console.log('═'.repeat(60));
-    console.log('  🌊 Solana Devnet → Sepolia');
-    console.log('═'.repeat(60) + '\n');
-
-    const greeting = process.argv[2] || 'Hello from Solana! 🌊';
-    console.log(`Message: "${greeting}"`);
-
+/**
+ * Send `greeting` and pay the Executor to relay it to `dstChain`: steps 1
+ * and 2 of the flow above. Returns the two transaction signatures and where
+ * to find the VAA.
+ */
+export async function sendAndRelay(
+    greeting: string,
+    finality: number | null,
+    dstChain: number = CHAIN_ID_SEPOLIA
+): Promise<{ sendSig: string; relaySig: string; vaaSequence: bigint; emitterHex: string }> {
     // Load keypair
     const keypair = loadSolanaKeypair();
     console.log(`Wallet: ${keypair.publicKey.toBase58()}`);
@@ -261,13 +328,21 @@ async function main() {
     const wormholeBridge = deriveWormholeBridge(wormholeProgram);
     const wormholeFeeCollector = deriveWormholeFeeCollector(wormholeProgram);
     const wormholeSequence = deriveWormholeSequence(wormholeProgram, emitterPda);
-    const peerPda = derivePeerPda(programId, CHAIN_ID_SEPOLIA);
+    const peerPda = derivePeerPda(programId, dstChain);
 
     // vaaSequence = actual Wormhole VAA sequence (= tracker value)
     // pdaSequence = vaaSequence + 1 (to avoid colliding with the init message PDA slot)
     const vaaSequence = await getCurrentSequence(connection, wormholeSequence);
     const pdaSequence = vaaSequence + 1n;
     const wormholeMessage = deriveMessagePda(programId, pdaSequence);
+    // Outbound record of this greeting, keyed by the VAA sequence itself
+    const sentRecord = deriveSentRecordPda(programId, vaaSequence);
+    // Protocol fee vault; charged only once the owner has set a fee
+    const treasury = deriveTreasuryPda(programId);
+    // Outbound spend cap; enforced only once the owner has set one
+    const spendCap = deriveSpendCapPda(programId);
+    // CPI caller allowlist; read only when called through CPI
+    const cpiGuard = deriveCpiGuardPda(programId);
 
     console.log(`\nVAA sequence:  ${vaaSequence}`);
     console.log(`Message PDA slot: ${pdaSequence}`);
@@ -280,12 +355,20 @@ async function main() {
     const greetingBytes = Buffer.from(greeting, 'utf-8');
     const lengthBuffer = Buffer.alloc(4);
     lengthBuffer.writeUInt32LE(greetingBytes.length);
-    const sendData = Buffer.concat([sendDiscriminator, lengthBuffer, greetingBytes]);
+    // Option<u8>: 0 for None, 1 followed by the level for Some
+    const finalityBuffer =
+        finality === null ? Buffer.from([0]) : Buffer.from([1, finality]);
+    const sendData = Buffer.concat([
+        sendDiscriminator,
+        lengthBuffer,
+        greetingBytes,
+        finalityBuffer,
+    ]);
 
     const sendInstruction = new TransactionInstruction({
         keys: [
             { pubkey: keypair.publicKey, isSigner: true, isWritable: true },
-            { pubkey: configPda, isSigner: false, isWritable: false },
+            { pubkey: configPda, isSigner: false, isWritable: true },
             { pubkey: wormholeProgram, isSigner: false, isWritable: false },
             { pubkey: wormholeBridge, isSigner: false, isWritable: true },
             { pubkey: wormholeFeeCollector, isSigner: false, isWritable: true },
@@ -295,6 +378,11 @@ async function main() {
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
             { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
             { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+            { pubkey: sentRecord, isSigner: false, isWritable: true },
+            { pubkey: treasury, isSigner: false, isWritable: true },
+            { pubkey: spendCap, isSigner: false, isWritable: true },
+            { pubkey: cpiGuard, isSigner: false, isWritable: false },
+            { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
         ],
         programId,
         data: sendData,
@@ -317,25 +405,27 @@ async function main() {
     console.log('\n📡 Step 2: Requesting Executor relay...');
 
     const GAS_LIMIT = 200000; // EVM gas for receiveWormholeMessages
-    const quote = await getExecutorQuote(CHAIN_ID_SEPOLIA, GAS_LIMIT);
+    const quote = await getExecutorQuote(dstChain, GAS_LIMIT);
 
     console.log(`  Payee: ${quote.payee.toBase58()}`);
     console.log(
         `  Exec amount: ${quote.execAmountLamports} lamports (${Number(quote.execAmountLamports) / 1e9} SOL)`
     );
 
-    // Encode relay instructions as bytes (same value as used in getExecutorQuote above)
-    const relayInstructionsBytes = Buffer.from(
-        createRelayInstructions(BigInt(GAS_LIMIT), 0n).slice(2), // strip '0x'
-        'hex'
-    );
+    // The program encodes the relay instructions from `gas` (the same value
+    // as used in getExecutorQuote above).
+    const gasInstruction = Buffer.alloc(32);
+    gasInstruction.writeBigUInt64LE(BigInt(GAS_LIMIT), 0); // gas_limit: u128 LE
+    // msg_value: u128 LE = 0
 
     // Encode RequestRelayArgs via Borsh:
     //   dst_chain:             u16 LE
     //   exec_amount:           u64 LE
     //   signed_quote_bytes:    Vec<u8>    (4-byte LE length prefix + bytes)
-    //   relay_instructions:    Vec<u8>    (4-byte LE length prefix + bytes)
+    //   gas:                   GasInstruction (gas_limit u128 LE, msg_value u128 LE)
     //   sequence:              Option<u64> (0x00 = None, 0x01 + u64 LE = Some(n))
+    //   gas_drop_off:          Option<GasDropOffInstruction> (0x00 = None)
+    //   refund_addr:           Option<Pubkey> (0x00 = None, refund the payer)
     //
     // We pass Some(vaaSequence) to relay exactly the message we just sent,
     // rather than relying on "latest message" defaulting logic.
@@ -345,10 +435,10 @@ async function main() {
 
     const requestRelayDiscriminator = getDiscriminator('request_relay');
     const argsBuffer = Buffer.alloc(
-        2 + 8 + 4 + quote.signedQuoteBytes.length + 4 + relayInstructionsBytes.length + sequenceOption.length
+        2 + 8 + 4 + quote.signedQuoteBytes.length + gasInstruction.length + sequenceOption.length + 1 + 1
     );
     let offset = 0;
-    argsBuffer.writeUInt16LE(CHAIN_ID_SEPOLIA, offset);
+    argsBuffer.writeUInt16LE(dstChain, offset);
     offset += 2;
     argsBuffer.writeBigUInt64LE(quote.execAmountLamports, offset);
     offset += 8;
@@ -356,24 +446,36 @@ async function main() {
     offset += 4;
     quote.signedQuoteBytes.copy(argsBuffer, offset);
     offset += quote.signedQuoteBytes.length;
-    argsBuffer.writeUInt32LE(relayInstructionsBytes.length, offset);
-    offset += 4;
-    relayInstructionsBytes.copy(argsBuffer, offset);
-    offset += relayInstructionsBytes.length;
+    gasInstruction.copy(argsBuffer, offset);
+    offset += gasInstruction.length;
     sequenceOption.copy(argsBuffer, offset);
+    offset += sequenceOption.length;
+    argsBuffer.writeUInt8(0, offset); // gas_drop_off: None
+    offset += 1;
+    argsBuffer.writeUInt8(0, offset); // refund_addr: None
 
     const relayData = Buffer.concat([requestRelayDiscriminator, argsBuffer]);
 
+    const relayRequest = deriveRelayRequestPda(programId, emitterPda, vaaSequence, dstChain);
+    console.log(`  Request ID: 0x${relayRequest.requestId.toString('hex')}`);
+
     const relayInstruction = new TransactionInstruction({
         keys: [
             { pubkey: keypair.publicKey, isSigner: true, isWritable: true },
             { pubkey: quote.payee, isSigner: false, isWritable: true },
             { pubkey: configPda, isSigner: false, isWritable: false },
-            { pubkey: peerPda, isSigner: false, isWritable: false },
+            { pubkey: peerPda, isSigner: false, isWritable: true },
             { pubkey: emitterPda, isSigner: false, isWritable: false },
             { pubkey: wormholeSequence, isSigner: false, isWritable: false },
+            { pubkey: relayRequest.pda, isSigner: false, isWritable: true },
+            { pubkey: relayRequest.indexPda, isSigner: false, isWritable: true },
             { pubkey: executorProgram, isSigner: false, isWritable: false },
             { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: spendCap, isSigner: false, isWritable: true },
+            // Lets the program price the payload length into its exec amount estimate
+            { pubkey: wormholeMessage, isSigner: false, isWritable: false },
+            // Records the destination on the greeting's Sent record
+            { pubkey: sentRecord, isSigner: false, isWritable: true },
         ],
         programId,
         data: relayData,
@@ -392,9 +494,27 @@ async function main() {
         `Explorer: https://explorer.solana.com/tx/${relaySig}?cluster=devnet`
     );
 
+    const emitterHex = Buffer.from(emitterPda.toBytes()).toString('hex');
+    return { sendSig, relaySig, vaaSequence, emitterHex };
+}
+
+// ============================================================================
+// Main
+// ============================================================================
+
+async function main() {
+    console.log('═'.repeat(60));
+    console.log('  🌊 Solana Devnet → Sepolia');
+    console.log('═'.repeat(60) + '\n');
+
+    const greeting = process.argv[2] || 'Hello from Solana! 🌊';
+    const finality = process.argv[3] === undefined ? null : Number(process.argv[3]);
+    console.log(`Message: "${greeting}"`);
+
+    const { sendSig, relaySig, vaaSequence, emitterHex } = await sendAndRelay(greeting, finality);
+
     // ── Poll for completion ─────────────────────────────────────────────────
 
-    const emitterHex = Buffer.from(emitterPda.toBytes()).toString('hex');
     const vaaData = await pollForVAA(CHAIN_ID_SOLANA, emitterHex, Number(vaaSequence));
 
     if (vaaData) {
@@ -435,7 +555,10 @@ async function main() {
     );
 }
 
-main().catch((error) => {
-    console.error('\n❌ Error:', error.message || error);
-    process.exit(1);
-});
+// Run only when executed directly, not when imported (e.g. by corridorTest.ts).
+if (import.meta.url === pathToFileURL(process.argv[1]).href) {
+    main().catch((error) => {
+        console.error('\n❌ Error:', error.message || error);
+        process.exit(1);
+    });
+}
@@ -0,0 +1,183 @@
+//! Fuzz the `fallback` entry point with arbitrary instruction data and
+//! account lists.
+//!
+//! ```text
+//! cargo +nightly fuzz run fallback
+//! ```
+//!
+//! Inputs run as transactions against one long-lived solana-program-test
+//! bank, so the program sees the same syscalls (logs, return data, sysvars)
+//! as on-chain. Every input must either succeed or fail with an instruction
+//! error; a panic, or any other kind of failure, is a finding.
+//!
+//! Instruction data starts with the Executor resolver discriminator for
+//! about half the inputs, so the resolver's parsing gets most of the
+//! coverage instead of the unknown-discriminator path.
+
+#![no_main]
+
+use std::sync::{Mutex, OnceLock};
+
+use anchor_lang::Discriminator;
+use arbitrary::Arbitrary;
+use hello_executor::{
+    fallback::EXECUTE_VAA_V1_DISCRIMINATOR,
+    pda,
+    state::{Config, Peer},
+};
+use libfuzzer_sys::fuzz_target;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use tokio::runtime::Runtime;
+
+/// Most accounts an input may pass, keeping transactions under the size
+/// limit together with [MAX_DATA_LEN].
+const MAX_ACCOUNTS: usize = 8;
+
+/// Most instruction data bytes an input may pass.
+const MAX_DATA_LEN: usize = 700;
+
+/// Address an input account is placed at.
+#[derive(Arbitrary, Debug)]
+enum AccountKey {
+    /// The Config PDA, which the resolver reads.
+    Config,
+    /// The Peer PDA for a chain.
+    Peer(u16),
+    /// One of 256 fixed unrelated addresses.
+    Other(u8),
+}
+
+impl AccountKey {
+    fn pubkey(&self) -> Pubkey {
+        match self {
+            AccountKey::Config => pda::config().0,
+            AccountKey::Peer(chain) => pda::peer(&[0; 8], *chain).0,
+            AccountKey::Other(seed) => Pubkey::new_from_array([*seed; 32]),
+        }
+    }
+}
+
+/// Anchor discriminator an account's data is prefixed with, so deserialization
+/// gets past the discriminator check.
+#[derive(Arbitrary, Debug)]
+enum AccountKind {
+    Config,
+    Peer,
+    Raw,
+}
+
+#[derive(Arbitrary, Debug)]
+struct FuzzAccount {
+    key: AccountKey,
+    kind: AccountKind,
+    data: Vec<u8>,
+    /// Owned by the program rather than the system program.
+    program_owned: bool,
+    is_writable: bool,
+}
+
+#[derive(Arbitrary, Debug)]
+struct FallbackInput {
+    /// Prefix `data` with the Executor resolver discriminator.
+    execute_vaa_v1: bool,
+    data: Vec<u8>,
+    accounts: Vec<FuzzAccount>,
+}
+
+struct Harness {
+    runtime: Runtime,
+    context: ProgramTestContext,
+}
+
+fn harness() -> &'static Mutex<Harness> {
+    static HARNESS: OnceLock<Mutex<Harness>> = OnceLock::new();
+    HARNESS.get_or_init(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let program_test = ProgramTest::new(
+            "hello_executor",
+            hello_executor::ID,
+            processor!(hello_executor::entry),
+        );
+        let context = runtime.block_on(program_test.start_with_context());
+        Mutex::new(Harness { runtime, context })
+    })
+}
+
+fuzz_target!(|input: FallbackInput| {
+    let mut data = Vec::with_capacity(8 + input.data.len());
+    if input.execute_vaa_v1 {
+        data.extend_from_slice(&EXECUTE_VAA_V1_DISCRIMINATOR);
+    }
+    data.extend(input.data.into_iter().take(MAX_DATA_LEN));
+
+    let mut guard = harness().lock().unwrap();
+    let Harness { runtime, context } = &mut *guard;
+
+    let mut metas = Vec::new();
+    for account in input.accounts.into_iter().take(MAX_ACCOUNTS) {
+        let pubkey = account.key.pubkey();
+        let mut account_data = match account.kind {
+            AccountKind::Config => Config::DISCRIMINATOR.to_vec(),
+            AccountKind::Peer => Peer::DISCRIMINATOR.to_vec(),
+            AccountKind::Raw => Vec::new(),
+        };
+        account_data.extend(account.data.into_iter().take(MAX_DATA_LEN));
+
+        let owner = if account.program_owned {
+            hello_executor::ID
+        } else {
+            solana_sdk::system_program::ID
+        };
+        let mut shared = AccountSharedData::new(1_000_000_000, account_data.len(), &owner);
+        shared.set_data_from_slice(&account_data);
+        context.set_account(&pubkey, &shared);
+
+        metas.push(if account.is_writable {
+            AccountMeta::new(pubkey, false)
+        } else {
+            AccountMeta::new_readonly(pubkey, false)
+        });
+    }
+
+    let instruction = Instruction {
+        program_id: hello_executor::ID,
+        accounts: metas,
+        data,
+    };
+    let payer = &context.payer;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+
+    match runtime.block_on(context.banks_client.process_transaction(transaction)) {
+        // A panic in the program surfaces as a failure to complete.
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::ProgramFailedToComplete,
+        ))) => panic!("program panicked"),
+        Ok(())
+        | Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(..) | TransactionError::AlreadyProcessed,
+        )) => {}
+        // Inputs the runtime refuses before the program runs (e.g. an
+        // oversized transaction or duplicate writable keys) say nothing
+        // about the program.
+        Err(BanksClientError::TransactionError(
+            TransactionError::AccountLoadedTwice | TransactionError::SanitizeFailure,
+        )) => {}
+        Err(e) => panic!("unstructured failure: {e:?}"),
+    }
+});
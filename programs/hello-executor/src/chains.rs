@@ -0,0 +1,205 @@
+//! Per-chain destination rules, by chain family.
+//!
+//! Wormhole addresses are 32 bytes on every chain, but what makes a valid
+//! 32-byte address depends on the chain family. `request_relay` checks the
+//! peer address against these rules before paying the Executor, since a
+//! relay to a malformed address cannot be recovered. Which relay
+//! instructions a destination accepts also depends on its family (see
+//! [crate::relay_instructions::build]).
+//!
+//! Supporting a new family is a [ChainFamily] variant, its chain IDs in
+//! [chain_family], and an arm in each `match` on it.
+//!
+//! [CorridorPreset]s bundle the peer and rate limit settings
+//! `configure_corridor` applies for common destinations.
+
+use anchor_lang::prelude::*;
+
+use crate::state::PayloadMode;
+
+/// Address format family of a Wormhole chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainFamily {
+    /// 20-byte addresses, left-padded with 12 zero bytes.
+    Evm,
+    /// 32-byte public keys (Solana and other SVM chains).
+    Svm,
+    /// 32-byte object IDs / account addresses (Sui, Aptos).
+    Move,
+    /// 32-byte CosmWasm contract addresses (Terra, Injective, Osmosis, Sei,
+    /// Wormchain and the IBC-connected chains).
+    Cosmos,
+    /// Any chain this program has no specific rule for.
+    Other,
+}
+
+/// Wormhole chain ID of Solana.
+pub const CHAIN_ID_SOLANA: u16 = 1;
+
+/// Family of the given Wormhole chain ID.
+pub fn chain_family(chain: u16) -> ChainFamily {
+    match chain {
+        CHAIN_ID_SOLANA => ChainFamily::Svm,
+        // Ethereum, BSC, Polygon, Avalanche, Oasis, Fantom, Karura, Acala,
+        // Klaytn, Celo, Moonbeam
+        2 | 4..=7 | 10..=14 | 16 => ChainFamily::Evm,
+        // Sui, Aptos
+        21 | 22 => ChainFamily::Move,
+        // Terra, Terra 2, Injective, Osmosis, XPLA, Sei
+        3 | 18..=20 | 28 | 32 => ChainFamily::Cosmos,
+        // Wormchain, then Cosmos Hub, Evmos, Kujira, Neutron, Celestia,
+        // Stargaze, SEDA, Dymension, Provenance, Noble
+        3104 | 4000..=4009 => ChainFamily::Cosmos,
+        // Arbitrum, Optimism, Gnosis, Base, Rootstock, Scroll, Mantle,
+        // Blast, X Layer, Linea, Berachain, SeiEVM
+        23..=25 | 30 | 33..=40 => ChainFamily::Evm,
+        // Sepolia, Arbitrum/Base/Optimism Sepolia, Holesky, Polygon Amoy
+        10002..=10007 => ChainFamily::Evm,
+        _ => ChainFamily::Other,
+    }
+}
+
+impl ChainFamily {
+    /// Whether a non-zero universal `address` fits this family's address
+    /// format.
+    pub fn is_valid_address(self, address: &[u8; 32]) -> bool {
+        match self {
+            ChainFamily::Evm => address[..12].iter().all(|&b| b == 0),
+            ChainFamily::Svm | ChainFamily::Move | ChainFamily::Cosmos | ChainFamily::Other => {
+                true
+            }
+        }
+    }
+
+    /// Whether the Executor delivers gas drop-offs to this family.
+    pub fn supports_gas_drop_off(self) -> bool {
+        match self {
+            ChainFamily::Evm | ChainFamily::Svm => true,
+            ChainFamily::Move | ChainFamily::Cosmos | ChainFamily::Other => false,
+        }
+    }
+}
+
+/// Built-in settings for a corridor to a common destination.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CorridorPreset {
+    /// Ethereum and Sepolia.
+    Ethereum,
+    /// Base and Base Sepolia.
+    Base,
+    /// Arbitrum and Arbitrum Sepolia.
+    Arbitrum,
+    /// Avalanche C-Chain (mainnet and Fuji share a chain ID).
+    Avalanche,
+}
+
+/// Settings a [CorridorPreset] applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CorridorSettings {
+    /// Peer's maximum accepted message age, see `Peer::max_age_seconds`.
+    pub max_age_seconds: u32,
+    /// Peer's payload mode.
+    pub payload_mode: PayloadMode,
+    /// Whether duplicate payloads from the peer are flagged.
+    pub dedup: bool,
+    /// Largest gas drop-off per relay, in wei.
+    pub max_gas_drop_off: u128,
+    /// Sender burst size, applied when no rate limit is configured.
+    pub rate_limit_capacity: u16,
+    /// Seconds for a sender to regain one greeting.
+    pub rate_limit_refill_seconds: u32,
+}
+
+impl CorridorPreset {
+    /// Wormhole chain IDs the preset is for, mainnet then testnet.
+    pub fn chains(self) -> &'static [u16] {
+        match self {
+            CorridorPreset::Ethereum => &[2, 10002],
+            CorridorPreset::Base => &[30, 10004],
+            CorridorPreset::Arbitrum => &[23, 10003],
+            CorridorPreset::Avalanche => &[6],
+        }
+    }
+
+    /// The preset's settings. Ethereum and its rollups wait for Ethereum
+    /// finality, so their messages are allowed a day; Avalanche finalizes
+    /// in seconds. Drop-offs are capped near the cost of a few transactions.
+    pub fn settings(self) -> CorridorSettings {
+        const ONE_ETH: u128 = 1_000_000_000_000_000_000;
+        let (max_age_seconds, max_gas_drop_off) = match self {
+            CorridorPreset::Ethereum => (86_400, ONE_ETH / 100),
+            CorridorPreset::Base | CorridorPreset::Arbitrum => (86_400, ONE_ETH / 1_000),
+            CorridorPreset::Avalanche => (3_600, ONE_ETH / 10),
+        };
+        CorridorSettings {
+            max_age_seconds,
+            payload_mode: PayloadMode::Greeting,
+            dedup: true,
+            max_gas_drop_off,
+            rate_limit_capacity: 10,
+            rate_limit_refill_seconds: 60,
+        }
+    }
+}
+
+/// Whether `address` is a well-formed destination address on `chain`.
+pub fn is_valid_address(chain: u16, address: &[u8; 32]) -> bool {
+    address.iter().any(|&b| b != 0) && chain_family(chain).is_valid_address(address)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_address() {
+        let mut evm = [0u8; 32];
+        evm[12..].copy_from_slice(&[0xab; 20]);
+        assert!(is_valid_address(10002, &evm));
+        assert!(is_valid_address(2, &evm));
+
+        // Full 32-byte values are not EVM addresses...
+        assert!(!is_valid_address(10002, &[0xab; 32]));
+        // ...but are valid Solana keys and Sui/Aptos object IDs.
+        assert!(is_valid_address(CHAIN_ID_SOLANA, &[0xab; 32]));
+        assert!(is_valid_address(21, &[0xab; 32]));
+
+        // The zero address is never a valid destination.
+        assert!(!is_valid_address(10002, &[0; 32]));
+        assert!(!is_valid_address(CHAIN_ID_SOLANA, &[0; 32]));
+        assert!(!is_valid_address(22, &[0; 32]));
+        assert!(!is_valid_address(19, &[0; 32]));
+    }
+
+    #[test]
+    fn test_chain_family() {
+        assert_eq!(chain_family(CHAIN_ID_SOLANA), ChainFamily::Svm);
+        assert_eq!(chain_family(10002), ChainFamily::Evm);
+        assert_eq!(chain_family(21), ChainFamily::Move);
+        assert_eq!(chain_family(32), ChainFamily::Cosmos);
+        assert_eq!(chain_family(3104), ChainFamily::Cosmos);
+        assert_eq!(chain_family(15), ChainFamily::Other);
+
+        assert!(ChainFamily::Evm.supports_gas_drop_off());
+        assert!(ChainFamily::Svm.supports_gas_drop_off());
+        assert!(!ChainFamily::Cosmos.supports_gas_drop_off());
+        assert!(!ChainFamily::Other.supports_gas_drop_off());
+    }
+
+    #[test]
+    fn test_corridor_presets() {
+        for preset in [
+            CorridorPreset::Ethereum,
+            CorridorPreset::Base,
+            CorridorPreset::Arbitrum,
+            CorridorPreset::Avalanche,
+        ] {
+            for &chain in preset.chains() {
+                assert_eq!(chain_family(chain), ChainFamily::Evm, "{preset:?}");
+            }
+            let settings = preset.settings();
+            assert!(settings.max_gas_drop_off > 0);
+            assert!(settings.rate_limit_refill_seconds > 0);
+        }
+    }
+}
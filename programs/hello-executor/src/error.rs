@@ -46,4 +46,411 @@ pub enum HelloExecutorError {
     #[msg("NoMessagesYet")]
     /// No Wormhole messages have been posted yet.
     NoMessagesYet,
+
+    #[msg("InvalidWormholeMessage")]
+    /// Specified Wormhole message PDA is wrong.
+    InvalidWormholeMessage,
+
+    #[msg("NoQuorum")]
+    /// Not enough guardian signatures for the guardian set's quorum.
+    NoQuorum,
+
+    #[msg("InvalidGuardianSet")]
+    /// Specified guardian set is wrong or expired.
+    InvalidGuardianSet,
+
+    #[msg("InvalidGuardianSignature")]
+    /// Guardian signature is malformed, out of order, or from the wrong key.
+    InvalidGuardianSignature,
+
+    #[msg("InvalidQueryResponse")]
+    /// Query response is malformed or does not target the registered peer.
+    InvalidQueryResponse,
+
+    #[msg("StaleQueryResponse")]
+    /// Query response is not newer than the stored result.
+    StaleQueryResponse,
+
+    #[msg("InvalidRelayRequest")]
+    /// Specified relay request PDA is wrong.
+    InvalidRelayRequest,
+
+    #[msg("MessageExpired")]
+    /// VAA is older than the peer's maximum message age.
+    MessageExpired,
+
+    #[msg("PublisherOnly")]
+    /// Only the owner or an allowlisted publisher is permitted.
+    PublisherOnly,
+
+    #[msg("InvalidFinality")]
+    /// Consistency level is not valid for the Solana Core Bridge.
+    InvalidFinality,
+
+    #[msg("InvalidDestinationAddress")]
+    /// Peer address is not a valid address on the destination chain
+    InvalidDestinationAddress,
+
+    #[msg("InvalidQuote")]
+    /// Signed quote is malformed or does not match the relay request
+    InvalidQuote,
+
+    #[msg("InvalidQuoteSigner")]
+    /// Signed quote is not signed by a configured quote signer
+    InvalidQuoteSigner,
+
+    #[msg("TooManyQuoteSigners")]
+    /// More quote signers than Config can hold
+    TooManyQuoteSigners,
+
+    #[msg("InvalidSignatureInstruction")]
+    /// Signature precompile instruction is missing or does not match
+    InvalidSignatureInstruction,
+
+    #[msg("StatsPublishedTooRecently")]
+    /// Stats for this peer were published too recently
+    StatsPublishedTooRecently,
+
+    #[msg("TimelockRequired")]
+    /// Peer address changes must be queued while a timelock is set
+    TimelockRequired,
+
+    #[msg("TimelockNotElapsed")]
+    /// Queued admin action is not executable yet
+    TimelockNotElapsed,
+
+    #[msg("InvalidAdminAction")]
+    /// Admin action is invalid or its accounts do not match
+    InvalidAdminAction,
+
+    #[msg("InsufficientFundsForFees")]
+    /// Payer cannot cover fees and rent while staying rent-exempt
+    InsufficientFundsForFees,
+
+    #[msg("InvalidRelayInstructions")]
+    /// Relay instructions could not be parsed
+    InvalidRelayInstructions,
+
+    #[msg("GasDropOffTooLarge")]
+    /// Gas drop-off exceeds the peer's limit, or drop-off is disabled for it
+    GasDropOffTooLarge,
+
+    #[msg("InvalidDropOffRecipient")]
+    /// Gas drop-off recipient is not a valid address on the destination chain
+    InvalidDropOffRecipient,
+
+    #[msg("ModerationOracleOnly")]
+    /// Only the moderation oracle can attest verdicts
+    ModerationOracleOnly,
+
+    #[msg("InsufficientExecAmount")]
+    /// exec_amount is below the quote's expected cost for the relay
+    InsufficientExecAmount,
+
+    #[msg("DeliveryOracleOnly")]
+    /// Only the delivery oracle can report deliveries
+    DeliveryOracleOnly,
+
+    #[msg("InvalidDeliveryTime")]
+    /// Reported delivery time is before the request or in the future
+    InvalidDeliveryTime,
+
+    #[msg("AlreadyDelivered")]
+    /// Relay request's delivery was already reported
+    AlreadyDelivered,
+
+    #[msg("AlreadyAnnounced")]
+    /// The Alive message was already posted
+    AlreadyAnnounced,
+
+    #[msg("NotAnnounced")]
+    /// announce must post the Alive message before anything else is published
+    NotAnnounced,
+
+    #[msg("InvalidNewOwner")]
+    /// Proposed owner is the current owner or the default key
+    InvalidNewOwner,
+
+    #[msg("PendingOwnerOnly")]
+    /// Only the pending owner can accept ownership
+    PendingOwnerOnly,
+
+    #[msg("OutboundPaused")]
+    /// Outbound messaging is paused by the owner
+    OutboundPaused,
+
+    #[msg("InboundPaused")]
+    /// Inbound messaging is paused by the owner
+    InboundPaused,
+
+    #[msg("NotPendingTranslation")]
+    /// Received message is not pending translation
+    NotPendingTranslation,
+
+    #[msg("PeerTrustTooLow")]
+    /// Peer's trust score is too low for this payload type
+    PeerTrustTooLow,
+
+    #[msg("TrustAuthorityOnly")]
+    /// Only the owner or the moderation oracle can set trust scores
+    TrustAuthorityOnly,
+
+    #[msg("QuotaExceeded")]
+    /// Payer has used up its send quota for this epoch
+    QuotaExceeded,
+
+    #[msg("SendQuotaRequired")]
+    /// The payer's send quota account must be passed
+    SendQuotaRequired,
+
+    #[msg("InvalidQuota")]
+    /// A send quota needs a non-zero epoch length
+    InvalidQuota,
+
+    #[msg("QuotaEpochNotOver")]
+    /// Only send quota accounts of past epochs can be closed
+    QuotaEpochNotOver,
+
+    #[msg("ReturnDataTooLarge")]
+    /// Return value exceeds the 1024-byte return data limit
+    ReturnDataTooLarge,
+
+    #[msg("GasDropOffUnsupported")]
+    /// The destination chain's family does not accept gas drop-offs
+    GasDropOffUnsupported,
+
+    #[msg("InvalidSentRecord")]
+    /// Sent record does not match the greeting's VAA sequence
+    InvalidSentRecord,
+
+    #[msg("PeerVersionTooLow")]
+    /// The peer reported a protocol version too old for this payload
+    PeerVersionTooLow,
+
+    #[msg("NoPendingProbe")]
+    /// The peer has no unanswered version probe
+    NoPendingProbe,
+
+    #[msg("RateLimited")]
+    /// Payer is sending faster than the rate limit allows
+    RateLimited,
+
+    #[msg("RateLimitRequired")]
+    /// Rate limit account required while a rate limit is set
+    RateLimitRequired,
+
+    #[msg("InvalidRateLimit")]
+    /// Rate limit capacity set without a refill interval
+    InvalidRateLimit,
+
+    #[msg("InsufficientTreasuryFunds")]
+    /// Withdrawal exceeds the fees held in the treasury
+    InsufficientTreasuryFunds,
+
+    #[msg("InvalidLookupTable")]
+    /// Lookup table account does not match the address derived from the slot
+    InvalidLookupTable,
+
+    #[msg("InvalidBackfillRange")]
+    /// Backfill range is empty, reversed or longer than BackfillRequest::MAX_BATCH
+    InvalidBackfillRange,
+
+    #[msg("SequenceNotMissing")]
+    /// Backfill range is not within a known sequence gap of the peer
+    SequenceNotMissing,
+
+    #[msg("InvalidBackfillAccounts")]
+    /// Remaining accounts are not the BackfillRequest PDAs of the range, in order
+    InvalidBackfillAccounts,
+
+    #[msg("InvalidGuardianSignatures")]
+    /// Guardian signatures account is not a verify VAA shim signatures account
+    InvalidGuardianSignatures,
+
+    #[msg("InvalidTokenGreeting")]
+    /// VAA is not a Token Bridge transfer carrying a greeting for this program
+    InvalidTokenGreeting,
+
+    #[msg("InvalidTokenRecipient")]
+    /// Recipient token account is not owned by the greeting's recipient
+    InvalidTokenRecipient,
+
+    #[msg("SpendCapExceeded")]
+    /// Spending this would exceed the epoch's spend cap
+    SpendCapExceeded,
+
+    #[msg("QuoteExpired")]
+    /// Signed quote is past its expiry time
+    QuoteExpired,
+
+    #[msg("TooManyCpiCallers")]
+    /// More CPI callers than CpiGuard can hold
+    TooManyCpiCallers,
+
+    #[msg("CpiCallerNotAllowed")]
+    /// Calling program is not allowed to call send_greeting through CPI
+    CpiCallerNotAllowed,
+
+    #[msg("AlreadyAcknowledged")]
+    /// Received message was already acknowledged to its peer
+    AlreadyAcknowledged,
+
+    #[msg("DeliveryPending")]
+    /// Received message is still pending translation
+    DeliveryPending,
+
+    #[msg("TestVaaExpired")]
+    /// Test VAA is past its expiry slot
+    TestVaaExpired,
+
+    #[msg("InvalidTestVaaPoster")]
+    /// Account is not the poster of the test VAA
+    InvalidTestVaaPoster,
+
+    #[msg("PresetChainMismatch")]
+    /// Corridor preset is not for the peer's chain
+    PresetChainMismatch,
+
+    #[msg("InvalidExecutorProgram")]
+    /// Executor program does not match config
+    InvalidExecutorProgram,
+
+    #[msg("InvalidRetentionPolicy")]
+    /// Pruner tip exceeds RetentionPolicy::MAX_TIP_BPS
+    InvalidRetentionPolicy,
+
+    #[msg("InvalidReceivedRecord")]
+    /// Received record passed to prune is not the peer's record for its ReceivedIndex
+    InvalidReceivedRecord,
+
+    #[msg("InvalidGovernanceEmitter")]
+    /// Governance emitter chain or address is zero
+    InvalidGovernanceEmitter,
+
+    #[msg("UnknownGovernanceEmitter")]
+    /// VAA is not from the governance emitter
+    UnknownGovernanceEmitter,
+
+    #[msg("InvalidGovernanceMessage")]
+    /// Governance payload is malformed or for another chain
+    InvalidGovernanceMessage,
+
+    #[msg("GovernanceReplay")]
+    /// Governance VAA is not newer than the last one applied
+    GovernanceReplay,
+
+    #[msg("CheckpointPublishedTooRecently")]
+    /// History checkpoint for this peer was published too recently
+    CheckpointPublishedTooRecently,
+
+    #[msg("InvalidResolverLimits")]
+    /// Resolver account limit exceeds Config::RESOLVER_MAX_ACCOUNTS_LIMIT
+    InvalidResolverLimits,
+
+    #[msg("InvalidHandshake")]
+    /// Handshake payload is malformed or addressed to another chain
+    InvalidHandshake,
+
+    #[msg("GovernanceSuperseded")]
+    /// Another governance VAA was applied since this RegisterAll broadcast started
+    GovernanceSuperseded,
+
+    #[msg("InvalidWormholeProgram")]
+    /// The Wormhole program passed is not the Core Bridge in config
+    InvalidWormholeProgram,
+}
+
+#[error_code(offset = 7000)]
+/// Errors returned by the Executor resolver.
+///
+/// Kept apart from [`HelloExecutorError`] so a relayer operator can tell from
+/// the code alone why a VAA could not be resolved, before anything executes.
+pub enum ResolverError {
+    #[msg("ResolverDataTruncated")]
+    /// Resolver instruction data is shorter than its Borsh length prefix.
+    ResolverDataTruncated,
+
+    #[msg("VaaBodyTooShort")]
+    /// VAA body is shorter than the fixed body header.
+    VaaBodyTooShort,
+
+    #[msg("UnknownEmitterChain")]
+    /// VAA emitter chain is not a valid Wormhole chain ID.
+    UnknownEmitterChain,
+
+    #[msg("PeerNotRegistered")]
+    /// The VAA emitter is not the registered peer for its chain.
+    PeerNotRegistered,
+
+    #[msg("PayloadUnsupported")]
+    /// The VAA payload cannot be delivered as a greeting.
+    PayloadUnsupported,
+
+    #[msg("ResolutionTooLarge")]
+    /// Serialized resolver result exceeds the 1024-byte return data limit.
+    ResolutionTooLarge,
+
+    #[msg("InboundPaused")]
+    /// Inbound messaging is paused; the message would not be received.
+    InboundPaused,
+
+    #[msg("PeerTrustTooLow")]
+    /// The peer is not trusted for this payload type.
+    PeerTrustTooLow,
+
+    #[msg("InvalidWormholeProgram")]
+    /// The Wormhole program passed is not the Core Bridge in config.
+    InvalidWormholeProgram,
+
+    #[msg("InvalidGuardianSet")]
+    /// The guardian set account passed cannot be read.
+    InvalidGuardianSet,
+
+    #[msg("ResolverOutputTooLarge")]
+    /// Resolver result exceeds the instruction group or account limits in config.
+    ResolverOutputTooLarge,
+}
+
+#[error_code(offset = 7100)]
+/// Errors returned by the shared VAA parser.
+pub enum VaaError {
+    #[msg("HeaderTooShort")]
+    /// Signed VAA is shorter than the fixed header.
+    HeaderTooShort,
+
+    #[msg("UnsupportedVersion")]
+    /// VAA version is not 1.
+    UnsupportedVersion,
+
+    #[msg("TooManySignatures")]
+    /// Signature count exceeds the largest guardian set.
+    TooManySignatures,
+
+    #[msg("SignaturesTruncated")]
+    /// Signed VAA ends inside its signature array.
+    SignaturesTruncated,
+
+    #[msg("GuardianIndexOutOfOrder")]
+    /// Guardian indices are not strictly increasing.
+    GuardianIndexOutOfOrder,
+
+    #[msg("GuardianIndexOutOfBounds")]
+    /// Guardian index is outside the guardian set.
+    GuardianIndexOutOfBounds,
+
+    #[msg("BodyTooShort")]
+    /// VAA body is shorter than the fixed body header.
+    BodyTooShort,
+
+    #[msg("NoQuorum")]
+    /// Fewer signatures than the guardian set's quorum.
+    NoQuorum,
+
+    #[msg("GuardianSetMismatch")]
+    /// Guardian set passed is not the one that signed the VAA.
+    GuardianSetMismatch,
+
+    #[msg("GuardianSetExpired")]
+    /// Guardian set that signed the VAA has expired.
+    GuardianSetExpired,
 }
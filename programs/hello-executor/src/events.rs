@@ -0,0 +1,77 @@
+//! Event emission during the migration to event-CPI.
+//!
+//! Events have always been `emit!` log lines, which RPC nodes may truncate.
+//! Event-CPI (`emit_cpi!`) events travel instead as the data of a self-CPI
+//! signed by the event authority PDA, where indexers read them from inner
+//! instructions. [Config::event_mode] chooses between them:
+//!
+//! * [EventMode::Legacy] (the default): log lines only.
+//! * [EventMode::Dual]: both, so existing indexers keep working while they
+//!   move to the CPI form.
+//! * [EventMode::Cpi]: the sunset of log lines; CPI only.
+//!
+//! The self-CPI needs the event authority and this program as accounts. Every
+//! instruction takes them among its remaining accounts, in any position, and
+//! the resolver appends them. Without them an event is logged whatever the
+//! mode, so none is lost.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::state::{Config, EventMode};
+
+/// Seed of the event authority PDA, which signs event self-CPIs. The same
+/// as Anchor's `#[event_cpi]`.
+pub const SEED_PREFIX_EVENT_AUTHORITY: &[u8] = b"__event_authority";
+
+/// The event authority.
+pub fn event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX_EVENT_AUTHORITY], &crate::ID)
+}
+
+/// Emit `event` as [Config::event_mode] says, through a self-CPI with the
+/// event authority and program found in `accounts`.
+pub fn emit_event<'info, E: Event>(
+    config: &Config,
+    accounts: &[AccountInfo<'info>],
+    event: E,
+) -> Result<()> {
+    let mode = config.event_mode();
+    let cpi = match mode {
+        EventMode::Legacy => None,
+        EventMode::Dual | EventMode::Cpi => find_cpi_accounts(accounts),
+    };
+    if mode != EventMode::Cpi || cpi.is_none() {
+        emit!(event);
+    }
+    let Some((authority, program, bump)) = cpi else {
+        return Ok(());
+    };
+
+    let data = [&EVENT_IX_TAG_LE[..], &event.data()].concat();
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: vec![AccountMeta::new_readonly(*authority.key, true)],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[authority.clone(), program.clone()],
+        &[&[SEED_PREFIX_EVENT_AUTHORITY, &[bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// The event authority, the program and the authority's bump, if both are
+/// among `accounts`.
+fn find_cpi_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Option<(&'a AccountInfo<'info>, &'a AccountInfo<'info>, u8)> {
+    let (authority_key, bump) = event_authority();
+    let authority = accounts.iter().find(|info| *info.key == authority_key)?;
+    let program = accounts.iter().find(|info| *info.key == crate::ID)?;
+    Some((authority, program, bump))
+}
@@ -0,0 +1,167 @@
+//! Minimum Executor payment estimate.
+//!
+//! The Executor drops relays whose `exec_amount` does not cover its quote,
+//! without any error on Solana. `request_relay` estimates the minimum from
+//! the signed quote's unit prices, the relay instructions and the length of
+//! the relayed payload, logs it, and — with `config.enforce_min_exec_amount`,
+//! the default — refuses to pay less.
+//!
+//! The estimate follows the Executor's pricing: the quote's base fee plus
+//! the destination cost (gas × gas price + msg value + drop-off), converted
+//! to lamports at the quote's `dst_price / src_price` rate. Gas is the larger
+//! of what the relay instructions request and what the payload is expected
+//! to need on the destination, so an under-provisioned gas limit is caught
+//! too.
+
+use crate::{
+    chains::{self, ChainFamily},
+    quote::{ExecutorQuote, QUOTE_DECIMALS},
+    relay_instructions::RelayTotals,
+};
+
+/// Decimals of the source chain's (Solana's) native token.
+pub const SRC_NATIVE_DECIMALS: u32 = 9;
+
+/// EVM gas `receiveWormholeMessages` needs before the payload: VAA
+/// verification, replay protection and the greeting event.
+pub const EVM_BASE_GAS: u128 = 100_000;
+
+/// EVM gas per payload byte: calldata, copying and event data.
+pub const EVM_GAS_PER_PAYLOAD_BYTE: u128 = 100;
+
+/// Compute units `receive_greeting` needs before the payload.
+pub const SVM_BASE_COMPUTE_UNITS: u128 = 200_000;
+
+/// Compute units per payload byte.
+pub const SVM_COMPUTE_UNITS_PER_PAYLOAD_BYTE: u128 = 10;
+
+/// Decimals of the destination's native token, if its family is priced.
+fn dst_native_decimals(dst_chain: u16) -> Option<u32> {
+    match chains::chain_family(dst_chain) {
+        ChainFamily::Evm => Some(18),
+        ChainFamily::Svm => Some(9),
+        ChainFamily::Move | ChainFamily::Cosmos | ChainFamily::Other => None,
+    }
+}
+
+/// Gas (compute units on SVM) a payload of `payload_len` bytes is expected
+/// to need on `dst_chain`. `None` for chains without defaults.
+pub fn required_gas(dst_chain: u16, payload_len: usize) -> Option<u128> {
+    let payload_len = payload_len as u128;
+    match chains::chain_family(dst_chain) {
+        ChainFamily::Evm => Some(EVM_BASE_GAS + EVM_GAS_PER_PAYLOAD_BYTE * payload_len),
+        ChainFamily::Svm => {
+            Some(SVM_BASE_COMPUTE_UNITS + SVM_COMPUTE_UNITS_PER_PAYLOAD_BYTE * payload_len)
+        }
+        ChainFamily::Move | ChainFamily::Cosmos | ChainFamily::Other => None,
+    }
+}
+
+/// Estimated minimum `exec_amount` (lamports) for a relay priced by `quote`.
+///
+/// `None` when the destination has no defaults, the quote has no source
+/// price, or the amount does not fit.
+pub fn minimum_exec_amount(
+    quote: &ExecutorQuote,
+    totals: &RelayTotals,
+    payload_len: usize,
+) -> Option<u64> {
+    let dst_decimals = dst_native_decimals(quote.dst_chain)?;
+    if quote.src_price == 0 {
+        return None;
+    }
+
+    // Destination cost, in the destination's smallest native unit.
+    let gas = totals.gas_limit.max(required_gas(quote.dst_chain, payload_len)?);
+    let dst_cost = gas
+        .checked_mul(quote.dst_gas_price.into())?
+        .checked_add(totals.msg_value)?
+        .checked_add(totals.drop_off)?;
+
+    // Both prices have QUOTE_DECIMALS decimals, so only the native token
+    // decimals need scaling.
+    let mut numerator = dst_cost.checked_mul(quote.dst_price.into())?;
+    let mut denominator = u128::from(quote.src_price);
+    if dst_decimals >= SRC_NATIVE_DECIMALS {
+        denominator = denominator.checked_mul(10u128.pow(dst_decimals - SRC_NATIVE_DECIMALS))?;
+    } else {
+        numerator = numerator.checked_mul(10u128.pow(SRC_NATIVE_DECIMALS - dst_decimals))?;
+    }
+    let converted = numerator.div_ceil(denominator);
+
+    let base_fee = u128::from(quote.base_fee)
+        .div_ceil(10u128.pow(QUOTE_DECIMALS - SRC_NATIVE_DECIMALS));
+
+    base_fee.checked_add(converted)?.try_into().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn quote(dst_chain: u16) -> ExecutorQuote {
+        ExecutorQuote {
+            quoter: [0; 20],
+            payee: [0; 32],
+            src_chain: chains::CHAIN_ID_SOLANA,
+            dst_chain,
+            expiry_time: 0,
+            // 0.001 SOL
+            base_fee: 10_000_000,
+            // 2 gwei
+            dst_gas_price: 2_000_000_000,
+            // SOL at $150, ETH at $3000
+            src_price: 1_500_000_000_000,
+            dst_price: 30_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_required_gas() {
+        assert_eq!(required_gas(10002, 0), Some(EVM_BASE_GAS));
+        assert_eq!(required_gas(10002, 10), Some(EVM_BASE_GAS + 1_000));
+        assert_eq!(
+            required_gas(chains::CHAIN_ID_SOLANA, 10),
+            Some(SVM_BASE_COMPUTE_UNITS + 100)
+        );
+        assert_eq!(required_gas(21, 10), None);
+    }
+
+    #[test]
+    fn test_minimum_exec_amount() {
+        let quote = quote(10002);
+        let totals = RelayTotals {
+            gas_limit: 200_000,
+            ..Default::default()
+        };
+
+        // 200k gas × 2 gwei = 0.0004 ETH = 0.008 SOL, plus the base fee.
+        assert_eq!(minimum_exec_amount(&quote, &totals, 100), Some(9_000_000));
+
+        // A payload needing more than the requested gas raises the minimum.
+        assert_eq!(minimum_exec_amount(&quote, &totals, 1_100), Some(9_400_000));
+
+        // msg value and drop-off are paid for on top.
+        let totals = RelayTotals {
+            msg_value: 1_000_000_000_000_000,
+            drop_off: 1_000_000_000_000_000,
+            ..totals
+        };
+        assert_eq!(minimum_exec_amount(&quote, &totals, 100), Some(49_000_000));
+    }
+
+    #[test]
+    fn test_minimum_exec_amount_unpriced() {
+        let totals = RelayTotals::default();
+        assert_eq!(minimum_exec_amount(&quote(21), &totals, 0), None);
+
+        let mut quote = quote(10002);
+        quote.src_price = 0;
+        assert_eq!(minimum_exec_amount(&quote, &totals, 0), None);
+
+        quote.src_price = 1;
+        quote.dst_price = u64::MAX;
+        quote.dst_gas_price = u64::MAX;
+        assert_eq!(minimum_exec_amount(&quote, &totals, 0), None);
+    }
+}
@@ -4,6 +4,8 @@ use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke;
 use std::str::FromStr;
 
+/// Canonical Executor program, used unless `config.executor_program` names
+/// another deployment.
 #[derive(Clone)]
 pub struct ExecutorProgram;
 
@@ -14,6 +16,11 @@ impl Id for ExecutorProgram {
     }
 }
 
+/// Anchor discriminator of the Executor's `request_for_execution`
+/// instruction: `sha256("global:request_for_execution")[..8]`, as listed in
+/// `idls/executor.json`. Checked by `tools/discriminators`.
+pub const REQUEST_FOR_EXECUTION_DISCRIMINATOR: [u8; 8] = [109, 107, 87, 37, 151, 192, 119, 115];
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RequestForExecutionArgs {
     pub amount: u64,
@@ -32,11 +39,8 @@ pub fn request_for_execution<'info>(
     system_program: &AccountInfo<'info>,
     args: RequestForExecutionArgs,
 ) -> Result<()> {
-    // Discriminator from executor.json IDL
-    const DISCRIMINATOR: [u8; 8] = [109, 107, 87, 37, 151, 192, 119, 115];
-
     let mut data = Vec::with_capacity(8 + args.try_to_vec()?.len());
-    data.extend_from_slice(&DISCRIMINATOR);
+    data.extend_from_slice(&REQUEST_FOR_EXECUTION_DISCRIMINATOR);
     data.extend_from_slice(&args.try_to_vec()?);
 
     let ix = Instruction {
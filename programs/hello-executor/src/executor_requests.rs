@@ -0,0 +1,103 @@
+//! Executor request builders.
+//!
+//! The Executor's `request_for_execution` takes opaque `request_bytes` that
+//! name what to relay; their 4-byte type tag tells the off-chain relayer
+//! which protocol to deliver and how to find the message:
+//!
+//! | Tag | Protocol | Layout (integers big-endian) |
+//! |---|---|---|
+//! | `ERV1` | Wormhole VAA | `chain(2) \| emitter(32) \| sequence(8)` |
+//! | `ERC1` | Circle CCTP v1 | `source_domain(4) \| nonce(8)` |
+//! | `ERN1` | Wormhole NTT v1 | `src_chain(2) \| src_manager(32) \| message_id(32)` |
+//!
+//! The request ID the Executor reports status under is
+//! `keccak256(request_bytes)`.
+
+/// Type tag of a Wormhole VAA v1 request.
+pub const REQUEST_PREFIX_VAA_V1: &[u8; 4] = b"ERV1";
+
+/// Type tag of a CCTP v1 request.
+pub const REQUEST_PREFIX_CCTP_V1: &[u8; 4] = b"ERC1";
+
+/// Type tag of an NTT v1 request.
+pub const REQUEST_PREFIX_NTT_V1: &[u8; 4] = b"ERN1";
+
+/// CCTP domain of Solana, the source domain of burns made here.
+pub const CCTP_DOMAIN_SOLANA: u32 = 5;
+
+/// Request the relay of the VAA `(chain, emitter, sequence)`.
+pub fn make_vaa_v1_request(chain: u16, emitter: &[u8; 32], sequence: u64) -> Vec<u8> {
+    let mut request = Vec::with_capacity(4 + 2 + 32 + 8);
+    request.extend_from_slice(REQUEST_PREFIX_VAA_V1);
+    request.extend_from_slice(&chain.to_be_bytes());
+    request.extend_from_slice(emitter);
+    request.extend_from_slice(&sequence.to_be_bytes());
+    request
+}
+
+/// Request the relay of the CCTP v1 message `(source_domain, nonce)`. The
+/// relayer fetches Circle's attestation and calls `receiveMessage` on the
+/// destination.
+pub fn make_cctp_v1_request(source_domain: u32, nonce: u64) -> Vec<u8> {
+    let mut request = Vec::with_capacity(4 + 4 + 8);
+    request.extend_from_slice(REQUEST_PREFIX_CCTP_V1);
+    request.extend_from_slice(&source_domain.to_be_bytes());
+    request.extend_from_slice(&nonce.to_be_bytes());
+    request
+}
+
+/// Request the relay of the NTT v1 message `message_id` sent by the NTT
+/// manager `src_manager` on `src_chain`. On Solana the message ID is the
+/// address of the manager's outbox item.
+pub fn make_ntt_v1_request(
+    src_chain: u16,
+    src_manager: &[u8; 32],
+    message_id: &[u8; 32],
+) -> Vec<u8> {
+    let mut request = Vec::with_capacity(4 + 2 + 32 + 32);
+    request.extend_from_slice(REQUEST_PREFIX_NTT_V1);
+    request.extend_from_slice(&src_chain.to_be_bytes());
+    request.extend_from_slice(src_manager);
+    request.extend_from_slice(message_id);
+    request
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::{trace_id, TRACE_ID_LENGTH};
+    use anchor_lang::solana_program::keccak;
+
+    #[test]
+    fn test_make_vaa_v1_request() {
+        let emitter = [7u8; 32];
+        let request = make_vaa_v1_request(1, &emitter, 42);
+        assert_eq!(request.len(), 46);
+        assert_eq!(&request[..4], b"ERV1");
+        assert_eq!(&request[4..6], &[0, 1]);
+        assert_eq!(&request[6..38], &emitter);
+        assert_eq!(&request[38..], &42u64.to_be_bytes());
+
+        // Trace IDs are prefixes of VAA request IDs.
+        let request_id = keccak::hash(&request).to_bytes();
+        assert_eq!(trace_id(1, &emitter, 42)[..], request_id[..TRACE_ID_LENGTH]);
+    }
+
+    #[test]
+    fn test_make_cctp_v1_request() {
+        let request = make_cctp_v1_request(CCTP_DOMAIN_SOLANA, 0x0102);
+        assert_eq!(
+            request,
+            [b"ERC1".as_ref(), &[0, 0, 0, 5], &[0, 0, 0, 0, 0, 0, 1, 2]].concat()
+        );
+    }
+
+    #[test]
+    fn test_make_ntt_v1_request() {
+        let request = make_ntt_v1_request(1, &[7; 32], &[9; 32]);
+        assert_eq!(request.len(), 70);
+        assert_eq!(&request[..6], b"ERN1\x00\x01");
+        assert_eq!(&request[6..38], &[7; 32]);
+        assert_eq!(&request[38..], &[9; 32]);
+    }
+}
@@ -0,0 +1,97 @@
+//! Routing for non-Anchor entry points.
+//!
+//! Callers such as the Wormhole Executor invoke the program with their own
+//! 8-byte discriminators, which Anchor does not recognize and hands to the
+//! `fallback` instruction. Each such entry point is one row in
+//! [`FALLBACK_ROUTES`]; adding one does not touch the fallback body.
+
+use anchor_lang::prelude::*;
+
+use crate::resolver;
+
+/// Handler for a fallback route. Receives the instruction data after the
+/// discriminator.
+pub type FallbackHandler =
+    for<'info> fn(&Pubkey, &'info [AccountInfo<'info>], &[u8]) -> Result<()>;
+
+/// A custom discriminator and the handler it routes to.
+pub struct FallbackRoute {
+    /// Discriminator the caller prefixes the instruction data with.
+    pub discriminator: [u8; 8],
+    /// Name used in logs.
+    pub name: &'static str,
+    /// Handler for the remaining instruction data.
+    pub handler: FallbackHandler,
+}
+
+/// Wormhole Executor resolver discriminator:
+/// `sha256("executor-account-resolver:execute-vaa-v1")[..8]`.
+///
+/// Defined as `RESOLVER_EXECUTE_VAA_V1` in executor-account-resolver-svm.
+/// Anchor 0.31+ with `interface-instructions` feature would allow replacing
+/// this route with `#[instruction(discriminator = &RESOLVER_EXECUTE_VAA_V1)]`
+/// on the named instruction, but that requires upgrading solana-program to
+/// 2.x (out of scope).
+pub const EXECUTE_VAA_V1_DISCRIMINATOR: [u8; 8] = [148, 184, 169, 222, 207, 8, 154, 127];
+
+/// All fallback routes. Discriminators must be unique.
+pub const FALLBACK_ROUTES: &[FallbackRoute] = &[FallbackRoute {
+    discriminator: EXECUTE_VAA_V1_DISCRIMINATOR,
+    name: "execute_vaa_v1",
+    handler: resolver::handle_resolve_raw,
+}];
+
+/// Find the route for `data`, if its discriminator is known.
+pub fn find_route(data: &[u8]) -> Option<&'static FallbackRoute> {
+    let discriminator = data.get(..8)?;
+    FALLBACK_ROUTES
+        .iter()
+        .find(|route| route.discriminator[..] == *discriminator)
+}
+
+/// Dispatch a fallback call to its route.
+pub(crate) fn dispatch<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<()> {
+    let Some(route) = find_route(data) else {
+        return Err(anchor_lang::error::ErrorCode::InstructionFallbackNotFound.into());
+    };
+
+    msg!("Fallback route: {}", route.name);
+    (route.handler)(program_id, accounts, &data[8..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn test_discriminators_are_unique() {
+        for (i, a) in FALLBACK_ROUTES.iter().enumerate() {
+            for b in &FALLBACK_ROUTES[i + 1..] {
+                assert_ne!(a.discriminator, b.discriminator, "{} / {}", a.name, b.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_route_execute_vaa_v1() {
+        let expected = hash(b"executor-account-resolver:execute-vaa-v1").to_bytes();
+        assert_eq!(EXECUTE_VAA_V1_DISCRIMINATOR[..], expected[..8]);
+
+        let mut data = EXECUTE_VAA_V1_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&0u32.to_le_bytes());
+        let route = find_route(&data).unwrap();
+        assert_eq!(route.name, "execute_vaa_v1");
+    }
+
+    #[test]
+    fn test_unknown_discriminator() {
+        assert!(find_route(&[0; 8]).is_none());
+        assert!(find_route(&EXECUTE_VAA_V1_DISCRIMINATOR[..7]).is_none());
+        assert!(find_route(&[]).is_none());
+    }
+}
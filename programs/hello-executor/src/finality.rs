@@ -0,0 +1,81 @@
+//! Consistency-level (finality) helpers for `post_message`.
+//!
+//! The Solana Core Bridge takes the consistency level as a Borsh enum
+//! ([`Finality`](wormhole_anchor_sdk::wormhole::Finality): `Confirmed = 0`,
+//! `Finalized = 1`), while EVM core contracts take raw levels where `200`
+//! means instant and `201` means safe. A value copied from an EVM config is
+//! not a valid Solana level, and the message never gets signed. Everything
+//! that sets `finality` goes through [`normalize_finality`].
+
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Solana Core Bridge `Confirmed` level.
+pub const FINALITY_CONFIRMED: u8 = 0;
+
+/// Solana Core Bridge `Finalized` level.
+pub const FINALITY_FINALIZED: u8 = 1;
+
+/// EVM "instant" consistency level, accepted as an alias for `Confirmed`.
+pub const EVM_CONSISTENCY_INSTANT: u8 = 200;
+
+/// EVM "safe" consistency level, accepted as an alias for `Confirmed`.
+pub const EVM_CONSISTENCY_SAFE: u8 = 201;
+
+/// Default finality for this build's network.
+///
+/// Devnets default to `Confirmed` so demo messages are signed in seconds;
+/// mainnet (and any other network) waits for `Finalized`.
+#[cfg(any(feature = "solana-devnet", feature = "tilt-devnet"))]
+pub const DEFAULT_FINALITY: u8 = FINALITY_CONFIRMED;
+#[cfg(not(any(feature = "solana-devnet", feature = "tilt-devnet")))]
+pub const DEFAULT_FINALITY: u8 = FINALITY_FINALIZED;
+
+/// Map a user-supplied consistency level to the Solana Core Bridge encoding.
+///
+/// Accepts the Solana values (`0`, `1`) and the EVM instant/safe aliases
+/// (`200`, `201` → `Confirmed`). Anything else is rejected rather than
+/// posted as a message the guardians will never sign.
+pub fn normalize_finality(level: u8) -> Result<u8> {
+    match level {
+        FINALITY_CONFIRMED | EVM_CONSISTENCY_INSTANT | EVM_CONSISTENCY_SAFE => {
+            Ok(FINALITY_CONFIRMED)
+        }
+        FINALITY_FINALIZED => Ok(FINALITY_FINALIZED),
+        _ => {
+            msg!("Unsupported consistency level: {}", level);
+            Err(HelloExecutorError::InvalidFinality.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wormhole_anchor_sdk::wormhole::Finality;
+
+    #[test]
+    fn test_normalize_solana_levels() {
+        assert_eq!(normalize_finality(0).unwrap(), Finality::Confirmed as u8);
+        assert_eq!(normalize_finality(1).unwrap(), Finality::Finalized as u8);
+    }
+
+    #[test]
+    fn test_normalize_evm_aliases() {
+        assert_eq!(normalize_finality(200).unwrap(), FINALITY_CONFIRMED);
+        assert_eq!(normalize_finality(201).unwrap(), FINALITY_CONFIRMED);
+    }
+
+    #[test]
+    fn test_normalize_rejects_unknown() {
+        for level in [2, 15, 199, 202, 255] {
+            assert!(normalize_finality(level).is_err());
+        }
+    }
+
+    #[test]
+    fn test_default_is_valid() {
+        assert_eq!(normalize_finality(DEFAULT_FINALITY).unwrap(), DEFAULT_FINALITY);
+    }
+}
@@ -0,0 +1,32 @@
+//! Payer balance checks.
+//!
+//! An underfunded payer otherwise surfaces as an opaque system-program
+//! failure deep inside a CPI. Checking up front lets the program report the
+//! exact shortfall.
+
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Require `payer` to stay rent-exempt, plus `buffer` lamports, after
+/// spending `spend` lamports.
+pub fn require_payer_can_afford(payer: &AccountInfo, spend: u64, buffer: u64) -> Result<()> {
+    let required = spend
+        .saturating_add(Rent::get()?.minimum_balance(payer.data_len()))
+        .saturating_add(buffer);
+    let balance = payer.lamports();
+
+    if balance < required {
+        msg!(
+            "Payer {} has {} lamports but needs {} ({} for fees and rent, {} short)",
+            payer.key,
+            balance,
+            required,
+            spend,
+            required - balance
+        );
+        return err!(HelloExecutorError::InsufficientFundsForFees);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+//! Cross-chain governance VAAs.
+//!
+//! The owner names a governance emitter with `set_governance`; VAAs from it
+//! are applied by `receive_governance` instead of the owner's signature.
+//! The format follows Wormhole's own governance messages, so the same
+//! tooling can build them.
+//!
+//! Layout of the VAA payload (all integers big-endian):
+//! `module(32) = "HelloExecutor" left-padded | action(1) | chain(2) | fields`
+//!
+//! `chain` is the Wormhole chain ID the action is for, so a VAA for another
+//! deployment's chain is refused. Fields per action:
+//!
+//! | Action | Name                  | Fields                          |
+//! |--------|-----------------------|---------------------------------|
+//! | 1      | `RegisterPeer`        | `chain(2) \| address(32)`       |
+//! | 2      | `SetPause`            | `outbound(1) \| inbound(1)`     |
+//! | 3      | `UpdateMessageParams` | `finality(1) \| batch_id(4)`    |
+//! | 4      | `SetPayerBuffer`      | `lamports(8)`                   |
+//! | 5      | `RegisterAll`         | `count(1) \| peers(34 * count)` |
+//!
+//! `RegisterAll` is a deployer registry's broadcast of every deployment,
+//! each peer encoded as in `RegisterPeer`. Its `chain` may be zero, for
+//! every chain. It can carry more peers than one transaction can create,
+//! so it is applied in chunks by `register_all` rather than by
+//! `receive_governance`.
+
+use anchor_lang::prelude::*;
+
+/// Name of the governance module, left-padded to 32 bytes.
+pub const GOVERNANCE_MODULE: [u8; 32] = {
+    let name = b"HelloExecutor";
+    let mut module = [0; 32];
+    let mut i = 0;
+    while i < name.len() {
+        module[32 - name.len() + i] = name[i];
+        i += 1;
+    }
+    module
+};
+
+/// Length of the payload before the action's fields.
+pub const GOVERNANCE_HEADER_LENGTH: usize = 32 + 1 + 2;
+
+/// Action ID of a `RegisterAll` broadcast.
+pub const ACTION_REGISTER_ALL: u8 = 5;
+
+/// A change applied by a governance VAA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GovernanceAction {
+    /// Register a peer, or change a registered peer's address.
+    RegisterPeer {
+        /// Wormhole chain ID of the peer.
+        chain: u16,
+        /// Universal address of the peer contract.
+        address: [u8; 32],
+    },
+    /// Pause or resume messaging, as `set_pause`.
+    SetPause {
+        /// Whether outbound messaging is paused.
+        outbound: bool,
+        /// Whether inbound messaging is paused.
+        inbound: bool,
+    },
+    /// Set the message parameters, as `update_message_params`.
+    UpdateMessageParams {
+        /// Consistency level of published messages.
+        finality: u8,
+        /// Batch ID of published messages.
+        batch_id: u32,
+    },
+    /// Set the payer buffer, as `set_payer_buffer`.
+    SetPayerBuffer {
+        /// Lamports a payer must keep above its costs.
+        lamports: u64,
+    },
+}
+
+impl GovernanceAction {
+    /// Action ID in the payload.
+    pub fn id(&self) -> u8 {
+        match self {
+            GovernanceAction::RegisterPeer { .. } => 1,
+            GovernanceAction::SetPause { .. } => 2,
+            GovernanceAction::UpdateMessageParams { .. } => 3,
+            GovernanceAction::SetPayerBuffer { .. } => 4,
+        }
+    }
+
+    /// Encode the action as a governance payload for `chain`.
+    pub fn to_payload(&self, chain: u16) -> Vec<u8> {
+        let mut out = Vec::with_capacity(GOVERNANCE_HEADER_LENGTH + 34);
+        out.extend_from_slice(&GOVERNANCE_MODULE);
+        out.push(self.id());
+        out.extend_from_slice(&chain.to_be_bytes());
+        match self {
+            GovernanceAction::RegisterPeer { chain, address } => {
+                out.extend_from_slice(&chain.to_be_bytes());
+                out.extend_from_slice(address);
+            }
+            GovernanceAction::SetPause { outbound, inbound } => {
+                out.extend_from_slice(&[u8::from(*outbound), u8::from(*inbound)]);
+            }
+            GovernanceAction::UpdateMessageParams { finality, batch_id } => {
+                out.push(*finality);
+                out.extend_from_slice(&batch_id.to_be_bytes());
+            }
+            GovernanceAction::SetPayerBuffer { lamports } => {
+                out.extend_from_slice(&lamports.to_be_bytes());
+            }
+        }
+        out
+    }
+}
+
+/// Encode a `RegisterAll` broadcast of `peers` for `chain` (zero for every
+/// chain). At most 255 peers fit the count.
+pub fn encode_register_all(chain: u16, peers: &[(u16, [u8; 32])]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(GOVERNANCE_HEADER_LENGTH + 1 + 34 * peers.len());
+    out.extend_from_slice(&GOVERNANCE_MODULE);
+    out.push(ACTION_REGISTER_ALL);
+    out.extend_from_slice(&chain.to_be_bytes());
+    out.push(peers.len() as u8);
+    for (chain, address) in peers {
+        out.extend_from_slice(&chain.to_be_bytes());
+        out.extend_from_slice(address);
+    }
+    out
+}
+
+/// Parse a `RegisterAll` broadcast into the chain it is for and its peers.
+/// `None` for any other payload, an empty or oversized list, or a length
+/// that does not match the count.
+pub fn parse_register_all(payload: &[u8]) -> Option<(u16, Vec<(u16, [u8; 32])>)> {
+    if !is_governance(payload)
+        || payload.len() <= GOVERNANCE_HEADER_LENGTH
+        || payload[32] != ACTION_REGISTER_ALL
+    {
+        return None;
+    }
+    let chain = u16::from_be_bytes(payload[33..35].try_into().unwrap());
+    let count = usize::from(payload[GOVERNANCE_HEADER_LENGTH]);
+    let entries = &payload[GOVERNANCE_HEADER_LENGTH + 1..];
+    if count == 0 || entries.len() != 34 * count {
+        return None;
+    }
+
+    let peers = entries
+        .chunks_exact(34)
+        .map(|entry| {
+            (
+                u16::from_be_bytes(entry[0..2].try_into().unwrap()),
+                entry[2..34].try_into().unwrap(),
+            )
+        })
+        .collect();
+
+    Some((chain, peers))
+}
+
+/// Whether `payload` is addressed to the governance module. The resolver
+/// routes such VAAs to `receive_governance`.
+pub fn is_governance(payload: &[u8]) -> bool {
+    payload.starts_with(&GOVERNANCE_MODULE)
+}
+
+/// Parse a governance payload into the chain it is for and its action.
+/// `None` for any other payload, an unknown action or trailing bytes.
+pub fn parse_governance(payload: &[u8]) -> Option<(u16, GovernanceAction)> {
+    if !is_governance(payload) || payload.len() < GOVERNANCE_HEADER_LENGTH {
+        return None;
+    }
+    let chain = u16::from_be_bytes(payload[33..35].try_into().unwrap());
+    let fields = &payload[GOVERNANCE_HEADER_LENGTH..];
+
+    let flag = |byte: u8| match byte {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    };
+    let action = match (payload[32], fields.len()) {
+        (1, 34) => GovernanceAction::RegisterPeer {
+            chain: u16::from_be_bytes(fields[0..2].try_into().unwrap()),
+            address: fields[2..34].try_into().unwrap(),
+        },
+        (2, 2) => GovernanceAction::SetPause {
+            outbound: flag(fields[0])?,
+            inbound: flag(fields[1])?,
+        },
+        (3, 5) => GovernanceAction::UpdateMessageParams {
+            finality: fields[0],
+            batch_id: u32::from_be_bytes(fields[1..5].try_into().unwrap()),
+        },
+        (4, 8) => GovernanceAction::SetPayerBuffer {
+            lamports: u64::from_be_bytes(fields.try_into().unwrap()),
+        },
+        _ => return None,
+    };
+
+    Some((chain, action))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_module() {
+        assert_eq!(&GOVERNANCE_MODULE[..19], &[0; 19]);
+        assert_eq!(&GOVERNANCE_MODULE[19..], b"HelloExecutor");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for action in [
+            GovernanceAction::RegisterPeer {
+                chain: 10002,
+                address: [7; 32],
+            },
+            GovernanceAction::SetPause {
+                outbound: true,
+                inbound: false,
+            },
+            GovernanceAction::UpdateMessageParams {
+                finality: 1,
+                batch_id: 42,
+            },
+            GovernanceAction::SetPayerBuffer { lamports: 5_000 },
+        ] {
+            let payload = action.to_payload(1);
+            assert!(is_governance(&payload));
+            assert_eq!(parse_governance(&payload), Some((1, action)));
+        }
+    }
+
+    #[test]
+    fn test_register_all() {
+        let peers = [(2, [2; 32]), (1, [1; 32]), (10002, [9; 32])];
+        let payload = encode_register_all(0, &peers);
+        assert!(is_governance(&payload));
+        assert_eq!(payload.len(), GOVERNANCE_HEADER_LENGTH + 1 + 34 * 3);
+        assert_eq!(parse_register_all(&payload), Some((0, peers.to_vec())));
+
+        // Not a single action, and the count must match the entries.
+        assert_eq!(parse_governance(&payload), None);
+        assert_eq!(parse_register_all(&payload[..payload.len() - 1]), None);
+        assert_eq!(parse_register_all(&encode_register_all(0, &[])), None);
+        assert_eq!(
+            parse_register_all(&GovernanceAction::SetPayerBuffer { lamports: 1 }.to_payload(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects() {
+        let payload = GovernanceAction::SetPause {
+            outbound: true,
+            inbound: true,
+        }
+        .to_payload(1);
+
+        // Other modules, unknown actions, bad lengths and non-boolean flags.
+        let mut other_module = payload.clone();
+        other_module[0] = 1;
+        assert_eq!(parse_governance(&other_module), None);
+
+        let mut unknown = payload.clone();
+        unknown[32] = 6;
+        assert_eq!(parse_governance(&unknown), None);
+
+        assert_eq!(parse_governance(&payload[..payload.len() - 1]), None);
+        assert_eq!(parse_governance(&[payload.clone(), vec![0]].concat()), None);
+        assert_eq!(parse_governance(&GOVERNANCE_MODULE), None);
+
+        let mut not_bool = payload;
+        not_bool[GOVERNANCE_HEADER_LENGTH] = 2;
+        assert_eq!(parse_governance(&not_bool), None);
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, events, state::Config};
+
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    /// Proposed owner. Must match config.pending_owner.
+    pub new_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = config.pending_owner == new_owner.key()
+            @ HelloExecutorError::PendingOwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the pending owner.
+    pub config: Account<'info, Config>,
+}
+
+/// Event emitted when a new owner accepts ownership.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnershipTransferred {
+    /// Owner before the transfer.
+    pub previous_owner: Pubkey,
+    /// Owner after the transfer.
+    pub new_owner: Pubkey,
+}
+
+pub(crate) fn handler(ctx: Context<AcceptOwnership>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_owner = config.owner;
+    config.owner = ctx.accounts.new_owner.key();
+    config.pending_owner = Pubkey::default();
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        OwnershipTransferred {
+            previous_owner,
+            new_owner: config.owner,
+        },
+    )?;
+
+    msg!("Ownership transferred from {} to {}", previous_owner, config.owner);
+
+    Ok(())
+}
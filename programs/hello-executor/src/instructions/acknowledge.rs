@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::HelloExecutorMessage,
+    state::{Config, Peer, Received, ReceivedStatus, WormholeEmitter},
+    wormhole_cpi,
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16, sequence: u64)]
+pub struct Acknowledge<'info> {
+    #[account(mut)]
+    /// Payer for the Wormhole fee. Anyone may acknowledge a received message.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
+    )]
+    /// Config account with Wormhole addresses.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer that sent the acknowledged message.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        mut,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &chain.to_le_bytes()[..],
+            &sequence.to_le_bytes()[..],
+        ],
+        bump = received.bump,
+    )]
+    /// Received record of the acknowledged message.
+    pub received: Account<'info, Received>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when a received message is acknowledged to its peer.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AckSent {
+    /// Wormhole chain ID of the acknowledged peer.
+    pub sender_chain: u16,
+    /// Sequence of the acknowledged message.
+    pub sequence: u64,
+    /// Trace ID of the acknowledged message.
+    pub trace_id: [u8; 16],
+    /// Whether the message was delivered, rather than expired, duplicate or
+    /// failed.
+    pub delivered: bool,
+    /// Sequence of the `DeliveryAck` VAA.
+    pub ack_sequence: u64,
+}
+
+pub(crate) fn handler(ctx: Context<Acknowledge>, chain: u16, sequence: u64) -> Result<()> {
+    let received = &ctx.accounts.received;
+    require!(
+        !received.acknowledged,
+        HelloExecutorError::AlreadyAcknowledged,
+    );
+    // The outcome of a held greeting is not known until it is translated.
+    require!(
+        received.status != ReceivedStatus::PendingTranslation,
+        HelloExecutorError::DeliveryPending,
+    );
+
+    let config = &ctx.accounts.config;
+    let delivered = received.status == ReceivedStatus::Delivered;
+    let payload = HelloExecutorMessage::DeliveryAck {
+        sequence,
+        delivered,
+    }
+    .try_to_vec()?;
+
+    let ack_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    let received = &mut ctx.accounts.received;
+    received.acknowledged = true;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        AckSent {
+            sender_chain: chain,
+            sequence,
+            trace_id: received.trace_id,
+            delivered,
+            ack_sequence,
+        },
+    )?;
+
+    msg!(
+        "Acknowledged sequence {} from chain {}: delivered {}. VAA sequence: {}",
+        sequence,
+        chain,
+        delivered,
+        ack_sequence
+    );
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Publisher},
+};
+
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct AddPublisher<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [Publisher::SEED_PREFIX, authority.as_ref()],
+        bump,
+        space = Publisher::MAXIMUM_SIZE,
+    )]
+    /// Allowlist entry for `authority`.
+    pub publisher: Account<'info, Publisher>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<AddPublisher>, authority: Pubkey) -> Result<()> {
+    let publisher = &mut ctx.accounts.publisher;
+    publisher.authority = authority;
+    publisher.bump = ctx.bumps.publisher;
+
+    msg!("Added publisher {}", authority);
+
+    Ok(())
+}
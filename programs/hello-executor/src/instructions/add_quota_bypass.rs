@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, QuotaBypass},
+};
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddQuotaBypass<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [QuotaBypass::SEED_PREFIX, wallet.as_ref()],
+        bump,
+        space = QuotaBypass::MAXIMUM_SIZE,
+    )]
+    /// Bypass list entry for `wallet`.
+    pub quota_bypass: Account<'info, QuotaBypass>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<AddQuotaBypass>, wallet: Pubkey) -> Result<()> {
+    let quota_bypass = &mut ctx.accounts.quota_bypass;
+    quota_bypass.wallet = wallet;
+    quota_bypass.bump = ctx.bumps.quota_bypass;
+
+    msg!("Exempted {} from the send quota", wallet);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::HelloExecutorError,
+    message::HelloExecutorMessage,
+    state::{Config, WormholeEmitter},
+    wormhole_cpi::{self, PostMessage},
+};
+
+use super::SEED_PREFIX_SENT;
+
+#[derive(Accounts)]
+pub struct Announce<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays the Wormhole fee.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config. Created by
+    /// Wormhole with this message.
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PREFIX_SENT,
+            &wormhole::INITIAL_SEQUENCE.to_le_bytes()[..],
+        ],
+        bump,
+    )]
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<Announce>) -> Result<()> {
+    // The Alive message takes sequence 0, which creates the tracker.
+    require!(
+        ctx.accounts.wormhole_sequence.data_is_empty(),
+        HelloExecutorError::AlreadyAnnounced,
+    );
+
+    let config = &ctx.accounts.config;
+    let payload = HelloExecutorMessage::Alive {
+        program_id: ctx.program_id.to_bytes(),
+    }
+    .try_to_vec()?;
+
+    wormhole_cpi::post_message(
+        PostMessage {
+            payer: ctx.accounts.owner.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    msg!("HelloExecutor announced. Emitter: {}", ctx.accounts.wormhole_emitter.key());
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{Config, ModerationVerdict, Received},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16, sequence: u64)]
+pub struct AttestModeration<'info> {
+    /// Moderation oracle. Must match config.moderation_oracle.
+    pub moderation_oracle: Signer<'info>,
+
+    #[account(
+        has_one = moderation_oracle @ HelloExecutorError::ModerationOracleOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the oracle.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &chain.to_le_bytes()[..],
+            &sequence.to_le_bytes()[..],
+        ],
+        bump = received.bump,
+    )]
+    /// Received record to attest.
+    pub received: Account<'info, Received>,
+}
+
+/// Event emitted when the moderation oracle attests a received message.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModerationAttested {
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// The attested verdict.
+    pub verdict: ModerationVerdict,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+}
+
+pub(crate) fn handler(
+    ctx: Context<AttestModeration>,
+    chain: u16,
+    sequence: u64,
+    verdict: ModerationVerdict,
+) -> Result<()> {
+    // The oracle may revise an earlier verdict.
+    let received = &mut ctx.accounts.received;
+    received.verdict = verdict;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        ModerationAttested {
+            sender_chain: chain,
+            sequence,
+            verdict,
+            trace_id: received.trace_id,
+        },
+    )?;
+
+    msg!("Message from chain {} seq {}: {:?}", chain, sequence, verdict);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{AdminAction, Config, PendingAdminAction},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CancelAdminAction<'info> {
+    #[account(mut)]
+    /// Owner of the program. Receives the reclaimed rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PendingAdminAction::SEED_PREFIX, &id.to_le_bytes()[..]],
+        bump = pending_admin_action.bump,
+    )]
+    /// The action to cancel.
+    pub pending_admin_action: Account<'info, PendingAdminAction>,
+}
+
+/// Event emitted when a queued admin action is cancelled.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminActionCancelled {
+    /// ID of the cancelled action.
+    pub id: u64,
+    /// The discarded change.
+    pub action: AdminAction,
+}
+
+pub(crate) fn handler(ctx: Context<CancelAdminAction>, id: u64) -> Result<()> {
+    let action = ctx.accounts.pending_admin_action.action;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        AdminActionCancelled { id, action },
+    )?;
+
+    msg!("Cancelled admin action {}", id);
+
+    Ok(())
+}
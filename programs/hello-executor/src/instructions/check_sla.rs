@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events, return_data,
+    state::{Config, RelayRequest},
+};
+
+#[derive(Accounts)]
+#[instruction(sequence: u64, dst_chain: u16)]
+pub struct CheckSla<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account with the SLA.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            RelayRequest::SEED_PREFIX,
+            &sequence.to_le_bytes()[..],
+            &dst_chain.to_le_bytes()[..],
+        ],
+        bump = relay_request.bump,
+    )]
+    /// RelayRequest to check.
+    pub relay_request: Account<'info, RelayRequest>,
+}
+
+/// Event emitted when a relay misses the delivery SLA.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlaBreached {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Sequence of the relayed VAA.
+    pub sequence: u64,
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Timestamp of the latest request.
+    pub requested_at: i64,
+    /// Reported delivery timestamp; zero while undelivered.
+    pub delivered_at: i64,
+    /// Seconds from request to delivery, or to now while undelivered.
+    pub elapsed_seconds: i64,
+    /// The configured SLA.
+    pub sla_seconds: u32,
+}
+
+/// Emit [SlaBreached] if `relay_request` is past `config`'s SLA at `now`.
+pub(crate) fn emit_if_breached(
+    config: &Config,
+    accounts: &[AccountInfo],
+    relay_request: &RelayRequest,
+    now: i64,
+) -> Result<bool> {
+    let sla_seconds = config.sla_seconds;
+    let Some(elapsed_seconds) = relay_request.sla_breach(sla_seconds, now) else {
+        return Ok(false);
+    };
+
+    events::emit_event(
+        config,
+        accounts,
+        SlaBreached {
+            request_id: relay_request.request_id,
+            dst_chain: relay_request.dst_chain,
+            sequence: relay_request.sequence,
+            trace_id: relay_request.trace_id,
+            requested_at: relay_request.requested_at,
+            delivered_at: relay_request.delivered_at,
+            elapsed_seconds,
+            sla_seconds,
+        },
+    )?;
+
+    Ok(true)
+}
+
+pub(crate) fn handler(ctx: Context<CheckSla>, _sequence: u64, _dst_chain: u16) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let relay_request = &ctx.accounts.relay_request;
+    let breached = emit_if_breached(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        relay_request,
+        now,
+    )?;
+
+    msg!(
+        "Request {}: delivered at {}, SLA breached: {}",
+        hex::encode(relay_request.request_id),
+        relay_request.delivered_at,
+        breached
+    );
+
+    return_data::set(&breached)
+}
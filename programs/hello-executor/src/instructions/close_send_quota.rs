@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, SendQuota},
+};
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct CloseSendQuota<'info> {
+    #[account(mut)]
+    /// Wallet the quota account belongs to. Receives the reclaimed rent.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Provides the current quota epoch.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [SendQuota::SEED_PREFIX, payer.key().as_ref(), &epoch.to_le_bytes()[..]],
+        bump,
+    )]
+    /// Send count of a past epoch.
+    pub send_quota: Account<'info, SendQuota>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseSendQuota>, epoch: u64) -> Result<()> {
+    // A closed current-epoch account would reset the payer's count.
+    let current = ctx.accounts.config.quota_epoch(Clock::get()?.unix_timestamp);
+    require!(epoch < current, HelloExecutorError::QuotaEpochNotOver);
+
+    msg!("Closed send quota for epoch {}", epoch);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    chains::CorridorPreset,
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct ConfigureCorridor<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Registered peer on the corridor's destination chain.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ConfigureCorridor>,
+    chain: u16,
+    preset: CorridorPreset,
+) -> Result<()> {
+    require!(
+        preset.chains().contains(&chain),
+        HelloExecutorError::PresetChainMismatch,
+    );
+    let settings = preset.settings();
+
+    let peer = &mut ctx.accounts.peer;
+    peer.max_age_seconds = settings.max_age_seconds;
+    peer.reject_expired = false;
+    peer.payload_mode = settings.payload_mode;
+    peer.dedup = settings.dedup;
+    peer.max_gas_drop_off = settings.max_gas_drop_off;
+
+    // The rate limit covers every corridor, so one already configured is
+    // left alone.
+    let config = &mut ctx.accounts.config;
+    if config.rate_limit_capacity == 0 {
+        config.rate_limit_capacity = settings.rate_limit_capacity;
+        config.rate_limit_refill_seconds = settings.rate_limit_refill_seconds;
+    }
+
+    msg!(
+        "Corridor to chain {} configured from the {:?} preset: {:?}",
+        chain,
+        preset,
+        settings
+    );
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use super::register_peer::check_registration;
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{Config, Peer, PendingPeer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16, address: [u8; 32])]
+pub struct ConfirmPendingPeer<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the Peer
+    /// account if the chain has none yet.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            PendingPeer::SEED_PREFIX,
+            config.salt_seed(),
+            &chain.to_le_bytes()[..],
+            &address,
+        ],
+        bump = pending_peer.bump,
+    )]
+    /// The handshake to confirm. Closed, refunding whoever delivered it.
+    pub pending_peer: Account<'info, PendingPeer>,
+
+    #[account(mut, address = pending_peer.payer)]
+    /// CHECK: Payer of the PendingPeer account, receiving its rent.
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        // init_if_needed as in register_peer: only the owner reaches it.
+        init_if_needed,
+        payer = owner,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump,
+        space = Peer::MAXIMUM_SIZE,
+    )]
+    /// Peer account for the handshake's chain.
+    pub peer: Account<'info, Peer>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when the owner confirms a handshake, registering its
+/// contract as the peer for its chain.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingPeerConfirmed {
+    /// Wormhole chain ID of the new peer.
+    pub chain: u16,
+    /// Universal address of the new peer.
+    pub address: [u8; 32],
+    /// Protocol version the peer announced.
+    pub protocol_version: u16,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ConfirmPendingPeer>,
+    chain: u16,
+    address: [u8; 32],
+) -> Result<()> {
+    check_registration(&ctx.accounts.config, &ctx.accounts.peer, chain, &address)?;
+
+    // The announced version stands in for a version report until the peer
+    // sends one.
+    let protocol_version = ctx.accounts.pending_peer.protocol_version;
+    let peer = &mut ctx.accounts.peer;
+    peer.chain = chain;
+    peer.address = address;
+    peer.reported_version = protocol_version;
+    peer.bump = ctx.bumps.peer;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        PendingPeerConfirmed {
+            chain,
+            address,
+            protocol_version,
+        },
+    )?;
+
+    msg!(
+        "Registered peer on chain {}: {}",
+        chain,
+        hex::encode(address)
+    );
+
+    Ok(())
+}
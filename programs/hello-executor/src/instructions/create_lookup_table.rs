@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    address_lookup_table::{self, instruction as alt_instruction},
+    program::invoke_signed,
+};
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, LookupTable},
+};
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the table.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [LookupTable::SEED_PREFIX],
+        bump,
+        space = LookupTable::MAXIMUM_SIZE,
+    )]
+    /// Record of the table, and its authority.
+    pub lookup_table: Account<'info, LookupTable>,
+
+    #[account(mut)]
+    /// CHECK: Address lookup table to create. Checked against the address
+    /// derived from the authority and `recent_slot`.
+    pub address_lookup_table: UncheckedAccount<'info>,
+
+    #[account(address = address_lookup_table::program::ID)]
+    /// CHECK: Address lookup table program.
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let authority = ctx.accounts.lookup_table.key();
+    let owner = ctx.accounts.owner.key();
+    let (create, address) =
+        alt_instruction::create_lookup_table_signed(authority, owner, recent_slot);
+    require_keys_eq!(
+        ctx.accounts.address_lookup_table.key(),
+        address,
+        HelloExecutorError::InvalidLookupTable
+    );
+
+    // The fixed accounts of every receive_greeting the resolver returns.
+    let extend = alt_instruction::extend_lookup_table(
+        address,
+        authority,
+        Some(owner),
+        vec![
+            ctx.accounts.config.key(),
+            wormhole::program::ID,
+            ctx.accounts.system_program.key(),
+        ],
+    );
+
+    let account_infos = [
+        ctx.accounts.address_lookup_table.to_account_info(),
+        ctx.accounts.lookup_table.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.address_lookup_table_program.to_account_info(),
+    ];
+    let signer_seeds: &[&[u8]] = &[LookupTable::SEED_PREFIX, &[ctx.bumps.lookup_table]];
+    invoke_signed(&create, &account_infos, &[signer_seeds])?;
+    invoke_signed(&extend, &account_infos, &[signer_seeds])?;
+
+    let lookup_table = &mut ctx.accounts.lookup_table;
+    lookup_table.address = address;
+    lookup_table.bump = ctx.bumps.lookup_table;
+
+    msg!("Created lookup table {}", address);
+
+    Ok(())
+}
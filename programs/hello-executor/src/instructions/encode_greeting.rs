@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    message::{encode_greeting_payload, GreetingOrigin, GREETING_MAX_LENGTH, TRACE_ID_LENGTH},
+    return_data,
+};
+
+#[derive(Accounts)]
+pub struct EncodeGreeting {}
+
+pub(crate) fn handler(
+    _ctx: Context<EncodeGreeting>,
+    greeting: String,
+    trace_id: [u8; TRACE_ID_LENGTH],
+    sender: Pubkey,
+    timestamp: u64,
+) -> Result<()> {
+    // Same validation as send_greeting, so a dry run fails where a send would.
+    require!(
+        greeting.len() <= GREETING_MAX_LENGTH,
+        HelloExecutorError::MessageTooLarge,
+    );
+
+    let payload = encode_greeting_payload(
+        &greeting,
+        trace_id,
+        GreetingOrigin {
+            sender: sender.to_bytes(),
+            timestamp,
+        },
+    )?;
+
+    msg!("Encoded {} byte greeting payload", payload.len());
+
+    return_data::set(&payload)
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events, pda,
+    state::{AdminAction, Config, Peer, PendingAdminAction},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(mut)]
+    /// Owner of the program. Receives the reclaimed rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PendingAdminAction::SEED_PREFIX, &id.to_le_bytes()[..]],
+        bump = pending_admin_action.bump,
+    )]
+    /// The action to execute.
+    pub pending_admin_action: Account<'info, PendingAdminAction>,
+
+    #[account(mut)]
+    /// Peer to update. Required for `UpdatePeer`, omitted otherwise.
+    pub peer: Option<Account<'info, Peer>>,
+}
+
+/// Event emitted when a queued admin action is executed.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminActionExecuted {
+    /// ID of the executed action.
+    pub id: u64,
+    /// The applied change.
+    pub action: AdminAction,
+}
+
+pub(crate) fn handler(ctx: Context<ExecuteAdminAction>, id: u64) -> Result<()> {
+    let pending = &ctx.accounts.pending_admin_action;
+    require!(
+        Clock::get()?.unix_timestamp >= pending.executable_at,
+        HelloExecutorError::TimelockNotElapsed,
+    );
+
+    let action = pending.action;
+    match action {
+        AdminAction::UpdatePeer { chain, address } => {
+            let peer = ctx
+                .accounts
+                .peer
+                .as_mut()
+                .ok_or(HelloExecutorError::InvalidAdminAction)?;
+            require_keys_eq!(
+                peer.key(),
+                pda::peer(&ctx.accounts.config.salt, chain).0,
+                HelloExecutorError::InvalidAdminAction
+            );
+            peer.address = address;
+
+            msg!("Updated peer on chain {}: {}", chain, hex::encode(address));
+        }
+        AdminAction::SetTimelock { seconds } => {
+            ctx.accounts.config.timelock_seconds = seconds;
+
+            msg!("Admin timelock set to {} seconds", seconds);
+        }
+    }
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        AdminActionExecuted { id, action },
+    )?;
+
+    Ok(())
+}
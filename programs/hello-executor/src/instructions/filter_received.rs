@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    return_data,
+    state::{ModerationVerdict, Received},
+};
+
+#[derive(Accounts)]
+pub struct FilterReceived {}
+
+pub(crate) fn handler(
+    ctx: Context<FilterReceived>,
+    verdict: ModerationVerdict,
+) -> Result<()> {
+    let mut matches = Vec::new();
+    for info in ctx.remaining_accounts {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let received = Received::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if received.verdict == verdict {
+            matches.push(info.key());
+        }
+    }
+
+    msg!(
+        "{} of {} received records are {:?}",
+        matches.len(),
+        ctx.remaining_accounts.len(),
+        verdict
+    );
+
+    return_data::set(&matches)
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    return_data,
+    state::{Config, Received, ReceivedIndex},
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct FindReceived<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account, for the PDA salt.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ReceivedIndex::SEED_PREFIX, &vaa_hash],
+        bump = received_index.bump,
+    )]
+    /// Index entry recorded for this VAA hash.
+    pub received_index: Account<'info, ReceivedIndex>,
+
+    #[account(
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &received_index.chain.to_le_bytes()[..],
+            &received_index.sequence.to_le_bytes()[..],
+        ],
+        bump = received.bump,
+    )]
+    /// Received record the index points to.
+    pub received: Account<'info, Received>,
+}
+
+pub(crate) fn handler(ctx: Context<FindReceived>, _vaa_hash: [u8; 32]) -> Result<()> {
+    let received_index = &ctx.accounts.received_index;
+
+    msg!(
+        "VAA {} was received from chain {} seq {} ({:?})",
+        hex::encode(ctx.accounts.received.wormhole_message_hash),
+        received_index.chain,
+        received_index.sequence,
+        ctx.accounts.received.status
+    );
+
+    return_data::set(&ReceivedIndex::clone(received_index))
+}
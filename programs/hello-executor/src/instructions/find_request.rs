@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    return_data,
+    state::{RelayRequest, RelayRequestIndex},
+};
+
+#[derive(Accounts)]
+#[instruction(request_id: [u8; 32], dst_chain: u16)]
+pub struct FindRequest<'info> {
+    #[account(
+        seeds = [
+            RelayRequestIndex::SEED_PREFIX,
+            &request_id,
+            &dst_chain.to_le_bytes()[..],
+        ],
+        bump = relay_request_index.bump,
+    )]
+    /// Index entry recorded for this request ID and destination.
+    pub relay_request_index: Account<'info, RelayRequestIndex>,
+
+    #[account(
+        seeds = [
+            RelayRequest::SEED_PREFIX,
+            &relay_request_index.sequence.to_le_bytes()[..],
+            &relay_request_index.dst_chain.to_le_bytes()[..],
+        ],
+        bump = relay_request.bump,
+    )]
+    /// RelayRequest the index points to.
+    pub relay_request: Account<'info, RelayRequest>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<FindRequest>,
+    _request_id: [u8; 32],
+    _dst_chain: u16,
+) -> Result<()> {
+    let relay_request = &ctx.accounts.relay_request;
+
+    msg!(
+        "Request {} relays sequence {} to chain {}",
+        hex::encode(relay_request.request_id),
+        relay_request.sequence,
+        relay_request.dst_chain
+    );
+
+    return_data::set(&RelayRequest::clone(relay_request))
+}
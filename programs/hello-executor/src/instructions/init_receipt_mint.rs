@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{error::HelloExecutorError, receipts::SEED_PREFIX_RECEIPT_MINT, state::Config};
+
+#[derive(Accounts)]
+pub struct InitReceiptMint<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Mint authority of the receipt mint.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SEED_PREFIX_RECEIPT_MINT],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+    )]
+    /// Receipt mint.
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<InitReceiptMint>) -> Result<()> {
+    msg!("Receipt mint created: {}", ctx.accounts.receipt_mint.key());
+
+    Ok(())
+}
@@ -1,14 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
-use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
+use wormhole_anchor_sdk::wormhole;
 
 use crate::{
-    message::HelloExecutorMessage,
-    state::{Config, WormholeEmitter},
+    error::HelloExecutorError,
+    executor_cpi::ExecutorProgram,
+    finality::{normalize_finality, DEFAULT_FINALITY},
+    state::{Config, WormholeAddresses, WormholeEmitter},
 };
 
-use super::SEED_PREFIX_SENT;
-
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
@@ -25,11 +24,12 @@ pub struct Initialize<'info> {
     /// Config account that stores program configuration.
     pub config: Account<'info, Config>,
 
-    /// Wormhole Core Bridge program.
-    pub wormhole_program: Program<'info, Wormhole>,
+    /// CHECK: Wormhole Core Bridge program - verified by
+    /// config.wormhole_program_id(), the build's Core Bridge for a new config
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
 
     #[account(
-        mut,
         seeds = [wormhole::BridgeData::SEED_PREFIX],
         bump,
         seeds::program = wormhole_program.key,
@@ -38,7 +38,6 @@ pub struct Initialize<'info> {
     pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
 
     #[account(
-        mut,
         seeds = [wormhole::FeeCollector::SEED_PREFIX],
         bump,
         seeds::program = wormhole_program.key,
@@ -57,7 +56,6 @@ pub struct Initialize<'info> {
     pub wormhole_emitter: Account<'info, WormholeEmitter>,
 
     #[account(
-        mut,
         seeds = [
             wormhole::SequenceTracker::SEED_PREFIX,
             wormhole_emitter.key().as_ref(),
@@ -65,103 +63,86 @@ pub struct Initialize<'info> {
         bump,
         seeds::program = wormhole_program.key,
     )]
-    /// CHECK: Emitter's sequence account. Created by Wormhole on first message.
+    /// CHECK: Emitter's sequence account. Only its address is recorded; it
+    /// is created by Wormhole on the first message (see `announce`).
     pub wormhole_sequence: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        seeds = [
-            SEED_PREFIX_SENT,
-            &wormhole::INITIAL_SEQUENCE.to_le_bytes()[..],
-        ],
-        bump,
-    )]
-    /// CHECK: Wormhole message account. Written by Wormhole program.
-    pub wormhole_message: UncheckedAccount<'info>,
-
-    /// Clock sysvar.
-    pub clock: Sysvar<'info, Clock>,
-
-    /// Rent sysvar.
-    pub rent: Sysvar<'info, Rent>,
-
     /// System program.
     pub system_program: Program<'info, System>,
 }
 
-pub(crate) fn handler(ctx: Context<Initialize>, chain_id: u16) -> Result<()> {
-    let config = &mut ctx.accounts.config;
+pub(crate) fn handler(
+    ctx: Context<Initialize>,
+    chain_id: u16,
+    finality: Option<u8>,
+    salt: Option<[u8; 8]>,
+    executor_program: Option<Pubkey>,
+) -> Result<()> {
+    configure(
+        &mut ctx.accounts.config,
+        ctx.accounts.owner.key(),
+        ctx.bumps.config,
+        ConfigureArgs {
+            chain_id,
+            finality,
+            salt,
+            executor_program,
+        },
+        WormholeAddresses {
+            bridge: ctx.accounts.wormhole_bridge.key(),
+            fee_collector: ctx.accounts.wormhole_fee_collector.key(),
+            sequence: ctx.accounts.wormhole_sequence.key(),
+        },
+    )?;
 
-    // Set the owner
-    config.owner = ctx.accounts.owner.key();
-    config.chain_id = chain_id;
+    // Initialize emitter account
+    ctx.accounts.wormhole_emitter.bump = ctx.bumps.wormhole_emitter;
 
-    // Set Wormhole addresses
-    {
-        let wormhole = &mut config.wormhole;
-        wormhole.bridge = ctx.accounts.wormhole_bridge.key();
-        wormhole.fee_collector = ctx.accounts.wormhole_fee_collector.key();
-        wormhole.sequence = ctx.accounts.wormhole_sequence.key();
-    }
+    msg!("HelloExecutor initialized. Owner: {}", ctx.accounts.config.owner);
 
-    // Set default values
-    config.batch_id = 0;
-    config.finality = wormhole::Finality::Finalized as u8;
+    Ok(())
+}
 
-    // Initialize emitter account
-    ctx.accounts.wormhole_emitter.bump = ctx.bumps.wormhole_emitter;
+/// Settings given to `initialize` and `repair_initialize`.
+pub(crate) struct ConfigureArgs {
+    /// Wormhole chain ID of this deployment.
+    pub chain_id: u16,
+    /// Consistency level of published messages; the default if `None`.
+    pub finality: Option<u8>,
+    /// PDA salt; unsalted if `None`.
+    pub salt: Option<[u8; 8]>,
+    /// Executor program; the canonical one if `None`.
+    pub executor_program: Option<Pubkey>,
+}
 
-    // Pay Wormhole fee if required
-    let fee = ctx.accounts.wormhole_bridge.fee();
-    if fee > 0 {
-        solana_program::program::invoke(
-            &solana_program::system_instruction::transfer(
-                &ctx.accounts.owner.key(),
-                &ctx.accounts.wormhole_fee_collector.key(),
-                fee,
-            ),
-            &ctx.accounts.to_account_infos(),
-        )?;
-    }
-
-    // Post initial "Alive" message to create sequence tracker
-    let wormhole_emitter = &ctx.accounts.wormhole_emitter;
-    let config = &ctx.accounts.config;
-
-    let payload = HelloExecutorMessage::Alive {
-        program_id: ctx.program_id.to_bytes(),
-    }
-    .try_to_vec()?;
-
-    wormhole::post_message(
-        CpiContext::new_with_signer(
-            ctx.accounts.wormhole_program.to_account_info(),
-            wormhole::PostMessage {
-                config: ctx.accounts.wormhole_bridge.to_account_info(),
-                message: ctx.accounts.wormhole_message.to_account_info(),
-                emitter: wormhole_emitter.to_account_info(),
-                sequence: ctx.accounts.wormhole_sequence.to_account_info(),
-                payer: ctx.accounts.owner.to_account_info(),
-                fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
-                clock: ctx.accounts.clock.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-            },
-            &[
-                &[
-                    SEED_PREFIX_SENT,
-                    &wormhole::INITIAL_SEQUENCE.to_le_bytes()[..],
-                    &[ctx.bumps.wormhole_message],
-                ],
-                &[WormholeEmitter::SEED_PREFIX, &[wormhole_emitter.bump]],
-            ],
-        ),
-        config.batch_id,
-        payload,
-        config.finality.try_into().unwrap(),
-    )?;
+/// Write a new deployment's config. Shared with `repair_initialize`.
+pub(crate) fn configure(
+    config: &mut Config,
+    owner: Pubkey,
+    bump: u8,
+    args: ConfigureArgs,
+    wormhole: WormholeAddresses,
+) -> Result<()> {
+    let ConfigureArgs {
+        chain_id,
+        finality,
+        salt,
+        executor_program,
+    } = args;
+
+    config.owner = owner;
+    config.chain_id = chain_id;
+    config.bump = bump;
+    config.wormhole = wormhole;
 
-    msg!("HelloExecutor initialized. Owner: {}", config.owner);
+    // Set default values
+    config.batch_id = 0;
+    config.finality = normalize_finality(finality.unwrap_or(DEFAULT_FINALITY))?;
+    config.salt = salt.unwrap_or_default();
+    config.executor_program = executor_program.unwrap_or_else(ExecutorProgram::id);
+    // Fail underpaid relays on Solana rather than let the Executor drop
+    // them silently.
+    config.enforce_min_exec_amount = true;
 
     Ok(())
 }
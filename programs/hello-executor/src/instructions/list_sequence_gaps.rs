@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    return_data,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct ListSequenceGaps<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account, for the PDA salt.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer whose gaps are listed.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<ListSequenceGaps>, chain: u16) -> Result<()> {
+    let gaps = &ctx.accounts.peer.sequence_gaps;
+
+    msg!("Chain {} has {} known sequence gaps", chain, gaps.len());
+
+    return_data::set(gaps)
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{decode_delivery_ack, HelloExecutorMessage},
+    state::{Config, Peer, RelayRequest, RelayStatus, Sent},
+};
+
+use super::{check_sla::emit_if_breached, receive_greeting::RawVaa};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32], sequence: u64)]
+pub struct MarkDelivered<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified Wormhole VAA carrying the peer's acknowledgment.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+        constraint = peer.verify(posted.emitter_address()) @ HelloExecutorError::UnknownEmitter,
+    )]
+    /// Registered peer that sent the acknowledgment.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        mut,
+        seeds = [
+            RelayRequest::SEED_PREFIX,
+            &sequence.to_le_bytes()[..],
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump = relay_request.bump,
+    )]
+    /// RelayRequest of the acknowledged message.
+    pub relay_request: Account<'info, RelayRequest>,
+
+    #[account(mut)]
+    /// Sent record of the acknowledged greeting. Optional: when passed, its
+    /// `status` is set as the RelayRequest's.
+    pub sent: Option<Account<'info, Sent>>,
+}
+
+/// Event emitted when a peer acknowledges a relayed message.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeliveryAcknowledged {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Wormhole chain ID of the acknowledging peer.
+    pub dst_chain: u16,
+    /// Sequence of the acknowledged message.
+    pub sequence: u64,
+    /// Whether the peer delivered the message, rather than rejecting it.
+    pub delivered: bool,
+}
+
+pub(crate) fn handler(
+    ctx: Context<MarkDelivered>,
+    _vaa_hash: [u8; 32],
+    _sequence: u64,
+) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let Some(HelloExecutorMessage::DeliveryAck {
+        sequence,
+        delivered,
+    }) = decode_delivery_ack(&posted.data().0)
+    else {
+        return err!(HelloExecutorError::InvalidMessage);
+    };
+
+    let relay_request = &mut ctx.accounts.relay_request;
+    require!(
+        relay_request.sequence == sequence && relay_request.dst_chain == posted.emitter_chain(),
+        HelloExecutorError::InvalidRelayRequest,
+    );
+    require!(
+        relay_request.status == RelayStatus::Requested,
+        HelloExecutorError::AlreadyDelivered,
+    );
+
+    if delivered {
+        relay_request.status = RelayStatus::Delivered;
+        relay_request.delivered_at = i64::from(posted.timestamp());
+    } else {
+        relay_request.status = RelayStatus::Failed;
+    }
+    if let Some(sent) = &mut ctx.accounts.sent {
+        require_eq!(sent.sequence, sequence, HelloExecutorError::InvalidSentRecord);
+        sent.status = relay_request.status;
+    }
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        DeliveryAcknowledged {
+            request_id: relay_request.request_id,
+            trace_id: relay_request.trace_id,
+            dst_chain: relay_request.dst_chain,
+            sequence,
+            delivered,
+        },
+    )?;
+    if delivered {
+        emit_if_breached(
+            &ctx.accounts.config,
+            ctx.remaining_accounts,
+            relay_request,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    msg!(
+        "Peer on chain {} acknowledged sequence {}: {:?}",
+        relay_request.dst_chain,
+        sequence,
+        relay_request.status
+    );
+
+    Ok(())
+}
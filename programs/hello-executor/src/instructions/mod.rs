@@ -1,16 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+pub use accept_ownership::*;
+pub use acknowledge::*;
+pub use add_publisher::*;
+pub use add_quota_bypass::*;
+pub use announce::*;
+pub use attest_moderation::*;
+pub use cancel_admin_action::*;
+pub use check_sla::*;
+pub use close_send_quota::*;
+pub use configure_corridor::*;
+pub use confirm_pending_peer::*;
+pub use create_lookup_table::*;
+pub use encode_greeting::*;
+pub use execute_admin_action::*;
+pub use filter_received::*;
+pub use find_received::*;
+pub use find_request::*;
+pub use init_receipt_mint::*;
 pub use initialize::*;
+pub use list_sequence_gaps::*;
+pub use mark_delivered::*;
+pub use open_inbox::*;
+#[cfg(feature = "integration-test")]
+pub use post_test_vaa::*;
+pub use probe_peer_version::*;
+pub use prune::*;
+pub use publish_checkpoint::*;
+pub use publish_payload::*;
+pub use publish_stats::*;
+pub use queue_admin_action::*;
+pub use receive_and_reply::*;
+pub use receive_governance::*;
 pub use receive_greeting::*;
+pub use receive_greeting_shim::*;
+#[cfg(feature = "integration-test")]
+pub use receive_greeting_test::*;
+pub use receive_greeting_v2::*;
+pub use receive_handshake::*;
+pub use receive_token_greeting::*;
+pub use register_all::*;
 pub use register_peer::*;
+pub use reject_pending_peer::*;
+pub use remove_publisher::*;
+pub use remove_quota_bypass::*;
+pub use repair_initialize::*;
+pub use report_delivery::*;
+pub use report_version::*;
+pub use request_backfill::*;
+pub use request_cctp_relay::*;
+pub use request_ntt_relay::*;
 pub use request_relay::*;
+pub use retry_relay::*;
 pub use send_greeting::*;
+pub use send_handshake::*;
+pub use set_cpi_callers::*;
+pub use set_delivery_sla::*;
+pub use set_enforce_min_exec_amount::*;
+pub use set_event_mode::*;
+pub use set_executor_program::*;
+pub use set_governance::*;
+pub use set_ignore_duplicate_deliveries::*;
+pub use set_moderation_oracle::*;
+pub use set_pause::*;
+pub use set_payer_buffer::*;
+pub use set_peer_dedup::*;
+pub use set_peer_gas_drop_off::*;
+pub use set_peer_payload_mode::*;
+pub use set_peer_receive_target::*;
+pub use set_peer_reply::*;
+pub use set_peer_trust::*;
+pub use set_peer_ttl::*;
+pub use set_protocol_fee::*;
+pub use set_quote_signers::*;
+pub use set_rate_limit::*;
+pub use set_redaction::*;
+pub use set_resolver_derives_posted_vaa::*;
+pub use set_resolver_limits::*;
+pub use set_resolver_requires_peer::*;
+pub use set_retention_policy::*;
+pub use set_send_quota::*;
+pub use set_spend_cap::*;
+pub use set_translate_above_len::*;
+pub use submit_query_response::*;
+pub use submit_translation::*;
+#[cfg(feature = "integration-test")]
+pub use sweep_test_vaas::*;
+pub use transfer_ownership::*;
 pub use update_config::*;
+pub use update_message_params::*;
+pub use withdraw_treasury::*;
 
+pub mod accept_ownership;
+pub mod acknowledge;
+pub mod add_publisher;
+pub mod add_quota_bypass;
+pub mod announce;
+pub mod attest_moderation;
+pub mod cancel_admin_action;
+pub mod check_sla;
+pub mod close_send_quota;
+pub mod configure_corridor;
+pub mod confirm_pending_peer;
+pub mod create_lookup_table;
+pub mod encode_greeting;
+pub mod execute_admin_action;
+pub mod filter_received;
+pub mod find_received;
+pub mod find_request;
+pub mod init_receipt_mint;
 pub mod initialize;
+pub mod list_sequence_gaps;
+pub mod mark_delivered;
+pub mod open_inbox;
+#[cfg(feature = "integration-test")]
+pub mod post_test_vaa;
+pub mod probe_peer_version;
+pub mod prune;
+pub mod publish_checkpoint;
+pub mod publish_payload;
+pub mod publish_stats;
+pub mod queue_admin_action;
+pub mod receive_and_reply;
+pub mod receive_governance;
 pub mod receive_greeting;
+pub mod receive_greeting_shim;
+#[cfg(feature = "integration-test")]
+pub mod receive_greeting_test;
+pub mod receive_greeting_v2;
+pub mod receive_handshake;
+pub mod receive_token_greeting;
+pub mod register_all;
 pub mod register_peer;
+pub mod reject_pending_peer;
+pub mod remove_publisher;
+pub mod remove_quota_bypass;
+pub mod repair_initialize;
+pub mod report_delivery;
+pub mod report_version;
+pub mod request_backfill;
+pub mod request_cctp_relay;
+pub mod request_ntt_relay;
 pub mod request_relay;
+pub mod retry_relay;
 pub mod send_greeting;
+pub mod send_handshake;
+pub mod set_cpi_callers;
+pub mod set_delivery_sla;
+pub mod set_enforce_min_exec_amount;
+pub mod set_event_mode;
+pub mod set_executor_program;
+pub mod set_governance;
+pub mod set_ignore_duplicate_deliveries;
+pub mod set_moderation_oracle;
+pub mod set_pause;
+pub mod set_payer_buffer;
+pub mod set_peer_dedup;
+pub mod set_peer_gas_drop_off;
+pub mod set_peer_payload_mode;
+pub mod set_peer_receive_target;
+pub mod set_peer_reply;
+pub mod set_peer_trust;
+pub mod set_peer_ttl;
+pub mod set_protocol_fee;
+pub mod set_quote_signers;
+pub mod set_rate_limit;
+pub mod set_redaction;
+pub mod set_resolver_derives_posted_vaa;
+pub mod set_resolver_limits;
+pub mod set_resolver_requires_peer;
+pub mod set_retention_policy;
+pub mod set_send_quota;
+pub mod set_spend_cap;
+pub mod set_translate_above_len;
+pub mod submit_query_response;
+pub mod submit_translation;
+#[cfg(feature = "integration-test")]
+pub mod sweep_test_vaas;
+pub mod transfer_ownership;
 pub mod update_config;
+pub mod update_message_params;
+pub mod withdraw_treasury;
 
 /// Seed prefix for sent message accounts.
 pub const SEED_PREFIX_SENT: &[u8; 4] = b"sent";
+
+/// Create a program-owned PDA whose seeds are only known inside a handler
+/// (so Anchor's `init` constraint cannot be used).
+///
+/// Mirrors what `init` does: tolerates lamports already sitting at the
+/// address by topping up to rent-exemption, then allocating and assigning.
+pub(crate) fn create_program_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(space);
+    let signer = &[signer_seeds];
+
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer,
+            ),
+            required,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer,
+        ),
+        &crate::ID,
+    )
+}
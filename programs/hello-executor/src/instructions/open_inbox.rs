@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Inbox;
+
+#[derive(Accounts)]
+pub struct OpenInbox<'info> {
+    #[account(mut)]
+    /// Wallet opening its inbox. Pays the rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [Inbox::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+        space = Inbox::MAXIMUM_SIZE,
+    )]
+    /// Inbox of `owner`.
+    pub inbox: Account<'info, Inbox>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenInbox>) -> Result<()> {
+    let inbox = &mut ctx.accounts.inbox;
+    inbox.owner = ctx.accounts.owner.key();
+    inbox.bump = ctx.bumps.inbox;
+
+    msg!("Opened inbox for {}", inbox.owner);
+
+    Ok(())
+}
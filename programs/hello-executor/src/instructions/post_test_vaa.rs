@@ -0,0 +1,66 @@
+//! Test-only message injection (`integration-test` feature).
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, TestVaa},
+};
+
+#[derive(Accounts)]
+#[instruction(payload: Vec<u8>, chain: u16, emitter: [u8; 32], sequence: u64)]
+pub struct PostTestVaa<'info> {
+    #[account(mut)]
+    /// Owner of the program, the only trusted poster. Pays for the test VAA.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [TestVaa::SEED_PREFIX, &chain.to_le_bytes()[..], &sequence.to_le_bytes()[..]],
+        bump,
+        space = TestVaa::space(payload.len()),
+    )]
+    /// Injected message.
+    pub test_vaa: Account<'info, TestVaa>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PostTestVaa>,
+    payload: Vec<u8>,
+    chain: u16,
+    emitter: [u8; 32],
+    sequence: u64,
+) -> Result<()> {
+    let expires_at_slot = Clock::get()?.slot + TestVaa::TTL_SLOTS;
+
+    let test_vaa = &mut ctx.accounts.test_vaa;
+    test_vaa.poster = ctx.accounts.owner.key();
+    test_vaa.emitter_chain = chain;
+    test_vaa.emitter_address = emitter;
+    test_vaa.sequence = sequence;
+    test_vaa.expires_at_slot = expires_at_slot;
+    test_vaa.payload = payload;
+    test_vaa.bump = ctx.bumps.test_vaa;
+
+    msg!(
+        "integration-test: {} posted test VAA for chain {} sequence {}, valid until slot {}",
+        test_vaa.poster,
+        chain,
+        sequence,
+        expires_at_slot
+    );
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    message::{HelloExecutorMessage, PROTOCOL_VERSION},
+    state::{Config, Peer, WormholeEmitter},
+    wormhole_cpi,
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct ProbePeerVersion<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays the Wormhole fee.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account with Wormhole addresses. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer to probe.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<ProbePeerVersion>, chain: u16) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let payload = HelloExecutorMessage::VersionProbe {
+        version: PROTOCOL_VERSION,
+    }
+    .try_to_vec()?;
+
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.owner.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    msg!("Version probe for chain {} published! VAA sequence: {}", chain, vaa_sequence);
+
+    Ok(())
+}
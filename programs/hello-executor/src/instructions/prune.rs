@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    error::HelloExecutorError,
+    events, pda,
+    state::{Config, Peer, Received, ReceivedIndex, RetentionPolicy},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct Prune<'info> {
+    #[account(mut)]
+    /// Anyone may prune; paid the policy's tip from the reclaimed rent.
+    pub pruner: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = config.owner @ HelloExecutorError::OwnerOnly,
+    )]
+    /// CHECK: Program owner, receiving the reclaimed rent minus the tip.
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer whose records are pruned.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        seeds = [RetentionPolicy::SEED_PREFIX],
+        bump = retention_policy.bump,
+    )]
+    /// Retention policy deciding which records may be closed.
+    pub retention_policy: Account<'info, RetentionPolicy>,
+}
+
+/// Event emitted when received records are pruned.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReceivedPruned {
+    /// Wormhole chain ID of the peer the records came from.
+    pub sender_chain: u16,
+    /// Records closed.
+    pub pruned: u16,
+    /// Lowest sequence closed.
+    pub first_sequence: u64,
+    /// Highest sequence closed.
+    pub last_sequence: u64,
+    /// Rent reclaimed from the closed records, tip included.
+    pub reclaimed_lamports: u64,
+    /// Lamports paid to the pruner.
+    pub tip_lamports: u64,
+    /// Wallet that pruned.
+    pub pruner: Pubkey,
+}
+
+/// Close up to `limit` of the candidate records passed as remaining
+/// accounts, in `(Received, ReceivedIndex)` pairs, oldest first. The pairs
+/// end at the first account this program does not own, e.g. the event-CPI
+/// accounts.
+///
+/// Candidates the policy does not allow yet are skipped. The ReceivedIndex
+/// is kept: receive paths refuse a message whose index outlives its record
+/// (see `require_not_pruned`), so pruning does not reopen replays.
+pub(crate) fn handler(ctx: Context<Prune>, chain: u16, limit: u16) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let policy = &ctx.accounts.retention_policy;
+    let highest = ctx.accounts.peer.highest_received_sequence;
+    let now = Clock::get()?.unix_timestamp;
+
+    let pruner = ctx.accounts.pruner.to_account_info();
+    let owner = ctx.accounts.owner.to_account_info();
+    let candidates = ctx
+        .remaining_accounts
+        .iter()
+        .take_while(|info| *info.owner == crate::ID)
+        .count();
+    require!(
+        candidates % 2 == 0,
+        HelloExecutorError::InvalidReceivedRecord,
+    );
+
+    let mut pruned: u16 = 0;
+    let mut sequences = (u64::MAX, 0);
+    let (mut reclaimed, mut tip) = (0u64, 0u64);
+    for pair in ctx.remaining_accounts[..candidates].chunks_exact(2) {
+        if pruned >= limit {
+            break;
+        }
+        let (info, index_info) = (&pair[0], &pair[1]);
+        let received = Received::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let index = ReceivedIndex::try_deserialize(&mut &index_info.try_borrow_data()?[..])?;
+        require!(
+            index.chain == chain
+                && *index_info.key == pda::received_index(&received.wormhole_message_hash).0
+                && *info.key == pda::received(&config.salt, chain, index.sequence).0,
+            HelloExecutorError::InvalidReceivedRecord,
+        );
+
+        if !policy.is_eligible(&received, index.sequence, highest, now) {
+            continue;
+        }
+
+        let lamports = info.lamports();
+        let pruner_share = policy.tip(lamports);
+        **pruner.try_borrow_mut_lamports()? += pruner_share;
+        **owner.try_borrow_mut_lamports()? += lamports - pruner_share;
+        **info.try_borrow_mut_lamports()? = 0;
+        info.realloc(0, false)?;
+        info.assign(&system_program::ID);
+
+        pruned += 1;
+        sequences.0 = sequences.0.min(index.sequence);
+        sequences.1 = sequences.1.max(index.sequence);
+        reclaimed += lamports;
+        tip += pruner_share;
+    }
+
+    if pruned == 0 {
+        msg!("Nothing to prune for chain {}", chain);
+        return Ok(());
+    }
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        ReceivedPruned {
+            sender_chain: chain,
+            pruned,
+            first_sequence: sequences.0,
+            last_sequence: sequences.1,
+            reclaimed_lamports: reclaimed,
+            tip_lamports: tip,
+            pruner: pruner.key(),
+        },
+    )?;
+
+    msg!(
+        "Pruned {} records from chain {}: {} lamports reclaimed, {} tipped",
+        pruned,
+        chain,
+        reclaimed,
+        tip
+    );
+
+    Ok(())
+}
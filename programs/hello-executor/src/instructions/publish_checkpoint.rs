@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::HelloExecutorMessage,
+    state::{Config, Peer, WormholeEmitter},
+    wormhole_cpi,
+};
+
+/// Minimum time between history checkpoints for a peer by non-owners.
+pub const CHECKPOINT_PUBLISH_INTERVAL_SECONDS: i64 = 60 * 60;
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct PublishCheckpoint<'info> {
+    #[account(mut)]
+    /// Payer for the Wormhole fee. Anyone may crank; non-owners are rate
+    /// limited.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account with Wormhole addresses.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer whose history hash is published.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when a history checkpoint for a peer is published.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryCheckpointPublished {
+    /// Chain ID of the peer the history is about.
+    pub chain: u16,
+    /// Messages received from the peer.
+    pub received_count: u64,
+    /// Sequence of the latest message received from the peer.
+    pub last_received_sequence: u64,
+    /// Running hash of the messages received from the peer.
+    pub history_hash: [u8; 32],
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+}
+
+pub(crate) fn handler(ctx: Context<PublishCheckpoint>, chain: u16) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    // The owner may publish at any time; cranks are limited so the emitter
+    // is not flooded with checkpoints.
+    let peer = &mut ctx.accounts.peer;
+    require!(
+        ctx.accounts.payer.key() == config.owner
+            || now.saturating_sub(peer.checkpoint_published_at)
+                >= CHECKPOINT_PUBLISH_INTERVAL_SECONDS,
+        HelloExecutorError::CheckpointPublishedTooRecently,
+    );
+    peer.checkpoint_published_at = now;
+
+    let (received_count, last_received_sequence, history_hash) = (
+        peer.received_count,
+        peer.last_received_sequence,
+        peer.history_hash,
+    );
+    let payload = HelloExecutorMessage::HistoryCheckpoint {
+        chain,
+        received_count,
+        last_received_sequence,
+        history_hash,
+    }
+    .try_to_vec()?;
+
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        HistoryCheckpointPublished {
+            chain,
+            received_count,
+            last_received_sequence,
+            history_hash,
+            sequence: vaa_sequence,
+        },
+    )?;
+
+    msg!(
+        "History checkpoint for chain {} published: {} messages, hash {}. VAA sequence: {}",
+        chain,
+        received_count,
+        hex::encode(history_hash),
+        vaa_sequence
+    );
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{self, GREETING_MAX_LENGTH},
+    state::{Config, Publisher, WormholeEmitter},
+    wormhole_cpi,
+};
+
+#[derive(Accounts)]
+pub struct PublishPayload<'info> {
+    #[account(mut)]
+    /// Publisher paying the Wormhole fee. Must be the owner or allowlisted.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
+    )]
+    /// Config account with Wormhole addresses.
+    pub config: Account<'info, Config>,
+
+    /// Allowlist entry for `payer`. Omit when the payer is the owner.
+    pub publisher: Option<Account<'info, Publisher>>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account
+    #[account(mut)]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when an arbitrary payload is published.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayloadPublished {
+    /// Account that published the payload.
+    pub publisher: Pubkey,
+    /// Intended destination chain. Informational only; not part of the payload.
+    pub dst_chain_hint: u16,
+    /// The published bytes.
+    pub payload: Vec<u8>,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID of the message. Not embedded: the payload is published verbatim.
+    pub trace_id: [u8; 16],
+}
+
+pub(crate) fn handler(
+    ctx: Context<PublishPayload>,
+    payload: Vec<u8>,
+    dst_chain_hint: u16,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let payer = ctx.accounts.payer.key();
+
+    // Owner may always publish; anyone else needs an allowlist entry.
+    let allowed = payer == config.owner
+        || ctx
+            .accounts
+            .publisher
+            .as_ref()
+            .is_some_and(|publisher| publisher.authority == payer);
+    require!(allowed, HelloExecutorError::PublisherOnly);
+
+    // Same cap as greetings so Solana peers can store the bytes in `Received`.
+    require!(
+        !payload.is_empty() && payload.len() <= GREETING_MAX_LENGTH,
+        HelloExecutorError::MessageTooLarge,
+    );
+
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        PayloadPublished {
+            publisher: payer,
+            dst_chain_hint,
+            payload,
+            sequence: vaa_sequence,
+            trace_id: message::trace_id(
+                config.chain_id,
+                &ctx.accounts.wormhole_emitter.key().to_bytes(),
+                vaa_sequence,
+            ),
+        },
+    )?;
+
+    msg!("Payload published! VAA sequence: {}", vaa_sequence);
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{ChainStats, HelloExecutorMessage},
+    state::{Config, Peer, WormholeEmitter},
+    wormhole_cpi,
+};
+
+/// Minimum time between stats publications for a peer by non-owners.
+pub const STATS_PUBLISH_INTERVAL_SECONDS: i64 = 60 * 60;
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct PublishStats<'info> {
+    #[account(mut)]
+    /// Payer for the Wormhole fee. Anyone may crank; non-owners are rate
+    /// limited.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account with Wormhole addresses.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer whose counters are published.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole message account. Written by Wormhole program.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when a peer's stats are published.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatsPublished {
+    /// Chain ID of the peer the stats are about.
+    pub chain: u16,
+    /// Messages received from the peer.
+    pub received_count: u64,
+    /// Messages published by the program emitter before this one.
+    pub messages_sent: u64,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+}
+
+pub(crate) fn handler(ctx: Context<PublishStats>, chain: u16) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    // The owner may publish at any time; cranks are limited so the emitter
+    // is not flooded with stats messages.
+    let peer = &mut ctx.accounts.peer;
+    require!(
+        ctx.accounts.payer.key() == config.owner
+            || now.saturating_sub(peer.stats_published_at) >= STATS_PUBLISH_INTERVAL_SECONDS,
+        HelloExecutorError::StatsPublishedTooRecently,
+    );
+    peer.stats_published_at = now;
+
+    // The sequence tracker counts every message the emitter has published.
+    let messages_sent = wormhole_cpi::next_sequence(&ctx.accounts.wormhole_sequence)?;
+
+    let stats = ChainStats {
+        chain,
+        received_count: peer.received_count,
+        last_received_sequence: peer.last_received_sequence,
+        messages_sent,
+        timestamp: now as u64,
+    };
+    let payload = HelloExecutorMessage::Stats(stats).try_to_vec()?;
+
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        StatsPublished {
+            chain,
+            received_count: stats.received_count,
+            messages_sent,
+            sequence: vaa_sequence,
+        },
+    )?;
+
+    msg!("Stats for chain {} published! VAA sequence: {}", chain, vaa_sequence);
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{AdminAction, Config, PendingAdminAction},
+};
+
+#[derive(Accounts)]
+pub struct QueueAdminAction<'info> {
+    #[account(mut)]
+    /// Owner of the program. Pays for the pending action account.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner and assigns the action ID.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            PendingAdminAction::SEED_PREFIX,
+            &config.next_admin_action_id.to_le_bytes()[..],
+        ],
+        bump,
+        space = PendingAdminAction::MAXIMUM_SIZE,
+    )]
+    /// The queued action.
+    pub pending_admin_action: Account<'info, PendingAdminAction>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when an admin action is queued.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdminActionQueued {
+    /// ID of the queued action.
+    pub id: u64,
+    /// The queued change.
+    pub action: AdminAction,
+    /// Earliest time the action may execute.
+    pub executable_at: i64,
+}
+
+pub(crate) fn handler(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Reject now what register_peer would reject, rather than after the delay.
+    if let AdminAction::UpdatePeer { chain, address } = action {
+        require!(
+            chain > 0 && chain != config.chain_id && address != [0; 32],
+            HelloExecutorError::InvalidPeer,
+        );
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let id = config.next_admin_action_id;
+    let executable_at = now.saturating_add(i64::from(config.timelock_seconds));
+    config.next_admin_action_id += 1;
+
+    let pending = &mut ctx.accounts.pending_admin_action;
+    pending.id = id;
+    pending.action = action;
+    pending.queued_at = now;
+    pending.executable_at = executable_at;
+    pending.bump = ctx.bumps.pending_admin_action;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        AdminActionQueued {
+            id,
+            action,
+            executable_at,
+        },
+    )?;
+
+    msg!("Queued admin action {}: {:?}, executable at {}", id, action, executable_at);
+
+    Ok(())
+}
@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{self, encode_greeting_for_peer, GreetingOrigin},
+    state::{Config, Peer, Received, ReceivedIndex, WormholeEmitter},
+    wormhole_cpi,
+};
+
+use super::receive_greeting::{mint_receipt, process_message, InboundMessage, RawVaa};
+
+/// [ReceiveGreeting](super::ReceiveGreeting)'s accounts, followed by those
+/// `send_greeting` needs to post the reply.
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveAndReply<'info> {
+    #[account(mut)]
+    /// Payer for the Received account, the Wormhole fee and the reply
+    /// message account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified Wormhole VAA containing the greeting.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        mut,
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+        constraint = peer.verify(posted.emitter_address()) @ HelloExecutorError::UnknownEmitter,
+    )]
+    /// Registered peer that sent this message, and receives the reply.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+            &posted.sequence().to_le_bytes()[..],
+        ],
+        bump,
+        space = Received::MAXIMUM_SIZE,
+    )]
+    /// Received account for replay protection.
+    pub received: Account<'info, Received>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [ReceivedIndex::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = ReceivedIndex::MAXIMUM_SIZE,
+    )]
+    /// Index from the VAA hash to the Received record.
+    pub received_index: Account<'info, ReceivedIndex>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ HelloExecutorError::InvalidWormholeConfig,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole fee collector - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ HelloExecutorError::InvalidWormholeFeeCollector,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Emitter's sequence account - verified by config
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ HelloExecutorError::InvalidWormholeSequence,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Reply message account. Written by Wormhole program; checked
+    /// by `wormhole_cpi::post_message`.
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when a reply is posted back to the sender's chain.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplySent {
+    /// The reply text.
+    pub reply: String,
+    /// Chain ID the reply is addressed to.
+    pub dst_chain: u16,
+    /// Sequence of the greeting being replied to.
+    pub in_reply_to: u64,
+    /// Sequence number of the reply's Wormhole message.
+    pub sequence: u64,
+    /// Trace ID embedded in the reply.
+    pub trace_id: [u8; 16],
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveAndReply>, vaa_hash: [u8; 32]) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let emitter_chain = posted.emitter_chain();
+    let sequence = posted.sequence();
+
+    let greeting = process_message(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.peer,
+        &mut ctx.accounts.received,
+        ctx.bumps.received,
+        InboundMessage {
+            payload: &posted.data().0,
+            emitter_chain,
+            emitter_address: *posted.emitter_address(),
+            sequence,
+            timestamp: posted.timestamp(),
+            batch_id: posted.batch_id(),
+            vaa_hash,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let received_index = &mut ctx.accounts.received_index;
+    received_index.chain = emitter_chain;
+    received_index.sequence = sequence;
+    received_index.bump = ctx.bumps.received_index;
+
+    // Only delivered greetings get a reply; a failed reply must not undo
+    // the delivery, so reasons to skip it are logged rather than raised.
+    let config = &ctx.accounts.config;
+    let reply = ctx.accounts.peer.reply_text.clone();
+    if greeting.is_none() {
+        msg!("Nothing delivered; no reply");
+    } else if reply.is_empty() {
+        msg!("Peer on chain {} has no reply configured", emitter_chain);
+    } else if config.outbound_paused {
+        msg!("Outbound messaging paused; no reply");
+    } else if wormhole_cpi::next_sequence(&ctx.accounts.wormhole_sequence)? == 0 {
+        msg!("Emitter not announced; no reply");
+    } else {
+        let vaa_sequence = post_reply(&ctx, reply, emitter_chain, sequence)?;
+
+        let config = &mut ctx.accounts.config;
+        config.sent_count += 1;
+        config.last_sent_sequence = vaa_sequence;
+        let peer = &mut ctx.accounts.peer;
+        peer.sent_count += 1;
+        peer.last_sent_sequence = vaa_sequence;
+    }
+
+    mint_receipt(
+        &ctx.accounts.config,
+        &ctx.accounts.peer,
+        ctx.remaining_accounts,
+        greeting.as_deref(),
+    )
+}
+
+/// Publish `reply` as a greeting from the program itself. Returns its VAA
+/// sequence.
+fn post_reply(
+    ctx: &Context<ReceiveAndReply>,
+    reply: String,
+    dst_chain: u16,
+    in_reply_to: u64,
+) -> Result<u64> {
+    let config = &ctx.accounts.config;
+    let emitter = &ctx.accounts.wormhole_emitter;
+
+    let trace_id = message::trace_id(
+        config.chain_id,
+        &emitter.key().to_bytes(),
+        wormhole_cpi::next_sequence(&ctx.accounts.wormhole_sequence)?,
+    );
+    let payload = encode_greeting_for_peer(
+        ctx.accounts.peer.reported_version,
+        &reply,
+        trace_id,
+        GreetingOrigin {
+            sender: crate::ID.to_bytes(),
+            timestamp: ctx.accounts.clock.unix_timestamp as u64,
+        },
+    )?;
+
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        emitter.bump,
+        config.batch_id,
+        config.finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        ReplySent {
+            reply,
+            dst_chain,
+            in_reply_to,
+            sequence: vaa_sequence,
+            trace_id,
+        },
+    )?;
+
+    msg!("Replied to chain {} seq {}: VAA sequence {}", dst_chain, in_reply_to, vaa_sequence);
+
+    Ok(vaa_sequence)
+}
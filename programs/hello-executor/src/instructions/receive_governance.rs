@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use super::{create_program_account, receive_greeting::RawVaa, register_peer::check_registration};
+use crate::{
+    error::HelloExecutorError,
+    events,
+    finality::normalize_finality,
+    governance::{parse_governance, GovernanceAction},
+    pda,
+    state::{Config, Governance, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveGovernance<'info> {
+    #[account(mut)]
+    /// Payer for a peer the VAA registers. Anyone may apply a governance
+    /// VAA.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Not checked for `inbound_paused`, so governance can
+    /// resume a paused program.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified governance VAA.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        mut,
+        seeds = [Governance::SEED_PREFIX],
+        bump = governance.bump,
+        constraint = governance.verify(posted.emitter_chain(), posted.emitter_address())
+            @ HelloExecutorError::UnknownGovernanceEmitter,
+    )]
+    /// Governance account naming the emitter.
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut)]
+    /// CHECK: Peer a `RegisterPeer` action registers, created if missing;
+    /// verified in the handler. Omitted for other actions.
+    pub peer: Option<UncheckedAccount<'info>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a governance VAA is applied.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GovernanceActionApplied {
+    /// Sequence of the governance VAA.
+    pub sequence: u64,
+    /// The applied change.
+    pub action: GovernanceAction,
+}
+
+/// Register or update the peer on `chain` in `info`, as `register_peer`
+/// does, creating the account if missing. Shared with `register_all`.
+pub(crate) fn register_peer_account<'info>(
+    config: &Config,
+    payer: &AccountInfo<'info>,
+    info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    chain: u16,
+    address: [u8; 32],
+) -> Result<()> {
+    let (expected, bump) = pda::peer(&config.salt, chain);
+    require_keys_eq!(info.key(), expected, HelloExecutorError::InvalidPeer);
+
+    let exists = info.owner == &crate::ID;
+    let mut peer = if exists {
+        Peer::try_deserialize(&mut &info.try_borrow_data()?[..])?
+    } else {
+        Peer::default()
+    };
+    check_registration(config, &peer, chain, &address)?;
+
+    if !exists {
+        create_program_account(
+            payer,
+            info,
+            system_program,
+            Peer::MAXIMUM_SIZE,
+            &[
+                Peer::SEED_PREFIX,
+                config.salt_seed(),
+                &chain.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    peer.chain = chain;
+    peer.address = address;
+    peer.bump = bump;
+    let mut data = info.try_borrow_mut_data()?;
+    peer.try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "Registered peer on chain {}: {}",
+        chain,
+        hex::encode(address)
+    );
+
+    Ok(())
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveGovernance>, _vaa_hash: [u8; 32]) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let sequence = posted.sequence();
+    let (chain, action) =
+        parse_governance(&posted.data().0).ok_or(HelloExecutorError::InvalidGovernanceMessage)?;
+    require_eq!(
+        chain,
+        ctx.accounts.config.chain_id,
+        HelloExecutorError::InvalidGovernanceMessage
+    );
+
+    // Each VAA is applied once, in sequence order.
+    ctx.accounts.governance.apply(sequence)?;
+
+    match action {
+        GovernanceAction::RegisterPeer { chain, address } => {
+            let peer = ctx
+                .accounts
+                .peer
+                .as_ref()
+                .ok_or(HelloExecutorError::InvalidPeer)?;
+            register_peer_account(
+                &ctx.accounts.config,
+                &ctx.accounts.payer.to_account_info(),
+                &peer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                chain,
+                address,
+            )?;
+        }
+        GovernanceAction::SetPause { outbound, inbound } => {
+            let config = &mut ctx.accounts.config;
+            config.outbound_paused = outbound;
+            config.inbound_paused = inbound;
+        }
+        GovernanceAction::UpdateMessageParams { finality, batch_id } => {
+            let config = &mut ctx.accounts.config;
+            config.finality = normalize_finality(finality)?;
+            config.batch_id = batch_id;
+        }
+        GovernanceAction::SetPayerBuffer { lamports } => {
+            ctx.accounts.config.payer_buffer_lamports = lamports;
+        }
+    }
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        GovernanceActionApplied { sequence, action },
+    )?;
+
+    msg!("Applied governance VAA {}: {:?}", sequence, action);
+
+    Ok(())
+}
@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
-use wormhole_anchor_sdk::wormhole::{self, program::Wormhole};
+use anchor_lang::solana_program::keccak;
+use wormhole_anchor_sdk::wormhole;
 
 use crate::{
     error::HelloExecutorError,
-    message::{HelloExecutorMessage, GREETING_MAX_LENGTH},
-    state::{Config, Peer, Received},
+    events,
+    message::{
+        self, decode_content, decode_origin, decode_trace_id, decode_version_message,
+        sanitize_for_log, ContentType, HelloExecutorMessage, GREETING_MAX_LENGTH,
+        LOG_GREETING_MAX_CHARS,
+    },
+    state::{Config, PayloadMode, Peer, Received, ReceivedIndex, ReceivedStatus},
 };
 
 /// Raw message wrapper that accepts any payload bytes.
@@ -40,7 +46,7 @@ impl AnchorSerialize for RawPayload {
 }
 
 /// Type alias for the posted VAA containing raw payload bytes.
-type RawVaa = wormhole::PostedVaa<RawPayload>;
+pub(crate) type RawVaa = wormhole::PostedVaa<RawPayload>;
 
 #[derive(Accounts)]
 #[instruction(vaa_hash: [u8; 32])]
@@ -50,14 +56,17 @@ pub struct ReceiveGreeting<'info> {
     pub payer: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [Config::SEED_PREFIX],
-        bump,
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
     )]
     /// Config account.
     pub config: Account<'info, Config>,
 
-    /// Wormhole Core Bridge program.
-    pub wormhole_program: Program<'info, Wormhole>,
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
 
     #[account(
         seeds = [
@@ -72,21 +81,24 @@ pub struct ReceiveGreeting<'info> {
     pub posted: Account<'info, RawVaa>,
 
     #[account(
+        mut,
         seeds = [
             Peer::SEED_PREFIX,
+            config.salt_seed(),
             &posted.emitter_chain().to_le_bytes()[..],
         ],
-        bump,
+        bump = peer.bump,
         constraint = peer.verify(posted.emitter_address()) @ HelloExecutorError::UnknownEmitter,
     )]
     /// Registered peer that sent this message.
     pub peer: Account<'info, Peer>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         seeds = [
             Received::SEED_PREFIX,
+            config.salt_seed(),
             &posted.emitter_chain().to_le_bytes()[..],
             &posted.sequence().to_le_bytes()[..],
         ],
@@ -94,17 +106,29 @@ pub struct ReceiveGreeting<'info> {
         space = Received::MAXIMUM_SIZE,
     )]
     /// Received account for replay protection.
-    /// Creating this account prevents the same message from being processed twice.
+    /// Creating this account prevents the same message from being processed twice;
+    /// an existing one is a duplicate delivery, see [skip_duplicate_delivery].
     pub received: Account<'info, Received>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [ReceivedIndex::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = ReceivedIndex::MAXIMUM_SIZE,
+    )]
+    /// Index from the VAA hash to the Received record.
+    pub received_index: Account<'info, ReceivedIndex>,
+
     /// System program.
     pub system_program: Program<'info, System>,
 }
 
 /// Event emitted when a greeting is received.
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GreetingReceived {
-    /// The greeting message.
+    /// The greeting message; empty while `config.redact_events` is set.
     pub greeting: String,
     /// Chain ID of the sender.
     pub sender_chain: u16,
@@ -112,68 +136,610 @@ pub struct GreetingReceived {
     pub sender: [u8; 32],
     /// Sequence number of the Wormhole message.
     pub sequence: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+    /// Wallet that sent the greeting; zero if the payload does not say.
+    pub origin_sender: [u8; 32],
+    /// Unix timestamp at which the greeting was sent; zero if unknown.
+    pub sent_at: u64,
+    /// Keccak256 hash of the greeting.
+    pub greeting_hash: [u8; 32],
+    /// Length of the greeting in bytes.
+    pub greeting_len: u32,
+}
+
+/// Event emitted when a raw-mode peer's payload, or a greeting with
+/// non-text content, is received.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayloadReceived {
+    /// The received bytes: the whole payload from a raw-mode peer, the
+    /// content of a typed greeting; empty while `config.redact_events` is
+    /// set.
+    pub payload: Vec<u8>,
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Universal address of the sender.
+    pub sender: [u8; 32],
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+    /// Keccak256 hash of the payload.
+    pub payload_hash: [u8; 32],
+    /// Length of the payload in bytes.
+    pub payload_len: u32,
+    /// How `payload` is to be interpreted; `Binary` from a raw-mode peer.
+    pub content_type: ContentType,
+}
+
+/// Event emitted when a greeting is held for translation instead of
+/// delivered. `GreetingReceived` follows once the translation is submitted.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranslationRequested {
+    /// The greeting to translate; empty while `config.redact_events` is
+    /// set, in which case the oracle reads it from the `Received` record.
+    pub greeting: String,
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+    /// Keccak256 hash of the greeting.
+    pub greeting_hash: [u8; 32],
+    /// Length of the greeting in bytes.
+    pub greeting_len: u32,
+}
+
+/// Event emitted when a peer reports its protocol version, in a probe or an
+/// answer to one.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerVersionReported {
+    /// Chain ID of the peer.
+    pub sender_chain: u16,
+    /// Reported protocol version.
+    pub version: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Whether the message was a probe, which `report_version` answers.
+    pub probe: bool,
+}
+
+/// Event emitted when a message from a peer skips sequences: they were
+/// published but have not been delivered (yet).
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceGapDetected {
+    /// Chain ID of the peer.
+    pub sender_chain: u16,
+    /// First missing sequence.
+    pub from_sequence: u64,
+    /// Last missing sequence.
+    pub to_sequence: u64,
+    /// Sequence of the message that revealed the gap.
+    pub sequence: u64,
+}
+
+/// Event emitted when a greeting is recorded as expired instead of delivered.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreetingExpired {
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// VAA timestamp.
+    pub vaa_timestamp: u32,
+    /// Peer's configured maximum age in seconds.
+    pub max_age_seconds: u32,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+}
+
+/// Event emitted when a message is recorded as a duplicate instead of delivered.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreetingDuplicate {
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+}
+
+/// Event emitted when a message delivered again after it was received is
+/// ignored (`config.ignore_duplicate_deliveries`), e.g. a self-relay that
+/// raced the Executor.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateDeliveryIgnored {
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID recorded by the first delivery.
+    pub trace_id: [u8; 16],
+    /// Wallet that paid for this delivery.
+    pub payer: Pubkey,
 }
 
 /// Payload ID for Hello message (from Solana senders)
 const PAYLOAD_ID_HELLO: u8 = 1;
 
+/// Payload ID for traced Hello message (from Solana senders)
+const PAYLOAD_ID_TRACED_HELLO: u8 = 3;
+
+/// Payload ID for Hello v2 message (from Solana senders)
+const PAYLOAD_ID_HELLO_V2: u8 = 4;
+
+/// Payload ID for typed Hello message (from Solana senders)
+const PAYLOAD_ID_TYPED_HELLO: u8 = 10;
+
 pub(crate) fn handler(ctx: Context<ReceiveGreeting>, vaa_hash: [u8; 32]) -> Result<()> {
     let posted = &ctx.accounts.posted;
-    let payload = &posted.data().0;
-
-    // Auto-detect payload format:
-    // - If first byte is 0x01, it's HelloExecutorMessage format (from Solana)
-    // - Otherwise, treat as raw bytes (from EVM)
-    let message: Vec<u8> = if !payload.is_empty() && payload[0] == PAYLOAD_ID_HELLO {
-        // Solana format: 0x01 (payload ID) + u16 big-endian length + message bytes
-        msg!("Detected structured payload format (Solana sender)");
-        
-        match HelloExecutorMessage::deserialize(&mut &payload[..]) {
-            Ok(HelloExecutorMessage::Hello { message }) => message,
-            Ok(HelloExecutorMessage::Alive { .. }) => {
-                msg!("Received Alive message, not a greeting");
-                return Err(HelloExecutorError::InvalidMessage.into());
-            }
-            Err(e) => {
-                msg!("Failed to parse as HelloExecutorMessage: {:?}", e);
-                return Err(HelloExecutorError::InvalidMessage.into());
-            }
+    require_not_pruned(
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        &ctx.accounts.received_index,
+        ctx.bumps.received_index,
+    )?;
+    if skip_duplicate_delivery(
+        &ctx.accounts.config,
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        posted.emitter_chain(),
+        posted.sequence(),
+        &ctx.accounts.payer.key(),
+        ctx.remaining_accounts,
+    )? {
+        return Ok(());
+    }
+
+    let greeting = process_message(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.peer,
+        &mut ctx.accounts.received,
+        ctx.bumps.received,
+        InboundMessage {
+            payload: &posted.data().0,
+            emitter_chain: posted.emitter_chain(),
+            emitter_address: *posted.emitter_address(),
+            sequence: posted.sequence(),
+            timestamp: posted.timestamp(),
+            batch_id: posted.batch_id(),
+            vaa_hash,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let received_index = &mut ctx.accounts.received_index;
+    received_index.chain = posted.emitter_chain();
+    received_index.sequence = posted.sequence();
+    received_index.bump = ctx.bumps.received_index;
+
+    mint_receipt(
+        &ctx.accounts.config,
+        &ctx.accounts.peer,
+        ctx.remaining_accounts,
+        greeting.as_deref(),
+    )
+}
+
+/// Refuse a message whose Received record `prune` closed. Its ReceivedIndex
+/// is kept, so a just-created (zeroed) `received` with an existing
+/// `received_index` means the message was received before.
+pub(crate) fn require_not_pruned(
+    received: &Received,
+    received_bump: u8,
+    received_index: &ReceivedIndex,
+    received_index_bump: u8,
+) -> Result<()> {
+    require!(
+        received.bump == received_bump || received_index.bump != received_index_bump,
+        HelloExecutorError::AlreadyReceived,
+    );
+    Ok(())
+}
+
+/// Whether `received` is the record of an earlier delivery of the message
+/// rather than an account created for this one, which is then skipped.
+///
+/// A created account is zeroed, so its bump is unset. Duplicates are
+/// skipped with a `DuplicateDeliveryIgnored` event while
+/// `config.ignore_duplicate_deliveries` is set, and revert with
+/// `AlreadyReceived` otherwise.
+pub(crate) fn skip_duplicate_delivery(
+    config: &Config,
+    received: &Received,
+    received_bump: u8,
+    emitter_chain: u16,
+    sequence: u64,
+    payer: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<bool> {
+    if received.bump != received_bump {
+        return Ok(false);
+    }
+    require!(
+        config.ignore_duplicate_deliveries(),
+        HelloExecutorError::AlreadyReceived,
+    );
+
+    events::emit_event(
+        config,
+        accounts,
+        DuplicateDeliveryIgnored {
+            sender_chain: emitter_chain,
+            sequence,
+            trace_id: received.trace_id,
+            payer: *payer,
+        },
+    )?;
+
+    msg!(
+        "Message from chain {} seq {} was already received; ignoring delivery",
+        emitter_chain,
+        sequence
+    );
+
+    Ok(true)
+}
+
+/// Mint a receipt for a delivered `greeting` (`receipts` feature), from the
+/// receipt accounts passed as remaining accounts.
+pub(crate) fn mint_receipt<'info>(
+    config: &Account<'info, Config>,
+    peer: &Peer,
+    remaining_accounts: &[AccountInfo<'info>],
+    greeting: Option<&str>,
+) -> Result<()> {
+    // Receipts put a token in the recipient's wallet; low-trust peers only
+    // get plain delivery.
+    #[cfg(feature = "receipts")]
+    if let Some(greeting) = greeting {
+        if !peer.is_high_trust() {
+            msg!("Peer trust too low for a receipt; skipping receipt");
+            return Ok(());
         }
+        crate::receipts::mint_receipt(config, remaining_accounts, greeting)?;
+    }
+    #[cfg(not(feature = "receipts"))]
+    let _ = (config, peer, remaining_accounts, greeting);
+
+    Ok(())
+}
+
+/// Record the protocol version a peer reported, and queue an answer to a
+/// probe for `report_version`.
+fn record_peer_version(
+    config: &Config,
+    peer: &mut Peer,
+    inbound: &InboundMessage,
+    message: HelloExecutorMessage,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let (chain, sequence) = (inbound.emitter_chain, inbound.sequence);
+    let (version, probe) = match message {
+        HelloExecutorMessage::VersionProbe { version } => (version, true),
+        HelloExecutorMessage::VersionReport { version, .. } => (version, false),
+        _ => return Ok(()),
+    };
+    peer.reported_version = version;
+    if probe {
+        peer.pending_probe = Some(sequence);
+    }
+
+    events::emit_event(
+        config,
+        accounts,
+        PeerVersionReported {
+            sender_chain: chain,
+            version,
+            sequence,
+            probe,
+        },
+    )?;
+
+    msg!("Peer on chain {} reports protocol version {}", chain, version);
+
+    Ok(())
+}
+
+/// Count a recorded message, track its sequence and extend the peer's
+/// history hash. Every recorded message counts, delivered or not.
+pub(crate) fn record_arrival(
+    config: &mut Config,
+    peer: &mut Peer,
+    inbound: &InboundMessage,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    peer.received_count += 1;
+    peer.last_received_sequence = inbound.sequence;
+    peer.record_history(&keccak::hash(inbound.payload).to_bytes());
+    config.received_count += 1;
+    if let Some(gap) = peer.record_sequence(inbound.sequence) {
+        events::emit_event(
+            config,
+            accounts,
+            SequenceGapDetected {
+                sender_chain: inbound.emitter_chain,
+                from_sequence: gap.from,
+                to_sequence: gap.to,
+                sequence: inbound.sequence,
+            },
+        )?;
+
+        msg!(
+            "Sequences {}..={} from chain {} not received",
+            gap.from,
+            gap.to,
+            inbound.emitter_chain
+        );
+    }
+
+    Ok(())
+}
+
+/// The parts of a verified inbound message that [`process_message`] uses.
+#[derive(Clone, Copy)]
+pub(crate) struct InboundMessage<'a> {
+    /// Message payload.
+    pub payload: &'a [u8],
+    /// Wormhole chain ID of the sender.
+    pub emitter_chain: u16,
+    /// Universal address of the sender.
+    pub emitter_address: [u8; 32],
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Message timestamp.
+    pub timestamp: u32,
+    /// Batch ID (nonce).
+    pub batch_id: u32,
+    /// Keccak256 hash of the VAA.
+    pub vaa_hash: [u8; 32],
+}
+
+/// Decode, check and record an inbound message from `peer`.
+///
+/// Everything `receive_greeting` does once Wormhole has verified the VAA.
+/// Returns the greeting if one was delivered. Events are emitted through
+/// the event-CPI accounts among `accounts`, if any (see [crate::events]).
+pub(crate) fn process_message(
+    config: &mut Config,
+    peer: &mut Peer,
+    received: &mut Received,
+    received_bump: u8,
+    inbound: InboundMessage,
+    accounts: &[AccountInfo],
+) -> Result<Option<String>> {
+    let payload = inbound.payload;
+    let raw_mode = peer.payload_mode == PayloadMode::Raw;
+    let version_message = decode_version_message(payload).filter(|_| !raw_mode);
+
+    let (content_type, message) = if raw_mode {
+        // Generic message-bus mode: store the bytes verbatim, no UTF-8 check.
+        // Raw payloads are consumed by other programs, so only high-trust
+        // peers may send them.
+        msg!("Peer uses raw payload mode");
+        require!(peer.is_high_trust(), HelloExecutorError::PeerTrustTooLow);
+        require!(
+            !payload.is_empty() && payload.len() <= GREETING_MAX_LENGTH,
+            HelloExecutorError::InvalidMessage,
+        );
+        (ContentType::Binary, payload.to_vec())
+    } else if version_message.is_some() {
+        // Version negotiation, recorded without a message.
+        (ContentType::Text, Vec::new())
     } else {
-        // EVM format: raw UTF-8 bytes
-        msg!("Detected raw payload format (EVM sender)");
-        payload.clone()
+        // Auto-detect payload format:
+        // - If first byte is 0x01, 0x03, 0x04 or 0x0a, it's HelloExecutorMessage
+        //   format (from Solana)
+        // - Otherwise, treat as raw bytes (from EVM)
+        if matches!(
+            payload.first(),
+            Some(
+                &PAYLOAD_ID_HELLO
+                    | &PAYLOAD_ID_TRACED_HELLO
+                    | &PAYLOAD_ID_HELLO_V2
+                    | &PAYLOAD_ID_TYPED_HELLO
+            )
+        ) {
+            msg!("Detected structured payload format (Solana sender)");
+        } else {
+            msg!("Detected raw payload format (EVM sender)");
+        }
+
+        // Decode and validate (length cap, UTF-8 for text) with the same
+        // rules the resolver applies before telling the Executor to deliver
+        // this VAA.
+        decode_content(payload).map_err(|e| {
+            msg!("Failed to decode greeting payload: {:?}", e);
+            HelloExecutorError::InvalidMessage
+        })?
     };
 
-    // Validate message length
-    require!(
-        message.len() <= GREETING_MAX_LENGTH,
-        HelloExecutorError::InvalidMessage,
-    );
+    // Carry the sender's trace ID through; untraced (e.g. EVM) messages get
+    // the one it would have assigned.
+    let trace_id = decode_trace_id(payload)
+        .filter(|_| !raw_mode)
+        .unwrap_or_else(|| {
+            message::trace_id(inbound.emitter_chain, &inbound.emitter_address, inbound.sequence)
+        });
+
+    // Only v2 greetings name their sending wallet.
+    let origin = decode_origin(payload).filter(|_| !raw_mode).unwrap_or_default();
+
+    // Enforce the peer's freshness window, if configured.
+    let now = Clock::get()?.unix_timestamp;
+    let expired = peer.is_expired(inbound.timestamp, now);
+    if expired {
+        require!(!peer.reject_expired, HelloExecutorError::MessageExpired);
+    }
+
+    record_arrival(config, peer, &inbound, accounts)?;
+
+    // Store in Received account for reference
+    received.receiver_version = 1;
+    received.batch_id = inbound.batch_id;
+    received.wormhole_message_hash = inbound.vaa_hash;
+    received.message = config.stored_message(&message);
+    received.trace_id = trace_id;
+    received.origin_sender = origin.sender;
+    received.sent_at = origin.timestamp;
+    received.content_type = content_type;
+    received.received_at = now;
+    received.bump = received_bump;
+
+    if expired {
+        // Keep the record (replay protection) but do not deliver the greeting.
+        received.status = ReceivedStatus::Expired;
+
+        events::emit_event(
+            config,
+            accounts,
+            GreetingExpired {
+                sender_chain: inbound.emitter_chain,
+                sequence: inbound.sequence,
+                vaa_timestamp: inbound.timestamp,
+                max_age_seconds: peer.max_age_seconds,
+                trace_id,
+            },
+        )?;
+
+        msg!(
+            "Greeting from chain {} seq {} expired; recorded without delivery",
+            inbound.emitter_chain,
+            inbound.sequence
+        );
+
+        return Ok(None);
+    }
+
+    // Flag exact repeats of a recent payload (e.g. sender-side retries).
+    if peer.dedup && peer.check_duplicate(&keccak::hash(payload).to_bytes()) {
+        received.status = ReceivedStatus::Duplicate;
+
+        events::emit_event(
+            config,
+            accounts,
+            GreetingDuplicate {
+                sender_chain: inbound.emitter_chain,
+                sequence: inbound.sequence,
+                trace_id,
+            },
+        )?;
+
+        msg!(
+            "Message from chain {} seq {} duplicates a recent payload; recorded without delivery",
+            inbound.emitter_chain,
+            inbound.sequence
+        );
+
+        return Ok(None);
+    }
+    received.status = ReceivedStatus::Delivered;
+
+    if let Some(version_message) = version_message {
+        record_peer_version(config, peer, &inbound, version_message, accounts)?;
+        return Ok(None);
+    }
+
+    let message_hash = keccak::hash(&message).to_bytes();
+    let message_len = message.len() as u32;
+
+    if content_type != ContentType::Text {
+        events::emit_event(
+            config,
+            accounts,
+            PayloadReceived {
+                payload: config.event_content(message),
+                sender_chain: inbound.emitter_chain,
+                sender: inbound.emitter_address,
+                sequence: inbound.sequence,
+                trace_id,
+                payload_hash: message_hash,
+                payload_len: message_len,
+                content_type,
+            },
+        )?;
+
+        msg!(
+            "Received {} byte {:?} payload from chain {}",
+            message_len,
+            content_type,
+            inbound.emitter_chain
+        );
+
+        return Ok(None);
+    }
 
     // Convert message to string for display
-    let greeting = String::from_utf8(message.clone())
+    let greeting = String::from_utf8(message)
         .map_err(|_| HelloExecutorError::InvalidMessage)?;
 
-    // Store in Received account for reference
-    let received = &mut ctx.accounts.received;
-    received.batch_id = posted.batch_id();
-    received.wormhole_message_hash = vaa_hash;
-    received.message = message;
+    // Long greetings wait for the oracle's translation (submit_translation).
+    if config.translate_above_len > 0 && greeting.len() > usize::from(config.translate_above_len) {
+        received.status = ReceivedStatus::PendingTranslation;
+        // The oracle reads the greeting from here; it is replaced (or
+        // dropped, with redacted storage) once translated.
+        received.message = greeting.clone().into_bytes();
+
+        events::emit_event(
+            config,
+            accounts,
+            TranslationRequested {
+                greeting: config.event_content(sanitize_for_log(&greeting, GREETING_MAX_LENGTH)),
+                sender_chain: inbound.emitter_chain,
+                sequence: inbound.sequence,
+                trace_id,
+                greeting_hash: message_hash,
+                greeting_len: message_len,
+            },
+        )?;
+
+        msg!(
+            "Greeting from chain {} seq {} held for translation",
+            inbound.emitter_chain,
+            inbound.sequence
+        );
+
+        return Ok(None);
+    }
 
     // Emit event
-    emit!(GreetingReceived {
-        greeting: greeting.clone(),
-        sender_chain: posted.emitter_chain(),
-        sender: *posted.emitter_address(),
-        sequence: posted.sequence(),
-    });
+    events::emit_event(
+        config,
+        accounts,
+        GreetingReceived {
+            greeting: config.event_content(sanitize_for_log(&greeting, GREETING_MAX_LENGTH)),
+            sender_chain: inbound.emitter_chain,
+            sender: inbound.emitter_address,
+            sequence: inbound.sequence,
+            trace_id,
+            origin_sender: origin.sender,
+            sent_at: origin.timestamp,
+            greeting_hash: message_hash,
+            greeting_len: message_len,
+        },
+    )?;
 
-    msg!(
-        "Received greeting from chain {}: \"{}\"",
-        posted.emitter_chain(),
-        greeting
-    );
+    if config.redact_events {
+        msg!(
+            "Received {} byte greeting from chain {}",
+            message_len,
+            inbound.emitter_chain
+        );
+    } else {
+        msg!(
+            "Received greeting from chain {}: \"{}\"",
+            inbound.emitter_chain,
+            sanitize_for_log(&greeting, LOG_GREETING_MAX_CHARS)
+        );
+    }
 
-    Ok(())
+    Ok(Some(greeting))
 }
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use super::receive_greeting::{
+    mint_receipt, process_message, require_not_pruned, skip_duplicate_delivery, InboundMessage,
+};
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer, Received, ReceivedIndex},
+    vaa,
+    verify_shim::{self, VerifyVaaShim},
+};
+
+/// [ReceiveGreeting](super::ReceiveGreeting) with the VAA body passed as
+/// data and verified through the verify VAA shim, instead of read from a
+/// `PostedVAA` account.
+#[derive(Accounts)]
+#[instruction(vaa_body: Vec<u8>)]
+pub struct ReceiveGreetingShim<'info> {
+    #[account(mut)]
+    /// Payer for creating the Received account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program, owner of the guardian set -
+    /// verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: Core Bridge guardian set the signatures were posted for,
+    /// checked by [verify_shim::verify_hash].
+    pub guardian_set: UncheckedAccount<'info>,
+
+    #[account(owner = VerifyVaaShim::id() @ HelloExecutorError::InvalidGuardianSignatures)]
+    /// CHECK: Guardian signatures over the VAA body, posted to the shim.
+    /// Verified by the shim.
+    pub guardian_signatures: UncheckedAccount<'info>,
+
+    #[account(address = VerifyVaaShim::id())]
+    /// CHECK: Wormhole verify VAA shim program.
+    pub verify_vaa_shim: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &vaa::parse_body(&vaa_body)?.emitter_chain.to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+        constraint = peer.verify(&vaa::parse_body(&vaa_body)?.emitter_address)
+            @ HelloExecutorError::UnknownEmitter,
+    )]
+    /// Registered peer that sent this message.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &vaa::parse_body(&vaa_body)?.emitter_chain.to_le_bytes()[..],
+            &vaa::parse_body(&vaa_body)?.sequence.to_le_bytes()[..],
+        ],
+        bump,
+        space = Received::MAXIMUM_SIZE,
+    )]
+    /// Received account for replay protection, shared with
+    /// `receive_greeting`: a message is received once, by either path.
+    pub received: Account<'info, Received>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [ReceivedIndex::SEED_PREFIX, &keccak::hash(&vaa_body).0],
+        bump,
+        space = ReceivedIndex::MAXIMUM_SIZE,
+    )]
+    /// Index from the VAA hash to the Received record.
+    pub received_index: Account<'info, ReceivedIndex>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveGreetingShim>, vaa_body: Vec<u8>) -> Result<()> {
+    verify_shim::verify_hash(
+        &ctx.accounts.verify_vaa_shim,
+        ctx.accounts.wormhole_program.key,
+        &ctx.accounts.guardian_set,
+        &ctx.accounts.guardian_signatures,
+        verify_shim::vaa_digest(&vaa_body),
+    )?;
+
+    let body = vaa::parse_body(&vaa_body)?;
+    require_not_pruned(
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        &ctx.accounts.received_index,
+        ctx.bumps.received_index,
+    )?;
+    if skip_duplicate_delivery(
+        &ctx.accounts.config,
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        body.emitter_chain,
+        body.sequence,
+        &ctx.accounts.payer.key(),
+        ctx.remaining_accounts,
+    )? {
+        return Ok(());
+    }
+
+    let greeting = process_message(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.peer,
+        &mut ctx.accounts.received,
+        ctx.bumps.received,
+        InboundMessage {
+            payload: body.payload,
+            emitter_chain: body.emitter_chain,
+            emitter_address: body.emitter_address,
+            sequence: body.sequence,
+            timestamp: body.timestamp,
+            batch_id: body.nonce,
+            // Same hash as the PostedVAA account's seed.
+            vaa_hash: keccak::hash(&vaa_body).0,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let received_index = &mut ctx.accounts.received_index;
+    received_index.chain = body.emitter_chain;
+    received_index.sequence = body.sequence;
+    received_index.bump = ctx.bumps.received_index;
+
+    mint_receipt(
+        &ctx.accounts.config,
+        &ctx.accounts.peer,
+        ctx.remaining_accounts,
+        greeting.as_deref(),
+    )
+}
@@ -0,0 +1,141 @@
+//! Test-only receive path (`integration-test` feature).
+//!
+//! Runs the same message processing as `receive_greeting` on a message the
+//! trusted poster (the owner) injected with `post_test_vaa` instead of a
+//! posted VAA, so payload handling can be tested without the Wormhole Core
+//! Bridge. Injected messages expire after [TestVaa::TTL_SLOTS] and are
+//! purged with `sweep_test_vaas`. Having no VAA, a message is indexed by
+//! [TestVaa::message_hash], so `prune` handles its record like any other.
+//! The path trusts the owner to forge messages; it must never be built into
+//! a deployed program.
+
+use anchor_lang::prelude::*;
+
+use super::receive_greeting::{process_message, InboundMessage};
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer, Received, ReceivedIndex, TestVaa},
+};
+
+#[cfg(any(
+    feature = "mainnet",
+    feature = "solana-devnet",
+    feature = "fogo-testnet"
+))]
+compile_error!("the `integration-test` feature must only be enabled for localnet builds");
+
+#[derive(Accounts)]
+pub struct ReceiveGreetingTest<'info> {
+    #[account(mut)]
+    /// Payer for creating the Received account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        address = test_vaa.poster @ HelloExecutorError::InvalidTestVaaPoster,
+    )]
+    /// CHECK: Poster of the test VAA, refunded its rent.
+    pub poster: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            TestVaa::SEED_PREFIX,
+            &test_vaa.emitter_chain.to_le_bytes()[..],
+            &test_vaa.sequence.to_le_bytes()[..],
+        ],
+        bump = test_vaa.bump,
+        close = poster,
+    )]
+    /// Injected message, consumed by this instruction.
+    pub test_vaa: Account<'info, TestVaa>,
+
+    #[account(
+        mut,
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &test_vaa.emitter_chain.to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+        constraint = peer.verify(&test_vaa.emitter_address) @ HelloExecutorError::UnknownEmitter,
+    )]
+    /// Registered peer the message claims to come from.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &test_vaa.emitter_chain.to_le_bytes()[..],
+            &test_vaa.sequence.to_le_bytes()[..],
+        ],
+        bump,
+        space = Received::MAXIMUM_SIZE,
+    )]
+    /// Received account for replay protection.
+    pub received: Account<'info, Received>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [ReceivedIndex::SEED_PREFIX, &test_vaa.message_hash()],
+        bump,
+        space = ReceivedIndex::MAXIMUM_SIZE,
+    )]
+    /// Index from [TestVaa::message_hash] to the Received record, as
+    /// `receive_greeting` indexes the VAA hash, so `prune` can close both.
+    pub received_index: Account<'info, ReceivedIndex>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveGreetingTest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let test_vaa = &ctx.accounts.test_vaa;
+    require!(
+        !test_vaa.is_expired(clock.slot),
+        HelloExecutorError::TestVaaExpired,
+    );
+
+    msg!(
+        "integration-test: processing unverified message posted by {}",
+        test_vaa.poster
+    );
+
+    process_message(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.peer,
+        &mut ctx.accounts.received,
+        ctx.bumps.received,
+        InboundMessage {
+            payload: &test_vaa.payload,
+            emitter_chain: test_vaa.emitter_chain,
+            emitter_address: test_vaa.emitter_address,
+            sequence: test_vaa.sequence,
+            timestamp: clock.unix_timestamp as u32,
+            batch_id: 0,
+            // No VAA exists; the record carries the stand-in hash.
+            vaa_hash: test_vaa.message_hash(),
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let received_index = &mut ctx.accounts.received_index;
+    received_index.chain = test_vaa.emitter_chain;
+    received_index.sequence = test_vaa.sequence;
+    received_index.bump = ctx.bumps.received_index;
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+//! Second-generation receive path, rolled out per source chain.
+//!
+//! `receive_greeting_v2` runs the same message processing as
+//! `receive_greeting` and shares its `Received` replay protection, so a
+//! message is received once by either version. On top of it:
+//!
+//! * Soft-fail: a message `receive_greeting` would revert on is recorded as
+//!   [ReceivedStatus::Failed] with its error code, instead of leaving the
+//!   VAA to be retried forever.
+//! * Inbox: a greeting addressed to a wallet (`@<pubkey> ...`) is added to
+//!   that wallet's [Inbox], if it opened one.
+//! * Status fields: records carry `receiver_version` and `error_code`.
+//!
+//! In shadow mode the v2 code runs with v1 outcomes: failures still revert
+//! and inboxes are not written; what v2 would have done is only logged.
+//! Peers are moved to it with `set_peer_receive_target`.
+
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use super::receive_greeting::{
+    mint_receipt, process_message, record_arrival, require_not_pruned, skip_duplicate_delivery,
+    InboundMessage, RawVaa,
+};
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{self, decode_trace_id},
+    pda, receipts,
+    state::{Config, Inbox, Peer, Received, ReceivedIndex, ReceivedStatus},
+};
+
+/// [ReceiveGreeting](super::ReceiveGreeting)'s accounts, followed by the
+/// recipient's inbox.
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveGreetingV2<'info> {
+    #[account(mut)]
+    /// Payer for creating the Received account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified Wormhole VAA containing the greeting.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        mut,
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+        constraint = peer.verify(posted.emitter_address()) @ HelloExecutorError::UnknownEmitter,
+    )]
+    /// Registered peer that sent this message.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+            &posted.sequence().to_le_bytes()[..],
+        ],
+        bump,
+        space = Received::MAXIMUM_SIZE,
+    )]
+    /// Received account for replay protection, shared with
+    /// `receive_greeting`.
+    pub received: Account<'info, Received>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [ReceivedIndex::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = ReceivedIndex::MAXIMUM_SIZE,
+    )]
+    /// Index from the VAA hash to the Received record.
+    pub received_index: Account<'info, ReceivedIndex>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    #[account(mut)]
+    /// CHECK: Inbox of the greeting's recipient. Checked by
+    /// [deliver_to_inbox]; a missing or unopened inbox is skipped.
+    pub inbox: Option<UncheckedAccount<'info>>,
+}
+
+/// Event emitted when `receive_greeting_v2` records a message it could not
+/// deliver instead of reverting.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreetingFailed {
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+    /// Error `receive_greeting` would have reverted with.
+    pub error_code: u32,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ReceiveGreetingV2>,
+    vaa_hash: [u8; 32],
+    shadow: bool,
+) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let inbound = InboundMessage {
+        payload: &posted.data().0,
+        emitter_chain: posted.emitter_chain(),
+        emitter_address: *posted.emitter_address(),
+        sequence: posted.sequence(),
+        timestamp: posted.timestamp(),
+        batch_id: posted.batch_id(),
+        vaa_hash,
+    };
+    require_not_pruned(
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        &ctx.accounts.received_index,
+        ctx.bumps.received_index,
+    )?;
+    if skip_duplicate_delivery(
+        &ctx.accounts.config,
+        &ctx.accounts.received,
+        ctx.bumps.received,
+        inbound.emitter_chain,
+        inbound.sequence,
+        &ctx.accounts.payer.key(),
+        ctx.remaining_accounts,
+    )? {
+        return Ok(());
+    }
+
+    let greeting = match process_message(
+        &mut ctx.accounts.config,
+        &mut ctx.accounts.peer,
+        &mut ctx.accounts.received,
+        ctx.bumps.received,
+        inbound,
+        ctx.remaining_accounts,
+    ) {
+        Ok(greeting) => greeting,
+        Err(error) if shadow => {
+            msg!("shadow: v2 would record this message as Failed");
+            return Err(error);
+        }
+        Err(error) => {
+            record_failure(
+                &mut ctx.accounts.config,
+                &mut ctx.accounts.peer,
+                &mut ctx.accounts.received,
+                ctx.bumps.received,
+                inbound,
+                &error,
+                ctx.remaining_accounts,
+            )?;
+            None
+        }
+    };
+    ctx.accounts.received.receiver_version = 2;
+
+    let received_index = &mut ctx.accounts.received_index;
+    received_index.chain = inbound.emitter_chain;
+    received_index.sequence = inbound.sequence;
+    received_index.bump = ctx.bumps.received_index;
+
+    if let (Some(greeting), Some(inbox)) = (greeting.as_deref(), &ctx.accounts.inbox) {
+        if shadow {
+            msg!("shadow: v2 would add this greeting to an inbox");
+        } else {
+            deliver_to_inbox(inbox, greeting, inbound.emitter_chain, inbound.sequence)?;
+        }
+    }
+
+    mint_receipt(
+        &ctx.accounts.config,
+        &ctx.accounts.peer,
+        ctx.remaining_accounts,
+        greeting.as_deref(),
+    )
+}
+
+/// Record a message `process_message` rejected with `error`, so the VAA is
+/// consumed instead of failing every delivery attempt.
+fn record_failure(
+    config: &mut Config,
+    peer: &mut Peer,
+    received: &mut Received,
+    received_bump: u8,
+    inbound: InboundMessage,
+    error: &Error,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let error_code = match error {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(error) => u64::from(error.program_error.clone()) as u32,
+    };
+    let trace_id = decode_trace_id(inbound.payload).unwrap_or_else(|| {
+        message::trace_id(
+            inbound.emitter_chain,
+            &inbound.emitter_address,
+            inbound.sequence,
+        )
+    });
+
+    record_arrival(config, peer, &inbound, accounts)?;
+
+    received.batch_id = inbound.batch_id;
+    received.wormhole_message_hash = inbound.vaa_hash;
+    received.message = Vec::new();
+    received.status = ReceivedStatus::Failed;
+    received.error_code = error_code;
+    received.trace_id = trace_id;
+    received.received_at = Clock::get()?.unix_timestamp;
+    received.bump = received_bump;
+
+    events::emit_event(
+        config,
+        accounts,
+        GreetingFailed {
+            sender_chain: inbound.emitter_chain,
+            sequence: inbound.sequence,
+            trace_id,
+            error_code,
+        },
+    )?;
+
+    msg!(
+        "Message from chain {} seq {} failed with error {}; recorded without delivery",
+        inbound.emitter_chain,
+        inbound.sequence,
+        error_code
+    );
+
+    Ok(())
+}
+
+/// Add a delivered `greeting` to its recipient's inbox, if `inbox` is that
+/// inbox and has been opened.
+fn deliver_to_inbox(inbox: &AccountInfo, greeting: &str, chain: u16, sequence: u64) -> Result<()> {
+    let Some(recipient) = receipts::parse_recipient(greeting) else {
+        return Ok(());
+    };
+    if *inbox.key != pda::inbox(&recipient).0 || inbox.owner != &crate::ID {
+        msg!("No inbox for {}; skipping inbox", recipient);
+        return Ok(());
+    }
+
+    let mut record = Inbox::try_deserialize(&mut &inbox.try_borrow_data()?[..])?;
+    record.push(chain, sequence);
+    record.try_serialize(&mut &mut inbox.try_borrow_mut_data()?[..])?;
+
+    msg!("Added greeting to the inbox of {}", recipient);
+
+    Ok(())
+}
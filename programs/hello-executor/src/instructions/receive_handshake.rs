@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use super::receive_greeting::RawVaa;
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::decode_handshake,
+    state::{Config, PendingPeer},
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveHandshake<'info> {
+    #[account(mut)]
+    /// Payer for the PendingPeer account. Anyone may deliver a handshake.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified handshake VAA. Its emitter need not be registered.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            PendingPeer::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+            posted.emitter_address(),
+        ],
+        bump,
+        space = PendingPeer::MAXIMUM_SIZE,
+    )]
+    /// Handshake awaiting the owner. Created here, so the same emitter
+    /// cannot have two pending handshakes.
+    pub pending_peer: Account<'info, PendingPeer>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a contract announces itself as a peer.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerHandshakeReceived {
+    /// Wormhole chain ID of the announcing contract.
+    pub chain: u16,
+    /// Universal address of the announcing contract.
+    pub address: [u8; 32],
+    /// Protocol version the contract announced.
+    pub protocol_version: u16,
+    /// Sequence of the handshake VAA.
+    pub sequence: u64,
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveHandshake>, _vaa_hash: [u8; 32]) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let (chain, address, sequence) = (
+        posted.emitter_chain(),
+        *posted.emitter_address(),
+        posted.sequence(),
+    );
+    let (target_chain, protocol_version) =
+        decode_handshake(&posted.data().0).ok_or(HelloExecutorError::InvalidHandshake)?;
+    require_eq!(
+        target_chain,
+        ctx.accounts.config.chain_id,
+        HelloExecutorError::InvalidHandshake
+    );
+    require!(
+        chain > 0 && chain != ctx.accounts.config.chain_id,
+        HelloExecutorError::InvalidPeer,
+    );
+
+    let pending_peer = &mut ctx.accounts.pending_peer;
+    pending_peer.chain = chain;
+    pending_peer.address = address;
+    pending_peer.protocol_version = protocol_version;
+    pending_peer.sequence = sequence;
+    pending_peer.received_at = Clock::get()?.unix_timestamp;
+    pending_peer.payer = ctx.accounts.payer.key();
+    pending_peer.bump = ctx.bumps.pending_peer;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        PeerHandshakeReceived {
+            chain,
+            address,
+            protocol_version,
+            sequence,
+        },
+    )?;
+
+    msg!(
+        "Handshake from chain {}: {} (version {}), awaiting the owner",
+        chain,
+        hex::encode(address),
+        protocol_version
+    );
+
+    Ok(())
+}
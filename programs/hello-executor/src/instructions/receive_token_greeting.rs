@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use wormhole_anchor_sdk::{
+    token_bridge::{self, program::TokenBridge},
+    wormhole,
+};
+
+use super::receive_greeting::RawVaa;
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{self, decode_token_greeting, sanitize_for_log, GREETING_MAX_LENGTH},
+    state::{Config, Peer},
+    token_bridge::{
+        is_for_this_program, parse_transfer_with_payload, SEED_PREFIX_REDEEMER, SEED_PREFIX_TMP,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveTokenGreeting<'info> {
+    #[account(mut)]
+    /// Payer for the temporary token account, refunded when it is closed.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.inbound_paused @ HelloExecutorError::InboundPaused,
+    )]
+    /// Config account.
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// Wormhole Token Bridge program.
+    pub token_bridge_program: Program<'info, TokenBridge>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified Token Bridge transfer VAA.
+    pub posted: Box<Account<'info, RawVaa>>,
+
+    #[account(
+        seeds = [
+            Peer::SEED_PREFIX,
+            config.salt_seed(),
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump = peer.bump,
+    )]
+    /// Registered peer on the transfer's source chain. Must be the
+    /// contract that sent the transfer.
+    pub peer: Box<Account<'info, Peer>>,
+
+    #[account(seeds = [SEED_PREFIX_REDEEMER], bump)]
+    /// CHECK: Redeemer the transfer is addressed to. Signs the redemption
+    /// and owns the temporary token account.
+    pub redeemer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// Mint of the transferred token; checked by the Token Bridge.
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_PREFIX_TMP, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = redeemer,
+    )]
+    /// Temporary account the transfer is redeemed into, closed afterwards.
+    pub tmp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = mint)]
+    /// Token account of the greeting's recipient.
+    pub recipient_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Token Bridge config; checked by the Token Bridge.
+    pub token_bridge_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token Bridge claim, created by the Token Bridge to prevent
+    /// redeeming the VAA twice.
+    pub token_bridge_claim: UncheckedAccount<'info>,
+
+    /// CHECK: Token Bridge registration of the VAA's emitter; checked by the
+    /// Token Bridge, so only its own transfers are redeemed.
+    pub token_bridge_foreign_endpoint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token Bridge custody (native tokens) or wrapped metadata
+    /// (wrapped tokens); checked by the Token Bridge.
+    pub token_bridge_asset: UncheckedAccount<'info>,
+
+    /// CHECK: Token Bridge custody signer (native tokens) or mint authority
+    /// (wrapped tokens); checked by the Token Bridge.
+    pub token_bridge_authority: UncheckedAccount<'info>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted when a greeting attached to a Token Bridge transfer is
+/// received and the tokens forwarded.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenGreetingReceived {
+    /// The greeting message; empty while `config.redact_events` is set.
+    pub greeting: String,
+    /// Chain ID of the sender.
+    pub sender_chain: u16,
+    /// Universal address of the sending peer contract.
+    pub sender: [u8; 32],
+    /// Sequence number of the Token Bridge's Wormhole message.
+    pub sequence: u64,
+    /// Wallet the tokens were forwarded to.
+    pub recipient: Pubkey,
+    /// Mint of the forwarded tokens.
+    pub mint: Pubkey,
+    /// Amount forwarded, in the mint's base units.
+    pub amount: u64,
+    /// Trace ID of the message.
+    pub trace_id: [u8; 16],
+}
+
+pub(crate) fn handler(ctx: Context<ReceiveTokenGreeting>, _vaa_hash: [u8; 32]) -> Result<()> {
+    let posted = &ctx.accounts.posted;
+    let transfer = parse_transfer_with_payload(&posted.data().0)
+        .filter(|transfer| is_for_this_program(transfer))
+        .ok_or(HelloExecutorError::InvalidTokenGreeting)?;
+    require!(
+        ctx.accounts.peer.verify(&transfer.from_address),
+        HelloExecutorError::UnknownEmitter
+    );
+    let (recipient, greeting) = decode_token_greeting(transfer.payload).map_err(|e| {
+        msg!("Failed to decode token greeting: {:?}", e);
+        HelloExecutorError::InvalidMessage
+    })?;
+    let recipient = Pubkey::from(recipient);
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        HelloExecutorError::InvalidTokenRecipient
+    );
+
+    // Redeem into the temporary account; the Token Bridge checks the VAA,
+    // its emitter, the mint and the claim.
+    let redeemer_seeds: &[&[u8]] = &[SEED_PREFIX_REDEEMER, &[ctx.bumps.redeemer]];
+    if transfer.is_native() {
+        token_bridge::complete_transfer_native_with_payload(CpiContext::new_with_signer(
+            ctx.accounts.token_bridge_program.to_account_info(),
+            token_bridge::CompleteTransferNativeWithPayload {
+                payer: ctx.accounts.payer.to_account_info(),
+                config: ctx.accounts.token_bridge_config.to_account_info(),
+                vaa: ctx.accounts.posted.to_account_info(),
+                claim: ctx.accounts.token_bridge_claim.to_account_info(),
+                foreign_endpoint: ctx.accounts.token_bridge_foreign_endpoint.to_account_info(),
+                to: ctx.accounts.tmp_token_account.to_account_info(),
+                redeemer: ctx.accounts.redeemer.to_account_info(),
+                custody: ctx.accounts.token_bridge_asset.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                custody_signer: ctx.accounts.token_bridge_authority.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            },
+            &[redeemer_seeds],
+        ))?;
+    } else {
+        token_bridge::complete_transfer_wrapped_with_payload(CpiContext::new_with_signer(
+            ctx.accounts.token_bridge_program.to_account_info(),
+            token_bridge::CompleteTransferWrappedWithPayload {
+                payer: ctx.accounts.payer.to_account_info(),
+                config: ctx.accounts.token_bridge_config.to_account_info(),
+                vaa: ctx.accounts.posted.to_account_info(),
+                claim: ctx.accounts.token_bridge_claim.to_account_info(),
+                foreign_endpoint: ctx.accounts.token_bridge_foreign_endpoint.to_account_info(),
+                to: ctx.accounts.tmp_token_account.to_account_info(),
+                redeemer: ctx.accounts.redeemer.to_account_info(),
+                wrapped_mint: ctx.accounts.mint.to_account_info(),
+                wrapped_metadata: ctx.accounts.token_bridge_asset.to_account_info(),
+                mint_authority: ctx.accounts.token_bridge_authority.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            },
+            &[redeemer_seeds],
+        ))?;
+    }
+
+    // Forward everything redeemed (the Token Bridge denormalizes the
+    // amount) to the recipient, then close the temporary account.
+    ctx.accounts.tmp_token_account.reload()?;
+    let amount = ctx.accounts.tmp_token_account.amount;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.tmp_token_account.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.redeemer.to_account_info(),
+            },
+            &[redeemer_seeds],
+        ),
+        amount,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.tmp_token_account.to_account_info(),
+            destination: ctx.accounts.payer.to_account_info(),
+            authority: ctx.accounts.redeemer.to_account_info(),
+        },
+        &[redeemer_seeds],
+    ))?;
+
+    let posted = &ctx.accounts.posted;
+    let trace_id = message::trace_id(
+        posted.emitter_chain(),
+        posted.emitter_address(),
+        posted.sequence(),
+    );
+    msg!(
+        "Token greeting from chain {} seq {}: {} tokens to {}",
+        posted.emitter_chain(),
+        posted.sequence(),
+        amount,
+        recipient
+    );
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        TokenGreetingReceived {
+            greeting: ctx
+                .accounts
+                .config
+                .event_content(sanitize_for_log(&greeting, GREETING_MAX_LENGTH)),
+            sender_chain: posted.emitter_chain(),
+            sender: transfer.from_address,
+            sequence: posted.sequence(),
+            recipient,
+            mint: ctx.accounts.mint.key(),
+            amount,
+            trace_id,
+        },
+    )?;
+
+    Ok(())
+}
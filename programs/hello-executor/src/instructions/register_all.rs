@@ -0,0 +1,184 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use wormhole_anchor_sdk::wormhole;
+
+use super::{
+    receive_governance::register_peer_account, receive_greeting::RawVaa,
+    register_peer::check_registration,
+};
+use crate::{
+    error::HelloExecutorError,
+    events,
+    governance::parse_register_all,
+    state::{Config, Governance, Peer, RegistrationCursor},
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct RegisterAll<'info> {
+    #[account(mut)]
+    /// Payer for the cursor and new peers. Anyone may apply a chunk; the
+    /// one applying the last chunk gets the cursor's rent.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Not checked for `inbound_paused`, like
+    /// `receive_governance`.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &vaa_hash,
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// The verified `RegisterAll` VAA.
+    pub posted: Account<'info, RawVaa>,
+
+    #[account(
+        mut,
+        seeds = [Governance::SEED_PREFIX],
+        bump = governance.bump,
+        constraint = governance.verify(posted.emitter_chain(), posted.emitter_address())
+            @ HelloExecutorError::UnknownGovernanceEmitter,
+    )]
+    /// Governance account naming the emitter, i.e. the deployer registry.
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [RegistrationCursor::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = RegistrationCursor::MAXIMUM_SIZE,
+    )]
+    /// Progress through the broadcast, created by the first chunk.
+    pub cursor: Account<'info, RegistrationCursor>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted for each applied chunk of a `RegisterAll` broadcast.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterAllApplied {
+    /// Sequence of the broadcast VAA.
+    pub sequence: u64,
+    /// Index of the first peer in this chunk.
+    pub from_index: u8,
+    /// Index of the next peer to apply; `total` once done.
+    pub next_index: u8,
+    /// Number of peers in the broadcast.
+    pub total: u8,
+    /// Peers registered or updated by this chunk.
+    pub registered: u8,
+    /// Peers in this chunk that `register_peer` would refuse, e.g. an
+    /// address change while the timelock is set.
+    pub skipped: u8,
+}
+
+/// Apply the next peers of a `RegisterAll` broadcast, one for each of the
+/// first `limit` remaining accounts: the Peer PDAs of those entries, in
+/// order, writable. Entries for this chain take no account and are passed
+/// over. The event-CPI accounts, if any, follow.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RegisterAll<'info>>,
+    _vaa_hash: [u8; 32],
+    limit: u8,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let posted = &ctx.accounts.posted;
+    let sequence = posted.sequence();
+    let (chain, peers) =
+        parse_register_all(&posted.data().0).ok_or(HelloExecutorError::InvalidGovernanceMessage)?;
+    require!(
+        chain == 0 || chain == config.chain_id,
+        HelloExecutorError::InvalidGovernanceMessage,
+    );
+
+    // The first chunk claims the VAA, as receive_governance would apply it;
+    // later chunks continue from the cursor, unless another governance VAA
+    // (e.g. a RegisterPeer) was applied in between.
+    let cursor = &mut ctx.accounts.cursor;
+    if cursor.total == 0 {
+        ctx.accounts.governance.apply(sequence)?;
+
+        cursor.sequence = sequence;
+        cursor.total = peers.len() as u8;
+        cursor.bump = ctx.bumps.cursor;
+    } else {
+        require!(
+            ctx.accounts.governance.is_last_applied(cursor.sequence),
+            HelloExecutorError::GovernanceSuperseded,
+        );
+    }
+
+    let limit = usize::from(limit);
+    require!(
+        limit <= ctx.remaining_accounts.len(),
+        ErrorCode::AccountNotEnoughKeys,
+    );
+    let mut accounts = ctx.remaining_accounts[..limit].iter();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let from_index = cursor.next_index;
+    let (mut registered, mut skipped) = (0u8, 0u8);
+    while let Some(&(peer_chain, address)) = peers.get(usize::from(cursor.next_index)) {
+        if peer_chain != config.chain_id {
+            let Some(info) = accounts.next() else {
+                break;
+            };
+            let peer = if info.owner == &crate::ID {
+                Peer::try_deserialize(&mut &info.try_borrow_data()?[..])?
+            } else {
+                Peer::default()
+            };
+            if check_registration(config, &peer, peer_chain, &address).is_ok() {
+                register_peer_account(config, &payer, info, &system_program, peer_chain, address)?;
+                registered += 1;
+            } else {
+                msg!("Skipped peer on chain {}", peer_chain);
+                skipped += 1;
+            }
+        }
+        cursor.next_index += 1;
+    }
+
+    let (next_index, total) = (cursor.next_index, cursor.total);
+    events::emit_event(
+        config,
+        &ctx.remaining_accounts[limit..],
+        RegisterAllApplied {
+            sequence,
+            from_index,
+            next_index,
+            total,
+            registered,
+            skipped,
+        },
+    )?;
+
+    msg!(
+        "Applied peers {}..{} of {} from broadcast {}",
+        from_index,
+        next_index,
+        total,
+        sequence
+    );
+
+    if next_index == total {
+        ctx.accounts.cursor.close(payer)?;
+    }
+
+    Ok(())
+}
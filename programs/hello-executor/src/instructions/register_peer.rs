@@ -15,7 +15,7 @@ pub struct RegisterPeer<'info> {
     #[account(
         has_one = owner @ HelloExecutorError::OwnerOnly,
         seeds = [Config::SEED_PREFIX],
-        bump,
+        bump = config.bump,
     )]
     /// Config account. Verifies the owner.
     pub config: Account<'info, Config>,
@@ -27,7 +27,7 @@ pub struct RegisterPeer<'info> {
         // owner from calling this instruction, making reinitialization attacks impossible.
         init_if_needed,
         payer = owner,
-        seeds = [Peer::SEED_PREFIX, &chain.to_le_bytes()[..]],
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
         bump,
         space = Peer::MAXIMUM_SIZE,
     )]
@@ -38,11 +38,18 @@ pub struct RegisterPeer<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub(crate) fn handler(ctx: Context<RegisterPeer>, chain: u16, address: [u8; 32]) -> Result<()> {
+/// Check that `peer` (registered or not) may be set to `address` on
+/// `chain`. Shared with `receive_governance`.
+pub(crate) fn check_registration(
+    config: &Config,
+    peer: &Peer,
+    chain: u16,
+    address: &[u8; 32],
+) -> Result<()> {
     // Validate the peer:
     // - Cannot be own chain ID (prevents self-registration)
     // - Cannot be zero address
-    let own_chain = ctx.accounts.config.chain_id;
+    let own_chain = config.chain_id;
     require!(
         chain > 0
             && chain != own_chain
@@ -50,10 +57,24 @@ pub(crate) fn handler(ctx: Context<RegisterPeer>, chain: u16, address: [u8; 32])
         HelloExecutorError::InvalidPeer,
     );
 
+    // Changing a registered peer's address is a timelocked admin action.
+    let registered = peer.address != [0; 32];
+    require!(
+        config.timelock_seconds == 0 || !registered || peer.address == *address,
+        HelloExecutorError::TimelockRequired,
+    );
+
+    Ok(())
+}
+
+pub(crate) fn handler(ctx: Context<RegisterPeer>, chain: u16, address: [u8; 32]) -> Result<()> {
+    check_registration(&ctx.accounts.config, &ctx.accounts.peer, chain, &address)?;
+
     // Save peer info
     let peer = &mut ctx.accounts.peer;
     peer.chain = chain;
     peer.address = address;
+    peer.bump = ctx.bumps.peer;
 
     msg!(
         "Registered peer on chain {}: {}",
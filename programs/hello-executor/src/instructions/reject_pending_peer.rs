@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{Config, PendingPeer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16, address: [u8; 32])]
+pub struct RejectPendingPeer<'info> {
+    #[account(mut)]
+    /// Owner of the program. Receives the reclaimed rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            PendingPeer::SEED_PREFIX,
+            config.salt_seed(),
+            &chain.to_le_bytes()[..],
+            &address,
+        ],
+        bump = pending_peer.bump,
+    )]
+    /// The handshake to reject. Its rent goes to the owner, so unwanted
+    /// handshakes cost whoever delivered them.
+    pub pending_peer: Account<'info, PendingPeer>,
+}
+
+/// Event emitted when the owner rejects a handshake.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingPeerRejected {
+    /// Wormhole chain ID of the announcing contract.
+    pub chain: u16,
+    /// Universal address of the announcing contract.
+    pub address: [u8; 32],
+}
+
+pub(crate) fn handler(
+    ctx: Context<RejectPendingPeer>,
+    chain: u16,
+    address: [u8; 32],
+) -> Result<()> {
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        PendingPeerRejected { chain, address },
+    )?;
+
+    msg!(
+        "Rejected handshake from chain {}: {}",
+        chain,
+        hex::encode(address)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Publisher},
+};
+
+#[derive(Accounts)]
+pub struct RemovePublisher<'info> {
+    #[account(mut)]
+    /// Owner of the program. Receives the reclaimed rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [Publisher::SEED_PREFIX, publisher.authority.as_ref()],
+        bump = publisher.bump,
+    )]
+    /// Allowlist entry to remove.
+    pub publisher: Account<'info, Publisher>,
+}
+
+pub(crate) fn handler(ctx: Context<RemovePublisher>) -> Result<()> {
+    msg!("Removed publisher {}", ctx.accounts.publisher.authority);
+
+    Ok(())
+}
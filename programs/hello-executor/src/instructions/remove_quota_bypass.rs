@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, QuotaBypass},
+};
+
+#[derive(Accounts)]
+pub struct RemoveQuotaBypass<'info> {
+    #[account(mut)]
+    /// Owner of the program. Receives the reclaimed rent.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [QuotaBypass::SEED_PREFIX, quota_bypass.wallet.as_ref()],
+        bump = quota_bypass.bump,
+    )]
+    /// Bypass list entry to remove.
+    pub quota_bypass: Account<'info, QuotaBypass>,
+}
+
+pub(crate) fn handler(ctx: Context<RemoveQuotaBypass>) -> Result<()> {
+    msg!("Removed send quota exemption for {}", ctx.accounts.quota_bypass.wallet);
+
+    Ok(())
+}
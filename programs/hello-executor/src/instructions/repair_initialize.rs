@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, WormholeAddresses, WormholeEmitter},
+};
+
+use super::initialize::{configure, ConfigureArgs};
+
+/// Same accounts as [Initialize](super::Initialize), but the config and
+/// emitter are created only if missing.
+#[derive(Accounts)]
+pub struct RepairInitialize<'info> {
+    #[account(mut)]
+    /// Owner. Must match config.owner if the config exists; otherwise
+    /// becomes the owner, as with `initialize`.
+    pub owner: Signer<'info>,
+
+    #[account(
+        // init_if_needed is the point of this instruction. An existing
+        // config is only touched by its owner (checked in the handler).
+        init_if_needed,
+        payer = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump,
+        space = Config::MAXIMUM_SIZE,
+    )]
+    /// Config account, created if missing.
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// Wormhole bridge data (config).
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// Wormhole fee collector account.
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump,
+        space = WormholeEmitter::MAXIMUM_SIZE,
+    )]
+    /// Program's Wormhole emitter account, created if missing.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        seeds = [
+            wormhole::SequenceTracker::SEED_PREFIX,
+            wormhole_emitter.key().as_ref(),
+        ],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// CHECK: Emitter's sequence account. Only its address is recorded; it
+    /// is created by Wormhole on the first message (see `announce`).
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RepairInitialize>,
+    chain_id: u16,
+    finality: Option<u8>,
+    salt: Option<[u8; 8]>,
+    executor_program: Option<Pubkey>,
+) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+
+    // A config just created by init_if_needed is zeroed.
+    let config = &mut ctx.accounts.config;
+    let config_missing = config.owner == Pubkey::default();
+    if config_missing {
+        configure(
+            config,
+            owner,
+            ctx.bumps.config,
+            ConfigureArgs {
+                chain_id,
+                finality,
+                salt,
+                executor_program,
+            },
+            WormholeAddresses {
+                bridge: ctx.accounts.wormhole_bridge.key(),
+                fee_collector: ctx.accounts.wormhole_fee_collector.key(),
+                sequence: ctx.accounts.wormhole_sequence.key(),
+            },
+        )?;
+        msg!("Repair: created config");
+    } else {
+        require_keys_eq!(owner, config.owner, HelloExecutorError::OwnerOnly);
+    }
+
+    let emitter_missing = ctx.accounts.wormhole_emitter.bump == 0;
+    if emitter_missing {
+        ctx.accounts.wormhole_emitter.bump = ctx.bumps.wormhole_emitter;
+        msg!("Repair: created emitter");
+    }
+
+    if !(config_missing || emitter_missing) {
+        msg!("Already initialized; nothing to repair");
+    }
+
+    Ok(())
+}
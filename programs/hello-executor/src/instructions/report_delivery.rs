@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{Config, RelayRequest, RelayStatus},
+};
+
+use super::check_sla::emit_if_breached;
+
+#[derive(Accounts)]
+#[instruction(sequence: u64, dst_chain: u16)]
+pub struct ReportDelivery<'info> {
+    /// Delivery oracle. Must match config.delivery_oracle.
+    pub delivery_oracle: Signer<'info>,
+
+    #[account(
+        has_one = delivery_oracle @ HelloExecutorError::DeliveryOracleOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the oracle.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            RelayRequest::SEED_PREFIX,
+            &sequence.to_le_bytes()[..],
+            &dst_chain.to_le_bytes()[..],
+        ],
+        bump = relay_request.bump,
+    )]
+    /// RelayRequest that was delivered.
+    pub relay_request: Account<'info, RelayRequest>,
+}
+
+/// Event emitted when the delivery oracle reports a relay delivered.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeliveryReported {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Seconds from request to delivery.
+    pub elapsed_seconds: i64,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ReportDelivery>,
+    _sequence: u64,
+    _dst_chain: u16,
+    delivered_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let relay_request = &mut ctx.accounts.relay_request;
+    require!(
+        relay_request.status == RelayStatus::Requested,
+        HelloExecutorError::AlreadyDelivered,
+    );
+    require!(
+        delivered_at >= relay_request.requested_at && delivered_at <= now,
+        HelloExecutorError::InvalidDeliveryTime,
+    );
+    relay_request.delivered_at = delivered_at;
+    relay_request.status = RelayStatus::Delivered;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        DeliveryReported {
+            request_id: relay_request.request_id,
+            trace_id: relay_request.trace_id,
+            elapsed_seconds: delivered_at - relay_request.requested_at,
+        },
+    )?;
+    emit_if_breached(&ctx.accounts.config, ctx.remaining_accounts, relay_request, now)?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events, pda,
+    state::{BackfillRequest, Config, Peer},
+};
+
+use super::create_program_account;
+
+/// Accounts for `request_backfill`. The BackfillRequest PDAs of
+/// `from_sequence..=to_sequence` follow as writable remaining accounts, in
+/// order.
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RequestBackfill<'info> {
+    #[account(mut)]
+    /// Pays for the BackfillRequest accounts. Anyone may request a backfill.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account, for the PDA salt.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer whose messages are missing. Its sequence gaps bound the range.
+    pub peer: Account<'info, Peer>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a backfill is requested for missing messages.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BackfillRequested {
+    /// Chain ID of the peer.
+    pub chain: u16,
+    /// Peer's emitter address, for looking up the VAAs.
+    pub emitter_address: [u8; 32],
+    /// First missing sequence.
+    pub from_sequence: u64,
+    /// Last missing sequence.
+    pub to_sequence: u64,
+    /// Wallet that requested the backfill.
+    pub requested_by: Pubkey,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RequestBackfill<'info>>,
+    chain: u16,
+    from_sequence: u64,
+    to_sequence: u64,
+) -> Result<()> {
+    require!(
+        from_sequence <= to_sequence && to_sequence - from_sequence < BackfillRequest::MAX_BATCH,
+        HelloExecutorError::InvalidBackfillRange,
+    );
+
+    // Only sequences the peer is known to have skipped.
+    let peer = &ctx.accounts.peer;
+    require!(
+        peer.sequence_gaps
+            .iter()
+            .any(|gap| gap.from <= from_sequence && to_sequence <= gap.to),
+        HelloExecutorError::SequenceNotMissing,
+    );
+    require_eq!(
+        ctx.remaining_accounts.len() as u64,
+        to_sequence - from_sequence + 1,
+        HelloExecutorError::InvalidBackfillAccounts
+    );
+
+    let config = &ctx.accounts.config;
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let now = Clock::get()?.unix_timestamp;
+
+    for (sequence, info) in (from_sequence..=to_sequence).zip(ctx.remaining_accounts) {
+        let (expected, bump) = pda::backfill_request(&config.salt, chain, sequence);
+        require_keys_eq!(
+            info.key(),
+            expected,
+            HelloExecutorError::InvalidBackfillAccounts
+        );
+
+        // Already requested: leave the earlier request in place.
+        if info.owner == &crate::ID {
+            continue;
+        }
+
+        create_program_account(
+            &payer,
+            info,
+            &system_program,
+            BackfillRequest::MAXIMUM_SIZE,
+            &[
+                BackfillRequest::SEED_PREFIX,
+                config.salt_seed(),
+                &chain.to_le_bytes(),
+                &sequence.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+
+        let request = BackfillRequest {
+            chain,
+            emitter_address: peer.address,
+            sequence,
+            requested_by: payer.key(),
+            requested_at: now,
+            bump,
+        };
+        request.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    events::emit_event(
+        config,
+        &[],
+        BackfillRequested {
+            chain,
+            emitter_address: peer.address,
+            from_sequence,
+            to_sequence,
+            requested_by: payer.key(),
+        },
+    )?;
+
+    msg!(
+        "Backfill requested for chain {} sequences {}..={}",
+        chain,
+        from_sequence,
+        to_sequence
+    );
+
+    Ok(())
+}
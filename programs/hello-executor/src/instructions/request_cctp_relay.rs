@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use super::request_relay::{charge_spend_cap, check_gas_drop_off, verify_quote};
+use crate::{
+    chains,
+    error::HelloExecutorError,
+    events,
+    executor_cpi::{self, RequestForExecutionArgs},
+    executor_requests::{self, CCTP_DOMAIN_SOLANA},
+    funds,
+    state::{Config, Peer, SpendCap},
+};
+
+/// Arguments for requesting an Executor relay of a CCTP v1 message.
+///
+/// Demonstrates the Executor's CCTP support: the burn is made separately
+/// (with Circle's Token Messenger), and this pays the Executor to fetch its
+/// attestation and call `receiveMessage` on the destination.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RequestCctpRelayArgs {
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Amount to pay the Executor (lamports). Get this from the Executor quote API.
+    pub exec_amount: u64,
+    /// Signed quote bytes from the Executor API.
+    pub signed_quote_bytes: Vec<u8>,
+    /// Relay instructions bytes (encodes gas limit + msgValue for the destination).
+    pub relay_instructions: Vec<u8>,
+    /// Nonce of the CCTP message, from its `MessageSent` account.
+    pub nonce: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RequestCctpRelayArgs)]
+pub struct RequestCctpRelay<'info> {
+    #[account(mut)]
+    /// Payer for the Executor request.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: payee is enforced by the Executor program via signed quote.
+    pub payee: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &args.dst_chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Registered peer on the destination chain, which the relay is
+    /// addressed to.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Executor program - verified by config.executor_program_id()
+    #[account(
+        executable,
+        address = config.executor_program_id() @ HelloExecutorError::InvalidExecutorProgram,
+    )]
+    pub executor_program: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Outbound spend cap. Only enforced once `set_spend_cap` has
+    /// created it, so it may still be an empty system account.
+    #[account(mut, seeds = [SpendCap::SEED_PREFIX], bump)]
+    pub spend_cap: UncheckedAccount<'info>,
+}
+
+/// Event emitted when an Executor relay of a CCTP v1 message is requested.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CctpRelayRequested {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// CCTP domain the message was sent from.
+    pub source_domain: u32,
+    /// Nonce of the CCTP message.
+    pub nonce: u64,
+}
+
+pub(crate) fn handler(ctx: Context<RequestCctpRelay>, args: RequestCctpRelayArgs) -> Result<()> {
+    // A relay to a malformed address cannot be undone; refuse before paying.
+    require!(
+        chains::is_valid_address(args.dst_chain, &ctx.accounts.peer.address),
+        HelloExecutorError::InvalidDestinationAddress,
+    );
+
+    verify_quote(
+        &ctx.accounts.config,
+        &ctx.accounts.payee.key(),
+        args.dst_chain,
+        &args.signed_quote_bytes,
+        None,
+    )?;
+    check_gas_drop_off(&ctx.accounts.peer, args.dst_chain, &args.relay_instructions)?;
+
+    funds::require_payer_can_afford(
+        &ctx.accounts.payer.to_account_info(),
+        args.exec_amount,
+        ctx.accounts.config.payer_buffer_lamports,
+    )?;
+    charge_spend_cap(
+        &ctx.accounts.config,
+        &ctx.accounts.spend_cap,
+        ctx.remaining_accounts,
+        args.exec_amount,
+    )?;
+
+    let request_bytes = executor_requests::make_cctp_v1_request(CCTP_DOMAIN_SOLANA, args.nonce);
+    let request_id = keccak::hash(&request_bytes).to_bytes();
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        CctpRelayRequested {
+            request_id,
+            dst_chain: args.dst_chain,
+            source_domain: CCTP_DOMAIN_SOLANA,
+            nonce: args.nonce,
+        },
+    )?;
+
+    executor_cpi::request_for_execution(
+        &ctx.accounts.executor_program.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.payee.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        RequestForExecutionArgs {
+            amount: args.exec_amount,
+            dst_chain: args.dst_chain,
+            dst_addr: ctx.accounts.peer.address,
+            refund_addr: ctx.accounts.payer.key(),
+            signed_quote_bytes: args.signed_quote_bytes,
+            request_bytes,
+            relay_instructions: args.relay_instructions,
+        },
+    )
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use super::request_relay::{charge_spend_cap, check_gas_drop_off, verify_quote};
+use crate::{
+    chains,
+    error::HelloExecutorError,
+    events,
+    executor_cpi::{self, RequestForExecutionArgs},
+    executor_requests, funds,
+    state::{Config, Peer, SpendCap},
+};
+
+/// Arguments for requesting an Executor relay of an NTT v1 message.
+///
+/// Demonstrates the Executor's NTT support: the transfer is made separately
+/// (with the token's NTT manager), and this pays the Executor to deliver its
+/// message to the destination manager.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RequestNttRelayArgs {
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Amount to pay the Executor (lamports). Get this from the Executor quote API.
+    pub exec_amount: u64,
+    /// Signed quote bytes from the Executor API.
+    pub signed_quote_bytes: Vec<u8>,
+    /// Relay instructions bytes (encodes gas limit + msgValue for the destination).
+    pub relay_instructions: Vec<u8>,
+    /// NTT manager program the transfer was made with.
+    pub ntt_manager: Pubkey,
+    /// Outbox item of the transfer, which is its NTT message ID.
+    pub outbox_item: Pubkey,
+    /// Universal address of the NTT manager on the destination chain.
+    pub dst_manager: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: RequestNttRelayArgs)]
+pub struct RequestNttRelay<'info> {
+    #[account(mut)]
+    /// Payer for the Executor request.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: payee is enforced by the Executor program via signed quote.
+    pub payee: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &args.dst_chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Registered peer on the destination chain. Bounds the gas drop-off.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Executor program - verified by config.executor_program_id()
+    #[account(
+        executable,
+        address = config.executor_program_id() @ HelloExecutorError::InvalidExecutorProgram,
+    )]
+    pub executor_program: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Outbound spend cap. Only enforced once `set_spend_cap` has
+    /// created it, so it may still be an empty system account.
+    #[account(mut, seeds = [SpendCap::SEED_PREFIX], bump)]
+    pub spend_cap: UncheckedAccount<'info>,
+}
+
+/// Event emitted when an Executor relay of an NTT v1 message is requested.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NttRelayRequested {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// NTT manager program the transfer was made with.
+    pub ntt_manager: Pubkey,
+    /// Outbox item of the transfer.
+    pub outbox_item: Pubkey,
+}
+
+pub(crate) fn handler(ctx: Context<RequestNttRelay>, args: RequestNttRelayArgs) -> Result<()> {
+    // A relay to a malformed address cannot be undone; refuse before paying.
+    require!(
+        chains::is_valid_address(args.dst_chain, &args.dst_manager),
+        HelloExecutorError::InvalidDestinationAddress,
+    );
+
+    verify_quote(
+        &ctx.accounts.config,
+        &ctx.accounts.payee.key(),
+        args.dst_chain,
+        &args.signed_quote_bytes,
+        None,
+    )?;
+    check_gas_drop_off(&ctx.accounts.peer, args.dst_chain, &args.relay_instructions)?;
+
+    funds::require_payer_can_afford(
+        &ctx.accounts.payer.to_account_info(),
+        args.exec_amount,
+        ctx.accounts.config.payer_buffer_lamports,
+    )?;
+    charge_spend_cap(
+        &ctx.accounts.config,
+        &ctx.accounts.spend_cap,
+        ctx.remaining_accounts,
+        args.exec_amount,
+    )?;
+
+    let request_bytes = executor_requests::make_ntt_v1_request(
+        ctx.accounts.config.chain_id,
+        &args.ntt_manager.to_bytes(),
+        &args.outbox_item.to_bytes(),
+    );
+    let request_id = keccak::hash(&request_bytes).to_bytes();
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        NttRelayRequested {
+            request_id,
+            dst_chain: args.dst_chain,
+            ntt_manager: args.ntt_manager,
+            outbox_item: args.outbox_item,
+        },
+    )?;
+
+    executor_cpi::request_for_execution(
+        &ctx.accounts.executor_program.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.payee.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        RequestForExecutionArgs {
+            amount: args.exec_amount,
+            dst_chain: args.dst_chain,
+            dst_addr: args.dst_manager,
+            refund_addr: ctx.accounts.payer.key(),
+            signed_quote_bytes: args.signed_quote_bytes,
+            request_bytes,
+            relay_instructions: args.relay_instructions,
+        },
+    )
+}
@@ -1,11 +1,29 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{keccak, sysvar};
 
 use crate::{
+    chains,
     error::HelloExecutorError,
-    state::{Config, Peer, WormholeEmitter},
+    events,
+    exec_amount,
+    executor_requests,
+    funds,
+    message::{self, TRACE_ID_LENGTH},
+    pda,
+    quote::{self, ExecutorQuote},
+    relay_instructions::{self, GasDropOffInstruction, GasInstruction, RelayTotals},
+    return_data,
+    sigverify,
+    state::{
+        Config, Peer, RelayRequest, RelayRequestIndex, RelayStatus, Sent, SpendCap,
+        WormholeEmitter,
+    },
+    wormhole_cpi,
 };
 
-use crate::executor_cpi::{self, ExecutorProgram, RequestForExecutionArgs};
+use super::create_program_account;
+
+use crate::executor_cpi::{self, RequestForExecutionArgs};
 
 /// Arguments for requesting an Executor relay.
 ///
@@ -28,8 +46,9 @@ pub struct RequestRelayArgs {
     pub exec_amount: u64,
     /// Signed quote bytes from the Executor API.
     pub signed_quote_bytes: Vec<u8>,
-    /// Relay instructions bytes (encodes gas limit + msgValue for the destination).
-    pub relay_instructions: Vec<u8>,
+    /// Gas limit and msgValue for the destination, encoded as the relay's
+    /// first instruction.
+    pub gas: GasInstruction,
     /// The specific VAA sequence to relay.
     /// - `None` / omitted → relay the most recently published message (current tracker − 1)
     /// - `Some(n)`        → relay the message at sequence `n` (useful if you skipped a relay)
@@ -37,6 +56,13 @@ pub struct RequestRelayArgs {
     /// Note: the Wormhole sequence tracker stores the NEXT sequence to be assigned,
     /// so valid sequences are `0 ..= tracker − 1`.
     pub sequence: Option<u64>,
+    /// Native tokens to deliver on the destination alongside the message,
+    /// encoded after `gas`. Limited by the peer's
+    /// `max_gas_drop_off`; the quote must be priced with it included.
+    pub gas_drop_off: Option<GasDropOffInstruction>,
+    /// Where the Executor refunds unused payment, e.g. the end user or an
+    /// integrator's treasury when a bot pays. Defaults to the payer.
+    pub refund_addr: Option<Pubkey>,
 }
 
 #[derive(Accounts)]
@@ -52,21 +78,23 @@ pub struct RequestRelay<'info> {
 
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump,
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
     )]
     /// Config account.
     pub config: Account<'info, Config>,
 
     #[account(
-        seeds = [Peer::SEED_PREFIX, &args.dst_chain.to_le_bytes()[..]],
-        bump,
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &args.dst_chain.to_le_bytes()[..]],
+        bump = peer.bump,
     )]
     /// Registered peer on the destination chain.
     pub peer: Account<'info, Peer>,
 
     #[account(
         seeds = [WormholeEmitter::SEED_PREFIX],
-        bump,
+        bump = wormhole_emitter.bump,
     )]
     /// Program's Wormhole emitter account.
     pub wormhole_emitter: Account<'info, WormholeEmitter>,
@@ -77,21 +105,102 @@ pub struct RequestRelay<'info> {
     )]
     pub wormhole_sequence: UncheckedAccount<'info>,
 
-    /// Executor program.
-    pub executor_program: Program<'info, ExecutorProgram>,
+    /// CHECK: RelayRequest PDA keyed by the sequence and destination chain.
+    /// The sequence is read in the handler, so the address is verified and
+    /// the account created there.
+    #[account(mut)]
+    pub relay_request: UncheckedAccount<'info>,
+
+    /// CHECK: RelayRequestIndex PDA keyed by the request ID and destination
+    /// chain, pointing to `relay_request`. Verified and created in the handler like it.
+    #[account(mut)]
+    pub relay_request_index: UncheckedAccount<'info>,
+
+    /// CHECK: Executor program - verified by config.executor_program_id()
+    #[account(
+        executable,
+        address = config.executor_program_id() @ HelloExecutorError::InvalidExecutorProgram,
+    )]
+    pub executor_program: UncheckedAccount<'info>,
 
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Outbound spend cap. Only enforced once `set_spend_cap` has
+    /// created it, so it may still be an empty system account.
+    #[account(mut, seeds = [SpendCap::SEED_PREFIX], bump)]
+    pub spend_cap: UncheckedAccount<'info>,
+
+    /// CHECK: Posted message being relayed (`pda::sent(sequence + 1)`),
+    /// verified in the handler. Optional: when passed, its payload length is
+    /// priced into the minimum exec_amount estimate.
+    pub wormhole_message: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// Sent record of the relayed greeting. Optional: when passed and the
+    /// payer sent the greeting, its `dst_chain` is set to this relay's
+    /// destination and a `Published` status becomes `Requested`.
+    pub sent: Option<Account<'info, Sent>>,
+
+    /// CHECK: Instructions sysvar. Optional: when passed, the quote signer
+    /// is checked against a Secp256k1 precompile instruction placed just
+    /// before this one instead of being recovered.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+/// Event emitted when an Executor relay is requested.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelayRequested {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Sequence of the relayed VAA.
+    pub sequence: u64,
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Lamports paid to the Executor.
+    pub exec_amount: u64,
+    /// Executor payee, from the signed quote.
+    pub payee: Pubkey,
+    /// Address the Executor refunds unused payment to.
+    pub refund_addr: Pubkey,
+}
+
+/// Event emitted when outbound spending first reaches
+/// [SpendCap::WARN_PERCENT] of the cap in an epoch.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpendCapWarning {
+    /// The spend cap's current epoch.
+    pub epoch: u64,
+    /// Lamports spent in the epoch.
+    pub spent_lamports: u64,
+    /// Lamports spendable per epoch.
+    pub max_lamports: u64,
+}
+
+/// What `request_relay` requested, set as its [return_data], so callers and
+/// simulations need not re-derive it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RequestRelayResult {
+    /// Keccak256 hash of `request_bytes`, keying the RelayRequest.
+    pub request_id: [u8; 32],
+    /// Sequence of the relayed VAA, resolved from `args.sequence` or the
+    /// latest greeting.
+    pub sequence: u64,
+    /// Request bytes passed to the Executor.
+    pub request_bytes: Vec<u8>,
 }
 
 pub(crate) fn handler(ctx: Context<RequestRelay>, args: RequestRelayArgs) -> Result<()> {
     // Read the sequence tracker to validate the requested sequence is in range
     // and to derive the default (most-recent) sequence when none is specified.
-    let seq_data = ctx.accounts.wormhole_sequence.try_borrow_data()?;
-    let tracker = u64::from_le_bytes(seq_data[0..8].try_into().unwrap());
-    drop(seq_data);
+    let tracker = wormhole_cpi::next_sequence(&ctx.accounts.wormhole_sequence)?;
 
-    // After initialize(), the tracker == 1 because the Alive message consumed sequence 0.
+    // After announce(), the tracker == 1 because the Alive message consumed sequence 0.
     // A tracker of 1 means send_greeting has never been called — there are no greetings
     // to relay. Relaying sequence 0 would send the Alive init message to the EVM side,
     // which would fail to parse and waste the relay fee.
@@ -110,13 +219,105 @@ pub(crate) fn handler(ctx: Context<RequestRelay>, args: RequestRelayArgs) -> Res
         None => tracker - 1, // default: most-recently published greeting
     };
 
-    // ERV1 payload: 4-byte type tag | u16 chain (BE) | 32-byte emitter | u64 sequence (BE)
-    let mut request_bytes = Vec::with_capacity(4 + 2 + 32 + 8);
-    request_bytes.extend_from_slice(b"ERV1");
-    request_bytes.extend_from_slice(&ctx.accounts.config.chain_id.to_be_bytes());
-    request_bytes.extend_from_slice(&ctx.accounts.wormhole_emitter.key().to_bytes());
-    request_bytes.extend_from_slice(&vaa_sequence.to_be_bytes());
+    // A relay to a malformed address cannot be undone; refuse before paying.
+    require!(
+        chains::is_valid_address(args.dst_chain, &ctx.accounts.peer.address),
+        HelloExecutorError::InvalidDestinationAddress,
+    );
+
+    let quote = verify_quote(
+        &ctx.accounts.config,
+        &ctx.accounts.payee.key(),
+        args.dst_chain,
+        &args.signed_quote_bytes,
+        ctx.accounts.instructions.as_deref(),
+    )?;
+
+    let relay_instructions =
+        build_relay_instructions(args.dst_chain, &args.gas, args.gas_drop_off.as_ref())?;
+    let message = ctx.accounts.wormhole_message.as_ref();
+    check_peer_version(&ctx.accounts.peer, message, vaa_sequence)?;
+    let totals = check_gas_drop_off(&ctx.accounts.peer, args.dst_chain, &relay_instructions)?;
+    check_exec_amount(
+        &ctx.accounts.config,
+        message,
+        args.exec_amount,
+        &quote,
+        &totals,
+        vaa_sequence,
+    )?;
+
+    let request_bytes = executor_requests::make_vaa_v1_request(
+        ctx.accounts.config.chain_id,
+        &ctx.accounts.wormhole_emitter.key().to_bytes(),
+        vaa_sequence,
+    );
+
+    // The payer funds the Executor payment and, on a first request, the
+    // RelayRequest record and its index, and must stay rent-exempt
+    // afterwards.
+    let rent = Rent::get()?;
+    let mut record_rent = 0;
+    if ctx.accounts.relay_request.owner != ctx.program_id {
+        record_rent += rent.minimum_balance(RelayRequest::MAXIMUM_SIZE);
+    }
+    if ctx.accounts.relay_request_index.owner != ctx.program_id {
+        record_rent += rent.minimum_balance(RelayRequestIndex::MAXIMUM_SIZE);
+    }
+    funds::require_payer_can_afford(
+        &ctx.accounts.payer.to_account_info(),
+        args.exec_amount.saturating_add(record_rent),
+        ctx.accounts.config.payer_buffer_lamports,
+    )?;
+    charge_spend_cap(
+        &ctx.accounts.config,
+        &ctx.accounts.spend_cap,
+        ctx.remaining_accounts,
+        args.exec_amount,
+    )?;
+
+    // Record the request with its ID so it can be joined with Executor data.
+    let request_id = keccak::hash(&request_bytes).to_bytes();
+    // The greeting's trace ID is a prefix of its request ID (see message::trace_id).
+    let trace_id: [u8; TRACE_ID_LENGTH] = request_id[..TRACE_ID_LENGTH].try_into().unwrap();
+    record_relay_request(&ctx, &args, request_id, trace_id, vaa_sequence)?;
+
+    let peer = &mut ctx.accounts.peer;
+    peer.sent_count += 1;
+    peer.last_sent_sequence = vaa_sequence;
 
+    // Only the sender's own relays update the record, so others relaying
+    // the greeting elsewhere cannot rewrite its destination.
+    if let Some(sent) = &mut ctx.accounts.sent {
+        require_eq!(sent.sequence, vaa_sequence, HelloExecutorError::InvalidSentRecord);
+        if sent.sender == ctx.accounts.payer.key() {
+            sent.dst_chain = args.dst_chain;
+            if sent.status == RelayStatus::Published {
+                sent.status = RelayStatus::Requested;
+            }
+        }
+    }
+
+    let refund_addr = args.refund_addr.unwrap_or(ctx.accounts.payer.key());
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        RelayRequested {
+            request_id,
+            dst_chain: args.dst_chain,
+            sequence: vaa_sequence,
+            trace_id,
+            exec_amount: args.exec_amount,
+            payee: ctx.accounts.payee.key(),
+            refund_addr,
+        },
+    )?;
+
+    let result = RequestRelayResult {
+        request_id,
+        sequence: vaa_sequence,
+        request_bytes: request_bytes.clone(),
+    };
     executor_cpi::request_for_execution(
         &ctx.accounts.executor_program.to_account_info(),
         &ctx.accounts.payer.to_account_info(),
@@ -126,10 +327,311 @@ pub(crate) fn handler(ctx: Context<RequestRelay>, args: RequestRelayArgs) -> Res
             amount: args.exec_amount,
             dst_chain: args.dst_chain,
             dst_addr: ctx.accounts.peer.address,
-            refund_addr: ctx.accounts.payer.key(),
+            refund_addr,
             signed_quote_bytes: args.signed_quote_bytes,
             request_bytes,
-            relay_instructions: args.relay_instructions,
+            relay_instructions,
         },
-    )
+    )?;
+
+    // Set after the CPI, which could otherwise overwrite it.
+    return_data::set(&result)
+}
+
+/// Encode `gas` followed by the optional drop-off, checking the drop-off
+/// suits `dst_chain`.
+pub(crate) fn build_relay_instructions(
+    dst_chain: u16,
+    gas: &GasInstruction,
+    gas_drop_off: Option<&GasDropOffInstruction>,
+) -> Result<Vec<u8>> {
+    let mut relay_instructions = gas.encode();
+    if let Some(drop_off) = gas_drop_off {
+        require!(
+            chains::chain_family(dst_chain).supports_gas_drop_off(),
+            HelloExecutorError::GasDropOffUnsupported,
+        );
+        require!(
+            chains::is_valid_address(dst_chain, &drop_off.recipient),
+            HelloExecutorError::InvalidDropOffRecipient,
+        );
+        relay_instructions::append_gas_drop_off(dst_chain, &mut relay_instructions, drop_off)?;
+    }
+
+    Ok(relay_instructions)
+}
+
+/// Refuse to relay a payload type the peer reported it is too old for.
+/// Needs the message account; without it, or before the peer has reported
+/// a version, nothing is checked.
+pub(crate) fn check_peer_version(
+    peer: &Peer,
+    message: Option<&UncheckedAccount>,
+    sequence: u64,
+) -> Result<()> {
+    let Some(message) = message else {
+        return Ok(());
+    };
+    if let Some(payload_id) = wormhole_cpi::posted_payload_id(message, sequence)? {
+        if peer.too_old_for(payload_id) {
+            msg!(
+                "Peer on chain {} reports protocol version {}; payload {} needs {}",
+                peer.chain,
+                peer.reported_version,
+                payload_id,
+                message::min_protocol_version(payload_id)
+            );
+            return err!(HelloExecutorError::PeerVersionTooLow);
+        }
+    }
+    Ok(())
+}
+
+/// Check the total drop-off in `relay_instructions` — whether from
+/// `gas_drop_off` or encoded by the caller of a CCTP or NTT relay — is
+/// within the peer's limit and supported by the destination's family.
+pub(crate) fn check_gas_drop_off(
+    peer: &Peer,
+    dst_chain: u16,
+    relay_instructions: &[u8],
+) -> Result<RelayTotals> {
+    let totals = relay_instructions::check(dst_chain, relay_instructions).map_err(|e| {
+        msg!("Failed to parse relay instructions: {:?}", e);
+        HelloExecutorError::InvalidRelayInstructions
+    })?;
+    require!(
+        totals.drop_off <= peer.max_gas_drop_off,
+        HelloExecutorError::GasDropOffTooLarge,
+    );
+
+    Ok(totals)
+}
+
+/// Log the estimated minimum exec_amount for this relay and, with
+/// `config.enforce_min_exec_amount`, refuse to pay less. The Executor
+/// silently drops underpaid relays.
+///
+/// Without the message account the payload is priced as empty, so the
+/// estimate is a lower bound.
+pub(crate) fn check_exec_amount(
+    config: &Config,
+    message: Option<&UncheckedAccount>,
+    exec_amount: u64,
+    quote: &ExecutorQuote,
+    totals: &RelayTotals,
+    sequence: u64,
+) -> Result<()> {
+    let payload_len = match message {
+        Some(message) => wormhole_cpi::posted_payload_len(message, sequence)?,
+        None => 0,
+    };
+    let Some(minimum) = exec_amount::minimum_exec_amount(quote, totals, payload_len) else {
+        msg!("No exec amount estimate for chain {}", quote.dst_chain);
+        return Ok(());
+    };
+
+    msg!(
+        "Exec amount {} lamports, estimated minimum {} for a {}-byte payload",
+        exec_amount,
+        minimum,
+        payload_len
+    );
+    if exec_amount < minimum {
+        msg!("Exec amount is {} lamports short", minimum - exec_amount);
+        require!(
+            !config.enforce_min_exec_amount,
+            HelloExecutorError::InsufficientExecAmount,
+        );
+    }
+
+    Ok(())
+}
+
+/// Check the quote is unexpired and matches a relay to `dst_chain` paid to
+/// `payee`, and, with quote signers configured, is signed by one of them.
+/// The Executor would take payment for a quote it then refuses to honor.
+pub(crate) fn verify_quote(
+    config: &Config,
+    payee: &Pubkey,
+    dst_chain: u16,
+    signed_quote_bytes: &[u8],
+    instructions: Option<&AccountInfo>,
+) -> Result<ExecutorQuote> {
+    let quote = quote::parse_quote(signed_quote_bytes).map_err(|e| {
+        msg!("Failed to parse signed quote: {:?}", e);
+        HelloExecutorError::InvalidQuote
+    })?;
+    require!(
+        quote.src_chain == config.chain_id
+            && quote.dst_chain == dst_chain
+            && quote.payee == payee.to_bytes(),
+        HelloExecutorError::InvalidQuote,
+    );
+    require!(
+        !quote.is_expired(Clock::get()?.unix_timestamp),
+        HelloExecutorError::QuoteExpired,
+    );
+
+    if config.checks_quotes() {
+        let signer = match instructions {
+            Some(instructions) => {
+                precompile_quote_signer(instructions, &quote, signed_quote_bytes)?
+            }
+            None => quote::recover_quote_signer(signed_quote_bytes)?,
+        };
+        require!(
+            signer == quote.quoter && config.is_quote_signer(&signer),
+            HelloExecutorError::InvalidQuoteSigner,
+        );
+    }
+
+    Ok(quote)
+}
+
+/// The quoter, once the Secp256k1 precompile instruction just before the
+/// current one has verified its signature over the quote body. Unlike
+/// [quote::recover_quote_signer], this costs no compute: the precompile
+/// fails the transaction on an invalid signature.
+fn precompile_quote_signer(
+    instructions: &AccountInfo,
+    quote: &ExecutorQuote,
+    signed_quote_bytes: &[u8],
+) -> Result<[u8; 20]> {
+    let current = sysvar::instructions::load_current_index_checked(instructions)?;
+    let index = current
+        .checked_sub(1)
+        .and_then(|index| u8::try_from(index).ok())
+        .ok_or_else(|| error!(HelloExecutorError::InvalidSignatureInstruction))?;
+    let precompile = sigverify::load_instruction(instructions, index.into())?;
+    let body = signed_quote_bytes
+        .get(..quote::QUOTE_BODY_LENGTH)
+        .ok_or_else(|| error!(HelloExecutorError::InvalidQuote))?;
+    sigverify::verify_secp256k1(&precompile, index, &quote.quoter, body)?;
+
+    Ok(quote.quoter)
+}
+
+/// Count `amount` lamports against the spend cap, if one is set, emitting
+/// `SpendCapWarning` when spending first nears it in the epoch.
+pub(crate) fn charge_spend_cap<'info>(
+    config: &Config,
+    spend_cap: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if spend_cap.owner != &crate::ID {
+        // Not created yet: no cap.
+        return Ok(());
+    }
+    let mut cap = SpendCap::try_deserialize(&mut &spend_cap.try_borrow_data()?[..])?;
+    let warn = cap.record(amount, Clock::get()?.unix_timestamp)?;
+    cap.try_serialize(&mut &mut spend_cap.try_borrow_mut_data()?[..])?;
+
+    if warn {
+        events::emit_event(
+            config,
+            accounts,
+            SpendCapWarning {
+                epoch: cap.epoch,
+                spent_lamports: cap.spent_lamports,
+                max_lamports: cap.max_lamports,
+            },
+        )?;
+
+        msg!("Spent {} of {} lamports this epoch", cap.spent_lamports, cap.max_lamports);
+    }
+
+    Ok(())
+}
+
+/// Create the RelayRequest record for `(sequence, dst_chain)`, or refresh
+/// it on a repeated request. A record that is already delivered or failed
+/// is refused with `AlreadyDelivered`, as `retry_relay` does.
+fn record_relay_request(
+    ctx: &Context<RequestRelay>,
+    args: &RequestRelayArgs,
+    request_id: [u8; 32],
+    trace_id: [u8; TRACE_ID_LENGTH],
+    sequence: u64,
+) -> Result<()> {
+    let (expected, bump) = pda::relay_request(sequence, args.dst_chain);
+    let info = ctx.accounts.relay_request.to_account_info();
+    require_keys_eq!(info.key(), expected, HelloExecutorError::InvalidRelayRequest);
+
+    if info.owner == ctx.program_id {
+        let existing = RelayRequest::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            existing.status == RelayStatus::Requested,
+            HelloExecutorError::AlreadyDelivered,
+        );
+    } else {
+        create_program_account(
+            &ctx.accounts.payer.to_account_info(),
+            &info,
+            &ctx.accounts.system_program.to_account_info(),
+            RelayRequest::MAXIMUM_SIZE,
+            &[
+                RelayRequest::SEED_PREFIX,
+                &sequence.to_le_bytes(),
+                &args.dst_chain.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    let record = RelayRequest {
+        request_id,
+        dst_chain: args.dst_chain,
+        sequence,
+        exec_amount: args.exec_amount,
+        payee: ctx.accounts.payee.key(),
+        payer: ctx.accounts.payer.key(),
+        requested_at: Clock::get()?.unix_timestamp,
+        delivered_at: 0,
+        trace_id,
+        status: RelayStatus::Requested,
+        bump,
+    };
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+
+    record_relay_request_index(ctx, request_id, sequence, args.dst_chain)
+}
+
+/// Point the request ID's RelayRequestIndex at this relay's record,
+/// creating the index on the first request.
+fn record_relay_request_index(
+    ctx: &Context<RequestRelay>,
+    request_id: [u8; 32],
+    sequence: u64,
+    dst_chain: u16,
+) -> Result<()> {
+    let (expected, bump) = pda::relay_request_index(&request_id, dst_chain);
+    let info = ctx.accounts.relay_request_index.to_account_info();
+    require_keys_eq!(info.key(), expected, HelloExecutorError::InvalidRelayRequest);
+
+    if info.owner != ctx.program_id {
+        create_program_account(
+            &ctx.accounts.payer.to_account_info(),
+            &info,
+            &ctx.accounts.system_program.to_account_info(),
+            RelayRequestIndex::MAXIMUM_SIZE,
+            &[
+                RelayRequestIndex::SEED_PREFIX,
+                &request_id,
+                &dst_chain.to_le_bytes(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    let index = RelayRequestIndex {
+        sequence,
+        dst_chain,
+        bump,
+    };
+    let mut data = info.try_borrow_mut_data()?;
+    index.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
 }
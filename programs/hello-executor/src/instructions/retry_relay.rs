@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    executor_cpi::{self, RequestForExecutionArgs},
+    executor_requests, funds,
+    relay_instructions::{GasDropOffInstruction, GasInstruction},
+    state::{Config, Peer, RelayRequest, RelayStatus, SpendCap, WormholeEmitter},
+};
+
+use super::request_relay::{
+    build_relay_instructions, charge_spend_cap, check_exec_amount, check_gas_drop_off,
+    check_peer_version, verify_quote,
+};
+
+/// Arguments for retrying a relay. The destination and sequence come from
+/// the RelayRequest; only the payment and gas are given again.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RetryRelayArgs {
+    /// Amount to pay the Executor (lamports), from a fresh quote.
+    pub exec_amount: u64,
+    /// Fresh signed quote bytes from the Executor API.
+    pub signed_quote_bytes: Vec<u8>,
+    /// Gas limit and msgValue for the destination.
+    pub gas: GasInstruction,
+    /// Native tokens to deliver on the destination alongside the message.
+    pub gas_drop_off: Option<GasDropOffInstruction>,
+}
+
+#[derive(Accounts)]
+#[instruction(sequence: u64, dst_chain: u16)]
+pub struct RetryRelay<'info> {
+    #[account(mut)]
+    /// Payer for the Executor request. Anyone may retry a relay.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: payee is enforced by the Executor program via signed quote.
+    pub payee: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            RelayRequest::SEED_PREFIX,
+            &sequence.to_le_bytes()[..],
+            &dst_chain.to_le_bytes()[..],
+        ],
+        bump = relay_request.bump,
+    )]
+    /// RelayRequest of the relay to retry.
+    pub relay_request: Account<'info, RelayRequest>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &relay_request.dst_chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Registered peer on the relay's destination chain.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump = wormhole_emitter.bump,
+    )]
+    /// Program's Wormhole emitter account.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Executor program - verified by config.executor_program_id()
+    #[account(
+        executable,
+        address = config.executor_program_id() @ HelloExecutorError::InvalidExecutorProgram,
+    )]
+    pub executor_program: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Outbound spend cap. Only enforced once `set_spend_cap` has
+    /// created it, so it may still be an empty system account.
+    #[account(mut, seeds = [SpendCap::SEED_PREFIX], bump)]
+    pub spend_cap: UncheckedAccount<'info>,
+
+    /// CHECK: Posted message being relayed (`pda::sent(sequence + 1)`),
+    /// verified in the handler. Optional, as for `request_relay`.
+    pub wormhole_message: Option<UncheckedAccount<'info>>,
+}
+
+/// Event emitted when a relay is retried.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelayRetried {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Sequence of the relayed VAA.
+    pub sequence: u64,
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Lamports paid to the Executor for this attempt.
+    pub exec_amount: u64,
+    /// Executor payee, from the fresh quote.
+    pub payee: Pubkey,
+    /// Timestamp of the request being retried.
+    pub previous_requested_at: i64,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RetryRelay>,
+    _sequence: u64,
+    _dst_chain: u16,
+    args: RetryRelayArgs,
+) -> Result<()> {
+    let relay_request = &ctx.accounts.relay_request;
+    let (dst_chain, sequence) = (relay_request.dst_chain, relay_request.sequence);
+    // A delivered relay needs no retry, and one the peer rejected would be
+    // rejected again.
+    require!(
+        relay_request.status == RelayStatus::Requested,
+        HelloExecutorError::AlreadyDelivered,
+    );
+
+    let quote = verify_quote(
+        &ctx.accounts.config,
+        &ctx.accounts.payee.key(),
+        dst_chain,
+        &args.signed_quote_bytes,
+        None,
+    )?;
+
+    let relay_instructions =
+        build_relay_instructions(dst_chain, &args.gas, args.gas_drop_off.as_ref())?;
+    let message = ctx.accounts.wormhole_message.as_ref();
+    check_peer_version(&ctx.accounts.peer, message, sequence)?;
+    let totals = check_gas_drop_off(&ctx.accounts.peer, dst_chain, &relay_instructions)?;
+    check_exec_amount(
+        &ctx.accounts.config,
+        message,
+        args.exec_amount,
+        &quote,
+        &totals,
+        sequence,
+    )?;
+
+    // Same request bytes, so the same request ID as the original relay.
+    let request_bytes = executor_requests::make_vaa_v1_request(
+        ctx.accounts.config.chain_id,
+        &ctx.accounts.wormhole_emitter.key().to_bytes(),
+        sequence,
+    );
+
+    funds::require_payer_can_afford(
+        &ctx.accounts.payer.to_account_info(),
+        args.exec_amount,
+        ctx.accounts.config.payer_buffer_lamports,
+    )?;
+    charge_spend_cap(
+        &ctx.accounts.config,
+        &ctx.accounts.spend_cap,
+        ctx.remaining_accounts,
+        args.exec_amount,
+    )?;
+
+    let relay_request = &mut ctx.accounts.relay_request;
+    let previous_requested_at = relay_request.requested_at;
+    relay_request.exec_amount = args.exec_amount;
+    relay_request.payee = ctx.accounts.payee.key();
+    relay_request.payer = ctx.accounts.payer.key();
+    relay_request.requested_at = Clock::get()?.unix_timestamp;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        RelayRetried {
+            request_id: relay_request.request_id,
+            dst_chain,
+            sequence,
+            trace_id: relay_request.trace_id,
+            exec_amount: args.exec_amount,
+            payee: ctx.accounts.payee.key(),
+            previous_requested_at,
+        },
+    )?;
+
+    msg!(
+        "Retrying relay of sequence {} to chain {}",
+        sequence,
+        dst_chain
+    );
+
+    executor_cpi::request_for_execution(
+        &ctx.accounts.executor_program.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.payee.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        RequestForExecutionArgs {
+            amount: args.exec_amount,
+            dst_chain,
+            dst_addr: ctx.accounts.peer.address,
+            refund_addr: ctx.accounts.payer.key(),
+            signed_quote_bytes: args.signed_quote_bytes,
+            request_bytes,
+            relay_instructions,
+        },
+    )
+}
@@ -1,13 +1,31 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{self, instruction::Instruction, program::invoke_signed};
+use anchor_lang::solana_program::{
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    keccak,
+    sysvar::{
+        self,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+use anchor_lang::system_program;
 
 use crate::{
     error::HelloExecutorError,
-    message::{HelloExecutorMessage, GREETING_MAX_LENGTH},
-    state::{Config, WormholeEmitter},
+    events,
+    finality::normalize_finality,
+    funds,
+    message::{
+        self, encode_greeting_for_peer, sanitize_for_log, GreetingOrigin, GREETING_MAX_LENGTH,
+    },
+    pda,
+    state::{
+        Config, CpiGuard, Peer, QuotaBypass, RateLimit, RelayStatus, SendQuota, Sent, SpendCap,
+        Treasury, WormholeEmitter,
+    },
+    wormhole_cpi,
 };
 
-use super::SEED_PREFIX_SENT;
+use super::{create_program_account, request_relay::charge_spend_cap};
 
 #[derive(Accounts)]
 pub struct SendGreeting<'info> {
@@ -16,13 +34,16 @@ pub struct SendGreeting<'info> {
     pub payer: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [Config::SEED_PREFIX],
-        bump,
+        bump = config.bump,
+        constraint = !config.outbound_paused @ HelloExecutorError::OutboundPaused,
     )]
     /// Config account with Wormhole addresses.
     pub config: Account<'info, Config>,
 
-    /// CHECK: Wormhole Core Bridge program - any chain's Wormhole program
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
     pub wormhole_program: UncheckedAccount<'info>,
 
     /// CHECK: Wormhole bridge data - verified by config.wormhole.bridge
@@ -41,7 +62,7 @@ pub struct SendGreeting<'info> {
 
     #[account(
         seeds = [WormholeEmitter::SEED_PREFIX],
-        bump,
+        bump = wormhole_emitter.bump,
     )]
     /// Program's emitter account.
     pub wormhole_emitter: Account<'info, WormholeEmitter>,
@@ -62,124 +83,367 @@ pub struct SendGreeting<'info> {
 
     /// Rent sysvar.
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Sent record PDA keyed by the VAA sequence. The sequence is
+    /// read in the handler, so the address is verified and the account
+    /// created there.
+    #[account(mut)]
+    pub sent: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol fee treasury. Only charged once `set_protocol_fee`
+    /// has created it, so it may still be an empty system account.
+    #[account(mut, seeds = [Treasury::SEED_PREFIX], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Outbound spend cap, charged the Wormhole fee. Only enforced
+    /// once `set_spend_cap` has created it, so it may still be an empty
+    /// system account.
+    #[account(mut, seeds = [SpendCap::SEED_PREFIX], bump)]
+    pub spend_cap: UncheckedAccount<'info>,
+
+    /// CHECK: Allowlist of CPI callers. Only enforced once `set_cpi_callers`
+    /// has created it, so it may still be an empty system account.
+    #[account(seeds = [CpiGuard::SEED_PREFIX], bump)]
+    pub cpi_guard: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the calling program.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            SendQuota::SEED_PREFIX,
+            payer.key().as_ref(),
+            &config.quota_epoch(clock.unix_timestamp).to_le_bytes()[..],
+        ],
+        bump,
+        space = SendQuota::MAXIMUM_SIZE,
+    )]
+    /// Payer's send count for the current quota epoch. Required while
+    /// `config.send_quota` is set, unless the payer is exempt.
+    pub send_quota: Option<Account<'info, SendQuota>>,
+
+    #[account(
+        seeds = [QuotaBypass::SEED_PREFIX, payer.key().as_ref()],
+        bump = quota_bypass.bump,
+    )]
+    /// Payer's bypass list entry, exempting it from the quota and the rate
+    /// limit.
+    pub quota_bypass: Option<Account<'info, QuotaBypass>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [RateLimit::SEED_PREFIX, payer.key().as_ref()],
+        bump,
+        space = RateLimit::MAXIMUM_SIZE,
+    )]
+    /// Payer's rate limit bucket. Required while
+    /// `config.rate_limit_capacity` is set, unless the payer is exempt.
+    pub rate_limit: Option<Account<'info, RateLimit>>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &peer.chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer the greeting is meant for. The greeting is encoded as a
+    /// `HelloV2` only if this peer reported support for it; without it, as
+    /// a `Hello`.
+    pub peer: Option<Account<'info, Peer>>,
 }
 
 /// Event emitted when a greeting is sent.
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GreetingSent {
-    /// The greeting message.
+    /// The greeting message; empty while `config.redact_events` is set.
     pub greeting: String,
     /// Sequence number of the Wormhole message.
     pub sequence: u64,
     /// Timestamp of the transaction.
     pub timestamp: i64,
+    /// Trace ID embedded in the payload.
+    pub trace_id: [u8; 16],
+    /// Keccak256 hash of the greeting.
+    pub greeting_hash: [u8; 32],
+    /// Length of the greeting in bytes.
+    pub greeting_len: u32,
+}
+
+/// Event emitted when `send_greeting` charges the protocol fee.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolFeeCollected {
+    /// Wallet that paid.
+    pub payer: Pubkey,
+    /// Lamports paid into the treasury.
+    pub amount: u64,
+    /// Sequence number of the greeting's Wormhole message.
+    pub sequence: u64,
 }
 
-pub(crate) fn handler(ctx: Context<SendGreeting>, greeting: String) -> Result<()> {
+pub(crate) fn handler(
+    ctx: Context<SendGreeting>,
+    greeting: String,
+    finality: Option<u8>,
+) -> Result<()> {
     // Validate message length
     require!(
         greeting.len() <= GREETING_MAX_LENGTH,
         HelloExecutorError::MessageTooLarge,
     );
 
-    // Read fee from bridge account
-    // Wormhole BridgeData layout (no Anchor discriminator):
-    // guardian_set_index(u32) + last_lamports(u64) + guardian_set_expiration_time(u32) + fee(u64)
-    // = offset 0 + 4 + 8 + 4 = 16 for fee
-    let bridge_data = ctx.accounts.wormhole_bridge.try_borrow_data()?;
-    let fee = u64::from_le_bytes(bridge_data[16..24].try_into().unwrap());
-    drop(bridge_data);
-
-    // Pay Wormhole fee if required
-    if fee > 0 {
-        solana_program::program::invoke(
-            &solana_program::system_instruction::transfer(
-                &ctx.accounts.payer.key(),
-                &ctx.accounts.wormhole_fee_collector.key(),
-                fee,
-            ),
-            &ctx.accounts.to_account_infos(),
-        )?;
-    }
-
-    // Read the Wormhole sequence tracker.
-    //
-    // The tracker stores the sequence number Wormhole will assign to the NEXT
-    // post_message call — i.e. the actual VAA sequence for THIS message.
-    //
-    // The message PDA uses `vaa_sequence + 1` to avoid colliding with the init
-    // message PDA, which was seeded with `wormhole::INITIAL_SEQUENCE` (= the
-    // tracker value right after initialize()).
-    let seq_data = ctx.accounts.wormhole_sequence.try_borrow_data()?;
-    let vaa_sequence = if seq_data.len() >= 8 {
-        u64::from_le_bytes(seq_data[0..8].try_into().unwrap())
-    } else {
-        0
-    };
-    drop(seq_data);
-    // PDA slot = vaa_sequence + 1 (avoids the init-time PDA at slot vaa_sequence)
-    let pda_sequence = vaa_sequence + 1;
+    let (caller_program, cpi_depth) = cpi_caller(&ctx.accounts.instructions)?;
+    check_cpi_caller(&ctx.accounts.cpi_guard, &caller_program)?;
+    check_quota(ctx.accounts)?;
+    check_rate_limit(ctx.accounts)?;
 
-    let wormhole_emitter = &ctx.accounts.wormhole_emitter;
     let config = &ctx.accounts.config;
 
-    // Encode the greeting as payload
-    let payload = HelloExecutorMessage::Hello {
-        message: greeting.as_bytes().to_vec(),
-    }
-    .try_to_vec()?;
-
-    // Build wormhole post_message instruction (raw CPI)
-    // Wormhole uses 1-byte instruction discriminator: PostMessage = 1
-    // Data format: [discriminator(1) | nonce(4) | payload_len(4) | payload | consistency(1)]
-    let mut ix_data = Vec::with_capacity(1 + 4 + 4 + payload.len() + 1);
-    ix_data.push(0x01); // PostMessage instruction
-    ix_data.extend_from_slice(&config.batch_id.to_le_bytes()); // nonce (u32)
-    ix_data.extend_from_slice(&(payload.len() as u32).to_le_bytes()); // payload length
-    ix_data.extend_from_slice(&payload);
-    ix_data.push(config.finality); // consistency level
-
-    let ix = Instruction {
-        program_id: ctx.accounts.wormhole_program.key(),
-        accounts: vec![
-            AccountMeta::new(ctx.accounts.wormhole_bridge.key(), false),
-            AccountMeta::new(ctx.accounts.wormhole_message.key(), true),
-            AccountMeta::new_readonly(wormhole_emitter.key(), true),
-            AccountMeta::new(ctx.accounts.wormhole_sequence.key(), false),
-            AccountMeta::new(ctx.accounts.payer.key(), true),
-            AccountMeta::new(ctx.accounts.wormhole_fee_collector.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
-        ],
-        data: ix_data,
+    // A per-message level overrides the config's, for this message only.
+    let finality = match finality {
+        Some(level) => normalize_finality(level)?,
+        None => config.finality,
     };
 
-    // Derive the message PDA bump using pda_sequence
-    let pda_seq_buf = pda_sequence.to_le_bytes();
-    let (_, message_bump) = Pubkey::find_program_address(
-        &[SEED_PREFIX_SENT, &pda_seq_buf],
-        ctx.program_id,
+    // Tag the greeting with the trace ID of the message it is about to become.
+    let trace_id = message::trace_id(
+        config.chain_id,
+        &ctx.accounts.wormhole_emitter.key().to_bytes(),
+        wormhole_cpi::next_sequence(&ctx.accounts.wormhole_sequence)?,
     );
 
-    invoke_signed(
-        &ix,
-        &ctx.accounts.to_account_infos(),
-        &[
-            &[SEED_PREFIX_SENT, &pda_seq_buf, &[message_bump]],
-            &[WormholeEmitter::SEED_PREFIX, &[wormhole_emitter.bump]],
-        ],
+    // Encode the greeting as payload, naming the payer as its sender, in the
+    // newest envelope the destination peer reported it decodes.
+    let clock = &ctx.accounts.clock;
+    let peer_version = ctx.accounts.peer.as_ref().map_or(0, |peer| peer.reported_version);
+    let payload = encode_greeting_for_peer(
+        peer_version,
+        &greeting,
+        trace_id,
+        GreetingOrigin {
+            sender: ctx.accounts.payer.key().to_bytes(),
+            timestamp: clock.unix_timestamp as u64,
+        },
+    )?;
+
+    charge_spend_cap(
+        config,
+        &ctx.accounts.spend_cap,
+        ctx.remaining_accounts,
+        wormhole_cpi::message_fee(&ctx.accounts.wormhole_bridge)?,
     )?;
 
+    let vaa_sequence = wormhole_cpi::post_message(
+        wormhole_cpi::PostMessage {
+            payer: ctx.accounts.payer.to_account_info(),
+            wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+            wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+            wormhole_emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+            wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+            wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        config.wormhole_program_id(),
+        ctx.accounts.wormhole_emitter.bump,
+        config.batch_id,
+        finality,
+        config.payer_buffer_lamports,
+        &payload,
+    )?;
+
+    record_sent(&ctx, vaa_sequence, &payload, trace_id, caller_program, cpi_depth)?;
+    collect_protocol_fee(&ctx, vaa_sequence)?;
+
+    let config = &mut ctx.accounts.config;
+    config.sent_count += 1;
+    config.last_sent_sequence = vaa_sequence;
+
     // Emit event with the ACTUAL VAA sequence (what the relay/explorer will see)
-    let clock = &ctx.accounts.clock;
-    emit!(GreetingSent {
-        greeting,
-        sequence: vaa_sequence,
-        timestamp: clock.unix_timestamp,
-    });
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        GreetingSent {
+            greeting_hash: keccak::hash(greeting.as_bytes()).to_bytes(),
+            greeting_len: greeting.len() as u32,
+            greeting: config.event_content(sanitize_for_log(&greeting, GREETING_MAX_LENGTH)),
+            sequence: vaa_sequence,
+            timestamp: clock.unix_timestamp,
+            trace_id,
+        },
+    )?;
 
     msg!("Greeting sent! VAA sequence: {}", vaa_sequence);
 
     Ok(())
 }
+
+/// Create the Sent record for the greeting published at `sequence`.
+fn record_sent(
+    ctx: &Context<SendGreeting>,
+    sequence: u64,
+    payload: &[u8],
+    trace_id: [u8; 16],
+    caller_program: Pubkey,
+    cpi_depth: u8,
+) -> Result<()> {
+    let (expected, bump) = pda::sent_record(sequence);
+    let info = ctx.accounts.sent.to_account_info();
+    require_keys_eq!(info.key(), expected, HelloExecutorError::InvalidSentRecord);
+
+    create_program_account(
+        &ctx.accounts.payer.to_account_info(),
+        &info,
+        &ctx.accounts.system_program.to_account_info(),
+        Sent::MAXIMUM_SIZE,
+        &[Sent::SEED_PREFIX, &sequence.to_le_bytes(), &[bump]],
+    )?;
+
+    let record = Sent {
+        sequence,
+        dst_chain: 0,
+        payload_hash: keccak::hash(payload).to_bytes(),
+        trace_id,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+        sender: ctx.accounts.payer.key(),
+        caller_program,
+        cpi_depth,
+        status: RelayStatus::Published,
+        bump,
+    };
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Program calling `send_greeting` through CPI and the CPI depth, or the
+/// default key and 0 for a top-level call.
+///
+/// Introspection only sees top-level instructions, so at depths above 1 this
+/// is the outermost program rather than the one that invoked us directly.
+fn cpi_caller(instructions: &AccountInfo) -> Result<(Pubkey, u8)> {
+    let depth = get_stack_height().saturating_sub(TRANSACTION_LEVEL_STACK_HEIGHT);
+    if depth == 0 {
+        return Ok((Pubkey::default(), 0));
+    }
+
+    let index = load_current_index_checked(instructions)?;
+    let ix = load_instruction_at_checked(index.into(), instructions)?;
+    Ok((ix.program_id, depth.try_into().unwrap_or(u8::MAX)))
+}
+
+/// Refuse a CPI caller the CPI guard does not allow, once it exists.
+fn check_cpi_caller(cpi_guard: &AccountInfo, caller_program: &Pubkey) -> Result<()> {
+    if *caller_program == Pubkey::default() || cpi_guard.owner != &crate::ID {
+        return Ok(());
+    }
+    let guard = CpiGuard::try_deserialize(&mut &cpi_guard.try_borrow_data()?[..])?;
+    if !guard.allows(caller_program) {
+        msg!("CPI caller {} is not allowed", caller_program);
+        return err!(HelloExecutorError::CpiCallerNotAllowed);
+    }
+
+    Ok(())
+}
+
+/// Charge the payer the treasury's protocol fee, if one is set.
+fn collect_protocol_fee(ctx: &Context<SendGreeting>, sequence: u64) -> Result<()> {
+    let info = ctx.accounts.treasury.to_account_info();
+    if info.owner != &crate::ID {
+        // Not created yet: no fee.
+        return Ok(());
+    }
+    let mut treasury = Treasury::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let amount = treasury.protocol_fee_lamports;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let payer = ctx.accounts.payer.to_account_info();
+    funds::require_payer_can_afford(&payer, amount, ctx.accounts.config.payer_buffer_lamports)?;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer,
+                to: info.clone(),
+            },
+        ),
+        amount,
+    )?;
+
+    treasury.collected_lamports += amount;
+    treasury.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        ProtocolFeeCollected {
+            payer: ctx.accounts.payer.key(),
+            amount,
+            sequence,
+        },
+    )?;
+
+    msg!("Protocol fee: {} lamports", amount);
+
+    Ok(())
+}
+
+/// Count the greeting against the payer's send quota, if one applies.
+fn check_quota(accounts: &mut SendGreeting) -> Result<()> {
+    let config = &accounts.config;
+    let payer = accounts.payer.key();
+    if config.send_quota == 0 || payer == config.owner || accounts.quota_bypass.is_some() {
+        return Ok(());
+    }
+
+    let epoch = config.quota_epoch(accounts.clock.unix_timestamp);
+    let quota = accounts
+        .send_quota
+        .as_mut()
+        .ok_or(HelloExecutorError::SendQuotaRequired)?;
+    // Just created by init_if_needed.
+    if quota.payer == Pubkey::default() {
+        quota.payer = payer;
+        quota.epoch = epoch;
+    }
+    quota.record(config.send_quota)?;
+
+    msg!("Send quota: {}/{} in epoch {}", quota.sent, config.send_quota, epoch);
+
+    Ok(())
+}
+
+/// Take one greeting from the payer's bucket of `rate_limit_capacity`, which
+/// regains one every `rate_limit_refill_seconds`, if a rate limit applies.
+fn check_rate_limit(accounts: &mut SendGreeting) -> Result<()> {
+    let config = &accounts.config;
+    let payer = accounts.payer.key();
+    if config.rate_limit_capacity == 0 || payer == config.owner || accounts.quota_bypass.is_some() {
+        return Ok(());
+    }
+
+    let now = accounts.clock.unix_timestamp;
+    let bucket = accounts
+        .rate_limit
+        .as_mut()
+        .ok_or(HelloExecutorError::RateLimitRequired)?;
+    // Just created by init_if_needed: start full.
+    if bucket.payer == Pubkey::default() {
+        bucket.payer = payer;
+        bucket.tokens = config.rate_limit_capacity;
+        bucket.refilled_at = now;
+    }
+    bucket.take(config.rate_limit_capacity, config.rate_limit_refill_seconds, now)?;
+
+    msg!("Rate limit: {}/{} left", bucket.tokens, config.rate_limit_capacity);
+
+    Ok(())
+}
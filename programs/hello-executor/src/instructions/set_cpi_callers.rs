@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, CpiGuard},
+};
+
+#[derive(Accounts)]
+pub struct SetCpiCallers<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the CPI guard
+    /// on the first call.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [CpiGuard::SEED_PREFIX],
+        bump,
+        space = CpiGuard::MAXIMUM_SIZE,
+    )]
+    /// CPI guard, created if missing.
+    pub cpi_guard: Account<'info, CpiGuard>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetCpiCallers>,
+    restricted: bool,
+    callers: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        callers.len() <= CpiGuard::MAX_CALLERS,
+        HelloExecutorError::TooManyCpiCallers,
+    );
+
+    let cpi_guard = &mut ctx.accounts.cpi_guard;
+    cpi_guard.restricted = restricted;
+    cpi_guard.callers = Default::default();
+    cpi_guard.callers[..callers.len()].copy_from_slice(&callers);
+    cpi_guard.bump = ctx.bumps.cpi_guard;
+
+    if restricted {
+        for caller in &callers {
+            msg!("CPI caller allowed: {}", caller);
+        }
+        if callers.is_empty() {
+            msg!("send_greeting can no longer be called through CPI");
+        }
+    } else {
+        msg!("Any program may call send_greeting through CPI");
+    }
+
+    Ok(())
+}
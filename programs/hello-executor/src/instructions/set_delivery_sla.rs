@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetDeliverySla<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetDeliverySla>,
+    sla_seconds: u32,
+    delivery_oracle: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.sla_seconds = sla_seconds;
+    config.delivery_oracle = delivery_oracle;
+
+    msg!("Delivery SLA {}s, oracle {}", sla_seconds, delivery_oracle);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetEnforceMinExecAmount<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetEnforceMinExecAmount>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.enforce_min_exec_amount = enabled;
+
+    msg!("Enforce minimum exec amount: {}", enabled);
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, EventMode},
+};
+
+#[derive(Accounts)]
+pub struct SetEventMode<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetEventMode>, mode: EventMode) -> Result<()> {
+    ctx.accounts.config.set_event_mode(mode);
+
+    msg!("Event mode: {:?}", mode);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetExecutorProgram<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetExecutorProgram>, executor_program: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.executor_program = executor_program;
+
+    msg!("Executor program: {}", config.executor_program_id());
+
+    Ok(())
+}
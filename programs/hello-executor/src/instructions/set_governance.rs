@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Governance},
+};
+
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the
+    /// governance account on the first call.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [Governance::SEED_PREFIX],
+        bump,
+        space = Governance::MAXIMUM_SIZE,
+    )]
+    /// Governance account, created if missing.
+    pub governance: Account<'info, Governance>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetGovernance>,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> Result<()> {
+    require!(
+        emitter_chain > 0 && emitter_address != [0; 32],
+        HelloExecutorError::InvalidGovernanceEmitter,
+    );
+
+    // Sequences are per emitter, so a new emitter starts from zero.
+    let governance = &mut ctx.accounts.governance;
+    if !governance.verify(emitter_chain, &emitter_address) {
+        governance.next_sequence = 0;
+    }
+    governance.emitter_chain = emitter_chain;
+    governance.emitter_address = emitter_address;
+    governance.bump = ctx.bumps.governance;
+
+    msg!(
+        "Governance emitter on chain {}: {}",
+        emitter_chain,
+        hex::encode(emitter_address)
+    );
+
+    Ok(())
+}
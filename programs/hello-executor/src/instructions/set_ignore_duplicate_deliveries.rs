@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetIgnoreDuplicateDeliveries<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetIgnoreDuplicateDeliveries>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.set_ignore_duplicate_deliveries(enabled);
+
+    msg!("Ignore duplicate deliveries: {}", enabled);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetModerationOracle<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetModerationOracle>, oracle: Pubkey) -> Result<()> {
+    ctx.accounts.config.moderation_oracle = oracle;
+
+    msg!("Moderation oracle set to {}", oracle);
+
+    Ok(())
+}
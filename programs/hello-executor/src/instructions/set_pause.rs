@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPause>, outbound: bool, inbound: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.outbound_paused = outbound;
+    config.inbound_paused = inbound;
+
+    msg!("Paused: outbound {}, inbound {}", outbound, inbound);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetPayerBuffer<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPayerBuffer>, lamports: u64) -> Result<()> {
+    ctx.accounts.config.payer_buffer_lamports = lamports;
+
+    msg!("Payer buffer set to {} lamports", lamports);
+
+    Ok(())
+}
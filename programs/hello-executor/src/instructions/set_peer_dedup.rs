@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerDedup<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPeerDedup>, chain: u16, enabled: bool) -> Result<()> {
+    let peer = &mut ctx.accounts.peer;
+    peer.dedup = enabled;
+    if !enabled {
+        // Start from an empty window if re-enabled later.
+        peer.recent_payload_hashes = Default::default();
+        peer.recent_payload_cursor = 0;
+    }
+
+    msg!("Peer on chain {}: dedup={}", chain, enabled);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerGasDropOff<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetPeerGasDropOff>,
+    chain: u16,
+    max_gas_drop_off: u128,
+) -> Result<()> {
+    ctx.accounts.peer.max_gas_drop_off = max_gas_drop_off;
+
+    msg!("Peer on chain {}: max_gas_drop_off={}", chain, max_gas_drop_off);
+
+    Ok(())
+}
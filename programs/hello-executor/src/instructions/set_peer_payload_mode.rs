@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, PayloadMode, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerPayloadMode<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPeerPayloadMode>, chain: u16, mode: PayloadMode) -> Result<()> {
+    ctx.accounts.peer.payload_mode = mode;
+
+    msg!("Peer on chain {}: payload mode {:?}", chain, mode);
+
+    Ok(())
+}
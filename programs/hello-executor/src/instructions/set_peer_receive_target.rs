@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, ReceiveTarget, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerReceiveTarget<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPeerReceiveTarget>, chain: u16, target: ReceiveTarget) -> Result<()> {
+    ctx.accounts.peer.receive_target = target;
+
+    msg!("Peer on chain {}: receive target {:?}", chain, target);
+
+    Ok(())
+}
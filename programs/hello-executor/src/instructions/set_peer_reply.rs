@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerReply<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPeerReply>, chain: u16, reply_text: String) -> Result<()> {
+    require!(
+        reply_text.len() <= Peer::REPLY_MAX_LENGTH,
+        HelloExecutorError::MessageTooLarge,
+    );
+
+    msg!("Peer on chain {}: reply {:?}", chain, reply_text);
+
+    ctx.accounts.peer.reply_text = reply_text;
+
+    Ok(())
+}
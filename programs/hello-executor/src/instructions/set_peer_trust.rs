@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerTrust<'info> {
+    /// Owner or moderation oracle.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = authority.key() == config.owner
+            || authority.key() == config.moderation_oracle
+            @ HelloExecutorError::TrustAuthorityOnly,
+    )]
+    /// Config account. Verifies the authority.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPeerTrust>, chain: u16, trust_score: u8) -> Result<()> {
+    ctx.accounts.peer.trust_score = trust_score;
+
+    msg!("Peer on chain {}: trust score {}", chain, trust_score);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Peer},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetPeerTtl<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer account to configure.
+    pub peer: Account<'info, Peer>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetPeerTtl>,
+    chain: u16,
+    max_age_seconds: u32,
+    reject_expired: bool,
+) -> Result<()> {
+    let peer = &mut ctx.accounts.peer;
+    peer.max_age_seconds = max_age_seconds;
+    peer.reject_expired = reject_expired;
+
+    msg!(
+        "Peer on chain {}: max_age_seconds={}, reject_expired={}",
+        chain,
+        max_age_seconds,
+        reject_expired
+    );
+
+    Ok(())
+}
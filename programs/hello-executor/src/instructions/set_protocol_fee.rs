@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, Treasury},
+};
+
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the
+    /// treasury on the first call.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [Treasury::SEED_PREFIX],
+        bump,
+        space = Treasury::MAXIMUM_SIZE,
+    )]
+    /// Protocol fee treasury, created if missing.
+    pub treasury: Account<'info, Treasury>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<SetProtocolFee>, lamports: u64) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.protocol_fee_lamports = lamports;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Protocol fee: {} lamports per greeting", lamports);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetQuoteSigners<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetQuoteSigners>, signers: Vec<[u8; 20]>) -> Result<()> {
+    require!(
+        signers.len() <= Config::MAX_QUOTE_SIGNERS,
+        HelloExecutorError::TooManyQuoteSigners,
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.quote_signers = Default::default();
+    config.quote_signers[..signers.len()].copy_from_slice(&signers);
+
+    for signer in &signers {
+        msg!("Quote signer: 0x{}", hex::encode(signer));
+    }
+    if signers.is_empty() {
+        msg!("Quote signers cleared; quotes are no longer checked");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetRateLimit<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetRateLimit>,
+    capacity: u16,
+    refill_seconds: u32,
+) -> Result<()> {
+    require!(
+        capacity == 0 || refill_seconds > 0,
+        HelloExecutorError::InvalidRateLimit,
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.rate_limit_capacity = capacity;
+    config.rate_limit_refill_seconds = refill_seconds;
+
+    msg!(
+        "Rate limit {} greetings, one back every {}s",
+        capacity,
+        refill_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetRedaction<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetRedaction>, events: bool, storage: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.redact_events = events;
+    config.redact_storage = storage;
+
+    msg!("Redaction: events {}, storage {}", events, storage);
+
+    Ok(())
+}
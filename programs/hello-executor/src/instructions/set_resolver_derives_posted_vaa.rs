@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetResolverDerivesPostedVaa<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SetResolverDerivesPostedVaa>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.set_resolver_derives_posted_vaa(enabled);
+
+    msg!("Resolver derives posted VAA: {}", enabled);
+
+    Ok(())
+}
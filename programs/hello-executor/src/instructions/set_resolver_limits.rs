@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetResolverLimits<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetResolverLimits>,
+    max_groups: u8,
+    max_accounts: u8,
+) -> Result<()> {
+    require!(
+        max_accounts <= Config::RESOLVER_MAX_ACCOUNTS_LIMIT,
+        HelloExecutorError::InvalidResolverLimits,
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.resolver_max_groups = max_groups;
+    config.resolver_max_accounts = max_accounts;
+
+    msg!(
+        "Resolver limits: {} instruction groups, {} accounts per instruction",
+        config.resolver_max_groups(),
+        config.resolver_max_accounts()
+    );
+
+    Ok(())
+}
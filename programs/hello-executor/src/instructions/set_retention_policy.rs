@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, RetentionPolicy},
+};
+
+#[derive(Accounts)]
+pub struct SetRetentionPolicy<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the policy
+    /// on the first call.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [RetentionPolicy::SEED_PREFIX],
+        bump,
+        space = RetentionPolicy::MAXIMUM_SIZE,
+    )]
+    /// Retention policy, created if missing.
+    pub retention_policy: Account<'info, RetentionPolicy>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetRetentionPolicy>,
+    max_records_per_peer: u32,
+    max_age_seconds: u32,
+    pruner_tip_bps: u16,
+) -> Result<()> {
+    require!(
+        pruner_tip_bps <= RetentionPolicy::MAX_TIP_BPS,
+        HelloExecutorError::InvalidRetentionPolicy,
+    );
+
+    let policy = &mut ctx.accounts.retention_policy;
+    policy.max_records_per_peer = max_records_per_peer;
+    policy.max_age_seconds = max_age_seconds;
+    policy.pruner_tip_bps = pruner_tip_bps;
+    policy.bump = ctx.bumps.retention_policy;
+
+    msg!(
+        "Retention: {} records per peer, {} seconds, {} bps pruner tip",
+        max_records_per_peer,
+        max_age_seconds,
+        pruner_tip_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct SetSendQuota<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetSendQuota>,
+    send_quota: u32,
+    quota_epoch_seconds: u32,
+) -> Result<()> {
+    require!(
+        send_quota == 0 || quota_epoch_seconds > 0,
+        HelloExecutorError::InvalidQuota,
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.send_quota = send_quota;
+    config.quota_epoch_seconds = quota_epoch_seconds;
+
+    msg!("Send quota {} per {}s", send_quota, quota_epoch_seconds);
+
+    Ok(())
+}
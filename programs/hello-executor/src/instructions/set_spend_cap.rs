@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, SpendCap},
+};
+
+#[derive(Accounts)]
+pub struct SetSpendCap<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Pays for the spend cap
+    /// on the first call.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [SpendCap::SEED_PREFIX],
+        bump,
+        space = SpendCap::MAXIMUM_SIZE,
+    )]
+    /// Spend cap, created if missing.
+    pub spend_cap: Account<'info, SpendCap>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetSpendCap>,
+    max_lamports: u64,
+    epoch_seconds: u32,
+) -> Result<()> {
+    let spend_cap = &mut ctx.accounts.spend_cap;
+    spend_cap.max_lamports = max_lamports;
+    spend_cap.bump = ctx.bumps.spend_cap;
+
+    // Epochs of a different length are numbered differently: start over.
+    if spend_cap.epoch_seconds != epoch_seconds {
+        spend_cap.epoch_seconds = epoch_seconds;
+        spend_cap.epoch = spend_cap.epoch_at(Clock::get()?.unix_timestamp);
+        spend_cap.spent_lamports = 0;
+        spend_cap.warned = false;
+    }
+
+    msg!(
+        "Spend cap: {} lamports per {}-second epoch",
+        max_lamports,
+        epoch_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    query::{
+        self, GuardianSetData, GUARDIAN_SIGNATURE_LENGTH, QUERY_RESULT_MAX_LENGTH,
+        SEED_PREFIX_GUARDIAN_SET,
+    },
+    state::{Config, Peer, QueryResult},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16, guardian_set_index: u32)]
+pub struct SubmitQueryResponse<'info> {
+    #[account(mut)]
+    /// Payer for creating the QueryResult account. Anyone may submit a
+    /// guardian-signed response.
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Registered peer whose state was queried.
+    pub peer: Account<'info, Peer>,
+
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ HelloExecutorError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_PREFIX_GUARDIAN_SET, &guardian_set_index.to_be_bytes()[..]],
+        bump,
+        seeds::program = wormhole_program.key,
+    )]
+    /// CHECK: Core Bridge guardian set that signed the response. Address is
+    /// verified by the seeds above; data is parsed in the handler.
+    pub guardian_set: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [QueryResult::SEED_PREFIX, &chain.to_le_bytes()[..]],
+        bump,
+        space = QueryResult::MAXIMUM_SIZE,
+    )]
+    /// Latest query result for this peer.
+    pub query_result: Account<'info, QueryResult>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a query response is verified and stored.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueryResponseSubmitted {
+    /// Chain ID of the queried peer.
+    pub chain: u16,
+    /// Block number the `eth_call` was executed at.
+    pub block_number: u64,
+    /// Raw return data of the `eth_call`.
+    pub result: Vec<u8>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SubmitQueryResponse>,
+    chain: u16,
+    guardian_set_index: u32,
+    response: Vec<u8>,
+    signatures: Vec<[u8; GUARDIAN_SIGNATURE_LENGTH]>,
+) -> Result<()> {
+    // Load the guardian set and make sure it may still sign.
+    let guardian_set = GuardianSetData::deserialize(
+        &mut &ctx.accounts.guardian_set.try_borrow_data()?[..],
+    )
+    .map_err(|_| HelloExecutorError::InvalidGuardianSet)?;
+    require!(
+        guardian_set.index == guardian_set_index
+            && guardian_set.is_active(Clock::get()?.unix_timestamp),
+        HelloExecutorError::InvalidGuardianSet,
+    );
+
+    // Verify a quorum of guardians signed this exact response.
+    let digest = query::query_response_digest(&response);
+    query::verify_guardian_signatures(&digest, &signatures, &guardian_set)?;
+
+    // The response must be an eth_call against the registered peer contract.
+    let eth_call = query::parse_eth_call_response(&response).map_err(|e| {
+        msg!("Failed to parse query response: {:?}", e);
+        HelloExecutorError::InvalidQueryResponse
+    })?;
+    let peer = &ctx.accounts.peer;
+    require!(
+        eth_call.chain == chain
+            && peer.address[..12].iter().all(|&x| x == 0)
+            && eth_call.to[..] == peer.address[12..]
+            && eth_call.result.len() <= QUERY_RESULT_MAX_LENGTH,
+        HelloExecutorError::InvalidQueryResponse,
+    );
+
+    // Only accept responses newer than what is already stored.
+    let query_result = &mut ctx.accounts.query_result;
+    require!(
+        eth_call.block_number > query_result.block_number,
+        HelloExecutorError::StaleQueryResponse,
+    );
+
+    query_result.chain = chain;
+    query_result.block_number = eth_call.block_number;
+    query_result.block_time_us = eth_call.block_time_us;
+    query_result.response_hash = keccak::hash(&response).to_bytes();
+    query_result.result = eth_call.result.clone();
+    query_result.submitted_at = Clock::get()?.unix_timestamp;
+    query_result.bump = ctx.bumps.query_result;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        QueryResponseSubmitted {
+            chain,
+            block_number: eth_call.block_number,
+            result: eth_call.result,
+        },
+    )?;
+
+    msg!(
+        "Stored query result for chain {} at block {}",
+        chain,
+        eth_call.block_number
+    );
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    message::{sanitize_for_log, GREETING_MAX_LENGTH},
+    state::{Config, Peer, Received, ReceivedStatus},
+};
+
+use super::receive_greeting::GreetingReceived;
+
+#[derive(Accounts)]
+#[instruction(chain: u16, sequence: u64)]
+pub struct SubmitTranslation<'info> {
+    /// Moderation oracle, which also translates. Must match
+    /// config.moderation_oracle.
+    pub moderation_oracle: Signer<'info>,
+
+    #[account(
+        has_one = moderation_oracle @ HelloExecutorError::ModerationOracleOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the oracle.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Peer::SEED_PREFIX, config.salt_seed(), &chain.to_le_bytes()[..]],
+        bump = peer.bump,
+    )]
+    /// Peer that sent the greeting. Provides the sender address for
+    /// `GreetingReceived`.
+    pub peer: Account<'info, Peer>,
+
+    #[account(
+        mut,
+        seeds = [
+            Received::SEED_PREFIX,
+            config.salt_seed(),
+            &chain.to_le_bytes()[..],
+            &sequence.to_le_bytes()[..],
+        ],
+        bump = received.bump,
+        constraint = received.status == ReceivedStatus::PendingTranslation
+            @ HelloExecutorError::NotPendingTranslation,
+    )]
+    /// Received record held for translation.
+    pub received: Account<'info, Received>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SubmitTranslation>,
+    chain: u16,
+    sequence: u64,
+    translation: String,
+) -> Result<()> {
+    require!(
+        !translation.is_empty() && translation.len() <= GREETING_MAX_LENGTH,
+        HelloExecutorError::InvalidMessage,
+    );
+
+    // The translation replaces the original, which stays in the VAA.
+    let config = &ctx.accounts.config;
+    let received = &mut ctx.accounts.received;
+    received.message = config.stored_message(translation.as_bytes());
+    received.status = ReceivedStatus::Delivered;
+
+    events::emit_event(
+        config,
+        ctx.remaining_accounts,
+        GreetingReceived {
+            greeting_hash: keccak::hash(translation.as_bytes()).to_bytes(),
+            greeting_len: translation.len() as u32,
+            greeting: config.event_content(sanitize_for_log(&translation, GREETING_MAX_LENGTH)),
+            sender_chain: chain,
+            sender: ctx.accounts.peer.address,
+            sequence,
+            trace_id: received.trace_id,
+            origin_sender: received.origin_sender,
+            sent_at: received.sent_at,
+        },
+    )?;
+
+    msg!("Translated greeting from chain {} seq {} delivered", chain, sequence);
+
+    Ok(())
+}
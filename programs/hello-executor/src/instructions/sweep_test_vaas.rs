@@ -0,0 +1,56 @@
+//! Test-only cleanup of injected messages (`integration-test` feature).
+//!
+//! Purges only TestVaa accounts. A consumed message's Received record and
+//! its ReceivedIndex (keyed by [TestVaa::message_hash]) are ordinary
+//! records, closed by `prune` like those of `receive_greeting`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    error::HelloExecutorError,
+    state::{Config, TestVaa},
+};
+
+#[derive(Accounts)]
+pub struct SweepTestVaas<'info> {
+    #[account(mut)]
+    /// Owner of the program. Must match config.owner. Refunded the rent of
+    /// the purged test VAAs.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<SweepTestVaas>) -> Result<()> {
+    let owner = ctx.accounts.owner.to_account_info();
+    let slot = Clock::get()?.slot;
+    let mut expired = 0;
+    for info in ctx.remaining_accounts {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        // Fails unless the account has the TestVaa discriminator.
+        let test_vaa = TestVaa::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if test_vaa.is_expired(slot) {
+            expired += 1;
+        }
+
+        **owner.try_borrow_mut_lamports()? += info.lamports();
+        **info.try_borrow_mut_lamports()? = 0;
+        info.realloc(0, false)?;
+        info.assign(&system_program::ID);
+    }
+
+    msg!(
+        "integration-test: purged {} test VAAs ({} expired)",
+        ctx.remaining_accounts.len(),
+        expired
+    );
+
+    Ok(())
+}
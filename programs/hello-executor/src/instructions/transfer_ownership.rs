@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, state::Config};
+
+#[derive(Accounts)]
+pub struct TransferOwnership<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(ctx: Context<TransferOwnership>, new_owner: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        new_owner != Some(config.owner) && new_owner != Some(Pubkey::default()),
+        HelloExecutorError::InvalidNewOwner,
+    );
+    config.pending_owner = new_owner.unwrap_or_default();
+
+    match new_owner {
+        Some(new_owner) => msg!("Ownership transfer to {} pending", new_owner),
+        None => msg!("Ownership transfer cancelled"),
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Config;
+use crate::{pda, state::Config};
 
 #[derive(Accounts)]
 pub struct UpdateWormholeConfig<'info> {
@@ -11,7 +11,7 @@ pub struct UpdateWormholeConfig<'info> {
     #[account(
         mut,
         seeds = [Config::SEED_PREFIX],
-        bump,
+        bump = config.bump,
         has_one = owner,
     )]
     /// Config account to update.
@@ -53,13 +53,21 @@ pub(crate) fn handler(ctx: Context<UpdateWormholeConfig>) -> Result<()> {
     );
     
     let config = &mut ctx.accounts.config;
-    
-    // Update Wormhole addresses
+
+    // Update Wormhole addresses. The resolver names this Core Bridge in the
+    // instructions it returns, so one build resolves on any network.
+    config.wormhole_program = wormhole_program;
     config.wormhole.bridge = ctx.accounts.wormhole_bridge.key();
     config.wormhole.fee_collector = ctx.accounts.wormhole_fee_collector.key();
-    
+    config.wormhole.sequence = Pubkey::find_program_address(
+        &[b"Sequence", pda::emitter().0.as_ref()],
+        &wormhole_program,
+    )
+    .0;
+
     msg!(
-        "Wormhole config updated. Bridge: {}, FeeCollector: {}",
+        "Wormhole config updated. Program: {}, Bridge: {}, FeeCollector: {}",
+        wormhole_program,
         config.wormhole.bridge,
         config.wormhole.fee_collector
     );
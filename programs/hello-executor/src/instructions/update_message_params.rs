@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::HelloExecutorError, finality::normalize_finality, state::Config};
+
+#[derive(Accounts)]
+pub struct UpdateMessageParams<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateMessageParams>,
+    finality: Option<u8>,
+    batch_id: Option<u32>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    if let Some(finality) = finality {
+        config.finality = normalize_finality(finality)?;
+    }
+    if let Some(batch_id) = batch_id {
+        config.batch_id = batch_id;
+    }
+
+    msg!(
+        "Message params: finality {}, batch ID {}",
+        config.finality,
+        config.batch_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::HelloExecutorError,
+    events,
+    state::{Config, Treasury},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    /// Owner of the program. Must match config.owner.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ HelloExecutorError::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    /// Config account. Verifies the owner.
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+    )]
+    /// Protocol fee treasury.
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    /// CHECK: Any account may receive the withdrawal; lamports are only
+    /// credited to it.
+    pub recipient: UncheckedAccount<'info>,
+}
+
+/// Event emitted when the owner withdraws from the treasury.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryWithdrawn {
+    /// Account credited.
+    pub recipient: Pubkey,
+    /// Lamports withdrawn.
+    pub amount: u64,
+}
+
+pub(crate) fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let recipient = ctx.accounts.recipient.to_account_info();
+
+    // The treasury must stay rent-exempt; everything above that is fees
+    // (or lamports sent to it directly).
+    let available = treasury
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(treasury.data_len()));
+    if amount > available {
+        msg!("Treasury holds {} withdrawable lamports, {} requested", available, amount);
+        return err!(HelloExecutorError::InsufficientTreasuryFunds);
+    }
+
+    // The program owns the treasury, so it can debit it directly.
+    **treasury.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
+
+    ctx.accounts.treasury.withdrawn_lamports += amount;
+
+    events::emit_event(
+        &ctx.accounts.config,
+        ctx.remaining_accounts,
+        TreasuryWithdrawn {
+            recipient: recipient.key(),
+            amount,
+        },
+    )?;
+
+    msg!("Withdrew {} lamports from the treasury to {}", amount, recipient.key);
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use chains::CorridorPreset;
 
 pub use error::*;
 pub use instructions::*;
@@ -6,12 +7,33 @@ pub use message::*;
 pub use resolver::*;
 pub use state::*;
 
+pub mod chains;
 pub mod error;
+pub mod events;
+pub mod exec_amount;
 pub mod executor_cpi;
+pub mod executor_requests;
+pub mod fallback;
+pub mod finality;
+pub mod funds;
+pub mod governance;
 pub mod instructions;
 pub mod message;
+pub mod pda;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod query;
+pub mod quote;
+pub mod receipts;
+pub mod relay_instructions;
 pub mod resolver;
+pub mod return_data;
+pub mod sigverify;
 pub mod state;
+pub mod token_bridge;
+pub mod vaa;
+pub mod verify_shim;
+pub mod wormhole_cpi;
 
 // TODO(redeploy): Update this ID when redeploying with a new keypair.
 // Run: solana-keygen pubkey target/deploy/hello_executor-keypair.json
@@ -28,11 +50,66 @@ pub mod hello_executor {
     use super::*;
 
     /// Initialize the program config and create the Wormhole emitter.
-    pub fn initialize(ctx: Context<Initialize>, chain_id: u16) -> Result<()> {
-        instructions::initialize::handler(ctx, chain_id)
+    ///
+    /// Nothing is published; call `announce` before sending greetings.
+    ///
+    /// `finality` overrides the network default consistency level; EVM-style
+    /// values (`200`/`201`) are accepted and mapped to `Confirmed`. `salt`
+    /// is mixed into Peer and Received seeds so forks of this program do not
+    /// derive the same addresses; leave it unset to keep the unsalted ones.
+    /// `executor_program` names the Executor deployment to relay through,
+    /// for testnets and forks; unset, it is the canonical Executor.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        chain_id: u16,
+        finality: Option<u8>,
+        salt: Option<[u8; 8]>,
+        executor_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::initialize::handler(ctx, chain_id, finality, salt, executor_program)
+    }
+
+    /// Complete a partial deployment: create the config and emitter if they
+    /// are missing. Takes the same accounts and arguments as `initialize`;
+    /// arguments only apply to a config it creates. An existing config
+    /// restricts this to its owner. A no-op on a fully initialized
+    /// deployment.
+    pub fn repair_initialize(
+        ctx: Context<RepairInitialize>,
+        chain_id: u16,
+        finality: Option<u8>,
+        salt: Option<[u8; 8]>,
+        executor_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::repair_initialize::handler(ctx, chain_id, finality, salt, executor_program)
+    }
+
+    /// Publish the "Alive" message (owner only, once), creating the
+    /// emitter's sequence tracker. Required before anything else is
+    /// published, so greetings start at sequence 1.
+    pub fn announce(ctx: Context<Announce>) -> Result<()> {
+        instructions::announce::handler(ctx)
+    }
+
+    /// Propose `new_owner` as the program owner (owner only). The transfer
+    /// completes when it calls `accept_ownership`; `None` cancels a pending
+    /// transfer.
+    pub fn transfer_ownership(
+        ctx: Context<TransferOwnership>,
+        new_owner: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::transfer_ownership::handler(ctx, new_owner)
+    }
+
+    /// Accept a pending ownership transfer (pending owner only).
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        instructions::accept_ownership::handler(ctx)
     }
 
     /// Register a peer contract on another chain.
+    ///
+    /// While `config.timelock_seconds` is non-zero, changing the address of an
+    /// already-registered peer must go through `queue_admin_action`.
     pub fn register_peer(
         ctx: Context<RegisterPeer>,
         chain: u16,
@@ -41,9 +118,491 @@ pub mod hello_executor {
         instructions::register_peer::handler(ctx, chain, address)
     }
 
+    /// Register the contract behind a `receive_handshake` as the peer for
+    /// its chain (owner only), as `register_peer` would. Refunds whoever
+    /// delivered the handshake.
+    pub fn confirm_pending_peer(
+        ctx: Context<ConfirmPendingPeer>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        instructions::confirm_pending_peer::handler(ctx, chain, address)
+    }
+
+    /// Discard a handshake (owner only). The owner gets its rent.
+    pub fn reject_pending_peer(
+        ctx: Context<RejectPendingPeer>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        instructions::reject_pending_peer::handler(ctx, chain, address)
+    }
+
+    /// Queue a sensitive admin action (owner only). It may execute once
+    /// `config.timelock_seconds` have passed.
+    pub fn queue_admin_action(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+        instructions::queue_admin_action::handler(ctx, action)
+    }
+
+    /// Execute a queued admin action whose timelock has elapsed (owner only).
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>, id: u64) -> Result<()> {
+        instructions::execute_admin_action::handler(ctx, id)
+    }
+
+    /// Discard a queued admin action (owner only).
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>, id: u64) -> Result<()> {
+        instructions::cancel_admin_action::handler(ctx, id)
+    }
+
+    /// Name the emitter whose VAAs `receive_governance` applies (owner
+    /// only). Changing the emitter restarts its sequence check.
+    pub fn set_governance(
+        ctx: Context<SetGovernance>,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_governance::handler(ctx, emitter_chain, emitter_address)
+    }
+
+    /// Set a peer's maximum accepted message age (owner only).
+    ///
+    /// With `max_age_seconds > 0`, greetings whose VAA timestamp is older are
+    /// recorded as `Expired` instead of delivered, or rejected outright when
+    /// `reject_expired` is set. Zero disables the check.
+    pub fn set_peer_ttl(
+        ctx: Context<SetPeerTtl>,
+        chain: u16,
+        max_age_seconds: u32,
+        reject_expired: bool,
+    ) -> Result<()> {
+        instructions::set_peer_ttl::handler(ctx, chain, max_age_seconds, reject_expired)
+    }
+
+    /// Enable or disable duplicate detection for a peer (owner only).
+    ///
+    /// When enabled, a payload identical to one of the peer's last
+    /// `Peer::DEDUP_WINDOW` payloads is recorded as `Duplicate` instead of
+    /// delivered.
+    pub fn set_peer_dedup(ctx: Context<SetPeerDedup>, chain: u16, enabled: bool) -> Result<()> {
+        instructions::set_peer_dedup::handler(ctx, chain, enabled)
+    }
+
+    /// Set the largest gas drop-off a relay to a peer may request (owner
+    /// only). Zero disables drop-off for the peer.
+    pub fn set_peer_gas_drop_off(
+        ctx: Context<SetPeerGasDropOff>,
+        chain: u16,
+        max_gas_drop_off: u128,
+    ) -> Result<()> {
+        instructions::set_peer_gas_drop_off::handler(ctx, chain, max_gas_drop_off)
+    }
+
+    /// Configure the corridor to a registered peer from a built-in preset
+    /// (owner only): its message age limit, payload mode, dedup and gas
+    /// drop-off cap, and the sender rate limit if none is set. See
+    /// `chains::CorridorPreset`.
+    pub fn configure_corridor(
+        ctx: Context<ConfigureCorridor>,
+        chain: u16,
+        preset: CorridorPreset,
+    ) -> Result<()> {
+        instructions::configure_corridor::handler(ctx, chain, preset)
+    }
+
+    /// Set how inbound payloads from a peer are interpreted (owner only).
+    ///
+    /// `Raw` stores payloads verbatim without UTF-8 validation, pairing with
+    /// `publish_payload` on the sending side.
+    pub fn set_peer_payload_mode(
+        ctx: Context<SetPeerPayloadMode>,
+        chain: u16,
+        mode: PayloadMode,
+    ) -> Result<()> {
+        instructions::set_peer_payload_mode::handler(ctx, chain, mode)
+    }
+
+    /// Set the instruction the resolver targets for a peer's messages
+    /// (owner only).
+    ///
+    /// Only takes effect when the resolver reads the Peer, i.e. with
+    /// `set_resolver_requires_peer(true)`.
+    pub fn set_peer_receive_target(
+        ctx: Context<SetPeerReceiveTarget>,
+        chain: u16,
+        target: ReceiveTarget,
+    ) -> Result<()> {
+        instructions::set_peer_receive_target::handler(ctx, chain, target)
+    }
+
+    /// Set the greeting `receive_and_reply` posts back to a peer after each
+    /// delivered greeting (owner only). An empty reply turns replies off.
+    pub fn set_peer_reply(ctx: Context<SetPeerReply>, chain: u16, reply_text: String) -> Result<()> {
+        instructions::set_peer_reply::handler(ctx, chain, reply_text)
+    }
+
+    /// Set a peer's trust score (owner or moderation oracle). Raw payloads
+    /// and receipt minting need at least `Peer::HIGH_TRUST_SCORE`; plain
+    /// greetings are accepted from any peer.
+    pub fn set_peer_trust(ctx: Context<SetPeerTrust>, chain: u16, trust_score: u8) -> Result<()> {
+        instructions::set_peer_trust::handler(ctx, chain, trust_score)
+    }
+
+    /// Publish a peer's receive counters and the emitter's send count as a
+    /// `Stats` message. Anyone may crank this, at most once per
+    /// `STATS_PUBLISH_INTERVAL_SECONDS` per peer; the owner is not limited.
+    pub fn publish_stats(ctx: Context<PublishStats>, chain: u16) -> Result<()> {
+        instructions::publish_stats::handler(ctx, chain)
+    }
+
+    /// Publish a peer's history hash, the running hash of every message
+    /// received from it, as a `HistoryCheckpoint` message, so the peer can
+    /// check it against what it sent. Anyone may crank this, at most once
+    /// per `CHECKPOINT_PUBLISH_INTERVAL_SECONDS` per peer; the owner is not
+    /// limited.
+    pub fn publish_checkpoint(ctx: Context<PublishCheckpoint>, chain: u16) -> Result<()> {
+        instructions::publish_checkpoint::handler(ctx, chain)
+    }
+
+    /// Announce this program to the deployment on `target_chain` with a
+    /// `PeerHandshake` message (owner only), so its owner can confirm it
+    /// instead of registering this program's emitter by hand.
+    pub fn send_handshake(ctx: Context<SendHandshake>, target_chain: u16) -> Result<()> {
+        instructions::send_handshake::handler(ctx, target_chain)
+    }
+
+    /// Ask a peer for its protocol version (owner only). Posts a
+    /// `VersionProbe` carrying this program's `PROTOCOL_VERSION`; the peer
+    /// answers with a `VersionReport` once someone calls its
+    /// `report_version`.
+    pub fn probe_peer_version(ctx: Context<ProbePeerVersion>, chain: u16) -> Result<()> {
+        instructions::probe_peer_version::handler(ctx, chain)
+    }
+
+    /// Answer the peer's pending version probe with a `VersionReport`.
+    /// Anyone may crank this and pay the Wormhole fee.
+    pub fn report_version(ctx: Context<ReportVersion>, chain: u16) -> Result<()> {
+        instructions::report_version::handler(ctx, chain)
+    }
+
+    /// Acknowledge the message received from `chain` at `sequence` with a
+    /// `DeliveryAck`, telling the peer whether it was delivered. The peer
+    /// records the outcome with its `mark_delivered`. Anyone may crank this
+    /// once per message and pay the Wormhole fee.
+    pub fn acknowledge(ctx: Context<Acknowledge>, chain: u16, sequence: u64) -> Result<()> {
+        instructions::acknowledge::handler(ctx, chain, sequence)
+    }
+
+    /// Set how many lamports above rent-exemption a payer must keep after
+    /// paying send and relay fees (owner only). Zero only requires the payer
+    /// to stay rent-exempt.
+    pub fn set_payer_buffer(ctx: Context<SetPayerBuffer>, lamports: u64) -> Result<()> {
+        instructions::set_payer_buffer::handler(ctx, lamports)
+    }
+
+    /// Change the consistency level and batch ID of outbound messages (owner
+    /// only). `None` keeps the current value; `finality` accepts the same
+    /// levels as `initialize`.
+    pub fn update_message_params(
+        ctx: Context<UpdateMessageParams>,
+        finality: Option<u8>,
+        batch_id: Option<u32>,
+    ) -> Result<()> {
+        instructions::update_message_params::handler(ctx, finality, batch_id)
+    }
+
+    /// Set the EVM addresses allowed to sign Executor quotes (owner only).
+    ///
+    /// Once set, `request_relay` rejects quotes not signed by one of them.
+    /// An empty list turns the check off; the quote's chains, payee and
+    /// expiry are checked regardless.
+    pub fn set_quote_signers(ctx: Context<SetQuoteSigners>, signers: Vec<[u8; 20]>) -> Result<()> {
+        instructions::set_quote_signers::handler(ctx, signers)
+    }
+
+    /// Set the Executor program relays are requested from (owner only);
+    /// the default key selects the canonical Executor.
+    pub fn set_executor_program(
+        ctx: Context<SetExecutorProgram>,
+        executor_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_executor_program::handler(ctx, executor_program)
+    }
+
+    /// Set the key allowed to attest moderation verdicts (owner only). The
+    /// default key disables moderation.
+    pub fn set_moderation_oracle(ctx: Context<SetModerationOracle>, oracle: Pubkey) -> Result<()> {
+        instructions::set_moderation_oracle::handler(ctx, oracle)
+    }
+
+    /// Hold received greetings longer than `translate_above_len` bytes for
+    /// the moderation oracle to translate (owner only). Zero turns
+    /// translation off.
+    pub fn set_translate_above_len(
+        ctx: Context<SetTranslateAboveLen>,
+        translate_above_len: u16,
+    ) -> Result<()> {
+        instructions::set_translate_above_len::handler(ctx, translate_above_len)
+    }
+
+    /// Make the Executor resolver require the emitter's Peer account, so VAAs
+    /// from unregistered emitters are refused at resolve time (owner only).
+    pub fn set_resolver_requires_peer(
+        ctx: Context<SetResolverRequiresPeer>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_resolver_requires_peer::handler(ctx, enabled)
+    }
+
+    /// Make the resolver name the Wormhole PostedVAA account instead of the
+    /// Executor's placeholder (owner only), for Executors that do not post
+    /// VAAs themselves. The VAA must then be posted before execution.
+    pub fn set_resolver_derives_posted_vaa(
+        ctx: Context<SetResolverDerivesPostedVaa>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_resolver_derives_posted_vaa::handler(ctx, enabled)
+    }
+
+    /// Set the most instruction groups the resolver may return and the most
+    /// accounts one resolved instruction may name (owner only). Zero keeps
+    /// the defaults; larger results fail with `ResolverOutputTooLarge`.
+    pub fn set_resolver_limits(
+        ctx: Context<SetResolverLimits>,
+        max_groups: u8,
+        max_accounts: u8,
+    ) -> Result<()> {
+        instructions::set_resolver_limits::handler(ctx, max_groups, max_accounts)
+    }
+
+    /// Have the receive instructions succeed without effect on a message
+    /// that was already received, emitting `DuplicateDeliveryIgnored`,
+    /// instead of reverting (owner only). For setups where the Executor and
+    /// a self-relay may both deliver a message.
+    pub fn set_ignore_duplicate_deliveries(
+        ctx: Context<SetIgnoreDuplicateDeliveries>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_ignore_duplicate_deliveries::handler(ctx, enabled)
+    }
+
+    /// Choose how events are emitted (owner only): `Legacy` log lines,
+    /// `Dual` log lines and event-CPI while indexers migrate, or `Cpi` once
+    /// log lines are retired. See `events`.
+    pub fn set_event_mode(ctx: Context<SetEventMode>, mode: EventMode) -> Result<()> {
+        instructions::set_event_mode::handler(ctx, mode)
+    }
+
+    /// Pause or resume outbound (`send_greeting`, `publish_payload`,
+    /// `request_relay`) and inbound (`receive_greeting`) messaging (owner
+    /// only). An emergency stop, e.g. if a peer contract is compromised.
+    pub fn set_pause(ctx: Context<SetPause>, outbound: bool, inbound: bool) -> Result<()> {
+        instructions::set_pause::handler(ctx, outbound, inbound)
+    }
+
+    /// Choose whether greeting and payload contents appear in events and
+    /// logs (`events`) and in `Received` records (`storage`) (owner only).
+    /// Redacted events carry only the contents' keccak256 hash and length;
+    /// the message itself stays public in the VAA.
+    pub fn set_redaction(ctx: Context<SetRedaction>, events: bool, storage: bool) -> Result<()> {
+        instructions::set_redaction::handler(ctx, events, storage)
+    }
+
+    /// Make `request_relay` refuse an `exec_amount` below the estimate for
+    /// the relay rather than only logging the shortfall (owner only). On by
+    /// default; turn it off if the estimate is stricter than the Executor.
+    pub fn set_enforce_min_exec_amount(
+        ctx: Context<SetEnforceMinExecAmount>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_enforce_min_exec_amount::handler(ctx, enabled)
+    }
+
+    /// Set the delivery SLA in seconds (zero disables it) and the key
+    /// allowed to report deliveries (owner only).
+    pub fn set_delivery_sla(
+        ctx: Context<SetDeliverySla>,
+        sla_seconds: u32,
+        delivery_oracle: Pubkey,
+    ) -> Result<()> {
+        instructions::set_delivery_sla::handler(ctx, sla_seconds, delivery_oracle)
+    }
+
     /// Send a cross-chain greeting message.
-    pub fn send_greeting(ctx: Context<SendGreeting>, greeting: String) -> Result<()> {
-        instructions::send_greeting::handler(ctx, greeting)
+    ///
+    /// `finality` overrides `config.finality` for this message only (same
+    /// values as `update_message_params`); `None` uses the config's. Called
+    /// through CPI, the calling program is recorded in the Sent record and
+    /// must be allowed by `set_cpi_callers` if it has restricted callers.
+    ///
+    /// The greeting is a `HelloV2` only when the optional `peer` account
+    /// names a destination that reported support for it, and a `Hello`
+    /// otherwise.
+    pub fn send_greeting(
+        ctx: Context<SendGreeting>,
+        greeting: String,
+        finality: Option<u8>,
+    ) -> Result<()> {
+        instructions::send_greeting::handler(ctx, greeting, finality)
+    }
+
+    /// Dry run of `send_greeting`: returns the exact payload bytes it would
+    /// publish, as a `Vec<u8>` in [return_data], without posting a message.
+    /// Takes no accounts; `trace_id` is the one `send_greeting` would embed
+    /// (see [`message::trace_id`]), `sender` its payer and `timestamp` the
+    /// clock's unix timestamp.
+    pub fn encode_greeting(
+        ctx: Context<EncodeGreeting>,
+        greeting: String,
+        trace_id: [u8; 16],
+        sender: Pubkey,
+        timestamp: u64,
+    ) -> Result<()> {
+        instructions::encode_greeting::handler(ctx, greeting, trace_id, sender, timestamp)
+    }
+
+    /// Publish caller-provided bytes verbatim under the program emitter
+    /// (owner or allowlisted publishers only).
+    ///
+    /// `dst_chain_hint` is only recorded in the `PayloadPublished` event; the
+    /// payload itself carries no envelope.
+    pub fn publish_payload(
+        ctx: Context<PublishPayload>,
+        payload: Vec<u8>,
+        dst_chain_hint: u16,
+    ) -> Result<()> {
+        instructions::publish_payload::handler(ctx, payload, dst_chain_hint)
+    }
+
+    /// Allow `authority` to call `publish_payload` (owner only).
+    pub fn add_publisher(ctx: Context<AddPublisher>, authority: Pubkey) -> Result<()> {
+        instructions::add_publisher::handler(ctx, authority)
+    }
+
+    /// Remove a publisher from the allowlist (owner only).
+    pub fn remove_publisher(ctx: Context<RemovePublisher>) -> Result<()> {
+        instructions::remove_publisher::handler(ctx)
+    }
+
+    /// Limit every wallet to `send_quota` greetings per `quota_epoch_seconds`
+    /// (owner only). Zero turns the quota off. The owner and wallets on the
+    /// bypass list are exempt.
+    pub fn set_send_quota(
+        ctx: Context<SetSendQuota>,
+        send_quota: u32,
+        quota_epoch_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_send_quota::handler(ctx, send_quota, quota_epoch_seconds)
+    }
+
+    /// Limit how fast a wallet can send greetings (owner only): bursts of up
+    /// to `capacity`, then one every `refill_seconds`. Zero turns the limit
+    /// off. The owner and wallets on the bypass list are exempt.
+    pub fn set_rate_limit(
+        ctx: Context<SetRateLimit>,
+        capacity: u16,
+        refill_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_rate_limit::handler(ctx, capacity, refill_seconds)
+    }
+
+    /// Exempt `wallet` from the send quota (owner only).
+    pub fn add_quota_bypass(ctx: Context<AddQuotaBypass>, wallet: Pubkey) -> Result<()> {
+        instructions::add_quota_bypass::handler(ctx, wallet)
+    }
+
+    /// Remove a wallet from the send quota bypass list (owner only).
+    pub fn remove_quota_bypass(ctx: Context<RemoveQuotaBypass>) -> Result<()> {
+        instructions::remove_quota_bypass::handler(ctx)
+    }
+
+    /// Close the payer's send quota account for a past `epoch` and reclaim
+    /// its rent.
+    pub fn close_send_quota(ctx: Context<CloseSendQuota>, epoch: u64) -> Result<()> {
+        instructions::close_send_quota::handler(ctx, epoch)
+    }
+
+    /// Set how long received records are kept (owner only), creating the
+    /// retention policy on first use: the newest `max_records_per_peer`
+    /// sequences per peer, for at most `max_age_seconds`; zero disables
+    /// either limit. `pruner_tip_bps` of the rent `prune` reclaims goes to
+    /// the pruner.
+    pub fn set_retention_policy(
+        ctx: Context<SetRetentionPolicy>,
+        max_records_per_peer: u32,
+        max_age_seconds: u32,
+        pruner_tip_bps: u16,
+    ) -> Result<()> {
+        instructions::set_retention_policy::handler(
+            ctx,
+            max_records_per_peer,
+            max_age_seconds,
+            pruner_tip_bps,
+        )
+    }
+
+    /// Close up to `limit` Received records from `chain` that the retention
+    /// policy no longer keeps. Anyone may call it, passing candidate
+    /// `(Received, ReceivedIndex)` pairs as writable remaining accounts;
+    /// the rent goes to the owner, minus the pruner's tip.
+    pub fn prune(ctx: Context<Prune>, chain: u16, limit: u16) -> Result<()> {
+        instructions::prune::handler(ctx, chain, limit)
+    }
+
+    /// Create the program's address lookup table (owner only), holding
+    /// config, the Wormhole program and the system program. The resolver
+    /// returns it with every `receive_greeting`, so the Executor's
+    /// transaction references those accounts by index. `recent_slot` must
+    /// be a recent slot; the table's address is derived from it.
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::create_lookup_table::handler(ctx, recent_slot)
+    }
+
+    /// Open the signer's inbox, which `receive_greeting_v2` adds greetings
+    /// addressed to the signer (`@<pubkey> ...`) to.
+    pub fn open_inbox(ctx: Context<OpenInbox>) -> Result<()> {
+        instructions::open_inbox::handler(ctx)
+    }
+
+    /// Set the protocol fee `send_greeting` charges per greeting into the
+    /// treasury (owner only), creating the treasury on first use. Zero
+    /// charges nothing.
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, lamports: u64) -> Result<()> {
+        instructions::set_protocol_fee::handler(ctx, lamports)
+    }
+
+    /// Cap what relay requests (`exec_amount`) and greetings (Wormhole fee)
+    /// may spend to `max_lamports` per `epoch_seconds` (owner only),
+    /// creating the spend cap on first use. Zero disables it.
+    pub fn set_spend_cap(
+        ctx: Context<SetSpendCap>,
+        max_lamports: u64,
+        epoch_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_spend_cap::handler(ctx, max_lamports, epoch_seconds)
+    }
+
+    /// Restrict which programs may call `send_greeting` through CPI to
+    /// `callers` while `restricted` is set (owner only), creating the CPI
+    /// guard on first use. Top-level calls are always allowed.
+    pub fn set_cpi_callers(
+        ctx: Context<SetCpiCallers>,
+        restricted: bool,
+        callers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_cpi_callers::handler(ctx, restricted, callers)
+    }
+
+    /// Withdraw `amount` lamports of collected fees from the treasury to
+    /// `recipient` (owner only). The treasury keeps its rent-exempt minimum.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, amount)
+    }
+
+    /// Create the greeting receipt mint (owner only).
+    ///
+    /// Receipts are only minted by builds with the `receipts` feature.
+    pub fn init_receipt_mint(ctx: Context<InitReceiptMint>) -> Result<()> {
+        instructions::init_receipt_mint::handler(ctx)
     }
 
     /// Receive and process a cross-chain greeting.
@@ -51,12 +610,280 @@ pub mod hello_executor {
         instructions::receive_greeting::handler(ctx, vaa_hash)
     }
 
+    /// `receive_greeting`, then post the peer's `reply_text` back to its
+    /// chain as a greeting. The resolver picks this over `receive_greeting`
+    /// for peers with a reply configured.
+    pub fn receive_and_reply(ctx: Context<ReceiveAndReply>, vaa_hash: [u8; 32]) -> Result<()> {
+        instructions::receive_and_reply::handler(ctx, vaa_hash)
+    }
+
+    /// `receive_greeting` for a VAA verified by the Wormhole verify VAA
+    /// shim instead of posted to the Core Bridge: the body is passed as
+    /// data, and checked against guardian signatures posted to the shim. No
+    /// `PostedVAA` account is created. The resolver picks this for peers
+    /// targeting `ReceiveTarget::ReceiveGreetingShim`.
+    pub fn receive_greeting_shim(ctx: Context<ReceiveGreetingShim>, vaa_body: Vec<u8>) -> Result<()> {
+        instructions::receive_greeting_shim::handler(ctx, vaa_body)
+    }
+
+    /// Second-generation `receive_greeting`: records messages it cannot
+    /// deliver as `Failed` instead of reverting, and adds greetings
+    /// addressed to a wallet to its inbox. With `shadow`, it runs with
+    /// `receive_greeting`'s outcomes and only logs the differences. The
+    /// resolver picks it for peers targeting `ReceiveTarget::ReceiveGreetingV2`
+    /// or `ReceiveTarget::ShadowV2`.
+    pub fn receive_greeting_v2(
+        ctx: Context<ReceiveGreetingV2>,
+        vaa_hash: [u8; 32],
+        shadow: bool,
+    ) -> Result<()> {
+        instructions::receive_greeting_v2::handler(ctx, vaa_hash, shadow)
+    }
+
+    /// Receive a Token Bridge transfer carrying a greeting: redeem it to the
+    /// program's redeemer, forward the tokens to the greeting's recipient
+    /// and emit the greeting. The resolver picks this for transfers
+    /// addressed to `token_bridge::redeemer()`.
+    pub fn receive_token_greeting(
+        ctx: Context<ReceiveTokenGreeting>,
+        vaa_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::receive_token_greeting::handler(ctx, vaa_hash)
+    }
+
+    /// Apply a governance VAA from the emitter named by `set_governance`:
+    /// register a peer, pause or resume messaging, or update config fields
+    /// (see the `governance` module). Anyone may call this; VAAs apply
+    /// once, in sequence order. The resolver picks this for governance
+    /// payloads.
+    pub fn receive_governance(ctx: Context<ReceiveGovernance>, vaa_hash: [u8; 32]) -> Result<()> {
+        instructions::receive_governance::handler(ctx, vaa_hash)
+    }
+
+    /// Apply the next chunk of a `RegisterAll` broadcast from the
+    /// governance emitter: register the peers for the first `limit`
+    /// remaining accounts, their Peer PDAs in broadcast order. A
+    /// `RegistrationCursor` tracks progress across transactions and is
+    /// closed with the last chunk. Anyone may call this.
+    pub fn register_all<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterAll<'info>>,
+        vaa_hash: [u8; 32],
+        limit: u8,
+    ) -> Result<()> {
+        instructions::register_all::handler(ctx, vaa_hash, limit)
+    }
+
+    /// Hold a `PeerHandshake` VAA from an unregistered contract as a
+    /// `PendingPeer` for the owner to confirm or reject. Anyone may call
+    /// this; the resolver picks it for handshake payloads.
+    pub fn receive_handshake(ctx: Context<ReceiveHandshake>, vaa_hash: [u8; 32]) -> Result<()> {
+        instructions::receive_handshake::handler(ctx, vaa_hash)
+    }
+
+    /// Inject an unverified message as if it arrived in a VAA from `chain`
+    /// (owner only). It can be consumed by `receive_greeting_test` for
+    /// `TestVaa::TTL_SLOTS` slots.
+    ///
+    /// Only built with the `integration-test` feature, for exercising
+    /// payload handling without the Wormhole Core Bridge.
+    #[cfg(feature = "integration-test")]
+    pub fn post_test_vaa(
+        ctx: Context<PostTestVaa>,
+        payload: Vec<u8>,
+        chain: u16,
+        emitter: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        instructions::post_test_vaa::handler(ctx, payload, chain, emitter, sequence)
+    }
+
+    /// Process a message injected with `post_test_vaa` before it expires,
+    /// closing the test VAA. Only built with the `integration-test` feature.
+    #[cfg(feature = "integration-test")]
+    pub fn receive_greeting_test(ctx: Context<ReceiveGreetingTest>) -> Result<()> {
+        instructions::receive_greeting_test::handler(ctx)
+    }
+
+    /// Close the test VAAs passed as remaining accounts, expired or not
+    /// (owner only). Only built with the `integration-test` feature.
+    #[cfg(feature = "integration-test")]
+    pub fn sweep_test_vaas(ctx: Context<SweepTestVaas>) -> Result<()> {
+        instructions::sweep_test_vaas::handler(ctx)
+    }
+
     /// Request Executor relay for the most recently posted message.
+    ///
+    /// Logs the estimated minimum `exec_amount` for the relay; pass the
+    /// relayed message's account to price its payload length. Returns the
+    /// request ID, sequence and request bytes (a `RequestRelayResult` in
+    /// [return_data]).
     pub fn request_relay(ctx: Context<RequestRelay>, args: RequestRelayArgs) -> Result<()> {
         instructions::request_relay::handler(ctx, args)
     }
 
-    /// Update Wormhole configuration (owner only).
+    /// Request the relay of VAA `sequence` to `dst_chain` again, with a
+    /// fresh quote. The RelayRequest must not be delivered or failed yet.
+    /// Anyone may pay for it.
+    pub fn retry_relay(
+        ctx: Context<RetryRelay>,
+        sequence: u64,
+        dst_chain: u16,
+        args: RetryRelayArgs,
+    ) -> Result<()> {
+        instructions::retry_relay::handler(ctx, sequence, dst_chain, args)
+    }
+
+    /// Request Executor relay of a CCTP v1 message burned on Solana, to the
+    /// peer on `args.dst_chain`. A demo of the Executor's `ERC1` requests.
+    pub fn request_cctp_relay(
+        ctx: Context<RequestCctpRelay>,
+        args: RequestCctpRelayArgs,
+    ) -> Result<()> {
+        instructions::request_cctp_relay::handler(ctx, args)
+    }
+
+    /// Request Executor relay of an NTT v1 message sent on Solana, to the
+    /// NTT manager `args.dst_manager`. A demo of the Executor's `ERN1`
+    /// requests.
+    pub fn request_ntt_relay(
+        ctx: Context<RequestNttRelay>,
+        args: RequestNttRelayArgs,
+    ) -> Result<()> {
+        instructions::request_ntt_relay::handler(ctx, args)
+    }
+
+    /// Look up the RelayRequest recorded for an Executor request ID
+    /// (`keccak256(request_bytes)`) relayed to `dst_chain`, through its
+    /// RelayRequestIndex. Returns the `RelayRequest` as return data (see
+    /// [return_data]).
+    pub fn find_request(
+        ctx: Context<FindRequest>,
+        request_id: [u8; 32],
+        dst_chain: u16,
+    ) -> Result<()> {
+        instructions::find_request::handler(ctx, request_id, dst_chain)
+    }
+
+    /// Record when a relay was delivered on the destination (delivery
+    /// oracle only). Emits `SlaBreached` if it was later than the SLA.
+    pub fn report_delivery(
+        ctx: Context<ReportDelivery>,
+        sequence: u64,
+        dst_chain: u16,
+        delivered_at: i64,
+    ) -> Result<()> {
+        instructions::report_delivery::handler(ctx, sequence, dst_chain, delivered_at)
+    }
+
+    /// Record the outcome of a relay from the peer's acknowledgment: the
+    /// posted `DeliveryAck` VAA with hash `vaa_hash` marks the RelayRequest
+    /// of VAA `sequence` to the peer's chain Delivered or Failed.
+    /// Permissionless.
+    pub fn mark_delivered(
+        ctx: Context<MarkDelivered>,
+        vaa_hash: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        instructions::mark_delivered::handler(ctx, vaa_hash, sequence)
+    }
+
+    /// Check a relay against the delivery SLA. Emits `SlaBreached` and
+    /// returns `true` (a `bool` in [return_data]) if it is still undelivered
+    /// past the SLA, or was delivered late. Anyone may call it; keepers use
+    /// it to pick relays to retry.
+    pub fn check_sla(ctx: Context<CheckSla>, sequence: u64, dst_chain: u16) -> Result<()> {
+        instructions::check_sla::handler(ctx, sequence, dst_chain)
+    }
+
+    /// Look up where the message with this VAA hash was recorded. Returns the
+    /// `(chain, sequence)` `ReceivedIndex` entry as return data (see
+    /// [return_data]).
+    pub fn find_received(ctx: Context<FindReceived>, vaa_hash: [u8; 32]) -> Result<()> {
+        instructions::find_received::handler(ctx, vaa_hash)
+    }
+
+    /// List the known sequence gaps of a peer: ranges it published (later
+    /// sequences arrived) that were never received. Returns a
+    /// `Vec<SequenceGap>`, oldest first, as return data (see
+    /// [return_data]).
+    pub fn list_sequence_gaps(ctx: Context<ListSequenceGaps>, chain: u16) -> Result<()> {
+        instructions::list_sequence_gaps::handler(ctx, chain)
+    }
+
+    /// Ask for missing messages from a peer to be delivered: creates a
+    /// `BackfillRequest` for each sequence in `from_sequence..=to_sequence`
+    /// (at most `BackfillRequest::MAX_BATCH`), which must lie in one of the
+    /// peer's known sequence gaps. Their PDAs are passed as remaining
+    /// accounts. A keeper fetches the VAAs and delivers them with
+    /// `receive_greeting`. Anyone may request, and pays the accounts' rent.
+    pub fn request_backfill<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestBackfill<'info>>,
+        chain: u16,
+        from_sequence: u64,
+        to_sequence: u64,
+    ) -> Result<()> {
+        instructions::request_backfill::handler(ctx, chain, from_sequence, to_sequence)
+    }
+
+    /// Record the moderation oracle's verdict on a received message
+    /// (oracle only). A verdict may be revised by attesting again.
+    pub fn attest_moderation(
+        ctx: Context<AttestModeration>,
+        chain: u16,
+        sequence: u64,
+        verdict: ModerationVerdict,
+    ) -> Result<()> {
+        instructions::attest_moderation::handler(ctx, chain, sequence, verdict)
+    }
+
+    /// Deliver a greeting held as `PendingTranslation` with the moderation
+    /// oracle's translation (oracle only). Emits `GreetingReceived`.
+    pub fn submit_translation(
+        ctx: Context<SubmitTranslation>,
+        chain: u16,
+        sequence: u64,
+        translation: String,
+    ) -> Result<()> {
+        instructions::submit_translation::handler(ctx, chain, sequence, translation)
+    }
+
+    /// Return the keys of the Received records passed as remaining accounts
+    /// whose verdict is `verdict`, as a `Vec<Pubkey>` in [return_data].
+    /// Meant for simulation; return data caps the result at 31 keys. To scan
+    /// every record, use a `getProgramAccounts` memcmp at
+    /// `Received::VERDICT_OFFSET` instead.
+    pub fn filter_received(
+        ctx: Context<FilterReceived>,
+        verdict: ModerationVerdict,
+    ) -> Result<()> {
+        instructions::filter_received::handler(ctx, verdict)
+    }
+
+    /// Verify a guardian-signed Wormhole Queries (CCQ) response about a peer's
+    /// state and store the `eth_call` result on-chain.
+    ///
+    /// This is the pull-based counterpart to `receive_greeting`: instead of
+    /// the peer pushing a VAA, anyone can fetch a signed query response from
+    /// the Query Proxy and submit it here.
+    pub fn submit_query_response(
+        ctx: Context<SubmitQueryResponse>,
+        chain: u16,
+        guardian_set_index: u32,
+        response: Vec<u8>,
+        signatures: Vec<[u8; 66]>,
+    ) -> Result<()> {
+        instructions::submit_query_response::handler(
+            ctx,
+            chain,
+            guardian_set_index,
+            response,
+            signatures,
+        )
+    }
+
+    /// Update Wormhole configuration (owner only): the Core Bridge passed
+    /// as `wormhole_program` and its accounts, which the resolver then uses
+    /// instead of the build's network.
     pub fn update_wormhole_config(ctx: Context<UpdateWormholeConfig>) -> Result<()> {
         instructions::update_config::handler(ctx)
     }
@@ -72,7 +899,7 @@ pub mod hello_executor {
     ///
     /// | Caller           | Entry point       | Discriminator     | Accounts passed? |
     /// |------------------|-------------------|-------------------|-----------------|
-    /// | Wormhole Executor | `fallback`        | `94b8a9decf089a7f`| No — derived    |
+    /// | Wormhole Executor | `fallback`        | `94b8a9decf089a7f`| On request      |
     /// | Test / manual     | this instruction  | Anchor-generated  | Yes             |
     ///
     /// If you are **integrating with the Executor service**, you do not call this
@@ -82,6 +909,9 @@ pub mod hello_executor {
     /// If you are **writing tests** against the resolver, you can call this
     /// instruction with an `ExecuteVaaV1` context to inspect the returned
     /// `InstructionGroups` without needing the Executor service.
+    ///
+    /// `vaa_body` may also be the full signed VAA; it is stripped to its body,
+    /// and sanity-checked against its guardian set if that account is passed.
     pub fn resolve_execute_vaa_v1(
         ctx: Context<ExecuteVaaV1>,
         vaa_body: Vec<u8>,
@@ -89,34 +919,23 @@ pub mod hello_executor {
         resolver::handle_resolve(ctx, vaa_body)
     }
 
-    /// Fallback instruction handler — routes the Executor's custom discriminator
-    /// to the VAA resolver.
+    /// Fallback instruction handler — routes custom discriminators through
+    /// [`fallback::FALLBACK_ROUTES`].
     ///
     /// The Wormhole Executor calls programs using discriminator
     /// `[148, 184, 169, 222, 207, 8, 154, 127]` (`94b8a9decf089a7f`), which
     /// does not match Anchor's auto-generated discriminator for any named
-    /// instruction. This fallback intercepts that call, parses the raw VAA bytes
-    /// from the instruction data, derives all required PDAs internally (the
-    /// Executor passes no accounts), and uses `set_return_data` to return the
-    /// `InstructionGroups` telling the Executor which instruction to execute.
+    /// instruction. Its route parses the raw VAA bytes from the instruction
+    /// data, asks for the Config account (and, with
+    /// `config.resolver_requires_peer`, the Peer) through `Resolver::Missing`,
+    /// along with the LookupTable record, derives the remaining PDAs, and
+    /// uses `set_return_data` to return the `InstructionGroups` telling the
+    /// Executor which instruction to execute and which lookup table to use.
     pub fn fallback<'info>(
         program_id: &Pubkey,
         accounts: &'info [AccountInfo<'info>],
         data: &[u8],
     ) -> Result<()> {
-        // Wormhole Executor resolver discriminator:
-        // sha256("executor-account-resolver:execute-vaa-v1")[..8] = 0x94b8a9decf089a7f
-        // Defined as RESOLVER_EXECUTE_VAA_V1 in executor-account-resolver-svm.
-        // Anchor 0.31+ with `interface-instructions` feature would allow replacing this
-        // fallback with `#[instruction(discriminator = &RESOLVER_EXECUTE_VAA_V1)]` on the
-        // named instruction, but that requires upgrading solana-program to 2.x (out of scope).
-        const EXECUTOR_DISCRIMINATOR: [u8; 8] = [148, 184, 169, 222, 207, 8, 154, 127];
-
-        if data.len() >= 8 && data[..8] == EXECUTOR_DISCRIMINATOR {
-            msg!("Executor resolver call detected");
-            return resolver::handle_resolve_raw(program_id, accounts, &data[8..]);
-        }
-
-        Err(anchor_lang::error::ErrorCode::InstructionFallbackNotFound.into())
+        crate::fallback::dispatch(program_id, accounts, data)
     }
 }
@@ -1,9 +1,9 @@
-use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{solana_program::keccak, AnchorDeserialize, AnchorSerialize};
 use std::io;
 use wormhole_io::Readable;
 
-/// Payload ID for Alive message (sent during initialization)
-const PAYLOAD_ID_ALIVE: u8 = 0;
+/// Payload ID for Alive message (sent by `announce`)
+pub(crate) const PAYLOAD_ID_ALIVE: u8 = 0;
 
 /// Payload ID for Hello/Greeting message.
 ///
@@ -18,13 +18,225 @@ const PAYLOAD_ID_ALIVE: u8 = 0;
 /// contains the clean message string.
 const PAYLOAD_ID_HELLO: u8 = 1;
 
+/// Payload ID for Stats message (sent by [`publish_stats`](crate::publish_stats)).
+const PAYLOAD_ID_STATS: u8 = 2;
+
+/// Payload ID for a greeting carrying its trace ID.
+///
+/// `0x03 | trace_id(16) | u16_be_len | message_bytes`. This is what
+/// [`send_greeting`](crate::send_greeting) publishes; EVM receivers must strip
+/// this 19-byte header where they strip the 3-byte `Hello` one.
+const PAYLOAD_ID_TRACED_HELLO: u8 = 3;
+
+/// Payload ID for a greeting carrying its trace ID and original sender.
+///
+/// `0x04 | trace_id(16) | sender(32) | u64_be_timestamp | u16_be_len |
+/// message_bytes`. This is what [`send_greeting`](crate::send_greeting)
+/// publishes; EVM receivers must strip this 59-byte header.
+const PAYLOAD_ID_HELLO_V2: u8 = 4;
+
+/// Payload ID for a request that the peer report its protocol version.
+///
+/// `0x05 | u16_be_version`, carrying the prober's own version. Sent by
+/// [`probe_peer_version`](crate::probe_peer_version).
+const PAYLOAD_ID_VERSION_PROBE: u8 = 5;
+
+/// Payload ID for a protocol version report, answering a probe.
+///
+/// `0x06 | u16_be_version | u64_be_probe_sequence`. Sent by
+/// [`report_version`](crate::report_version).
+const PAYLOAD_ID_VERSION_REPORT: u8 = 6;
+
+/// Payload ID for a protobuf-encoded greeting.
+///
+/// `0x07 | ProtoGreeting`, see [`proto`](crate::proto). Only decoded when
+/// the program is built with the `protobuf` feature; other builds reject
+/// it rather than delivering the protobuf bytes as text. Not gated by
+/// [`min_protocol_version`], as support depends on the peer's build, not
+/// its version.
+pub(crate) const PAYLOAD_ID_PROTO_HELLO: u8 = 7;
+
+/// Payload ID for a greeting attached to a Token Bridge transfer.
+///
+/// `0x08 | recipient(32) | u16_be_len | message_bytes`, carried as the
+/// payload of a Token Bridge `TransferWithPayload`. Received by
+/// [`receive_token_greeting`](crate::receive_token_greeting), which forwards
+/// the tokens to `recipient`.
+const PAYLOAD_ID_TOKEN_HELLO: u8 = 8;
+
+/// Payload ID for a peer's acknowledgment of a relayed message.
+///
+/// `0x09 | u64_be_sequence | u8_delivered`, naming the sequence of the
+/// message this program published and whether the peer delivered it (1) or
+/// rejected it (0). Consumed by [`mark_delivered`](crate::mark_delivered).
+const PAYLOAD_ID_DELIVERY_ACK: u8 = 9;
+
+/// Payload ID for a greeting tagged with the type of its content.
+///
+/// `0x0a | trace_id(16) | sender(32) | u64_be_timestamp | u8_content_type |
+/// u16_be_len | content`: a `HelloV2` header plus a [`ContentType`] tag, so
+/// receivers know how to interpret non-text content.
+const PAYLOAD_ID_TYPED_HELLO: u8 = 10;
+
+/// Payload ID for a checkpoint of the history received from a peer.
+///
+/// `0x0b | chain(2) | received_count(8) | last_received_sequence(8) |
+/// history_hash(32)`. Sent by
+/// [`publish_checkpoint`](crate::publish_checkpoint), so the peer can check
+/// the hash against the messages it sent.
+const PAYLOAD_ID_HISTORY_CHECKPOINT: u8 = 11;
+
+/// Payload ID for a contract announcing itself as a peer.
+///
+/// `0x0c | target_chain(2) | u16_be_version`, asking the deployment on
+/// `target_chain` to register the VAA's emitter. Sent by
+/// [`send_handshake`](crate::send_handshake); received by
+/// [`receive_handshake`](crate::receive_handshake), which holds it for the
+/// owner to confirm.
+const PAYLOAD_ID_PEER_HANDSHAKE: u8 = 12;
+
+/// Protocol version of this program, as reported to peers.
+///
+/// | Version | Adds                             |
+/// |---------|----------------------------------|
+/// | 1       | `Hello`, `Stats`                 |
+/// | 2       | `TracedHello`                    |
+/// | 3       | `HelloV2`, version probe/report  |
+/// | 4       | `TokenHello`                     |
+/// | 5       | `DeliveryAck`                    |
+/// | 6       | `TypedHello`                     |
+/// | 7       | `HistoryCheckpoint`              |
+/// | 8       | `PeerHandshake`                  |
+///
+/// Bump it with every new payload type, and map the type in
+/// [`min_protocol_version`].
+pub const PROTOCOL_VERSION: u16 = 8;
+
+/// Oldest protocol version that understands the payload with ID
+/// `payload_id`. Zero for payloads that are not Hello Executor messages
+/// (e.g. raw bytes), which are not gated.
+pub fn min_protocol_version(payload_id: u8) -> u16 {
+    match payload_id {
+        PAYLOAD_ID_ALIVE | PAYLOAD_ID_HELLO | PAYLOAD_ID_STATS => 1,
+        PAYLOAD_ID_TRACED_HELLO => 2,
+        PAYLOAD_ID_HELLO_V2 | PAYLOAD_ID_VERSION_PROBE | PAYLOAD_ID_VERSION_REPORT => 3,
+        PAYLOAD_ID_TOKEN_HELLO => 4,
+        PAYLOAD_ID_DELIVERY_ACK => 5,
+        PAYLOAD_ID_TYPED_HELLO => 6,
+        PAYLOAD_ID_HISTORY_CHECKPOINT => 7,
+        PAYLOAD_ID_PEER_HANDSHAKE => 8,
+        _ => 0,
+    }
+}
+
+/// Length of the [`HelloExecutorMessage::HelloV2`] header.
+pub const HELLO_V2_HEADER_LEN: usize = 1 + TRACE_ID_LENGTH + 32 + 8 + 2;
+
 /// Maximum length of a greeting message in bytes
 pub const GREETING_MAX_LENGTH: usize = 512;
 
+/// How the content of a [`HelloExecutorMessage::TypedHello`] is to be
+/// interpreted. Encoded as one byte, in declaration order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContentType {
+    /// UTF-8 text. The type of every untagged greeting.
+    #[default]
+    Text,
+    /// JSON document.
+    Json,
+    /// Opaque bytes.
+    Binary,
+    /// CBOR item.
+    Cbor,
+}
+
+impl ContentType {
+    /// Content type with the wire tag `tag`.
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(ContentType::Text),
+            1 => Some(ContentType::Json),
+            2 => Some(ContentType::Binary),
+            3 => Some(ContentType::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Check `content` is acceptable for this type. Every type is capped at
+    /// [GREETING_MAX_LENGTH]; text must also be UTF-8. Other types are not
+    /// parsed on-chain.
+    pub fn check(self, content: &[u8]) -> io::Result<()> {
+        if content.len() > GREETING_MAX_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+            ));
+        }
+        if self == ContentType::Text {
+            std::str::from_utf8(content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Most characters of a greeting written to a log line.
+pub const LOG_GREETING_MAX_CHARS: usize = 128;
+
+/// `text` as it may be written to logs and events: without control
+/// characters (newlines, terminal escapes) and cut to `max_chars`
+/// characters, with `…` marking the cut.
+///
+/// Greetings are untrusted cross-chain content, and tooling parses the
+/// program's log lines; a greeting must not be able to forge one. Events
+/// keep the whole greeting (`max_chars` = [GREETING_MAX_LENGTH]), logs
+/// [LOG_GREETING_MAX_CHARS].
+pub fn sanitize_for_log(text: &str, max_chars: usize) -> String {
+    let mut chars = text.chars().filter(|c| !c.is_control());
+    let mut sanitized: String = chars.by_ref().take(max_chars).collect();
+    if chars.next().is_some() {
+        sanitized.push('…');
+    }
+    sanitized
+}
+
+/// Length of a greeting trace ID in bytes.
+pub const TRACE_ID_LENGTH: usize = 16;
+
+/// Trace ID of the message `(chain, emitter, sequence)`.
+///
+/// The first 16 bytes of the message's Executor request ID,
+/// `keccak256("ERV1" | chain_be | emitter | sequence_be)`, so a trace can be
+/// joined with Executor status and recomputed by any system that knows the
+/// VAA ID — including for EVM senders, whose payloads do not carry one.
+pub fn trace_id(chain: u16, emitter: &[u8; 32], sequence: u64) -> [u8; TRACE_ID_LENGTH] {
+    let request_id = keccak::hashv(&[
+        b"ERV1",
+        &chain.to_be_bytes(),
+        emitter,
+        &sequence.to_be_bytes(),
+    ]);
+    request_id.0[..TRACE_ID_LENGTH].try_into().unwrap()
+}
+
 /// Message types for the Hello Executor program.
 ///
-/// * `Alive` - Payload ID 0: Emitted when [`initialize`](crate::initialize) is called.
+/// * `Alive` - Payload ID 0: Emitted when [`announce`](crate::announce) is called.
 /// * `Hello` - Payload ID 1: Emitted when [`send_greeting`](crate::send_greeting) is called.
+/// * `Stats` - Payload ID 2: Emitted when [`publish_stats`](crate::publish_stats) is called.
+/// * `TracedHello` - Payload ID 3: Emitted by [`send_greeting`](crate::send_greeting) before `HelloV2`.
+/// * `HelloV2` - Payload ID 4: Emitted when [`send_greeting`](crate::send_greeting) is called.
+/// * `VersionProbe` - Payload ID 5: Emitted when [`probe_peer_version`](crate::probe_peer_version) is called.
+/// * `VersionReport` - Payload ID 6: Emitted when [`report_version`](crate::report_version) is called.
+/// * `TokenHello` - Payload ID 8: Attached to Token Bridge transfers, received by [`receive_token_greeting`](crate::receive_token_greeting).
+/// * `DeliveryAck` - Payload ID 9: Sent by peers, consumed by [`mark_delivered`](crate::mark_delivered).
+/// * `TypedHello` - Payload ID 10: A `HelloV2` greeting tagged with its [`ContentType`].
+/// * `HistoryCheckpoint` - Payload ID 11: Emitted when [`publish_checkpoint`](crate::publish_checkpoint) is called.
+/// * `PeerHandshake` - Payload ID 12: Emitted when [`send_handshake`](crate::send_handshake) is called.
+///
+/// Payload ID 7 is the protobuf greeting of the `protobuf` feature, which
+/// has its own codec in `proto` rather than a variant here.
 #[derive(Clone, Debug)]
 pub enum HelloExecutorMessage {
     /// Initialization message containing the program ID
@@ -37,6 +249,119 @@ pub enum HelloExecutorMessage {
         /// The greeting message bytes (UTF-8 encoded string)
         message: Vec<u8>,
     },
+    /// Snapshot of Solana-side counters for one peer chain
+    Stats(ChainStats),
+    /// Greeting message tagged with its trace ID
+    TracedHello {
+        /// Trace ID, see [`trace_id`].
+        trace_id: [u8; TRACE_ID_LENGTH],
+        /// The greeting message bytes (UTF-8 encoded string)
+        message: Vec<u8>,
+    },
+    /// Greeting message tagged with its trace ID, the wallet that sent it
+    /// and when
+    HelloV2 {
+        /// Trace ID, see [`trace_id`].
+        trace_id: [u8; TRACE_ID_LENGTH],
+        /// Universal address of the original sender (wallet).
+        sender: [u8; 32],
+        /// Unix timestamp at which the greeting was sent.
+        timestamp: u64,
+        /// The greeting message bytes (UTF-8 encoded string)
+        message: Vec<u8>,
+    },
+    /// Request for the peer's protocol version
+    VersionProbe {
+        /// Protocol version of the prober.
+        version: u16,
+    },
+    /// Protocol version, in answer to a probe
+    VersionReport {
+        /// Protocol version of the reporter.
+        version: u16,
+        /// Sequence of the probe being answered.
+        probe_sequence: u64,
+    },
+    /// Greeting attached to a Token Bridge transfer
+    TokenHello {
+        /// Solana wallet the transferred tokens are forwarded to.
+        recipient: [u8; 32],
+        /// The greeting message bytes (UTF-8 encoded string)
+        message: Vec<u8>,
+    },
+    /// A peer's acknowledgment of a message relayed to it
+    DeliveryAck {
+        /// Sequence of the acknowledged message.
+        sequence: u64,
+        /// Whether the peer delivered the message, rather than rejecting it.
+        delivered: bool,
+    },
+    /// Greeting tagged with its trace ID, sender, send time and content type
+    TypedHello {
+        /// Trace ID, see [`trace_id`].
+        trace_id: [u8; TRACE_ID_LENGTH],
+        /// Universal address of the original sender (wallet).
+        sender: [u8; 32],
+        /// Unix timestamp at which the greeting was sent.
+        timestamp: u64,
+        /// How `content` is to be interpreted.
+        content_type: ContentType,
+        /// The greeting content.
+        content: Vec<u8>,
+    },
+    /// Running hash of the messages received from one peer chain
+    HistoryCheckpoint {
+        /// Wormhole chain ID of the peer the history is about.
+        chain: u16,
+        /// Messages received from that peer.
+        received_count: u64,
+        /// Sequence of the latest message received from that peer.
+        last_received_sequence: u64,
+        /// [`Peer::history_hash`](crate::state::Peer::history_hash).
+        history_hash: [u8; 32],
+    },
+    /// Request from the emitter to be registered as a peer
+    PeerHandshake {
+        /// Wormhole chain ID of the deployment asked to register the
+        /// emitter.
+        target_chain: u16,
+        /// Protocol version of the emitter.
+        protocol_version: u16,
+    },
+}
+
+/// Original sender of a greeting, as carried by
+/// [`HelloExecutorMessage::HelloV2`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GreetingOrigin {
+    /// Universal address of the sending wallet.
+    pub sender: [u8; 32],
+    /// Unix timestamp at which the greeting was sent.
+    pub timestamp: u64,
+}
+
+/// Solana-side counters for one peer chain, published to peers.
+///
+/// Encoded after the payload ID as fixed-width big-endian fields, so EVM
+/// contracts can decode it with `abi.decodePacked`-style slicing:
+/// `chain(2) | received_count(8) | last_received_sequence(8) | messages_sent(8) | timestamp(8)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChainStats {
+    /// Wormhole chain ID of the peer these counters are about.
+    pub chain: u16,
+    /// Messages received from that peer.
+    pub received_count: u64,
+    /// Sequence of the latest message received from that peer.
+    pub last_received_sequence: u64,
+    /// Messages published by this program's emitter (all destinations).
+    pub messages_sent: u64,
+    /// Unix timestamp of the snapshot.
+    pub timestamp: u64,
+}
+
+impl ChainStats {
+    /// Encoded size, excluding the payload ID.
+    pub const LEN: usize = 2 + 8 + 8 + 8 + 8;
 }
 
 impl AnchorSerialize for HelloExecutorMessage {
@@ -58,6 +383,113 @@ impl AnchorSerialize for HelloExecutorMessage {
                 (message.len() as u16).to_be_bytes().serialize(writer)?;
                 writer.write_all(message)
             }
+            HelloExecutorMessage::TracedHello { trace_id, message } => {
+                if message.len() > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                PAYLOAD_ID_TRACED_HELLO.serialize(writer)?;
+                writer.write_all(trace_id)?;
+                (message.len() as u16).to_be_bytes().serialize(writer)?;
+                writer.write_all(message)
+            }
+            HelloExecutorMessage::HelloV2 {
+                trace_id,
+                sender,
+                timestamp,
+                message,
+            } => {
+                if message.len() > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                PAYLOAD_ID_HELLO_V2.serialize(writer)?;
+                writer.write_all(trace_id)?;
+                writer.write_all(sender)?;
+                writer.write_all(&timestamp.to_be_bytes())?;
+                (message.len() as u16).to_be_bytes().serialize(writer)?;
+                writer.write_all(message)
+            }
+            HelloExecutorMessage::Stats(stats) => {
+                PAYLOAD_ID_STATS.serialize(writer)?;
+                writer.write_all(&stats.chain.to_be_bytes())?;
+                writer.write_all(&stats.received_count.to_be_bytes())?;
+                writer.write_all(&stats.last_received_sequence.to_be_bytes())?;
+                writer.write_all(&stats.messages_sent.to_be_bytes())?;
+                writer.write_all(&stats.timestamp.to_be_bytes())
+            }
+            HelloExecutorMessage::VersionProbe { version } => {
+                PAYLOAD_ID_VERSION_PROBE.serialize(writer)?;
+                writer.write_all(&version.to_be_bytes())
+            }
+            HelloExecutorMessage::VersionReport {
+                version,
+                probe_sequence,
+            } => {
+                PAYLOAD_ID_VERSION_REPORT.serialize(writer)?;
+                writer.write_all(&version.to_be_bytes())?;
+                writer.write_all(&probe_sequence.to_be_bytes())
+            }
+            HelloExecutorMessage::TokenHello { recipient, message } => {
+                if message.len() > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                PAYLOAD_ID_TOKEN_HELLO.serialize(writer)?;
+                writer.write_all(recipient)?;
+                (message.len() as u16).to_be_bytes().serialize(writer)?;
+                writer.write_all(message)
+            }
+            HelloExecutorMessage::DeliveryAck {
+                sequence,
+                delivered,
+            } => {
+                PAYLOAD_ID_DELIVERY_ACK.serialize(writer)?;
+                writer.write_all(&sequence.to_be_bytes())?;
+                writer.write_all(&[u8::from(*delivered)])
+            }
+            HelloExecutorMessage::TypedHello {
+                trace_id,
+                sender,
+                timestamp,
+                content_type,
+                content,
+            } => {
+                content_type.check(content)?;
+                PAYLOAD_ID_TYPED_HELLO.serialize(writer)?;
+                writer.write_all(trace_id)?;
+                writer.write_all(sender)?;
+                writer.write_all(&timestamp.to_be_bytes())?;
+                content_type.serialize(writer)?;
+                (content.len() as u16).to_be_bytes().serialize(writer)?;
+                writer.write_all(content)
+            }
+            HelloExecutorMessage::HistoryCheckpoint {
+                chain,
+                received_count,
+                last_received_sequence,
+                history_hash,
+            } => {
+                PAYLOAD_ID_HISTORY_CHECKPOINT.serialize(writer)?;
+                writer.write_all(&chain.to_be_bytes())?;
+                writer.write_all(&received_count.to_be_bytes())?;
+                writer.write_all(&last_received_sequence.to_be_bytes())?;
+                writer.write_all(history_hash)
+            }
+            HelloExecutorMessage::PeerHandshake {
+                target_chain,
+                protocol_version,
+            } => {
+                PAYLOAD_ID_PEER_HANDSHAKE.serialize(writer)?;
+                writer.write_all(&target_chain.to_be_bytes())?;
+                writer.write_all(&protocol_version.to_be_bytes())
+            }
         }
     }
 }
@@ -82,6 +514,134 @@ impl AnchorDeserialize for HelloExecutorMessage {
                 reader.read_exact(&mut message)?;
                 Ok(HelloExecutorMessage::Hello { message })
             }
+            PAYLOAD_ID_TRACED_HELLO => {
+                let mut trace_id = [0u8; TRACE_ID_LENGTH];
+                reader.read_exact(&mut trace_id)?;
+                let length = u16::read(reader)? as usize;
+                if length > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                let mut message = vec![0u8; length];
+                reader.read_exact(&mut message)?;
+                Ok(HelloExecutorMessage::TracedHello { trace_id, message })
+            }
+            PAYLOAD_ID_HELLO_V2 => {
+                let mut trace_id = [0u8; TRACE_ID_LENGTH];
+                reader.read_exact(&mut trace_id)?;
+                let mut sender = [0u8; 32];
+                reader.read_exact(&mut sender)?;
+                let timestamp = u64::read(reader)?;
+                let length = u16::read(reader)? as usize;
+                if length > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                let mut message = vec![0u8; length];
+                reader.read_exact(&mut message)?;
+                Ok(HelloExecutorMessage::HelloV2 {
+                    trace_id,
+                    sender,
+                    timestamp,
+                    message,
+                })
+            }
+            PAYLOAD_ID_STATS => Ok(HelloExecutorMessage::Stats(ChainStats {
+                chain: u16::read(reader)?,
+                received_count: u64::read(reader)?,
+                last_received_sequence: u64::read(reader)?,
+                messages_sent: u64::read(reader)?,
+                timestamp: u64::read(reader)?,
+            })),
+            PAYLOAD_ID_VERSION_PROBE => Ok(HelloExecutorMessage::VersionProbe {
+                version: u16::read(reader)?,
+            }),
+            PAYLOAD_ID_VERSION_REPORT => Ok(HelloExecutorMessage::VersionReport {
+                version: u16::read(reader)?,
+                probe_sequence: u64::read(reader)?,
+            }),
+            PAYLOAD_ID_TOKEN_HELLO => {
+                let mut recipient = [0u8; 32];
+                reader.read_exact(&mut recipient)?;
+                let length = u16::read(reader)? as usize;
+                if length > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                let mut message = vec![0u8; length];
+                reader.read_exact(&mut message)?;
+                Ok(HelloExecutorMessage::TokenHello { recipient, message })
+            }
+            PAYLOAD_ID_DELIVERY_ACK => {
+                let sequence = u64::read(reader)?;
+                let delivered = match u8::read(reader)? {
+                    0 => false,
+                    1 => true,
+                    other => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("invalid delivery outcome: {other}"),
+                        ))
+                    }
+                };
+                Ok(HelloExecutorMessage::DeliveryAck {
+                    sequence,
+                    delivered,
+                })
+            }
+            PAYLOAD_ID_TYPED_HELLO => {
+                let mut trace_id = [0u8; TRACE_ID_LENGTH];
+                reader.read_exact(&mut trace_id)?;
+                let mut sender = [0u8; 32];
+                reader.read_exact(&mut sender)?;
+                let timestamp = u64::read(reader)?;
+                let tag = u8::read(reader)?;
+                let content_type = ContentType::from_tag(tag).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid content type: {tag}"),
+                    )
+                })?;
+                let length = u16::read(reader)? as usize;
+                if length > GREETING_MAX_LENGTH {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                    ));
+                }
+                let mut content = vec![0u8; length];
+                reader.read_exact(&mut content)?;
+                Ok(HelloExecutorMessage::TypedHello {
+                    trace_id,
+                    sender,
+                    timestamp,
+                    content_type,
+                    content,
+                })
+            }
+            PAYLOAD_ID_HISTORY_CHECKPOINT => {
+                let chain = u16::read(reader)?;
+                let received_count = u64::read(reader)?;
+                let last_received_sequence = u64::read(reader)?;
+                let mut history_hash = [0u8; 32];
+                reader.read_exact(&mut history_hash)?;
+                Ok(HelloExecutorMessage::HistoryCheckpoint {
+                    chain,
+                    received_count,
+                    last_received_sequence,
+                    history_hash,
+                })
+            }
+            PAYLOAD_ID_PEER_HANDSHAKE => Ok(HelloExecutorMessage::PeerHandshake {
+                target_chain: u16::read(reader)?,
+                protocol_version: u16::read(reader)?,
+            }),
             id => Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 format!("invalid payload ID: {id}"),
@@ -90,6 +650,261 @@ impl AnchorDeserialize for HelloExecutorMessage {
     }
 }
 
+/// Encode an outbound greeting as a `HelloV2`, as `send_greeting` publishes
+/// it to a peer that supports one.
+pub fn encode_greeting_payload(
+    greeting: &str,
+    trace_id: [u8; TRACE_ID_LENGTH],
+    origin: GreetingOrigin,
+) -> io::Result<Vec<u8>> {
+    HelloExecutorMessage::HelloV2 {
+        trace_id,
+        sender: origin.sender,
+        timestamp: origin.timestamp,
+        message: greeting.as_bytes().to_vec(),
+    }
+    .try_to_vec()
+}
+
+/// Encode an outbound greeting for a peer that reported `peer_version`
+/// (zero if it has not reported one).
+///
+/// Deployed EVM receivers strip only the 3-byte `Hello` header, so the
+/// greeting stays a `Hello` until the peer reports a version that decodes
+/// `HelloV2`.
+pub fn encode_greeting_for_peer(
+    peer_version: u16,
+    greeting: &str,
+    trace_id: [u8; TRACE_ID_LENGTH],
+    origin: GreetingOrigin,
+) -> io::Result<Vec<u8>> {
+    if peer_version >= min_protocol_version(PAYLOAD_ID_HELLO_V2) {
+        encode_greeting_payload(greeting, trace_id, origin)
+    } else {
+        HelloExecutorMessage::Hello {
+            message: greeting.as_bytes().to_vec(),
+        }
+        .try_to_vec()
+    }
+}
+
+/// Encode a greeting tagged with its content type. `send_greeting`
+/// publishes text only; other senders (or `publish_payload`) use this.
+pub fn encode_typed_payload(
+    content_type: ContentType,
+    content: &[u8],
+    trace_id: [u8; TRACE_ID_LENGTH],
+    origin: GreetingOrigin,
+) -> io::Result<Vec<u8>> {
+    HelloExecutorMessage::TypedHello {
+        trace_id,
+        sender: origin.sender,
+        timestamp: origin.timestamp,
+        content_type,
+        content: content.to_vec(),
+    }
+    .try_to_vec()
+}
+
+/// Trace ID embedded in an inbound payload, if it uses a traced envelope.
+pub fn decode_trace_id(payload: &[u8]) -> Option<[u8; TRACE_ID_LENGTH]> {
+    match payload.split_first() {
+        Some((&PAYLOAD_ID_TRACED_HELLO | &PAYLOAD_ID_HELLO_V2 | &PAYLOAD_ID_TYPED_HELLO, rest)) => {
+            rest.get(..TRACE_ID_LENGTH)?.try_into().ok()
+        }
+        #[cfg(feature = "protobuf")]
+        Some((&PAYLOAD_ID_PROTO_HELLO, _)) => {
+            crate::proto::decode_proto_greeting_payload(payload).ok()?.trace_id()
+        }
+        _ => None,
+    }
+}
+
+/// Original sender embedded in an inbound payload, if it uses the
+/// [`HelloExecutorMessage::HelloV2`] or [`HelloExecutorMessage::TypedHello`]
+/// envelope.
+pub fn decode_origin(payload: &[u8]) -> Option<GreetingOrigin> {
+    match payload.split_first() {
+        Some((&PAYLOAD_ID_HELLO_V2 | &PAYLOAD_ID_TYPED_HELLO, rest)) => {
+            let rest = rest.get(TRACE_ID_LENGTH..TRACE_ID_LENGTH + 32 + 8)?;
+            Some(GreetingOrigin {
+                sender: rest[..32].try_into().ok()?,
+                timestamp: u64::from_be_bytes(rest[32..].try_into().ok()?),
+            })
+        }
+        #[cfg(feature = "protobuf")]
+        Some((&PAYLOAD_ID_PROTO_HELLO, _)) => {
+            crate::proto::decode_proto_greeting_payload(payload).ok()?.origin()
+        }
+        _ => None,
+    }
+}
+
+/// Decode a version probe or report. `None` for any other payload.
+pub fn decode_version_message(payload: &[u8]) -> Option<HelloExecutorMessage> {
+    match payload.first() {
+        Some(&PAYLOAD_ID_VERSION_PROBE | &PAYLOAD_ID_VERSION_REPORT) => {
+            HelloExecutorMessage::try_from_slice(payload).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Decode a delivery acknowledgment. `None` for any other payload.
+pub fn decode_delivery_ack(payload: &[u8]) -> Option<HelloExecutorMessage> {
+    match payload.first() {
+        Some(&PAYLOAD_ID_DELIVERY_ACK) => HelloExecutorMessage::try_from_slice(payload).ok(),
+        _ => None,
+    }
+}
+
+/// Decode a peer handshake into its target chain and protocol version.
+/// `None` for any other payload.
+pub fn decode_handshake(payload: &[u8]) -> Option<(u16, u16)> {
+    match payload.first() {
+        Some(&PAYLOAD_ID_PEER_HANDSHAKE) => match HelloExecutorMessage::try_from_slice(payload) {
+            Ok(HelloExecutorMessage::PeerHandshake {
+                target_chain,
+                protocol_version,
+            }) => Some((target_chain, protocol_version)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Decode the greeting attached to a Token Bridge transfer: its recipient
+/// and the greeting, checked like [`decode_greeting_payload`]'s.
+pub fn decode_token_greeting(payload: &[u8]) -> io::Result<([u8; 32], String)> {
+    match HelloExecutorMessage::try_from_slice(payload)? {
+        HelloExecutorMessage::TokenHello { recipient, message } => {
+            let greeting = String::from_utf8(message)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Ok((recipient, greeting))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a token greeting",
+        )),
+    }
+}
+
+/// Extract the content of an inbound VAA payload and its type, checked as
+/// [`ContentType::check`] requires.
+///
+/// [`HelloExecutorMessage::TypedHello`] payloads carry their type; every
+/// other greeting is text, decoded by [`decode_greeting_payload`]. Shared
+/// by `receive_greeting` and the Executor resolver so both agree on what is
+/// deliverable.
+pub fn decode_content(payload: &[u8]) -> io::Result<(ContentType, Vec<u8>)> {
+    if payload.first() != Some(&PAYLOAD_ID_TYPED_HELLO) {
+        return Ok((ContentType::Text, decode_greeting_payload(payload)?));
+    }
+    match HelloExecutorMessage::deserialize(&mut &payload[..])? {
+        HelloExecutorMessage::TypedHello {
+            content_type,
+            content,
+            ..
+        } => {
+            content_type.check(&content)?;
+            Ok((content_type, content))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a typed greeting",
+        )),
+    }
+}
+
+/// Extract the greeting text from an inbound VAA payload.
+///
+/// Solana senders publish the tagged [`HelloExecutorMessage::HelloV2`]
+/// (or, from older versions, [`HelloExecutorMessage::TracedHello`] or
+/// [`HelloExecutorMessage::Hello`]) format; EVM senders
+/// publish the raw UTF-8 greeting. Protobuf greetings are accepted with the
+/// `protobuf` feature, and [`HelloExecutorMessage::TypedHello`] ones tagged
+/// as text.
+pub fn decode_greeting_payload(payload: &[u8]) -> io::Result<Vec<u8>> {
+    let message = match payload.first() {
+        Some(&PAYLOAD_ID_TYPED_HELLO) => match decode_content(payload)? {
+            (ContentType::Text, content) => content,
+            (content_type, _) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{content_type:?} content is not a text greeting"),
+                ))
+            }
+        },
+        Some(&PAYLOAD_ID_HELLO | &PAYLOAD_ID_TRACED_HELLO | &PAYLOAD_ID_HELLO_V2) => {
+            match HelloExecutorMessage::deserialize(&mut &payload[..])? {
+                HelloExecutorMessage::Hello { message }
+                | HelloExecutorMessage::TracedHello { message, .. }
+                | HelloExecutorMessage::HelloV2 { message, .. } => message,
+                HelloExecutorMessage::Alive { .. }
+                | HelloExecutorMessage::Stats(_)
+                | HelloExecutorMessage::VersionProbe { .. }
+                | HelloExecutorMessage::VersionReport { .. }
+                | HelloExecutorMessage::TokenHello { .. }
+                | HelloExecutorMessage::DeliveryAck { .. }
+                | HelloExecutorMessage::TypedHello { .. }
+                | HelloExecutorMessage::HistoryCheckpoint { .. }
+                | HelloExecutorMessage::PeerHandshake { .. } => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "not a greeting",
+                    ))
+                }
+            }
+        }
+        // Stats snapshots, history checkpoints, version messages,
+        // handshakes and acknowledgments from a peer are not greetings; token
+        // greetings only arrive through the Token Bridge.
+        Some(
+            &PAYLOAD_ID_STATS
+            | &PAYLOAD_ID_VERSION_PROBE
+            | &PAYLOAD_ID_VERSION_REPORT
+            | &PAYLOAD_ID_TOKEN_HELLO
+            | &PAYLOAD_ID_DELIVERY_ACK
+            | &PAYLOAD_ID_HISTORY_CHECKPOINT
+            | &PAYLOAD_ID_PEER_HANDSHAKE,
+        ) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "not a greeting",
+            ))
+        }
+        #[cfg(feature = "protobuf")]
+        Some(&PAYLOAD_ID_PROTO_HELLO) => {
+            crate::proto::decode_proto_greeting_payload(payload)?.message.into_bytes()
+        }
+        #[cfg(not(feature = "protobuf"))]
+        Some(&PAYLOAD_ID_PROTO_HELLO) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "protobuf greetings need the `protobuf` feature",
+            ))
+        }
+        Some(_) => payload.to_vec(),
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty payload",
+            ))
+        }
+    };
+
+    if message.len() > GREETING_MAX_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+        ));
+    }
+    std::str::from_utf8(&message)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(message)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -135,6 +950,173 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_message_stats() {
+        let stats = ChainStats {
+            chain: 10002,
+            received_count: 3,
+            last_received_sequence: 41,
+            messages_sent: 7,
+            timestamp: 1_700_000_000,
+        };
+
+        let mut encoded = Vec::new();
+        HelloExecutorMessage::Stats(stats).serialize(&mut encoded).unwrap();
+
+        assert_eq!(encoded.len(), 1 + ChainStats::LEN);
+        assert_eq!(encoded[0], PAYLOAD_ID_STATS);
+        assert_eq!(encoded[1..3], 10002u16.to_be_bytes());
+
+        match HelloExecutorMessage::deserialize(&mut encoded.as_slice()).unwrap() {
+            HelloExecutorMessage::Stats(decoded) => assert_eq!(decoded, stats),
+            _ => panic!("wrong message type"),
+        }
+
+        // A stats payload is never a deliverable greeting.
+        assert!(decode_greeting_payload(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_history_checkpoint() {
+        let encoded = HelloExecutorMessage::HistoryCheckpoint {
+            chain: 10002,
+            received_count: 3,
+            last_received_sequence: 41,
+            history_hash: [7; 32],
+        }
+        .try_to_vec()
+        .unwrap();
+
+        assert_eq!(encoded.len(), 1 + 2 + 8 + 8 + 32);
+        assert_eq!(encoded[0], PAYLOAD_ID_HISTORY_CHECKPOINT);
+        assert_eq!(encoded[1..3], 10002u16.to_be_bytes());
+        assert_eq!(encoded[19..], [7; 32]);
+
+        match HelloExecutorMessage::try_from_slice(&encoded).unwrap() {
+            HelloExecutorMessage::HistoryCheckpoint {
+                received_count,
+                history_hash,
+                ..
+            } => assert_eq!((received_count, history_hash), (3, [7; 32])),
+            _ => panic!("wrong message type"),
+        }
+        assert!(decode_greeting_payload(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_peer_handshake() {
+        let encoded = HelloExecutorMessage::PeerHandshake {
+            target_chain: 1,
+            protocol_version: PROTOCOL_VERSION,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        assert_eq!(encoded, [PAYLOAD_ID_PEER_HANDSHAKE, 0, 1, 0, 8]);
+        assert_eq!(decode_handshake(&encoded), Some((1, PROTOCOL_VERSION)));
+        assert_eq!(decode_handshake(&encoded[..4]), None);
+        assert!(decode_greeting_payload(&encoded).is_err());
+
+        let ack = HelloExecutorMessage::DeliveryAck {
+            sequence: 1,
+            delivered: true,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(decode_handshake(&ack), None);
+    }
+
+    #[test]
+    fn test_message_version() {
+        let probe = HelloExecutorMessage::VersionProbe { version: 3 }.try_to_vec().unwrap();
+        assert_eq!(probe, [PAYLOAD_ID_VERSION_PROBE, 0, 3]);
+
+        let report = HelloExecutorMessage::VersionReport {
+            version: 3,
+            probe_sequence: 41,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(report.len(), 1 + 2 + 8);
+        assert_eq!(report[3..], 41u64.to_be_bytes());
+        assert!(matches!(
+            decode_version_message(&report),
+            Some(HelloExecutorMessage::VersionReport {
+                version: 3,
+                probe_sequence: 41
+            })
+        ));
+
+        // Version messages are never greetings, and greetings never version
+        // messages.
+        assert!(decode_greeting_payload(&probe).is_err());
+        assert!(decode_version_message(b"gm").is_none());
+        assert!(decode_version_message(&probe[..2]).is_none());
+    }
+
+    #[test]
+    fn test_message_token_hello() {
+        let encoded = HelloExecutorMessage::TokenHello {
+            recipient: [5u8; 32],
+            message: b"gm".to_vec(),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(encoded.len(), 1 + 32 + 2 + 2);
+        assert_eq!(encoded[0], PAYLOAD_ID_TOKEN_HELLO);
+        assert_eq!(decode_token_greeting(&encoded).unwrap(), ([5u8; 32], "gm".to_string()));
+
+        // Only the Token Bridge path delivers token greetings.
+        assert!(decode_greeting_payload(&encoded).is_err());
+        assert!(decode_token_greeting(b"gm").is_err());
+        assert!(decode_token_greeting(&encoded[..encoded.len() - 1]).is_err());
+
+        let mut invalid_utf8 = encoded.clone();
+        invalid_utf8[35..].copy_from_slice(&[0xff, 0xfe]);
+        assert!(decode_token_greeting(&invalid_utf8).is_err());
+    }
+
+    #[test]
+    fn test_message_delivery_ack() {
+        let encoded = HelloExecutorMessage::DeliveryAck {
+            sequence: 41,
+            delivered: true,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(encoded.len(), 1 + 8 + 1);
+        assert_eq!(encoded[1..9], 41u64.to_be_bytes());
+        assert!(matches!(
+            decode_delivery_ack(&encoded),
+            Some(HelloExecutorMessage::DeliveryAck {
+                sequence: 41,
+                delivered: true
+            })
+        ));
+
+        let mut invalid = encoded.clone();
+        invalid[9] = 2;
+        assert!(decode_delivery_ack(&invalid).is_none());
+        assert!(decode_delivery_ack(&encoded[..9]).is_none());
+        assert!(decode_delivery_ack(b"gm").is_none());
+
+        // An acknowledgment is never a greeting.
+        assert!(decode_greeting_payload(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_min_protocol_version() {
+        let v2 = encode_greeting_payload("gm", [0; TRACE_ID_LENGTH], GreetingOrigin::default())
+            .unwrap();
+        assert_eq!(min_protocol_version(v2[0]), 3);
+        assert_eq!(min_protocol_version(PAYLOAD_ID_HELLO), 1);
+        assert_eq!(min_protocol_version(b'g'), 0);
+        assert_eq!(min_protocol_version(PAYLOAD_ID_PEER_HANDSHAKE), PROTOCOL_VERSION);
+        for id in 0..=PAYLOAD_ID_PEER_HANDSHAKE {
+            assert!(min_protocol_version(id) <= PROTOCOL_VERSION);
+        }
+    }
+
     #[test]
     fn test_message_too_large() {
         let message = vec![0u8; GREETING_MAX_LENGTH + 1];
@@ -144,4 +1126,166 @@ mod test {
         let result = msg.serialize(&mut encoded);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_decode_greeting_payload() {
+        let structured = HelloExecutorMessage::Hello { message: b"gm".to_vec() }
+            .try_to_vec()
+            .unwrap();
+        assert_eq!(decode_greeting_payload(&structured).unwrap(), b"gm");
+        assert_eq!(decode_greeting_payload(b"Hello from EVM").unwrap(), b"Hello from EVM");
+
+        assert!(decode_greeting_payload(&[]).is_err());
+        assert!(decode_greeting_payload(&[PAYLOAD_ID_HELLO, 0, 5, b'a']).is_err());
+        assert!(decode_greeting_payload(&[0xff, 0xfe]).is_err());
+        assert!(decode_greeting_payload(&vec![b'a'; GREETING_MAX_LENGTH + 1]).is_err());
+        #[cfg(not(feature = "protobuf"))]
+        assert!(decode_greeting_payload(&[PAYLOAD_ID_PROTO_HELLO, 0x22, 2, b'g', b'm']).is_err());
+    }
+
+    #[test]
+    fn test_encode_greeting_payload() {
+        let trace = [9u8; TRACE_ID_LENGTH];
+        let origin = GreetingOrigin {
+            sender: [5u8; 32],
+            timestamp: 1_700_000_000,
+        };
+        let encoded = encode_greeting_payload("gm", trace, origin).unwrap();
+        assert_eq!(encoded.len(), HELLO_V2_HEADER_LEN + 2);
+        assert_eq!(encoded[0], PAYLOAD_ID_HELLO_V2);
+        assert_eq!(encoded[1..17], trace);
+        assert_eq!(encoded[17..49], [5u8; 32]);
+        assert_eq!(encoded[49..57], 1_700_000_000u64.to_be_bytes());
+        assert_eq!(encoded[57..], [0, 2, b'g', b'm']);
+        assert_eq!(decode_greeting_payload(&encoded).unwrap(), b"gm");
+        assert_eq!(decode_trace_id(&encoded), Some(trace));
+        assert_eq!(decode_origin(&encoded), Some(origin));
+
+        match HelloExecutorMessage::deserialize(&mut encoded.as_slice()).unwrap() {
+            HelloExecutorMessage::HelloV2 { sender, timestamp, .. } => {
+                assert_eq!(sender, origin.sender);
+                assert_eq!(timestamp, origin.timestamp);
+            }
+            _ => panic!("wrong message type"),
+        }
+
+        let too_long = "a".repeat(GREETING_MAX_LENGTH + 1);
+        assert!(encode_greeting_payload(&too_long, trace, origin).is_err());
+    }
+
+    #[test]
+    fn test_encode_greeting_for_peer() {
+        let trace = [9u8; TRACE_ID_LENGTH];
+        let origin = GreetingOrigin {
+            sender: [5u8; 32],
+            timestamp: 1_700_000_000,
+        };
+
+        // Unreported or too old for HelloV2: the v1 encoding.
+        for version in [0, 1, 2] {
+            let encoded = encode_greeting_for_peer(version, "gm", trace, origin).unwrap();
+            assert_eq!(encoded, [PAYLOAD_ID_HELLO, 0, 2, b'g', b'm']);
+        }
+
+        let encoded = encode_greeting_for_peer(3, "gm", trace, origin).unwrap();
+        assert_eq!(encoded, encode_greeting_payload("gm", trace, origin).unwrap());
+        let encoded = encode_greeting_for_peer(PROTOCOL_VERSION, "gm", trace, origin).unwrap();
+        assert_eq!(encoded[0], PAYLOAD_ID_HELLO_V2);
+
+        let too_long = "a".repeat(GREETING_MAX_LENGTH + 1);
+        assert!(encode_greeting_for_peer(0, &too_long, trace, origin).is_err());
+    }
+
+    #[test]
+    fn test_typed_payload() {
+        let trace = [9u8; TRACE_ID_LENGTH];
+        let origin = GreetingOrigin {
+            sender: [5u8; 32],
+            timestamp: 1_700_000_000,
+        };
+        // The tag follows the HelloV2 header fields.
+        let tag = 1 + TRACE_ID_LENGTH + 32 + 8;
+        let json = encode_typed_payload(ContentType::Json, b"{\"gm\":1}", trace, origin).unwrap();
+        assert_eq!(json[0], PAYLOAD_ID_TYPED_HELLO);
+        assert_eq!(json[tag], ContentType::Json as u8);
+        assert_eq!(decode_content(&json).unwrap(), (ContentType::Json, b"{\"gm\":1}".to_vec()));
+        assert_eq!(decode_trace_id(&json), Some(trace));
+        assert_eq!(decode_origin(&json), Some(origin));
+        // Only text is a greeting.
+        assert!(decode_greeting_payload(&json).is_err());
+
+        let text = encode_typed_payload(ContentType::Text, b"gm", trace, origin).unwrap();
+        assert_eq!(decode_greeting_payload(&text).unwrap(), b"gm");
+
+        // Binary content skips the UTF-8 check; text does not.
+        let binary = encode_typed_payload(ContentType::Binary, &[0xff], trace, origin).unwrap();
+        assert_eq!(decode_content(&binary).unwrap(), (ContentType::Binary, vec![0xff]));
+        assert!(encode_typed_payload(ContentType::Text, &[0xff], trace, origin).is_err());
+        let mut invalid_text = binary.clone();
+        invalid_text[tag] = ContentType::Text as u8;
+        assert!(decode_content(&invalid_text).is_err());
+
+        let mut unknown = binary.clone();
+        unknown[tag] = 4;
+        assert!(decode_content(&unknown).is_err());
+        let too_long = vec![0u8; GREETING_MAX_LENGTH + 1];
+        assert!(encode_typed_payload(ContentType::Cbor, &too_long, trace, origin).is_err());
+
+        // Untagged greetings are text.
+        assert_eq!(decode_content(b"gm").unwrap(), (ContentType::Text, b"gm".to_vec()));
+    }
+
+    #[test]
+    fn test_decode_origin() {
+        // Only v2 greetings carry an origin.
+        let traced = HelloExecutorMessage::TracedHello {
+            trace_id: [9u8; TRACE_ID_LENGTH],
+            message: b"gm".to_vec(),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(decode_origin(&traced), None);
+        assert_eq!(decode_trace_id(&traced), Some([9u8; TRACE_ID_LENGTH]));
+        assert_eq!(decode_origin(b"Hello from EVM"), None);
+        assert_eq!(decode_origin(&[PAYLOAD_ID_HELLO_V2, 1, 2]), None);
+    }
+
+    #[test]
+    fn test_sanitize_for_log() {
+        assert_eq!(sanitize_for_log("gm", LOG_GREETING_MAX_CHARS), "gm");
+        assert_eq!(
+            sanitize_for_log("gm\nProgram log: forged\x1b[0m", LOG_GREETING_MAX_CHARS),
+            "gmProgram log: forged[0m"
+        );
+        assert_eq!(sanitize_for_log("héllo", 2), "hé…");
+        assert_eq!(sanitize_for_log("héllo", 5), "héllo");
+
+        let long = "a".repeat(GREETING_MAX_LENGTH);
+        assert_eq!(sanitize_for_log(&long, GREETING_MAX_LENGTH), long);
+        assert_eq!(
+            sanitize_for_log(&long, LOG_GREETING_MAX_CHARS).chars().count(),
+            LOG_GREETING_MAX_CHARS + 1
+        );
+    }
+
+    #[test]
+    fn test_trace_id() {
+        let emitter = [7u8; 32];
+        let mut request_bytes = b"ERV1".to_vec();
+        request_bytes.extend_from_slice(&1u16.to_be_bytes());
+        request_bytes.extend_from_slice(&emitter);
+        request_bytes.extend_from_slice(&42u64.to_be_bytes());
+        let request_id = keccak::hash(&request_bytes).to_bytes();
+
+        assert_eq!(trace_id(1, &emitter, 42)[..], request_id[..TRACE_ID_LENGTH]);
+        assert_ne!(trace_id(1, &emitter, 42), trace_id(1, &emitter, 43));
+
+        // Untraced payloads carry none.
+        assert_eq!(decode_trace_id(b"Hello from EVM"), None);
+        assert_eq!(decode_trace_id(&[PAYLOAD_ID_TRACED_HELLO, 1, 2]), None);
+        let hello = HelloExecutorMessage::Hello { message: b"gm".to_vec() }
+            .try_to_vec()
+            .unwrap();
+        assert_eq!(decode_trace_id(&hello), None);
+    }
 }
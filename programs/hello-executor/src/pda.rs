@@ -0,0 +1,268 @@
+//! Program-derived addresses.
+//!
+//! Every PDA owned by this program is derived here, so the program, the
+//! resolver, tests and the client crate agree on seeds and seed encoding
+//! (chain IDs and sequences are little-endian). Each function returns the
+//! address and its canonical bump.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::SEED_PREFIX_SENT,
+    receipts::SEED_PREFIX_RECEIPT_MINT,
+    state::{
+        BackfillRequest, Config, CpiGuard, Governance, Inbox, LookupTable, Peer,
+        PendingAdminAction, PendingPeer, Publisher, QueryResult, QuotaBypass, RateLimit,
+        Received, ReceivedIndex, RegistrationCursor, RelayRequest, RelayRequestIndex, SendQuota,
+        Sent, SpendCap, Treasury, WormholeEmitter,
+    },
+};
+
+/// Config account.
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Config::SEED_PREFIX], &crate::ID)
+}
+
+/// Wormhole emitter account.
+pub fn emitter() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WormholeEmitter::SEED_PREFIX], &crate::ID)
+}
+
+/// Seed form of `config.salt`: empty when unset, so unsalted deployments
+/// keep the same Peer and Received addresses.
+pub fn salt_seed(salt: &[u8; 8]) -> &[u8] {
+    if *salt == [0; 8] {
+        &[]
+    } else {
+        salt
+    }
+}
+
+/// Registered peer for `chain`, under the deployment's `config.salt`.
+pub fn peer(salt: &[u8; 8], chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[Peer::SEED_PREFIX, salt_seed(salt), &chain.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Handshake from the contract `address` on `chain` awaiting the owner,
+/// under the deployment's `config.salt`.
+pub fn pending_peer(salt: &[u8; 8], chain: u16, address: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PendingPeer::SEED_PREFIX,
+            salt_seed(salt),
+            &chain.to_le_bytes(),
+            address,
+        ],
+        &crate::ID,
+    )
+}
+
+/// Replay-protection record for the VAA `(chain, sequence)`, under the
+/// deployment's `config.salt`.
+pub fn received(salt: &[u8; 8], chain: u16, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Received::SEED_PREFIX,
+            salt_seed(salt),
+            &chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Backfill request for the missing message `(chain, sequence)`, under the
+/// deployment's `config.salt`.
+pub fn backfill_request(salt: &[u8; 8], chain: u16, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            BackfillRequest::SEED_PREFIX,
+            salt_seed(salt),
+            &chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Index entry for a received VAA, keyed by its hash.
+pub fn received_index(vaa_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ReceivedIndex::SEED_PREFIX, vaa_hash], &crate::ID)
+}
+
+/// Outbound Wormhole message account. `id` is the message slot, i.e. the
+/// sequence tracker value at post time plus one (see `wormhole_cpi`).
+pub fn sent(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX_SENT, &id.to_le_bytes()], &crate::ID)
+}
+
+/// Record of the greeting published at VAA `sequence`.
+pub fn sent_record(sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Sent::SEED_PREFIX, &sequence.to_le_bytes()], &crate::ID)
+}
+
+/// Relay request record for the message at VAA `sequence` relayed to
+/// `dst_chain`.
+pub fn relay_request(sequence: u64, dst_chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RelayRequest::SEED_PREFIX,
+            &sequence.to_le_bytes(),
+            &dst_chain.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Index entry for an Executor request ID relayed to `dst_chain`.
+pub fn relay_request_index(request_id: &[u8; 32], dst_chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RelayRequestIndex::SEED_PREFIX,
+            request_id,
+            &dst_chain.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Latest query result for `chain`.
+pub fn query_result(chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[QueryResult::SEED_PREFIX, &chain.to_le_bytes()], &crate::ID)
+}
+
+/// Publisher allowlist entry for `authority`.
+pub fn publisher(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Publisher::SEED_PREFIX, authority.as_ref()], &crate::ID)
+}
+
+/// Send count of `payer` in quota `epoch`.
+pub fn send_quota(payer: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SendQuota::SEED_PREFIX, payer.as_ref(), &epoch.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Rate limit bucket of `payer`.
+pub fn rate_limit(payer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RateLimit::SEED_PREFIX, payer.as_ref()], &crate::ID)
+}
+
+/// Inbox of `owner`.
+pub fn inbox(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Inbox::SEED_PREFIX, owner.as_ref()], &crate::ID)
+}
+
+/// Protocol fee treasury.
+pub fn treasury() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Treasury::SEED_PREFIX], &crate::ID)
+}
+
+/// Outbound spend cap.
+pub fn spend_cap() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SpendCap::SEED_PREFIX], &crate::ID)
+}
+
+/// Allowlist of programs calling `send_greeting` through CPI.
+pub fn cpi_guard() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CpiGuard::SEED_PREFIX], &crate::ID)
+}
+
+/// Record (and authority) of the program's address lookup table.
+pub fn lookup_table() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LookupTable::SEED_PREFIX], &crate::ID)
+}
+
+/// Send quota bypass list entry for `wallet`.
+pub fn quota_bypass(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[QuotaBypass::SEED_PREFIX, wallet.as_ref()], &crate::ID)
+}
+
+/// Queued admin action with the given ID.
+pub fn admin_action(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PendingAdminAction::SEED_PREFIX, &id.to_le_bytes()], &crate::ID)
+}
+
+/// Governance emitter record.
+pub fn governance() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Governance::SEED_PREFIX], &crate::ID)
+}
+
+/// Progress through the `RegisterAll` broadcast with hash `vaa_hash`.
+pub fn registration_cursor(vaa_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RegistrationCursor::SEED_PREFIX, vaa_hash], &crate::ID)
+}
+
+/// Receipt token mint.
+pub fn receipt_mint() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX_RECEIPT_MINT], &crate::ID)
+}
+
+/// Test VAA injected for `chain` at `sequence` (`integration-test` feature).
+#[cfg(feature = "integration-test")]
+pub fn test_vaa(chain: u16, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            crate::state::TestVaa::SEED_PREFIX,
+            &chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NO_SALT: [u8; 8] = [0; 8];
+
+    #[test]
+    fn test_received_is_keyed_by_chain_and_sequence() {
+        assert_ne!(received(&NO_SALT, 2, 1).0, received(&NO_SALT, 2, 2).0);
+        assert_ne!(received(&NO_SALT, 2, 1).0, received(&NO_SALT, 3, 1).0);
+        assert_eq!(received(&NO_SALT, 2, 1), received(&NO_SALT, 2, 1));
+    }
+
+    #[test]
+    fn test_relay_request_is_keyed_by_sequence_and_destination() {
+        assert_ne!(relay_request(1, 2).0, relay_request(1, 3).0);
+        assert_ne!(relay_request(1, 2).0, relay_request(2, 2).0);
+        assert_eq!(relay_request(1, 2), relay_request(1, 2));
+    }
+
+    #[test]
+    fn test_relay_request_index_is_keyed_by_destination() {
+        let request_id = [7; 32];
+        assert_ne!(
+            relay_request_index(&request_id, 2).0,
+            relay_request_index(&request_id, 3).0
+        );
+        assert_ne!(
+            relay_request_index(&request_id, 2).0,
+            relay_request_index(&[8; 32], 2).0
+        );
+    }
+
+    #[test]
+    fn test_sent_does_not_collide_with_received() {
+        assert_ne!(sent(1).0, received(&NO_SALT, 1, 0).0);
+        assert_ne!(sent(1).0, sent(2).0);
+    }
+
+    #[test]
+    fn test_salt() {
+        // An unset salt derives the original, unsalted addresses.
+        let unsalted =
+            Pubkey::find_program_address(&[Peer::SEED_PREFIX, &2u16.to_le_bytes()], &crate::ID);
+        assert_eq!(peer(&NO_SALT, 2), unsalted);
+
+        assert_ne!(peer(&[1; 8], 2).0, peer(&NO_SALT, 2).0);
+        assert_ne!(peer(&[1; 8], 2).0, peer(&[2; 8], 2).0);
+        assert_ne!(received(&[1; 8], 2, 1).0, received(&NO_SALT, 2, 1).0);
+    }
+}
@@ -0,0 +1,159 @@
+//! Optional protobuf greeting codec (`protobuf` feature).
+//!
+//! For peers that standardize on proto schemas instead of the packed
+//! big-endian envelopes in [`message`](crate::message). The payload is
+//! `0x07 | ProtoGreeting`, the protobuf encoding of:
+//!
+//! ```proto
+//! message ProtoGreeting {
+//!   bytes trace_id = 1;   // 16 bytes, see message::trace_id
+//!   bytes sender = 2;     // 32-byte universal address
+//!   uint64 timestamp = 3;
+//!   string message = 4;   // at most GREETING_MAX_LENGTH bytes
+//! }
+//! ```
+//!
+//! The payload ID byte keeps it distinguishable from the other Hello
+//! Executor payloads; the protobuf body itself is not self-describing.
+//! Decoding goes through [`decode_greeting_payload`], so `receive_greeting`
+//! and the resolver accept it when the feature is enabled and reject it
+//! otherwise.
+//!
+//! [`decode_greeting_payload`]: crate::message::decode_greeting_payload
+
+use prost::Message;
+use std::io;
+
+use crate::message::{
+    GreetingOrigin, GREETING_MAX_LENGTH, PAYLOAD_ID_PROTO_HELLO, TRACE_ID_LENGTH,
+};
+
+/// Protobuf greeting, see the module docs for the schema.
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoGreeting {
+    #[prost(bytes = "vec", tag = "1")]
+    pub trace_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub sender: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub timestamp: u64,
+    #[prost(string, tag = "4")]
+    pub message: String,
+}
+
+impl ProtoGreeting {
+    /// Trace ID, if the sender set one of the right length.
+    pub fn trace_id(&self) -> Option<[u8; TRACE_ID_LENGTH]> {
+        self.trace_id.as_slice().try_into().ok()
+    }
+
+    /// Original sender, if the sender set a 32-byte address.
+    pub fn origin(&self) -> Option<GreetingOrigin> {
+        Some(GreetingOrigin {
+            sender: self.sender.as_slice().try_into().ok()?,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+/// Encode a greeting as a `0x07 | ProtoGreeting` payload.
+pub fn encode_proto_greeting_payload(
+    greeting: &str,
+    trace_id: [u8; TRACE_ID_LENGTH],
+    origin: GreetingOrigin,
+) -> io::Result<Vec<u8>> {
+    if greeting.len() > GREETING_MAX_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+        ));
+    }
+    let proto = ProtoGreeting {
+        trace_id: trace_id.to_vec(),
+        sender: origin.sender.to_vec(),
+        timestamp: origin.timestamp,
+        message: greeting.to_string(),
+    };
+    let mut payload = Vec::with_capacity(1 + proto.encoded_len());
+    payload.push(PAYLOAD_ID_PROTO_HELLO);
+    proto
+        .encode(&mut payload)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    Ok(payload)
+}
+
+/// Decode a `0x07 | ProtoGreeting` payload.
+pub fn decode_proto_greeting_payload(payload: &[u8]) -> io::Result<ProtoGreeting> {
+    match payload.split_first() {
+        Some((&PAYLOAD_ID_PROTO_HELLO, body)) => {
+            let proto = ProtoGreeting::decode(body)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            if proto.message.len() > GREETING_MAX_LENGTH {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("message exceeds {GREETING_MAX_LENGTH} bytes"),
+                ));
+            }
+            Ok(proto)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a protobuf greeting",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::{decode_greeting_payload, decode_origin, decode_trace_id};
+
+    #[test]
+    fn test_proto_greeting_round_trip() {
+        let trace = [9u8; TRACE_ID_LENGTH];
+        let origin = GreetingOrigin {
+            sender: [5u8; 32],
+            timestamp: 1_700_000_000,
+        };
+        let encoded = encode_proto_greeting_payload("gm", trace, origin).unwrap();
+        assert_eq!(encoded[0], PAYLOAD_ID_PROTO_HELLO);
+
+        let decoded = decode_proto_greeting_payload(&encoded).unwrap();
+        assert_eq!(decoded.message, "gm");
+        assert_eq!(decoded.trace_id(), Some(trace));
+        assert_eq!(decoded.origin(), Some(origin));
+
+        // The shared decode paths understand it too.
+        assert_eq!(decode_greeting_payload(&encoded).unwrap(), b"gm");
+        assert_eq!(decode_trace_id(&encoded), Some(trace));
+        assert_eq!(decode_origin(&encoded), Some(origin));
+    }
+
+    #[test]
+    fn test_proto_greeting_invalid() {
+        let too_long = "a".repeat(GREETING_MAX_LENGTH + 1);
+        let origin = GreetingOrigin::default();
+        assert!(encode_proto_greeting_payload(&too_long, [0; TRACE_ID_LENGTH], origin).is_err());
+
+        // Encoded by a peer that does not enforce the cap.
+        let mut oversized = vec![PAYLOAD_ID_PROTO_HELLO];
+        ProtoGreeting {
+            message: too_long,
+            ..Default::default()
+        }
+        .encode(&mut oversized)
+        .unwrap();
+        assert!(decode_proto_greeting_payload(&oversized).is_err());
+        assert!(decode_greeting_payload(&oversized).is_err());
+
+        // Truncated body, and a payload that is not protobuf at all.
+        let encoded = encode_proto_greeting_payload("gm", [0; TRACE_ID_LENGTH], origin).unwrap();
+        assert!(decode_proto_greeting_payload(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode_proto_greeting_payload(b"gm").is_err());
+
+        // Missing fields decode as empty, so there is no trace or origin.
+        let bare = decode_proto_greeting_payload(&[PAYLOAD_ID_PROTO_HELLO]).unwrap();
+        assert_eq!(bare.trace_id(), None);
+        assert_eq!(bare.origin(), None);
+    }
+}
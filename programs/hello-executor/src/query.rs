@@ -0,0 +1,310 @@
+//! Wormhole Queries (CCQ) support.
+//!
+//! Guardians answer cross-chain queries off-chain and sign the serialized
+//! response. This module parses the subset of the response format used by
+//! `submit_query_response` — a single `eth_call` against one EVM chain — and
+//! verifies the guardian signatures against a Core Bridge guardian set.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{keccak, secp256k1_recover::secp256k1_recover};
+use std::io;
+use wormhole_io::Readable;
+
+use crate::error::HelloExecutorError;
+
+/// Guardians sign `keccak256(QUERY_RESPONSE_PREFIX || keccak256(response))`.
+pub const QUERY_RESPONSE_PREFIX: &[u8] = b"query_response_0000000000000000000|";
+
+/// Per-chain query type for `eth_call`.
+pub const QUERY_TYPE_ETH_CALL: u8 = 1;
+
+/// Guardian signature: guardian index (1) | r (32) | s (32) | recovery id (1).
+pub const GUARDIAN_SIGNATURE_LENGTH: usize = 66;
+
+/// Seed prefix of the Core Bridge guardian set PDA (index is big-endian).
+pub const SEED_PREFIX_GUARDIAN_SET: &[u8] = b"GuardianSet";
+
+/// Maximum length of an `eth_call` result stored on-chain.
+pub const QUERY_RESULT_MAX_LENGTH: usize = 64;
+
+/// Core Bridge guardian set account (Borsh, no discriminator).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GuardianSetData {
+    /// Index of this guardian set.
+    pub index: u32,
+    /// Ethereum addresses of the guardians.
+    pub keys: Vec<[u8; 20]>,
+    /// Creation time (unix seconds).
+    pub creation_time: u32,
+    /// Expiration time (unix seconds). Zero for the active set.
+    pub expiration_time: u32,
+}
+
+impl GuardianSetData {
+    /// Minimum number of signatures required (2/3 + 1 of the set).
+    pub fn quorum(&self) -> usize {
+        self.keys.len() * 2 / 3 + 1
+    }
+
+    /// Whether the set can still be used to verify signatures at `now`.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expiration_time == 0 || i64::from(self.expiration_time) > now
+    }
+}
+
+/// The result of a single-chain, single-call `eth_call` query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthCallQuery {
+    /// Wormhole chain ID the query was executed on.
+    pub chain: u16,
+    /// Contract that was called.
+    pub to: [u8; 20],
+    /// Call data sent to the contract.
+    pub call_data: Vec<u8>,
+    /// Block the call was executed at.
+    pub block_number: u64,
+    /// Block time in microseconds.
+    pub block_time_us: u64,
+    /// Raw return data of the call.
+    pub result: Vec<u8>,
+}
+
+/// Digest the guardians sign for a query response.
+pub fn query_response_digest(response: &[u8]) -> [u8; 32] {
+    let response_hash = keccak::hash(response).to_bytes();
+    keccak::hashv(&[QUERY_RESPONSE_PREFIX, &response_hash]).to_bytes()
+}
+
+/// Verify that a quorum of `guardian_set` signed `digest`.
+///
+/// Signatures must be sorted by strictly increasing guardian index.
+pub fn verify_guardian_signatures(
+    digest: &[u8; 32],
+    signatures: &[[u8; GUARDIAN_SIGNATURE_LENGTH]],
+    guardian_set: &GuardianSetData,
+) -> Result<()> {
+    require!(
+        signatures.len() >= guardian_set.quorum(),
+        HelloExecutorError::NoQuorum,
+    );
+
+    let mut last_index: Option<u8> = None;
+    for signature in signatures {
+        let index = signature[0];
+        require!(
+            last_index.map_or(true, |last| index > last),
+            HelloExecutorError::InvalidGuardianSignature,
+        );
+        last_index = Some(index);
+
+        let expected = guardian_set
+            .keys
+            .get(usize::from(index))
+            .ok_or(HelloExecutorError::InvalidGuardianSignature)?;
+
+        let recovered = secp256k1_recover(digest, signature[65], &signature[1..65])
+            .map_err(|_| HelloExecutorError::InvalidGuardianSignature)?;
+        let recovered_hash = keccak::hash(&recovered.to_bytes()).to_bytes();
+
+        require!(
+            recovered_hash[12..] == expected[..],
+            HelloExecutorError::InvalidGuardianSignature,
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a serialized query response containing exactly one `eth_call`.
+pub fn parse_eth_call_response(response: &[u8]) -> io::Result<EthCallQuery> {
+    let reader = &mut &response[..];
+
+    // Response envelope: version | request chain | request ID | request | responses
+    expect_u8(reader, 1, "response version")?;
+    let request_chain = u16::read(reader)?;
+    // Off-chain requests carry the requester's 65-byte signature; on-chain
+    // requests carry a 32-byte request ID.
+    skip(reader, if request_chain == 0 { 65 } else { 32 })?;
+
+    // Query request: version | nonce | per-chain queries
+    let request_len = u32::read(reader)? as usize;
+    let request = &mut take(reader, request_len)?;
+    expect_u8(request, 1, "request version")?;
+    let _nonce = u32::read(request)?;
+    expect_u8(request, 1, "per-chain query count")?;
+    let (chain, query) = read_per_chain(request)?;
+    ensure_consumed(request)?;
+
+    // eth_call request: block ID | call data
+    let query = &mut &query[..];
+    let block_id_len = u32::read(query)? as usize;
+    skip(query, block_id_len)?;
+    expect_u8(query, 1, "call data count")?;
+    let mut to = [0u8; 20];
+    io::Read::read_exact(query, &mut to)?;
+    let call_data_len = u32::read(query)? as usize;
+    let call_data = take(query, call_data_len)?.to_vec();
+    ensure_consumed(query)?;
+
+    // Per-chain responses must mirror the request.
+    expect_u8(reader, 1, "per-chain response count")?;
+    let (response_chain, result) = read_per_chain(reader)?;
+    ensure_consumed(reader)?;
+    if response_chain != chain {
+        return Err(invalid("response chain does not match request"));
+    }
+
+    // eth_call response: block number | block hash | block time | results
+    let result = &mut &result[..];
+    let block_number = u64::read(result)?;
+    skip(result, 32)?;
+    let block_time_us = u64::read(result)?;
+    expect_u8(result, 1, "result count")?;
+    let result_len = u32::read(result)? as usize;
+    let call_result = take(result, result_len)?.to_vec();
+    ensure_consumed(result)?;
+
+    Ok(EthCallQuery {
+        chain,
+        to,
+        call_data,
+        block_number,
+        block_time_us,
+        result: call_result,
+    })
+}
+
+/// Read a per-chain query or response: chain | query type | length | bytes.
+fn read_per_chain(reader: &mut &[u8]) -> io::Result<(u16, Vec<u8>)> {
+    let chain = u16::read(reader)?;
+    expect_u8(reader, QUERY_TYPE_ETH_CALL, "query type")?;
+    let len = u32::read(reader)? as usize;
+    Ok((chain, take(reader, len)?.to_vec()))
+}
+
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if reader.len() < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "query response truncated",
+        ));
+    }
+    let (head, tail) = reader.split_at(len);
+    *reader = tail;
+    Ok(head)
+}
+
+fn skip(reader: &mut &[u8], len: usize) -> io::Result<()> {
+    take(reader, len).map(|_| ())
+}
+
+fn expect_u8(reader: &mut &[u8], expected: u8, what: &str) -> io::Result<()> {
+    let value = u8::read(reader)?;
+    if value != expected {
+        return Err(invalid(&format!("unsupported {what}: {value}")));
+    }
+    Ok(())
+}
+
+fn ensure_consumed(reader: &[u8]) -> io::Result<()> {
+    if !reader.is_empty() {
+        return Err(invalid("trailing bytes"));
+    }
+    Ok(())
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn per_chain(chain: u16, body: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&chain.to_be_bytes());
+        out.push(QUERY_TYPE_ETH_CALL);
+        out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn build_response(request_chain: u16, response_chain: u16) -> Vec<u8> {
+        let mut call = Vec::new();
+        call.extend_from_slice(&6u32.to_be_bytes());
+        call.extend_from_slice(b"latest");
+        call.push(1);
+        call.extend_from_slice(&[0xaa; 20]);
+        call.extend_from_slice(&4u32.to_be_bytes());
+        call.extend_from_slice(&[1, 2, 3, 4]);
+
+        let mut request = vec![1];
+        request.extend_from_slice(&7u32.to_be_bytes());
+        request.push(1);
+        request.extend_from_slice(&per_chain(request_chain, &call));
+
+        let mut result = Vec::new();
+        result.extend_from_slice(&42u64.to_be_bytes());
+        result.extend_from_slice(&[0u8; 32]);
+        result.extend_from_slice(&1_000_000u64.to_be_bytes());
+        result.push(1);
+        result.extend_from_slice(&32u32.to_be_bytes());
+        result.extend_from_slice(&[9u8; 32]);
+
+        let mut response = vec![1];
+        response.extend_from_slice(&0u16.to_be_bytes());
+        response.extend_from_slice(&[0u8; 65]);
+        response.extend_from_slice(&(request.len() as u32).to_be_bytes());
+        response.extend_from_slice(&request);
+        response.push(1);
+        response.extend_from_slice(&per_chain(response_chain, &result));
+        response
+    }
+
+    #[test]
+    fn test_parse_eth_call_response() {
+        let response = build_response(10002, 10002);
+        let query = parse_eth_call_response(&response).unwrap();
+
+        assert_eq!(query.chain, 10002);
+        assert_eq!(query.to, [0xaa; 20]);
+        assert_eq!(query.call_data, vec![1, 2, 3, 4]);
+        assert_eq!(query.block_number, 42);
+        assert_eq!(query.block_time_us, 1_000_000);
+        assert_eq!(query.result, vec![9u8; 32]);
+    }
+
+    #[test]
+    fn test_parse_rejects_chain_mismatch() {
+        let response = build_response(10002, 2);
+        assert!(parse_eth_call_response(&response).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated() {
+        let response = build_response(10002, 10002);
+        for len in [0, 1, 10, response.len() - 1] {
+            assert!(parse_eth_call_response(&response[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_trailing_bytes() {
+        let mut response = build_response(10002, 10002);
+        response.push(0);
+        assert!(parse_eth_call_response(&response).is_err());
+    }
+
+    #[test]
+    fn test_quorum() {
+        let set = GuardianSetData {
+            index: 0,
+            keys: vec![[0u8; 20]; 19],
+            creation_time: 0,
+            expiration_time: 0,
+        };
+        assert_eq!(set.quorum(), 13);
+        assert!(set.is_active(i64::MAX));
+    }
+}
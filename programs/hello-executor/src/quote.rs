@@ -0,0 +1,171 @@
+//! Executor signed-quote parsing and signer recovery.
+//!
+//! `request_relay` forwards `signed_quote_bytes` to the Executor, which
+//! charges according to it. Before paying, the program checks the quote is
+//! unexpired and made out for this relay: its chains and payee. When the
+//! owner configures quote signers, the quote must also be signed by one of
+//! them, so a caller cannot substitute a quote from an arbitrary signer.
+//!
+//! Layout (`EQ01`, all integers big-endian):
+//! `prefix(4) | quoter(20) | payee(32) | src_chain(2) | dst_chain(2) |
+//!  expiry_time(8) | base_fee(8) | dst_gas_price(8) | src_price(8) |
+//!  dst_price(8) | signature(65)`
+//!
+//! The signature is secp256k1 (`r | s | v`) over `keccak256` of everything
+//! before it; the quoter field is the signer's EVM address.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{keccak, secp256k1_recover::secp256k1_recover};
+use std::io;
+use wormhole_io::Readable;
+
+use crate::error::HelloExecutorError;
+
+/// Quote format prefix.
+pub const QUOTE_PREFIX: &[u8; 4] = b"EQ01";
+
+/// Length of the signed part of a quote.
+pub const QUOTE_BODY_LENGTH: usize = 100;
+
+/// Length of a signed quote: body + 65-byte signature.
+pub const SIGNED_QUOTE_LENGTH: usize = QUOTE_BODY_LENGTH + 65;
+
+/// Decimals of the quote's base fee and prices.
+pub const QUOTE_DECIMALS: u32 = 10;
+
+/// Fields of a signed quote that the program checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutorQuote {
+    /// EVM address of the quote signer.
+    pub quoter: [u8; 20],
+    /// Executor payee on the source chain.
+    pub payee: [u8; 32],
+    /// Wormhole chain ID the quote is valid from.
+    pub src_chain: u16,
+    /// Wormhole chain ID the quote is valid to.
+    pub dst_chain: u16,
+    /// Unix timestamp after which the quote is no longer valid.
+    pub expiry_time: u64,
+    /// Flat fee in source-chain terms, with [QUOTE_DECIMALS] decimals.
+    pub base_fee: u64,
+    /// Destination gas price, in the destination's smallest native unit.
+    pub dst_gas_price: u64,
+    /// Source-chain native token price, with [QUOTE_DECIMALS] decimals.
+    pub src_price: u64,
+    /// Destination-chain native token price, with [QUOTE_DECIMALS] decimals.
+    pub dst_price: u64,
+}
+
+impl ExecutorQuote {
+    /// Whether the quote is no longer valid at unix time `now`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        now.max(0) as u64 > self.expiry_time
+    }
+}
+
+/// Parse the fields of a signed quote.
+pub fn parse_quote(signed_quote: &[u8]) -> io::Result<ExecutorQuote> {
+    if signed_quote.len() != SIGNED_QUOTE_LENGTH || &signed_quote[..4] != QUOTE_PREFIX {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an EQ01 signed quote",
+        ));
+    }
+
+    let reader = &mut &signed_quote[4..];
+    let mut quoter = [0u8; 20];
+    io::Read::read_exact(reader, &mut quoter)?;
+    let mut payee = [0u8; 32];
+    io::Read::read_exact(reader, &mut payee)?;
+
+    Ok(ExecutorQuote {
+        quoter,
+        payee,
+        src_chain: u16::read(reader)?,
+        dst_chain: u16::read(reader)?,
+        expiry_time: u64::read(reader)?,
+        base_fee: u64::read(reader)?,
+        dst_gas_price: u64::read(reader)?,
+        src_price: u64::read(reader)?,
+        dst_price: u64::read(reader)?,
+    })
+}
+
+/// Recover the EVM address that signed the quote.
+pub fn recover_quote_signer(signed_quote: &[u8]) -> Result<[u8; 20]> {
+    require!(
+        signed_quote.len() == SIGNED_QUOTE_LENGTH,
+        HelloExecutorError::InvalidQuote,
+    );
+
+    let digest = keccak::hash(&signed_quote[..QUOTE_BODY_LENGTH]).to_bytes();
+    let signature = &signed_quote[QUOTE_BODY_LENGTH..];
+    // Accept both raw (0/1) and Ethereum-style (27/28) recovery IDs.
+    let recovery_id = match signature[64] {
+        v @ (0 | 1) => v,
+        v @ (27 | 28) => v - 27,
+        _ => return err!(HelloExecutorError::InvalidQuoteSigner),
+    };
+
+    let recovered = secp256k1_recover(&digest, recovery_id, &signature[..64])
+        .map_err(|_| HelloExecutorError::InvalidQuoteSigner)?;
+    let recovered_hash = keccak::hash(&recovered.to_bytes()).to_bytes();
+
+    let mut signer = [0u8; 20];
+    signer.copy_from_slice(&recovered_hash[12..]);
+    Ok(signer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_quote() -> Vec<u8> {
+        let mut quote = Vec::with_capacity(SIGNED_QUOTE_LENGTH);
+        quote.extend_from_slice(QUOTE_PREFIX);
+        quote.extend_from_slice(&[0x11; 20]);
+        quote.extend_from_slice(&[0x22; 32]);
+        quote.extend_from_slice(&1u16.to_be_bytes());
+        quote.extend_from_slice(&10002u16.to_be_bytes());
+        quote.extend_from_slice(&1_700_000_000u64.to_be_bytes());
+        quote.extend_from_slice(&5u64.to_be_bytes());
+        quote.extend_from_slice(&6u64.to_be_bytes());
+        quote.extend_from_slice(&7u64.to_be_bytes());
+        quote.extend_from_slice(&8u64.to_be_bytes());
+        quote.extend_from_slice(&[0u8; 65]);
+        quote
+    }
+
+    #[test]
+    fn test_parse_quote() {
+        let quote = parse_quote(&build_quote()).unwrap();
+
+        assert_eq!(quote.quoter, [0x11; 20]);
+        assert_eq!(quote.payee, [0x22; 32]);
+        assert_eq!(quote.src_chain, 1);
+        assert_eq!(quote.dst_chain, 10002);
+        assert_eq!(quote.expiry_time, 1_700_000_000);
+        assert_eq!(quote.base_fee, 5);
+        assert_eq!(quote.dst_gas_price, 6);
+        assert_eq!(quote.src_price, 7);
+        assert_eq!(quote.dst_price, 8);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let quote = parse_quote(&build_quote()).unwrap();
+        assert!(!quote.is_expired(1_699_999_999));
+        assert!(!quote.is_expired(1_700_000_000));
+        assert!(quote.is_expired(1_700_000_001));
+    }
+
+    #[test]
+    fn test_parse_quote_rejects_malformed() {
+        let mut quote = build_quote();
+        quote[0] = b'X';
+        assert!(parse_quote(&quote).is_err());
+
+        let quote = build_quote();
+        assert!(parse_quote(&quote[..SIGNED_QUOTE_LENGTH - 1]).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Optional greeting receipts (`receipts` feature).
+//!
+//! A greeting addressed to a Solana wallet — one that starts with
+//! `@<base58 pubkey>` — mints one receipt token from the program's receipt
+//! mint to that wallet's associated token account when it is delivered.
+//!
+//! The receipt accounts travel as `receive_greeting` remaining accounts
+//! (`[receipt_mint, recipient_token_account, token_program]`), appended by
+//! the resolver. A missing or uninitialized token account skips the receipt
+//! instead of failing delivery.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, MintTo, Token, TokenAccount},
+};
+use std::str::FromStr;
+
+use crate::{pda, state::Config};
+
+/// Seed for the receipt mint PDA.
+pub const SEED_PREFIX_RECEIPT_MINT: &[u8] = b"receipt_mint";
+
+/// Parse the recipient of a greeting addressed as `@<base58 pubkey> ...`.
+pub fn parse_recipient(greeting: &str) -> Option<Pubkey> {
+    let addressed = greeting.strip_prefix('@')?;
+    let recipient = addressed.split_whitespace().next()?;
+    Pubkey::from_str(recipient).ok()
+}
+
+/// Resolver-side account metas for minting a receipt to `recipient`:
+/// `(receipt_mint, recipient_token_account, token_program)`.
+pub fn receipt_accounts(recipient: &Pubkey) -> [Pubkey; 3] {
+    let (mint, _) = pda::receipt_mint();
+    [
+        mint,
+        get_associated_token_address(recipient, &mint),
+        Token::id(),
+    ]
+}
+
+/// Mint one receipt token for a delivered greeting, if it is addressed and
+/// the receipt accounts were passed.
+pub fn mint_receipt<'info>(
+    config: &Account<'info, Config>,
+    remaining_accounts: &[AccountInfo<'info>],
+    greeting: &str,
+) -> Result<()> {
+    let Some(recipient) = parse_recipient(greeting) else {
+        return Ok(());
+    };
+    let [receipt_mint, recipient_token_account, token_program, ..] = remaining_accounts else {
+        msg!("Receipt accounts not passed; skipping receipt");
+        return Ok(());
+    };
+
+    let [mint, ata, token_program_id] = receipt_accounts(&recipient);
+    if *receipt_mint.key != mint
+        || *recipient_token_account.key != ata
+        || *token_program.key != token_program_id
+    {
+        msg!("Receipt accounts do not match recipient {}; skipping receipt", recipient);
+        return Ok(());
+    }
+
+    // Delivery must not depend on the recipient having created their ATA.
+    if recipient_token_account.owner != &token_program_id
+        || TokenAccount::try_deserialize(&mut &recipient_token_account.try_borrow_data()?[..])
+            .is_err()
+    {
+        msg!("Recipient {} has no receipt token account; skipping receipt", recipient);
+        return Ok(());
+    }
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: receipt_mint.clone(),
+                to: recipient_token_account.clone(),
+                authority: config.to_account_info(),
+            },
+            &[&[Config::SEED_PREFIX, &[config.bump]]],
+        ),
+        1,
+    )?;
+
+    msg!("Minted greeting receipt to {}", recipient);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipient() {
+        let recipient = Pubkey::new_unique();
+
+        assert_eq!(parse_recipient(&format!("@{recipient} gm")), Some(recipient));
+        assert_eq!(parse_recipient(&format!("@{recipient}")), Some(recipient));
+        assert_eq!(parse_recipient(&format!("gm @{recipient}")), None);
+        assert_eq!(parse_recipient("@not-a-key gm"), None);
+        assert_eq!(parse_recipient("Hello, World!"), None);
+    }
+}
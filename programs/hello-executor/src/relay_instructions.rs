@@ -0,0 +1,415 @@
+//! Executor relay instruction encoding.
+//!
+//! Relay instructions tell the Executor what to spend on the destination
+//! chain. They are a concatenation of typed entries (integers big-endian):
+//!
+//! * Gas — `0x01 | gas_limit(u128) | msg_value(u128)`
+//! * Gas drop-off — `0x02 | drop_off(u128) | recipient(32)`: native tokens
+//!   delivered to `recipient` alongside the message.
+//!
+//! The gas entry is read in the destination family's units: gas and wei on
+//! EVM, compute units and lamports on SVM. [ExecutionBudget] names the two,
+//! and [build] only accepts the one matching the destination's family.
+//!
+//! [GasInstruction] and [GasDropOffInstruction] are the typed entries, as
+//! instruction arguments take them; `encode` writes the bytes.
+//!
+//! The quote must be requested with the same instructions the relay is made
+//! with, drop-off included, or the Executor will not deliver.
+
+use anchor_lang::prelude::*;
+use std::io;
+use wormhole_io::Readable;
+
+use crate::chains::{self, ChainFamily};
+
+/// Relay instruction type for a gas limit and message value.
+pub const RELAY_INSTRUCTION_GAS: u8 = 1;
+
+/// Relay instruction type for a gas drop-off.
+pub const RELAY_INSTRUCTION_GAS_DROP_OFF: u8 = 2;
+
+/// Most compute units an SVM transaction may request.
+pub const SVM_MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// What the destination may spend executing the message, in its family's
+/// units. Both encode as a gas instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionBudget {
+    /// EVM (and other gas-metered) destinations.
+    Gas {
+        /// Gas limit of the delivery call.
+        gas_limit: u128,
+        /// Value sent with the call, in wei.
+        msg_value: u128,
+    },
+    /// SVM destinations.
+    ComputeBudget {
+        /// Compute unit limit of the delivery transaction.
+        compute_units: u32,
+        /// Lamports for the receiver's rent and fees.
+        lamports: u64,
+    },
+}
+
+impl ExecutionBudget {
+    /// Encode as a gas instruction.
+    pub fn instruction(&self) -> Vec<u8> {
+        GasInstruction::from(*self).encode()
+    }
+}
+
+impl From<ExecutionBudget> for GasInstruction {
+    fn from(budget: ExecutionBudget) -> Self {
+        match budget {
+            ExecutionBudget::Gas {
+                gas_limit,
+                msg_value,
+            } => GasInstruction {
+                gas_limit,
+                msg_value,
+            },
+            ExecutionBudget::ComputeBudget {
+                compute_units,
+                lamports,
+            } => GasInstruction {
+                gas_limit: compute_units.into(),
+                msg_value: lamports.into(),
+            },
+        }
+    }
+}
+
+/// Gas limit and message value of a relay, in the destination family's
+/// units (see [ExecutionBudget]).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasInstruction {
+    /// Gas limit (compute units on SVM).
+    pub gas_limit: u128,
+    /// Value sent with the delivery (wei on EVM, lamports on SVM).
+    pub msg_value: u128,
+}
+
+impl GasInstruction {
+    /// Encode as a gas instruction.
+    pub fn encode(&self) -> Vec<u8> {
+        gas_instruction(self.gas_limit, self.msg_value)
+    }
+}
+
+/// Native token delivery to a destination account, requested with a relay.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasDropOffInstruction {
+    /// Amount in the destination chain's native base units (wei, lamports).
+    pub amount: u128,
+    /// Universal address of the recipient on the destination chain.
+    pub recipient: [u8; 32],
+}
+
+impl GasDropOffInstruction {
+    /// Encode as a gas drop-off instruction.
+    pub fn encode(&self) -> Vec<u8> {
+        gas_drop_off_instruction(self)
+    }
+}
+
+/// Encode a gas instruction.
+pub fn gas_instruction(gas_limit: u128, msg_value: u128) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + 16 + 16);
+    out.push(RELAY_INSTRUCTION_GAS);
+    out.extend_from_slice(&gas_limit.to_be_bytes());
+    out.extend_from_slice(&msg_value.to_be_bytes());
+    out
+}
+
+/// Encode a gas drop-off instruction.
+pub fn gas_drop_off_instruction(drop_off: &GasDropOffInstruction) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + 16 + 32);
+    out.push(RELAY_INSTRUCTION_GAS_DROP_OFF);
+    out.extend_from_slice(&drop_off.amount.to_be_bytes());
+    out.extend_from_slice(&drop_off.recipient);
+    out
+}
+
+/// Encode the relay instructions for a relay to `dst_chain`: the budget,
+/// then the drop-off if any.
+///
+/// Fails for a budget in the wrong units for `dst_chain`'s family, a
+/// compute budget over [SVM_MAX_COMPUTE_UNITS], a drop-off to a family the
+/// Executor does not deliver drop-offs to, or a recipient that is not an
+/// address on `dst_chain`.
+pub fn build(
+    dst_chain: u16,
+    budget: ExecutionBudget,
+    drop_off: Option<&GasDropOffInstruction>,
+) -> io::Result<Vec<u8>> {
+    let family = chains::chain_family(dst_chain);
+    match (family, budget) {
+        (ChainFamily::Svm, ExecutionBudget::ComputeBudget { compute_units, .. }) => {
+            if compute_units > SVM_MAX_COMPUTE_UNITS {
+                return Err(compute_units_too_large());
+            }
+        }
+        (ChainFamily::Svm, ExecutionBudget::Gas { .. }) => {
+            return Err(wrong_budget(family, "a compute budget"))
+        }
+        (_, ExecutionBudget::ComputeBudget { .. }) => {
+            return Err(wrong_budget(family, "a gas limit"))
+        }
+        (
+            ChainFamily::Evm | ChainFamily::Move | ChainFamily::Cosmos | ChainFamily::Other,
+            ExecutionBudget::Gas { .. },
+        ) => {}
+    }
+
+    let mut out = budget.instruction();
+    if let Some(drop_off) = drop_off {
+        append_gas_drop_off(dst_chain, &mut out, drop_off)?;
+    }
+    Ok(out)
+}
+
+/// Append a drop-off instruction for `dst_chain` to `relay_instructions`,
+/// with the same checks as [build].
+pub fn append_gas_drop_off(
+    dst_chain: u16,
+    relay_instructions: &mut Vec<u8>,
+    drop_off: &GasDropOffInstruction,
+) -> io::Result<()> {
+    let family = chains::chain_family(dst_chain);
+    if !family.supports_gas_drop_off() {
+        return Err(unsupported_drop_off(family));
+    }
+    if !chains::is_valid_address(dst_chain, &drop_off.recipient) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "drop-off recipient is not an address on the destination chain",
+        ));
+    }
+    relay_instructions.extend(gas_drop_off_instruction(drop_off));
+    Ok(())
+}
+
+fn wrong_budget(family: ChainFamily, expected: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{family:?} destinations take {expected}"),
+    )
+}
+
+fn compute_units_too_large() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("SVM destinations allow at most {SVM_MAX_COMPUTE_UNITS} compute units"),
+    )
+}
+
+fn unsupported_drop_off(family: ChainFamily) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("gas drop-off is not supported for {family:?} destinations"),
+    )
+}
+
+/// Check caller-encoded `relay_instructions` only request what `dst_chain`'s
+/// family supports, and sum them (see [totals]).
+pub fn check(dst_chain: u16, relay_instructions: &[u8]) -> io::Result<RelayTotals> {
+    let totals = totals(relay_instructions)?;
+    let family = chains::chain_family(dst_chain);
+    if totals.drop_off > 0 && !family.supports_gas_drop_off() {
+        return Err(unsupported_drop_off(family));
+    }
+    if family == ChainFamily::Svm {
+        // Compute units and lamports; a relay asking for more than one
+        // transaction can use would never execute.
+        if totals.gas_limit > SVM_MAX_COMPUTE_UNITS.into() {
+            return Err(compute_units_too_large());
+        }
+        if totals.msg_value > u64::MAX.into() || totals.drop_off > u64::MAX.into() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "lamport amounts must fit in a u64",
+            ));
+        }
+    }
+    Ok(totals)
+}
+
+fn read_u128(reader: &mut &[u8]) -> io::Result<u128> {
+    let mut buf = [0u8; 16];
+    io::Read::read_exact(reader, &mut buf)?;
+    Ok(u128::from_be_bytes(buf))
+}
+
+/// What a set of relay instructions asks the Executor to spend.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelayTotals {
+    /// Sum of gas limits.
+    pub gas_limit: u128,
+    /// Sum of message values.
+    pub msg_value: u128,
+    /// Sum of gas drop-offs.
+    pub drop_off: u128,
+}
+
+/// Sum what `relay_instructions` request, by kind.
+///
+/// Fails on unknown instruction types, since what they spend cannot be
+/// checked.
+pub fn totals(relay_instructions: &[u8]) -> io::Result<RelayTotals> {
+    let reader = &mut &relay_instructions[..];
+    let mut totals = RelayTotals::default();
+    let overflow = || io::Error::new(io::ErrorKind::InvalidData, "relay instruction overflow");
+
+    while !reader.is_empty() {
+        match u8::read(reader)? {
+            RELAY_INSTRUCTION_GAS => {
+                let gas_limit = read_u128(reader)?;
+                let msg_value = read_u128(reader)?;
+                totals.gas_limit = totals.gas_limit.checked_add(gas_limit).ok_or_else(overflow)?;
+                totals.msg_value = totals.msg_value.checked_add(msg_value).ok_or_else(overflow)?;
+            }
+            RELAY_INSTRUCTION_GAS_DROP_OFF => {
+                let amount = read_u128(reader)?;
+                let mut recipient = [0u8; 32];
+                io::Read::read_exact(reader, &mut recipient)?;
+                totals.drop_off = totals.drop_off.checked_add(amount).ok_or_else(overflow)?;
+            }
+            kind => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown relay instruction type: {kind}"),
+                ))
+            }
+        }
+    }
+
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gas_instruction() {
+        let encoded = gas_instruction(200_000, 0);
+        assert_eq!(encoded.len(), 33);
+        assert_eq!(encoded[0], RELAY_INSTRUCTION_GAS);
+        assert_eq!(encoded[1..17], 200_000u128.to_be_bytes());
+        assert_eq!(
+            totals(&encoded).unwrap(),
+            RelayTotals {
+                gas_limit: 200_000,
+                ..Default::default()
+            }
+        );
+
+        let typed = GasInstruction {
+            gas_limit: 200_000,
+            msg_value: 0,
+        };
+        assert_eq!(typed.encode(), encoded);
+        let budget = ExecutionBudget::ComputeBudget {
+            compute_units: 500_000,
+            lamports: 15_000_000,
+        };
+        assert_eq!(
+            GasInstruction::from(budget),
+            GasInstruction {
+                gas_limit: 500_000,
+                msg_value: 15_000_000,
+            }
+        );
+        assert_eq!(budget.instruction(), gas_instruction(500_000, 15_000_000));
+    }
+
+    #[test]
+    fn test_totals_drop_off() {
+        let drop_off = GasDropOffInstruction {
+            amount: 1_000_000_000_000_000,
+            recipient: [7; 32],
+        };
+        let encoded = gas_drop_off_instruction(&drop_off);
+        assert_eq!(encoded.len(), 49);
+        assert_eq!(encoded[17..], [7; 32]);
+
+        let mut instructions = gas_instruction(200_000, 0);
+        instructions.extend(gas_drop_off_instruction(&drop_off));
+        instructions.extend(gas_drop_off_instruction(&drop_off));
+        assert_eq!(totals(&instructions).unwrap().drop_off, 2 * drop_off.amount);
+
+        assert_eq!(totals(&[]).unwrap(), RelayTotals::default());
+        assert!(totals(&encoded[..48]).is_err());
+        assert!(totals(&[9]).is_err());
+    }
+
+    #[test]
+    fn test_build_by_family() {
+        let drop_off = GasDropOffInstruction {
+            amount: 1_000,
+            recipient: [7; 32],
+        };
+
+        let gas = ExecutionBudget::Gas {
+            gas_limit: 200_000,
+            msg_value: 0,
+        };
+        let compute = ExecutionBudget::ComputeBudget {
+            compute_units: 500_000,
+            lamports: 15_000_000,
+        };
+
+        // SVM: compute budget then drop-off.
+        let built = build(chains::CHAIN_ID_SOLANA, compute, Some(&drop_off)).unwrap();
+        assert_eq!(built.len(), 33 + 49);
+        assert_eq!(built[..33], gas_instruction(500_000, 15_000_000));
+        assert_eq!(check(chains::CHAIN_ID_SOLANA, &built).unwrap().drop_off, 1_000);
+
+        // A full 32-byte recipient is not an EVM address.
+        assert_eq!(
+            build(10002, gas, Some(&drop_off)).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+
+        // Cosmos destinations take gas but not drop-offs, built or encoded.
+        assert_eq!(build(32, gas, None).unwrap(), gas_instruction(200_000, 0));
+        assert_eq!(
+            build(32, gas, Some(&drop_off)).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+        assert_eq!(check(32, &built).unwrap_err().kind(), io::ErrorKind::Unsupported);
+        assert!(check(32, &gas_instruction(200_000, 0)).is_ok());
+    }
+
+    #[test]
+    fn test_svm_budget() {
+        let solana = chains::CHAIN_ID_SOLANA;
+        let gas = ExecutionBudget::Gas {
+            gas_limit: 200_000,
+            msg_value: 0,
+        };
+        let max = ExecutionBudget::ComputeBudget {
+            compute_units: SVM_MAX_COMPUTE_UNITS,
+            lamports: 0,
+        };
+        let over = ExecutionBudget::ComputeBudget {
+            compute_units: SVM_MAX_COMPUTE_UNITS + 1,
+            lamports: 0,
+        };
+
+        // Units must match the family.
+        assert!(build(solana, gas, None).is_err());
+        assert!(build(10002, max, None).is_err());
+
+        assert!(check(solana, &build(solana, max, None).unwrap()).is_ok());
+        assert!(build(solana, over, None).is_err());
+
+        // Caller-encoded instructions get the same limits.
+        let over = gas_instruction(u128::from(SVM_MAX_COMPUTE_UNITS) + 1, 0);
+        assert!(check(solana, &over).is_err());
+        assert!(check(10002, &over).is_ok());
+        let lamports = gas_instruction(200_000, u128::from(u64::MAX) + 1);
+        assert!(check(solana, &lamports).is_err());
+    }
+}
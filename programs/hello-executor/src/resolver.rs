@@ -2,55 +2,313 @@
 //!
 //! This module handles the resolve_execute_vaa_v1 instruction that returns
 //! the instructions needed to execute a VAA on this program.
+//!
+//! Resolution takes up to three rounds. Each round that needs on-chain data
+//! returns [Resolver::Missing]. The Executor then calls again with those
+//! accounts passed, and each request lists every account still needed:
+//!
+//! 1. No accounts: ask for Config (and the LookupTable record).
+//! 2. With `config.resolver_requires_peer`, ask for the Peer too. A passed
+//!    Peer is read, not derived blindly: an unregistered emitter or a
+//!    payload the peer's mode rejects fails resolution here instead of in
+//!    `receive_greeting`.
+//! 3. If the Peer has a reply or targets `receive_and_reply`, ask for the
+//!    emitter's sequence tracker, to place the reply message account.
+//!
+//! Accounts a caller passes without being asked (tests, simulations) are
+//! used the same way.
+//!
+//! The VAA may be a bare body or a full signed VAA, stripped to its body
+//! by [vaa::strip_header]; its guardian set, if passed, is sanity-checked.
 
 use anchor_lang::prelude::*;
 use anchor_lang::InstructionData;
+use anchor_spl::associated_token::get_associated_token_address;
 use executor_account_resolver_svm::{
-    InstructionGroup, InstructionGroups, Resolver, SerializableAccountMeta,
-    SerializableInstruction, RESOLVER_PUBKEY_PAYER, RESOLVER_PUBKEY_POSTED_VAA,
+    InstructionGroup, InstructionGroups, MissingAccounts, Resolver, SerializableAccountMeta,
+    SerializableInstruction, RESOLVER_PUBKEY_GUARDIAN_SET, RESOLVER_PUBKEY_PAYER,
+    RESOLVER_PUBKEY_POSTED_VAA, RESOLVER_PUBKEY_SHIM_VAA_SIGS,
 };
 use solana_program::program::set_return_data;
 
-use wormhole_anchor_sdk::wormhole;
+use wormhole_anchor_sdk::{token_bridge::program::TokenBridge, wormhole};
 
-use crate::state::{Config, Peer, Received};
+use crate::{
+    error::ResolverError,
+    events,
+    governance::{self, GovernanceAction},
+    message::{
+        decode_content, decode_greeting_payload, decode_handshake, decode_token_greeting,
+        decode_version_message, GREETING_MAX_LENGTH,
+    },
+    pda,
+    query::{self, GuardianSetData},
+    state::{Config, EventMode, LookupTable, PayloadMode, Peer, ReceiveTarget},
+    token_bridge, vaa,
+    verify_shim::VerifyVaaShim,
+    wormhole_cpi,
+};
 
 #[derive(Accounts)]
 pub struct ExecuteVaaV1<'info> {
-    #[account(seeds = [Config::SEED_PREFIX], bump)]
+    #[account(seeds = [Config::SEED_PREFIX], bump = config.bump)]
     pub config: Account<'info, Config>,
-    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    /// CHECK: Wormhole Core Bridge program - verified by config.wormhole_program_id()
+    #[account(address = config.wormhole_program_id() @ ResolverError::InvalidWormholeProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
 // Re-export types for lib.rs
 pub use executor_account_resolver_svm::{InstructionGroups as ResolverInstructionGroups, Resolver as ResolverType};
 
+/// Solana's return data limit. A larger resolver result would reach the
+/// Executor truncated, so it is refused instead.
+pub const MAX_RETURN_DATA: usize = 1024;
+
 // ============ Handlers ============
 
-fn parse_vaa_body(vaa_body: &[u8]) -> Result<(u16, [u8; 32], u64)> {
-    // VAA body layout:
-    // timestamp(4) | nonce(4) | emitter_chain(2) | emitter_address(32) | sequence(8) | consistency(1) | payload(...)
-    if vaa_body.len() < 51 {
-        return Err(ProgramError::InvalidInstructionData.into());
+fn parse_vaa_body(vaa_body: &[u8]) -> Result<vaa::VaaBody<'_>> {
+    vaa::parse_body(vaa_body).map_err(|_| {
+        msg!("VAA body too short: {} bytes", vaa_body.len());
+        ResolverError::VaaBodyTooShort.into()
+    })
+}
+
+/// Check the peer for the emitter chain, if the caller passed its account.
+///
+/// The Executor only passes the Peer PDA when asked for it (with
+/// `config.resolver_requires_peer`); other callers may include it (tests,
+/// simulations). A missing account is not an error — the resolver cannot
+/// tell "not passed" from "not registered".
+fn check_peer(
+    program_id: &Pubkey,
+    peer_key: &Pubkey,
+    emitter_address: &[u8; 32],
+    accounts: &[AccountInfo],
+) -> Result<Option<Peer>> {
+    let Some(info) = accounts.iter().find(|info| info.key == peer_key) else {
+        return Ok(None);
+    };
+
+    if info.owner != program_id || info.data_is_empty() {
+        msg!("Will not execute: no peer registered at {}", peer_key);
+        return Err(ResolverError::PeerNotRegistered.into());
+    }
+
+    let peer = Peer::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    if !peer.verify(emitter_address) {
+        msg!("Emitter {} is not the registered peer", hex::encode(emitter_address));
+        return Err(ResolverError::PeerNotRegistered.into());
+    }
+
+    Ok(Some(peer))
+}
+
+/// Core Bridge guardian set account with index `index`.
+fn guardian_set_account(wormhole_program_key: &Pubkey, index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[query::SEED_PREFIX_GUARDIAN_SET, &index.to_be_bytes()],
+        wormhole_program_key,
+    )
+    .0
+}
+
+/// Sanity-check a signed VAA's header against the guardian set that signed
+/// it, if the caller passed its account (see [vaa::VaaHeader::check_guardian_set]).
+///
+/// The Executor is never asked for the guardian set; a VAA that fails here
+/// would only fail again when posted, so this just fails it earlier.
+fn check_guardian_set(
+    header: &vaa::VaaHeader,
+    wormhole_program_key: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let key = guardian_set_account(wormhole_program_key, header.guardian_set_index);
+    let Some(info) = accounts
+        .iter()
+        .find(|info| info.key == &key && info.owner == wormhole_program_key)
+    else {
+        return Ok(());
+    };
+
+    let guardian_set = GuardianSetData::deserialize(&mut &info.try_borrow_data()?[..])
+        .map_err(|_| ResolverError::InvalidGuardianSet)?;
+    header
+        .check_guardian_set(&guardian_set, Clock::get()?.unix_timestamp)
+        .map_err(|e| {
+            msg!(
+                "Will not execute: VAA fails guardian set {}",
+                header.guardian_set_index
+            );
+            e
+        })
+}
+
+/// Read Config, if the caller passed its account.
+///
+/// The Executor passes no accounts on its first call; the raw resolver then
+/// asks for Config via [Resolver::Missing], since the PDA salt and the
+/// resolver options live there.
+fn read_config(
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<Option<Config>> {
+    let Some(info) = accounts
+        .iter()
+        .find(|info| info.key == config_key && info.owner == program_id)
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(Config::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+}
+
+/// Address of the program's lookup table, if the caller passed its
+/// LookupTable record and the table has been created.
+fn read_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<Option<Pubkey>> {
+    let (record_key, _) = pda::lookup_table();
+    let Some(info) = accounts
+        .iter()
+        .find(|info| info.key == &record_key && info.owner == program_id)
+    else {
+        return Ok(None);
+    };
+
+    let record = LookupTable::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(Some(record.address))
+}
+
+/// PostedVAA account for `receive_greeting`: the Executor's placeholder, or
+/// with `config.resolver_derives_posted_vaa()` the Core Bridge PDA itself.
+///
+/// Only the account changes: no group posting the VAA is returned ahead of
+/// the receive group, since `verify_signatures` needs a new signature set
+/// keypair to sign and no resolver placeholder stands for one. Whoever
+/// relays for such an Executor posts the VAA first (e.g. with the Wormhole
+/// SDK), then executes.
+fn posted_vaa_account(
+    config: &Config,
+    wormhole_program_key: &Pubkey,
+    vaa_hash: &[u8; 32],
+) -> Pubkey {
+    if !config.resolver_derives_posted_vaa() {
+        return RESOLVER_PUBKEY_POSTED_VAA;
+    }
+    Pubkey::find_program_address(
+        &[wormhole::SEED_PREFIX_POSTED_VAA, vaa_hash],
+        wormhole_program_key,
+    )
+    .0
+}
+
+/// Ask the Executor to resolve again with `accounts` passed, plus the
+/// LookupTable record so the result can name the program's lookup table.
+fn missing_accounts(mut accounts: Vec<Pubkey>) -> Resolver<InstructionGroups> {
+    accounts.push(pda::lookup_table().0);
+    Resolver::Missing(MissingAccounts {
+        accounts,
+        address_lookup_tables: vec![],
+    })
+}
+
+/// Reject payloads `receive_greeting` would refuse for this peer.
+///
+/// Without the Peer account the payload mode is unknown, so only the checks
+/// shared by every mode (non-empty, within the size cap) apply.
+fn check_payload(payload: &[u8], peer: Option<&Peer>) -> Result<()> {
+    if peer.is_some_and(|peer| peer.payload_mode == PayloadMode::Raw && !peer.is_high_trust()) {
+        msg!("Will not execute: raw payloads need a high-trust peer");
+        return Err(ResolverError::PeerTrustTooLow.into());
+    }
+
+    let result = match peer.map(|peer| peer.payload_mode) {
+        Some(PayloadMode::Greeting) if decode_version_message(payload).is_some() => Ok(()),
+        Some(PayloadMode::Greeting) => decode_content(payload).map(|_| ()),
+        Some(PayloadMode::Raw) | None if payload.is_empty() || payload.len() > GREETING_MAX_LENGTH => {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "payload empty or too large",
+            ))
+        }
+        Some(PayloadMode::Raw) | None => Ok(()),
+    };
+
+    result.map_err(|e| {
+        msg!("Unsupported payload: {:?}", e);
+        ResolverError::PayloadUnsupported.into()
+    })
+}
+
+/// Solana wallet a greeting payload is addressed to (`@<pubkey> ...`).
+fn recipient(payload: &[u8]) -> Option<Pubkey> {
+    let greeting = String::from_utf8(decode_greeting_payload(payload).ok()?).ok()?;
+    crate::receipts::parse_recipient(&greeting)
+}
+
+/// The event authority and the program, which instructions need to emit
+/// events through CPI (see [crate::events]). None while events are only
+/// logged.
+fn event_cpi_accounts(config: &Config, program_id: &Pubkey) -> Vec<SerializableAccountMeta> {
+    if config.event_mode() == EventMode::Legacy {
+        return Vec::new();
     }
+    [events::event_authority().0, *program_id]
+        .map(|pubkey| SerializableAccountMeta {
+            pubkey,
+            is_signer: false,
+            is_writable: false,
+        })
+        .to_vec()
+}
 
-    let emitter_chain = u16::from_be_bytes(
-        vaa_body[8..10]
-            .try_into()
-            .map_err(|_| ProgramError::InvalidInstructionData)?,
-    );
+/// Serialize `result` for return data, refusing results over
+/// [MAX_RETURN_DATA].
+fn return_data(result: &Resolver<InstructionGroups>) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    result.serialize(&mut data)?;
+    if data.len() > MAX_RETURN_DATA {
+        msg!(
+            "Resolver result is {} bytes, over the {}-byte return data limit",
+            data.len(),
+            MAX_RETURN_DATA
+        );
+        return Err(ResolverError::ResolutionTooLarge.into());
+    }
 
-    let mut emitter_address = [0u8; 32];
-    emitter_address.copy_from_slice(&vaa_body[10..42]);
+    Ok(data)
+}
 
-    let sequence = u64::from_be_bytes(
-        vaa_body[42..50]
-            .try_into()
-            .map_err(|_| ProgramError::InvalidInstructionData)?,
-    );
+/// Refuse a resolved result with more instruction groups, or an instruction
+/// with more accounts, than config allows
+/// ([Config::resolver_max_groups], [Config::resolver_max_accounts]).
+///
+/// The Executor sends each group as a transaction; a result over the limits
+/// would fail there, after the Executor has spent on it.
+fn check_output_limits(config: &Config, result: &Resolver<InstructionGroups>) -> Result<()> {
+    let Resolver::Resolved(InstructionGroups(groups)) = result else {
+        return Ok(());
+    };
+    let accounts = groups
+        .iter()
+        .flat_map(|group| &group.instructions)
+        .map(|instruction| instruction.accounts.len())
+        .max()
+        .unwrap_or(0);
 
-    Ok((emitter_chain, emitter_address, sequence))
+    if groups.len() > config.resolver_max_groups() || accounts > config.resolver_max_accounts() {
+        msg!(
+            "Resolver result has {} instruction groups and up to {} accounts per instruction; limits {} and {}",
+            groups.len(),
+            accounts,
+            config.resolver_max_groups(),
+            config.resolver_max_accounts()
+        );
+        return Err(ResolverError::ResolverOutputTooLarge.into());
+    }
+
+    Ok(())
 }
 
 /// Handle resolver call via Anchor Context.
@@ -61,24 +319,27 @@ pub(crate) fn handle_resolve(
     let result = build_resolver_result(
         &crate::ID,
         &ctx.accounts.config.key(),
+        &ctx.accounts.config,
         &ctx.accounts.wormhole_program.key(),
         &ctx.accounts.system_program.key(),
         &vaa_body,
+        ctx.remaining_accounts,
     )?;
 
     // Also set as return data for the executor
-    let mut result_data = Vec::new();
-    result.serialize(&mut result_data)?;
-    set_return_data(&result_data);
+    set_return_data(&return_data(&result)?);
 
     Ok(result)
 }
 
 /// Handle resolver call via raw accounts (for fallback).
-/// The executor calls this with minimal/no accounts - we derive everything from program ID.
+/// The executor first calls this with no accounts; Config is requested via
+/// `Resolver::Missing` and everything else is derived from it, including
+/// the Core Bridge (`config.wormhole_program_id()`), so the same build
+/// resolves on localnet, devnet and mainnet.
 pub(crate) fn handle_resolve_raw<'info>(
     program_id: &Pubkey,
-    _accounts: &'info [AccountInfo<'info>],
+    accounts: &'info [AccountInfo<'info>],
     data: &[u8],
 ) -> Result<()> {
     msg!("handle_resolve_raw called");
@@ -86,78 +347,264 @@ pub(crate) fn handle_resolve_raw<'info>(
     // Parse vaa_body from Borsh-encoded data
     if data.len() < 4 {
         msg!("Data too short");
-        return Err(ProgramError::InvalidInstructionData.into());
+        return Err(ResolverError::ResolverDataTruncated.into());
     }
     let vaa_len = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
     if data.len() < 4 + vaa_len {
         msg!("VAA data truncated");
-        return Err(ProgramError::InvalidInstructionData.into());
+        return Err(ResolverError::ResolverDataTruncated.into());
     }
     let vaa_body = &data[4..4 + vaa_len];
 
-    // Derive all required PDAs from program ID - executor doesn't pass accounts
-    let (config_key, _) = Pubkey::find_program_address(&[Config::SEED_PREFIX], program_id);
-    
-    // Wormhole Core Bridge address (resolved via feature flags: solana-devnet, mainnet, etc.)
-    let wormhole_program_key = wormhole::program::ID;
+    let (config_key, _) = pda::config();
     let system_program_key = solana_program::system_program::ID;
 
-    let result = build_resolver_result(
-        program_id,
-        &config_key,
-        &wormhole_program_key,
-        &system_program_key,
-        vaa_body,
-    )?;
+    let result = match read_config(program_id, &config_key, accounts)? {
+        Some(config) => build_resolver_result(
+            program_id,
+            &config_key,
+            &config,
+            &config.wormhole_program_id(),
+            &system_program_key,
+            vaa_body,
+            accounts,
+        )?,
+        None => {
+            msg!("Config not passed; requesting it");
+            missing_accounts(vec![config_key])
+        }
+    };
 
     // Serialize and set as return data
-    let mut result_data = Vec::new();
-    result.serialize(&mut result_data)?;
+    let result_data = return_data(&result)?;
     msg!("Returning {} bytes", result_data.len());
     set_return_data(&result_data);
 
     Ok(())
 }
 
+/// Build the resolver result, refusing one over the configured output
+/// limits (see [check_output_limits]).
+fn build_resolver_result(
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    config: &Config,
+    wormhole_program_key: &Pubkey,
+    system_program_key: &Pubkey,
+    vaa_body: &[u8],
+    accounts: &[AccountInfo],
+) -> Result<Resolver<InstructionGroups>> {
+    let result = build_instructions(
+        program_id,
+        config_key,
+        config,
+        wormhole_program_key,
+        system_program_key,
+        vaa_body,
+        accounts,
+    )?;
+    check_output_limits(config, &result)?;
+
+    Ok(result)
+}
+
 /// Build the resolver result containing the instruction to execute.
 /// 
 /// Uses RESOLVER_PUBKEY_POSTED_VAA placeholder to tell the Executor to:
 /// 1. First post the VAA to the Wormhole Core Bridge
 /// 2. Replace the placeholder with the actual posted_vaa address
-fn build_resolver_result(
+///
+/// With `config.resolver_derives_posted_vaa()`, names the PostedVAA account
+/// instead, for Executors that do not post VAAs; it must then be posted
+/// before execution (see [posted_vaa_account]).
+///
+/// Peers targeting [ReceiveTarget::ReceiveGreetingV2] or
+/// [ReceiveTarget::ShadowV2] get `receive_greeting_v2` instead, followed by
+/// the recipient's inbox.
+///
+/// Peers targeting [ReceiveTarget::ReceiveGreetingShim] get
+/// `receive_greeting_shim` with the guardian set and shim signatures
+/// placeholders instead; the VAA body travels as instruction data, so long
+/// greetings can exceed [MAX_RETURN_DATA].
+///
+/// Token Bridge transfers addressed to [token_bridge::redeemer] get
+/// `receive_token_greeting`, whatever the peer's target.
+fn build_instructions(
     program_id: &Pubkey,
     config_key: &Pubkey,
+    config: &Config,
     wormhole_program_key: &Pubkey,
     system_program_key: &Pubkey,
     vaa_body: &[u8],
+    accounts: &[AccountInfo],
 ) -> Result<Resolver<InstructionGroups>> {
+    let (header, vaa_body) = vaa::strip_header(vaa_body)?;
+    if let Some(header) = &header {
+        check_guardian_set(header, wormhole_program_key, accounts)?;
+    }
+
     let vaa_hash = solana_program::keccak::hashv(&[vaa_body]).to_bytes();
-    let (emitter_chain, _emitter_address, sequence) = parse_vaa_body(vaa_body)?;
+    let vaa::VaaBody {
+        emitter_chain,
+        emitter_address,
+        sequence,
+        payload,
+        ..
+    } = parse_vaa_body(vaa_body)?;
     
     msg!("Building resolver for chain {} seq {}", emitter_chain, sequence);
 
+    if emitter_chain == 0 {
+        return Err(ResolverError::UnknownEmitterChain.into());
+    }
+
+    // Governance VAAs go to receive_governance, even while inbound messaging
+    // is paused so governance can resume it. The emitter is checked there.
+    if governance::is_governance(payload) {
+        return build_governance_result(
+            program_id,
+            config_key,
+            config,
+            wormhole_program_key,
+            system_program_key,
+            &vaa_hash,
+            payload,
+            accounts,
+        );
+    }
+
+    if config.inbound_paused {
+        msg!("Will not execute: inbound messaging is paused");
+        return Err(ResolverError::InboundPaused.into());
+    }
+
+    // Handshakes come from contracts that are not peers yet, so they go to
+    // receive_handshake without a Peer check.
+    if let Some((target_chain, _)) = decode_handshake(payload) {
+        if target_chain != config.chain_id {
+            msg!("Will not execute: handshake is for chain {}", target_chain);
+            return Err(ResolverError::PayloadUnsupported.into());
+        }
+        let (pending_peer, _) = pda::pending_peer(&config.salt, emitter_chain, &emitter_address);
+        return build_handshake_result(
+            program_id,
+            config_key,
+            config,
+            wormhole_program_key,
+            system_program_key,
+            &vaa_hash,
+            &pending_peer,
+            accounts,
+        );
+    }
+
     // Derive PDAs for peer and received (these are program-specific)
-    let (peer, _) = Pubkey::find_program_address(
-        &[Peer::SEED_PREFIX, &emitter_chain.to_le_bytes()],
-        program_id,
-    );
+    let (peer, _) = pda::peer(&config.salt, emitter_chain);
 
-    let (received, _) = Pubkey::find_program_address(
-        &[
-            Received::SEED_PREFIX,
-            &emitter_chain.to_le_bytes(),
-            &sequence.to_le_bytes(),
-        ],
-        program_id,
-    );
+    // A Token Bridge transfer to the redeemer carries a token greeting. The
+    // VAA's emitter is the Token Bridge; the peer is the transfer's sender.
+    if let Some(transfer) = token_bridge::parse_transfer_with_payload(payload)
+        .filter(|transfer| token_bridge::is_for_this_program(transfer))
+    {
+        check_peer(program_id, &peer, &transfer.from_address, accounts)?;
+        let (recipient, _) = decode_token_greeting(transfer.payload).map_err(|_| {
+            msg!("Will not execute: transfer carries no token greeting");
+            ResolverError::PayloadUnsupported
+        })?;
+        let mint = transfer.mint();
+        let [asset, authority] = token_bridge::asset_accounts(&mint, transfer.is_native());
+        let posted_vaa = posted_vaa_account(config, wormhole_program_key, &vaa_hash);
+        let claim = token_bridge::claim(emitter_chain, &emitter_address, sequence).0;
+        let foreign_endpoint = token_bridge::foreign_endpoint(emitter_chain, &emitter_address).0;
+
+        // The recipient's associated token account must already exist.
+        let mut instruction = SerializableInstruction {
+            program_id: *program_id,
+            accounts: [
+                (RESOLVER_PUBKEY_PAYER, true, true),
+                (*config_key, false, false),
+                (*wormhole_program_key, false, false),
+                (TokenBridge::id(), false, false),
+                (posted_vaa, false, false),
+                (peer, false, false),
+                (token_bridge::redeemer().0, false, false),
+                (mint, false, true),
+                (token_bridge::tmp_token_account(&mint).0, false, true),
+                (get_associated_token_address(&Pubkey::from(recipient), &mint), false, true),
+                (token_bridge::config().0, false, false),
+                (claim, false, true),
+                (foreign_endpoint, false, false),
+                (asset, false, true),
+                (authority, false, false),
+                (solana_program::sysvar::rent::ID, false, false),
+                (*system_program_key, false, false),
+                (anchor_spl::token::ID, false, false),
+            ]
+            .map(|(pubkey, is_signer, is_writable)| SerializableAccountMeta {
+                pubkey,
+                is_signer,
+                is_writable,
+            })
+            .to_vec(),
+            data: crate::instruction::ReceiveTokenGreeting { vaa_hash }.data(),
+        };
+        instruction.accounts.extend(event_cpi_accounts(config, program_id));
+        let address_lookup_tables = read_lookup_table(program_id, accounts)?.into_iter().collect();
+
+        return Ok(Resolver::Resolved(InstructionGroups(vec![InstructionGroup {
+            instructions: vec![instruction],
+            address_lookup_tables,
+        }])));
+    }
+
+    if config.resolver_requires_peer && !accounts.iter().any(|info| info.key == &peer) {
+        // Have the Executor pass the Peer, so check_peer can refuse an
+        // unknown emitter instead of resolving a doomed receive_greeting.
+        msg!("Peer for chain {} required; requesting it", emitter_chain);
+        return Ok(missing_accounts(vec![*config_key, peer]));
+    }
+    let peer_account = check_peer(program_id, &peer, &emitter_address, accounts)?;
+
+    let target = peer_account.as_ref().map(|peer| peer.receive_target).unwrap_or_default();
+
+    // Refuse to resolve payloads receive_greeting would reject anyway;
+    // receive_greeting_v2 records them as Failed instead.
+    if target != ReceiveTarget::ReceiveGreetingV2 {
+        check_payload(payload, peer_account.as_ref())?;
+    }
+
+    // Peers with a reply (or targeting it, see ReceiveTarget) get
+    // receive_and_reply, which posts the reply at the message slot after the
+    // emitter's current sequence. Without the Peer account (see
+    // `resolver_requires_peer`) neither is known here.
+    let reply_sequence = match &peer_account {
+        Some(account) if account.targets_reply(config.outbound_paused) => {
+            match accounts.iter().find(|info| info.key == &config.wormhole.sequence) {
+                Some(info) => Some(wormhole_cpi::next_sequence(info)?),
+                None => {
+                    msg!("Peer for chain {} replies; requesting sequence tracker", emitter_chain);
+                    return Ok(missing_accounts(vec![
+                        *config_key,
+                        peer,
+                        config.wormhole.sequence,
+                    ]));
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let (received, _) = pda::received(&config.salt, emitter_chain, sequence);
+    let (received_index, _) = pda::received_index(&vaa_hash);
 
     // Build the receive_greeting instruction
     // Use RESOLVER_PUBKEY_POSTED_VAA placeholder - Executor will:
     // 1. Post the VAA to Wormhole Core Bridge
     // 2. Replace placeholder with actual posted_vaa account address
+    let posted_vaa = posted_vaa_account(config, wormhole_program_key, &vaa_hash);
     let receive_data = crate::instruction::ReceiveGreeting { vaa_hash }.data();
 
-    let instruction = SerializableInstruction {
+    let mut instruction = SerializableInstruction {
         program_id: *program_id,
         accounts: vec![
             SerializableAccountMeta {
@@ -168,7 +615,7 @@ fn build_resolver_result(
             SerializableAccountMeta {
                 pubkey: *config_key,
                 is_signer: false,
-                is_writable: false,
+                is_writable: true,
             },
             SerializableAccountMeta {
                 pubkey: *wormhole_program_key,
@@ -176,21 +623,26 @@ fn build_resolver_result(
                 is_writable: false,
             },
             SerializableAccountMeta {
-                // Use placeholder - Executor will post VAA and replace with actual address
-                pubkey: RESOLVER_PUBKEY_POSTED_VAA,
+                // Placeholder unless derived - Executor will post VAA and replace it
+                pubkey: posted_vaa,
                 is_signer: false,
                 is_writable: false,
             },
             SerializableAccountMeta {
                 pubkey: peer,
                 is_signer: false,
-                is_writable: false,
+                is_writable: true,
             },
             SerializableAccountMeta {
                 pubkey: received,
                 is_signer: false,
                 is_writable: true,
             },
+            SerializableAccountMeta {
+                pubkey: received_index,
+                is_signer: false,
+                is_writable: true,
+            },
             SerializableAccountMeta {
                 pubkey: *system_program_key,
                 is_signer: false,
@@ -200,8 +652,382 @@ fn build_resolver_result(
         data: receive_data,
     };
 
+    // Peers targeting the verify VAA shim get receive_greeting_shim, which
+    // takes the body as data. The Executor posts the guardian signatures to
+    // the shim and substitutes both placeholders, so no PostedVAA is created.
+    if target == ReceiveTarget::ReceiveGreetingShim {
+        instruction.data = crate::instruction::ReceiveGreetingShim {
+            vaa_body: vaa_body.to_vec(),
+        }
+        .data();
+        instruction.accounts.splice(
+            3..4,
+            [
+                RESOLVER_PUBKEY_GUARDIAN_SET,
+                RESOLVER_PUBKEY_SHIM_VAA_SIGS,
+                VerifyVaaShim::id(),
+            ]
+            .map(|pubkey| SerializableAccountMeta {
+                pubkey,
+                is_signer: false,
+                is_writable: false,
+            }),
+        );
+    }
+
+    // Peers in the v2 rollout get receive_greeting_v2, with the inbox of the
+    // greeting's recipient (or none) after the system program.
+    if let ReceiveTarget::ReceiveGreetingV2 | ReceiveTarget::ShadowV2 = target {
+        let shadow = target == ReceiveTarget::ShadowV2;
+        instruction.data = crate::instruction::ReceiveGreetingV2 { vaa_hash, shadow }.data();
+        let inbox = recipient(payload).map(|recipient| pda::inbox(&recipient).0);
+        instruction.accounts.push(SerializableAccountMeta {
+            // The program ID stands for an omitted optional account.
+            pubkey: inbox.unwrap_or(*program_id),
+            is_signer: false,
+            is_writable: inbox.is_some(),
+        });
+    }
+
+    if let Some(vaa_sequence) = reply_sequence {
+        instruction.data = crate::instruction::ReceiveAndReply { vaa_hash }.data();
+        instruction.accounts.extend(
+            [
+                (config.wormhole.bridge, true),
+                (config.wormhole.fee_collector, true),
+                (pda::emitter().0, false),
+                (config.wormhole.sequence, true),
+                (pda::sent(vaa_sequence + 1).0, true),
+                (solana_program::sysvar::clock::ID, false),
+                (solana_program::sysvar::rent::ID, false),
+            ]
+            .map(|(pubkey, is_writable)| SerializableAccountMeta {
+                pubkey,
+                is_signer: false,
+                is_writable,
+            }),
+        );
+    }
+
+    // Greetings addressed to a Solana wallet carry the receipt accounts as
+    // remaining accounts of receive_greeting (or receive_and_reply).
+    #[cfg(feature = "receipts")]
+    if let Some(recipient) = recipient(payload) {
+        let [mint, token_account, token_program] =
+            crate::receipts::receipt_accounts(&recipient);
+        instruction.accounts.extend([
+            SerializableAccountMeta {
+                pubkey: mint,
+                is_signer: false,
+                is_writable: true,
+            },
+            SerializableAccountMeta {
+                pubkey: token_account,
+                is_signer: false,
+                is_writable: true,
+            },
+            SerializableAccountMeta {
+                pubkey: token_program,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]);
+    }
+
+    // Event-CPI accounts go last, after any receipt accounts.
+    instruction.accounts.extend(event_cpi_accounts(config, program_id));
+
+    // The program's lookup table holds config, the Wormhole program and the
+    // system program, shrinking the Executor's transaction.
+    let address_lookup_tables = read_lookup_table(program_id, accounts)?.into_iter().collect();
+
     Ok(Resolver::Resolved(InstructionGroups(vec![InstructionGroup {
         instructions: vec![instruction],
-        address_lookup_tables: vec![],
+        address_lookup_tables,
+    }])))
+}
+
+/// Build the `receive_governance` instruction for a governance VAA. The
+/// peer is only passed for a `RegisterPeer` action; otherwise the program ID
+/// stands in for the omitted account.
+#[allow(clippy::too_many_arguments)]
+fn build_governance_result(
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    config: &Config,
+    wormhole_program_key: &Pubkey,
+    system_program_key: &Pubkey,
+    vaa_hash: &[u8; 32],
+    payload: &[u8],
+    accounts: &[AccountInfo],
+) -> Result<Resolver<InstructionGroups>> {
+    let peer = match governance::parse_governance(payload) {
+        Some((_, GovernanceAction::RegisterPeer { chain, .. })) => pda::peer(&config.salt, chain).0,
+        Some(_) => *program_id,
+        None if governance::parse_register_all(payload).is_some() => {
+            msg!("Will not execute: RegisterAll broadcasts are applied in chunks with register_all");
+            return Err(ResolverError::PayloadUnsupported.into());
+        }
+        None => {
+            msg!("Will not execute: malformed governance payload");
+            return Err(ResolverError::PayloadUnsupported.into());
+        }
+    };
+    let posted_vaa = posted_vaa_account(config, wormhole_program_key, vaa_hash);
+
+    let mut instruction = SerializableInstruction {
+        program_id: *program_id,
+        accounts: [
+            (RESOLVER_PUBKEY_PAYER, true, true),
+            (*config_key, false, true),
+            (*wormhole_program_key, false, false),
+            (posted_vaa, false, false),
+            (pda::governance().0, false, true),
+            (peer, false, peer != *program_id),
+            (*system_program_key, false, false),
+        ]
+        .map(|(pubkey, is_signer, is_writable)| SerializableAccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        })
+        .to_vec(),
+        data: crate::instruction::ReceiveGovernance {
+            vaa_hash: *vaa_hash,
+        }
+        .data(),
+    };
+    instruction.accounts.extend(event_cpi_accounts(config, program_id));
+    let address_lookup_tables = read_lookup_table(program_id, accounts)?.into_iter().collect();
+
+    Ok(Resolver::Resolved(InstructionGroups(vec![InstructionGroup {
+        instructions: vec![instruction],
+        address_lookup_tables,
     }])))
 }
+
+/// Build `receive_handshake`, creating `pending_peer` for the VAA's
+/// emitter.
+#[allow(clippy::too_many_arguments)]
+fn build_handshake_result(
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    config: &Config,
+    wormhole_program_key: &Pubkey,
+    system_program_key: &Pubkey,
+    vaa_hash: &[u8; 32],
+    pending_peer: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<Resolver<InstructionGroups>> {
+    let posted_vaa = posted_vaa_account(config, wormhole_program_key, vaa_hash);
+
+    let mut instruction = SerializableInstruction {
+        program_id: *program_id,
+        accounts: [
+            (RESOLVER_PUBKEY_PAYER, true, true),
+            (*config_key, false, false),
+            (*wormhole_program_key, false, false),
+            (posted_vaa, false, false),
+            (*pending_peer, false, true),
+            (*system_program_key, false, false),
+        ]
+        .map(|(pubkey, is_signer, is_writable)| SerializableAccountMeta {
+            pubkey,
+            is_signer,
+            is_writable,
+        })
+        .to_vec(),
+        data: crate::instruction::ReceiveHandshake {
+            vaa_hash: *vaa_hash,
+        }
+        .data(),
+    };
+    instruction.accounts.extend(event_cpi_accounts(config, program_id));
+    let address_lookup_tables = read_lookup_table(program_id, accounts)?.into_iter().collect();
+
+    Ok(Resolver::Resolved(InstructionGroups(vec![InstructionGroup {
+        instructions: vec![instruction],
+        address_lookup_tables,
+    }])))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::error::Error;
+
+    fn resolved(accounts: usize, data_len: usize) -> Resolver<InstructionGroups> {
+        Resolver::Resolved(InstructionGroups(vec![InstructionGroup {
+            instructions: vec![SerializableInstruction {
+                program_id: crate::ID,
+                accounts: (0..accounts)
+                    .map(|_| SerializableAccountMeta {
+                        pubkey: Pubkey::new_unique(),
+                        is_signer: false,
+                        is_writable: false,
+                    })
+                    .collect(),
+                data: vec![0; data_len],
+            }],
+            address_lookup_tables: vec![],
+        }]))
+    }
+
+    #[test]
+    fn test_guardian_set_account() {
+        let wormhole_program = Pubkey::new_unique();
+        assert_eq!(
+            guardian_set_account(&wormhole_program, 4),
+            Pubkey::find_program_address(
+                &[b"GuardianSet".as_ref(), &[0, 0, 0, 4]],
+                &wormhole_program
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_posted_vaa_account() {
+        let mut config = Config::default();
+        let wormhole_program = Pubkey::new_unique();
+        let vaa_hash = [7; 32];
+        assert_eq!(
+            posted_vaa_account(&config, &wormhole_program, &vaa_hash),
+            RESOLVER_PUBKEY_POSTED_VAA
+        );
+
+        config.set_resolver_derives_posted_vaa(true);
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"PostedVAA".as_ref(), &vaa_hash],
+            &wormhole_program,
+        );
+        assert_eq!(
+            posted_vaa_account(&config, &wormhole_program, &vaa_hash),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_return_data_limit() {
+        let base = return_data(&resolved(0, 0)).unwrap().len();
+
+        let at_limit = return_data(&resolved(0, MAX_RETURN_DATA - base)).unwrap();
+        assert_eq!(at_limit.len(), MAX_RETURN_DATA);
+
+        assert_eq!(
+            return_data(&resolved(0, MAX_RETURN_DATA - base + 1)).unwrap_err(),
+            Error::from(ResolverError::ResolutionTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_output_limits() {
+        let mut config = Config::default();
+        assert!(check_output_limits(&config, &resolved(32, 0)).is_ok());
+        assert!(check_output_limits(&config, &missing_accounts(vec![])).is_ok());
+        assert_eq!(
+            check_output_limits(&config, &resolved(33, 0)).unwrap_err(),
+            Error::from(ResolverError::ResolverOutputTooLarge)
+        );
+
+        config.resolver_max_accounts = 16;
+        assert_eq!(
+            check_output_limits(&config, &resolved(17, 0)).unwrap_err(),
+            Error::from(ResolverError::ResolverOutputTooLarge)
+        );
+
+        let four = Resolver::Resolved(InstructionGroups(
+            (0..4)
+                .flat_map(|_| match resolved(1, 0) {
+                    Resolver::Resolved(InstructionGroups(groups)) => groups,
+                    _ => unreachable!(),
+                })
+                .collect(),
+        ));
+        config.resolver_max_groups = 4;
+        assert!(check_output_limits(&config, &four).is_ok());
+        config.resolver_max_groups = 3;
+        assert_eq!(
+            check_output_limits(&config, &four).unwrap_err(),
+            Error::from(ResolverError::ResolverOutputTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_receive_and_reply_fits() {
+        // receive_and_reply's 15 accounts plus the 3 receipt and 2 event-CPI
+        // accounts.
+        assert!(return_data(&resolved(15 + 3 + 2, 8 + 32)).is_ok());
+    }
+
+    #[test]
+    fn test_receive_and_reply_fits_with_lookup_table() {
+        let Resolver::Resolved(InstructionGroups(mut groups)) = resolved(15 + 3, 8 + 32) else {
+            unreachable!()
+        };
+        groups[0].address_lookup_tables.push(Pubkey::new_unique());
+        assert!(return_data(&Resolver::Resolved(InstructionGroups(groups))).is_ok());
+    }
+
+    #[test]
+    fn test_receive_greeting_shim_fits() {
+        use crate::message::HELLO_V2_HEADER_LEN;
+        use crate::vaa::VAA_BODY_HEADER_LENGTH;
+
+        // receive_greeting_shim's 10 accounts plus the 3 receipt accounts,
+        // with the VAA body as data: a 256-byte greeting fits, even with the
+        // lookup table, but a maximal one does not.
+        let data_len =
+            |greeting_len| 8 + 4 + VAA_BODY_HEADER_LENGTH + HELLO_V2_HEADER_LEN + greeting_len;
+        let Resolver::Resolved(InstructionGroups(mut groups)) = resolved(10 + 3, data_len(256))
+        else {
+            unreachable!()
+        };
+        groups[0].address_lookup_tables.push(Pubkey::new_unique());
+        assert!(return_data(&Resolver::Resolved(InstructionGroups(groups))).is_ok());
+
+        assert_eq!(
+            return_data(&resolved(10 + 3, data_len(GREETING_MAX_LENGTH))).unwrap_err(),
+            Error::from(ResolverError::ResolutionTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_receive_token_greeting_fits() {
+        // receive_token_greeting's 18 accounts, with the lookup table.
+        let Resolver::Resolved(InstructionGroups(mut groups)) = resolved(18, 8 + 32) else {
+            unreachable!()
+        };
+        groups[0].address_lookup_tables.push(Pubkey::new_unique());
+        assert!(return_data(&Resolver::Resolved(InstructionGroups(groups))).is_ok());
+    }
+
+    #[test]
+    fn test_event_cpi_accounts() {
+        let mut config = Config::default();
+        assert!(event_cpi_accounts(&config, &crate::ID).is_empty());
+
+        config.set_event_mode(EventMode::Dual);
+        let accounts = event_cpi_accounts(&config, &crate::ID);
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].pubkey, events::event_authority().0);
+        assert_eq!(accounts[1].pubkey, crate::ID);
+    }
+
+    #[test]
+    fn test_recipient() {
+        let wallet = Pubkey::new_unique();
+        let greeting = format!("@{wallet} gm");
+        assert_eq!(recipient(greeting.as_bytes()), Some(wallet));
+        assert_eq!(recipient(b"gm"), None);
+        assert_eq!(recipient(&[0xff, 0xfe]), None);
+    }
+
+    #[test]
+    fn test_receive_greeting_fits_with_headroom() {
+        // receive_greeting's 8 accounts plus the 3 receipt accounts, with
+        // room left for a few more.
+        let len = return_data(&resolved(8 + 3, 8 + 32)).unwrap().len();
+        assert!(len + 4 * 34 <= MAX_RETURN_DATA, "{len} bytes");
+    }
+}
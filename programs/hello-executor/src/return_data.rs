@@ -0,0 +1,138 @@
+//! Versioned envelope for values the program returns via `set_return_data`.
+//!
+//! Views (`find_request`, `find_received`, `check_sla`, `filter_received`,
+//! `encode_greeting`, `list_sequence_gaps`) return
+//!
+//! ```text
+//! magic(4) = "HEXR" | version(1) | borsh body
+//! ```
+//!
+//! so callers (CPI callers, simulating clients, third-party resolvers built
+//! on these views) can tell a Hello Executor result from other return data
+//! and refuse a layout they do not understand. `request_relay` returns its
+//! `RequestRelayResult` the same way.
+//!
+//! Compatibility rules for [RETURN_DATA_VERSION]:
+//! - Fields may be appended to a body without a version bump. [decode]
+//!   ignores trailing bytes, so older decoders keep working.
+//! - Removing, reordering or retyping a field bumps the version.
+//!
+//! Resolver results are the exception: the Executor decodes them as a bare
+//! `Resolver<InstructionGroups>`, so they are returned without an envelope.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use std::io;
+
+use crate::{error::HelloExecutorError, resolver::MAX_RETURN_DATA};
+
+/// Leading bytes of every enveloped return value.
+pub const RETURN_DATA_MAGIC: [u8; 4] = *b"HEXR";
+
+/// Current envelope version.
+pub const RETURN_DATA_VERSION: u8 = 1;
+
+/// Length of the envelope header (magic + version).
+pub const RETURN_DATA_HEADER_LEN: usize = RETURN_DATA_MAGIC.len() + 1;
+
+/// Wrap `value` in the envelope.
+pub fn encode<T: AnchorSerialize>(value: &T) -> io::Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(RETURN_DATA_HEADER_LEN);
+    data.extend_from_slice(&RETURN_DATA_MAGIC);
+    data.push(RETURN_DATA_VERSION);
+    value.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Unwrap an enveloped value. Fails on a foreign magic, an unsupported
+/// version or a truncated body; bytes after the body are ignored.
+pub fn decode<T: AnchorDeserialize>(data: &[u8]) -> io::Result<T> {
+    let Some(body) = data.strip_prefix(&RETURN_DATA_MAGIC[..]) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not Hello Executor return data",
+        ));
+    };
+    match body.split_first() {
+        Some((&RETURN_DATA_VERSION, mut body)) => T::deserialize(&mut body),
+        Some((version, _)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported return data version {version}"),
+        )),
+        None => Err(io::ErrorKind::UnexpectedEof.into()),
+    }
+}
+
+/// Set `value` as the instruction's return data, in the envelope.
+pub fn set<T: AnchorSerialize>(value: &T) -> Result<()> {
+    let data = encode(value)?;
+    if data.len() > MAX_RETURN_DATA {
+        msg!(
+            "Return data is {} bytes, over the {}-byte limit",
+            data.len(),
+            MAX_RETURN_DATA
+        );
+        return Err(HelloExecutorError::ReturnDataTooLarge.into());
+    }
+    set_return_data(&data);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::ReceivedIndex;
+
+    #[test]
+    fn test_round_trip() {
+        let data = encode(&true).unwrap();
+        assert_eq!(data, [b'H', b'E', b'X', b'R', 1, 1]);
+        assert!(decode::<bool>(&data).unwrap());
+
+        let keys = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        assert_eq!(decode::<Vec<Pubkey>>(&encode(&keys).unwrap()).unwrap(), keys);
+    }
+
+    #[test]
+    fn test_v1_layout_is_stable() {
+        // find_received's v1 result; changing these bytes breaks callers.
+        let index = ReceivedIndex {
+            chain: 10002,
+            sequence: 7,
+            bump: 254,
+        };
+        let mut expected = b"HEXR\x01".to_vec();
+        expected.extend_from_slice(&10002u16.to_le_bytes());
+        expected.extend_from_slice(&7u64.to_le_bytes());
+        expected.push(254);
+        assert_eq!(encode(&index).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_appended_fields_are_ignored() {
+        let mut data = encode(&ReceivedIndex::default()).unwrap();
+        data.extend_from_slice(&[9; 16]);
+        let index: ReceivedIndex = decode(&data).unwrap();
+        assert_eq!(index.sequence, 0);
+    }
+
+    #[test]
+    fn test_rejects_foreign_data() {
+        // A bare borsh value (e.g. pre-envelope return data).
+        assert!(decode::<bool>(&[1]).is_err());
+        assert!(decode::<bool>(b"HEXR").is_err());
+        assert!(decode::<bool>(b"HEXR\x02\x01").is_err());
+        assert!(decode::<u64>(b"HEXR\x01\x01").is_err());
+    }
+
+    #[test]
+    fn test_set_refuses_oversized_values() {
+        let fits = vec![0u8; MAX_RETURN_DATA - RETURN_DATA_HEADER_LEN - 4];
+        assert_eq!(encode(&fits).unwrap().len(), MAX_RETURN_DATA);
+        let too_big = vec![0u8; fits.len() + 1];
+        assert_eq!(
+            set(&too_big).unwrap_err(),
+            Error::from(HelloExecutorError::ReturnDataTooLarge)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Signature verification through the native precompiles.
+//!
+//! The Ed25519 and Secp256k1 programs verify signatures before the
+//! transaction executes and fail it if any is invalid, at no compute cost to
+//! the caller. A program that relies on this loads the precompile
+//! instruction from the instructions sysvar and checks that it verified the
+//! expected key and message — the introspection helpers here.
+//!
+//! Only self-contained precompile instructions are accepted: signature, key
+//! and message must all live in the precompile instruction's own data, so
+//! the checked bytes are exactly the bytes that were verified.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program, instruction::Instruction, secp256k1_program,
+    sysvar::instructions::load_instruction_at_checked,
+};
+
+use crate::error::HelloExecutorError;
+
+/// Ed25519 precompile marker for "this instruction" in offset indices.
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Size of one Ed25519 signature offsets entry.
+const ED25519_OFFSETS_LENGTH: usize = 14;
+
+/// Size of one Secp256k1 signature offsets entry.
+const SECP256K1_OFFSETS_LENGTH: usize = 11;
+
+/// Load the instruction at `index` of the current transaction.
+pub fn load_instruction(instructions_sysvar: &AccountInfo, index: usize) -> Result<Instruction> {
+    load_instruction_at_checked(index, instructions_sysvar)
+        .map_err(|_| error!(HelloExecutorError::InvalidSignatureInstruction))
+}
+
+/// Check that `ix` is an Ed25519 precompile instruction that verified
+/// `signature` by `pubkey` over `message`.
+pub fn verify_ed25519(
+    ix: &Instruction,
+    pubkey: &[u8; 32],
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        HelloExecutorError::InvalidSignatureInstruction
+    );
+    let offsets = single_entry(&ix.data, 2, ED25519_OFFSETS_LENGTH)?;
+
+    let u16_at = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+    let (signature_offset, signature_ix) = (u16_at(0), u16_at(2));
+    let (pubkey_offset, pubkey_ix) = (u16_at(4), u16_at(6));
+    let (message_offset, message_size, message_ix) = (u16_at(8), u16_at(10), u16_at(12));
+
+    require!(
+        [signature_ix, pubkey_ix, message_ix]
+            .iter()
+            .all(|&index| index == ED25519_CURRENT_INSTRUCTION),
+        HelloExecutorError::InvalidSignatureInstruction,
+    );
+    require!(
+        usize::from(message_size) == message.len()
+            && slice_at(&ix.data, signature_offset, 64)? == signature
+            && slice_at(&ix.data, pubkey_offset, 32)? == pubkey
+            && slice_at(&ix.data, message_offset, message.len())? == message,
+        HelloExecutorError::InvalidSignatureInstruction,
+    );
+
+    Ok(())
+}
+
+/// Check that `ix`, found at `ix_index` in the transaction, is a Secp256k1
+/// precompile instruction that verified a signature by `eth_address` over
+/// `message`. The precompile hashes `message` with keccak256 itself.
+pub fn verify_secp256k1(
+    ix: &Instruction,
+    ix_index: u8,
+    eth_address: &[u8; 20],
+    message: &[u8],
+) -> Result<()> {
+    require_keys_eq!(
+        ix.program_id,
+        secp256k1_program::ID,
+        HelloExecutorError::InvalidSignatureInstruction
+    );
+    let offsets = single_entry(&ix.data, 1, SECP256K1_OFFSETS_LENGTH)?;
+
+    let u16_at = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+    let signature_ix = offsets[2];
+    let (address_offset, address_ix) = (u16_at(3), offsets[5]);
+    let (message_offset, message_size, message_ix) = (u16_at(6), u16_at(8), offsets[10]);
+
+    require!(
+        [signature_ix, address_ix, message_ix]
+            .iter()
+            .all(|&index| index == ix_index),
+        HelloExecutorError::InvalidSignatureInstruction,
+    );
+    require!(
+        usize::from(message_size) == message.len()
+            && slice_at(&ix.data, address_offset, 20)? == eth_address
+            && slice_at(&ix.data, message_offset, message.len())? == message,
+        HelloExecutorError::InvalidSignatureInstruction,
+    );
+
+    Ok(())
+}
+
+/// Return the offsets entry of a precompile instruction carrying exactly one
+/// signature. `header_len` is the size of the count header before entries.
+fn single_entry(data: &[u8], header_len: usize, entry_len: usize) -> Result<&[u8]> {
+    require!(
+        data.first() == Some(&1) && data.len() >= header_len + entry_len,
+        HelloExecutorError::InvalidSignatureInstruction,
+    );
+    Ok(&data[header_len..header_len + entry_len])
+}
+
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = usize::from(offset);
+    data.get(start..start + len)
+        .ok_or_else(|| error!(HelloExecutorError::InvalidSignatureInstruction))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ed25519_ix(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Instruction {
+        // header(2) | offsets(14) | pubkey(32) | signature(64) | message
+        let pubkey_offset = 2 + ED25519_OFFSETS_LENGTH as u16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey);
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn secp256k1_ix(ix_index: u8, eth_address: &[u8; 20], message: &[u8]) -> Instruction {
+        // header(1) | offsets(11) | address(20) | signature(64) | recovery(1) | message
+        let address_offset = 1 + SECP256K1_OFFSETS_LENGTH as u16;
+        let signature_offset = address_offset + 20;
+        let message_offset = signature_offset + 65;
+
+        let mut data = vec![1];
+        data.extend_from_slice(&signature_offset.to_le_bytes());
+        data.push(ix_index);
+        data.extend_from_slice(&address_offset.to_le_bytes());
+        data.push(ix_index);
+        data.extend_from_slice(&message_offset.to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.push(ix_index);
+        data.extend_from_slice(eth_address);
+        data.extend_from_slice(&[0u8; 65]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: secp256k1_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_verify_ed25519() {
+        let ix = ed25519_ix(&[1; 32], b"quote", &[2; 64]);
+
+        assert!(verify_ed25519(&ix, &[1; 32], b"quote", &[2; 64]).is_ok());
+        assert!(verify_ed25519(&ix, &[3; 32], b"quote", &[2; 64]).is_err());
+        assert!(verify_ed25519(&ix, &[1; 32], b"other", &[2; 64]).is_err());
+        assert!(verify_ed25519(&ix, &[1; 32], b"quote", &[3; 64]).is_err());
+
+        let mut wrong_program = ix.clone();
+        wrong_program.program_id = secp256k1_program::ID;
+        assert!(verify_ed25519(&wrong_program, &[1; 32], b"quote", &[2; 64]).is_err());
+
+        // Data referenced from another instruction is not accepted.
+        let mut external = ix;
+        external.data[4..6].copy_from_slice(&0u16.to_le_bytes());
+        assert!(verify_ed25519(&external, &[1; 32], b"quote", &[2; 64]).is_err());
+    }
+
+    #[test]
+    fn test_verify_secp256k1() {
+        let ix = secp256k1_ix(0, &[5; 20], b"quote");
+
+        assert!(verify_secp256k1(&ix, 0, &[5; 20], b"quote").is_ok());
+        assert!(verify_secp256k1(&ix, 1, &[5; 20], b"quote").is_err());
+        assert!(verify_secp256k1(&ix, 0, &[6; 20], b"quote").is_err());
+        assert!(verify_secp256k1(&ix, 0, &[5; 20], b"quote!").is_err());
+
+        let mut two_signatures = ix;
+        two_signatures.data[0] = 2;
+        assert!(verify_secp256k1(&two_signatures, 0, &[5; 20], b"quote").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+/// A sensitive admin change that must wait out `config.timelock_seconds`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdminAction {
+    /// Change the address of an already-registered peer.
+    UpdatePeer {
+        /// Wormhole chain ID of the peer.
+        chain: u16,
+        /// New universal address of the peer contract.
+        address: [u8; 32],
+    },
+    /// Change the timelock delay itself.
+    SetTimelock {
+        /// New delay in seconds.
+        seconds: u32,
+    },
+}
+
+impl AdminAction {
+    /// Largest serialized variant: tag + chain + address.
+    pub const MAXIMUM_SIZE: usize = 1 + 2 + 32;
+}
+
+impl Default for AdminAction {
+    fn default() -> Self {
+        AdminAction::SetTimelock { seconds: 0 }
+    }
+}
+
+/// Admin action waiting for its timelock to elapse.
+#[account]
+#[derive(Default)]
+pub struct PendingAdminAction {
+    /// Sequential ID, from `config.next_admin_action_id`.
+    pub id: u64,
+    /// The queued change.
+    pub action: AdminAction,
+    /// When the action was queued.
+    pub queued_at: i64,
+    /// Earliest time the action may execute.
+    pub executable_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl PendingAdminAction {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // id
+        + AdminAction::MAXIMUM_SIZE // action
+        + 8 // queued_at
+        + 8 // executable_at
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving PendingAdminAction PDAs.
+    pub const SEED_PREFIX: &'static [u8; 12] = b"admin_action";
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/// Request to deliver a message a peer published but that never arrived,
+/// created by `request_backfill` from the peer's sequence gaps.
+///
+/// A keeper (or anyone) looks up the VAA for `(chain, emitter_address,
+/// sequence)` and delivers it with `receive_greeting`; the request is done
+/// once the `Received` record for the same `(chain, sequence)` exists.
+#[account]
+#[derive(Default)]
+pub struct BackfillRequest {
+    /// Wormhole chain ID of the peer.
+    pub chain: u16,
+    /// Peer's emitter address, for looking up the VAA.
+    pub emitter_address: [u8; 32],
+    /// Sequence of the missing message.
+    pub sequence: u64,
+    /// Wallet that requested (and paid for) the backfill.
+    pub requested_by: Pubkey,
+    /// Unix timestamp of the request.
+    pub requested_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl BackfillRequest {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // chain
+        + 32 // emitter_address
+        + 8 // sequence
+        + 32 // requested_by
+        + 8 // requested_at
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving BackfillRequest PDAs.
+    pub const SEED_PREFIX: &'static [u8; 8] = b"backfill";
+
+    /// Most sequences one `request_backfill` call may cover.
+    pub const MAX_BATCH: u64 = 16;
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::executor_cpi::ExecutorProgram;
 
 /// Wormhole program related addresses stored in config.
 #[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -15,9 +18,20 @@ impl WormholeAddresses {
     pub const LEN: usize = 32 + 32 + 32; // bridge + fee_collector + sequence
 }
 
+/// Where events are emitted; see [crate::events].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventMode {
+    /// `emit!` log lines only.
+    #[default]
+    Legacy,
+    /// Both log lines and event-CPI, for the migration window.
+    Dual,
+    /// Event-CPI only, once indexers have migrated.
+    Cpi,
+}
+
 /// Program configuration account.
 #[account]
-#[derive(Default)]
 pub struct Config {
     /// Program's owner (can register peers).
     pub owner: Pubkey,
@@ -30,17 +44,505 @@ pub struct Config {
     /// Consistency level for posted messages.
     /// u8 representation of [Finality](wormhole_anchor_sdk::wormhole::Finality).
     pub finality: u8,
+    /// PDA bump seed.
+    pub bump: u8,
+    /// EVM addresses allowed to sign Executor quotes. Zero entries are
+    /// unused; with no signers set, quotes are not checked.
+    pub quote_signers: [[u8; 20]; 4],
+    /// Delay before a queued admin action may execute. While zero, peer
+    /// address changes apply immediately.
+    pub timelock_seconds: u32,
+    /// ID assigned to the next queued admin action.
+    pub next_admin_action_id: u64,
+    /// Lamports a payer must keep above rent-exemption after paying fees.
+    pub payer_buffer_lamports: u64,
+    /// Extra seed for Peer and Received PDAs, so forks deployed from this
+    /// template with the same seeds do not share addresses. Zero means
+    /// unsalted. Set once at initialize.
+    pub salt: [u8; 8],
+    /// Key allowed to attest moderation verdicts on received messages.
+    /// Zero (the default) means moderation is disabled.
+    pub moderation_oracle: Pubkey,
+    /// Have the resolver require the emitter's Peer account, refusing to
+    /// resolve VAAs from unregistered emitters.
+    pub resolver_requires_peer: bool,
+    /// Have `request_relay` refuse an `exec_amount` below the estimated
+    /// minimum for the relay, instead of only logging the shortfall. Set
+    /// by `initialize`.
+    pub enforce_min_exec_amount: bool,
+    /// Delivery SLA: a relay not delivered within this many seconds of its
+    /// request is reported as breached. Zero disables SLA tracking.
+    pub sla_seconds: u32,
+    /// Key allowed to report relay deliveries. Zero (the default) means no
+    /// deliveries are reported.
+    pub delivery_oracle: Pubkey,
+    /// Proposed new owner, set by `transfer_ownership` until it accepts
+    /// with `accept_ownership`. Zero (the default) means none is pending.
+    ///
+    /// A plain `Pubkey` rather than an `Option<Pubkey>`: an `Option` would
+    /// make the serialized config one or 33 bytes long here, shifting every
+    /// later field. `transfer_ownership` still takes an `Option` and
+    /// refuses the zero key.
+    pub pending_owner: Pubkey,
+    /// Emergency stop for `send_greeting`, `publish_payload` and
+    /// `request_relay`.
+    pub outbound_paused: bool,
+    /// Emergency stop for `receive_greeting`.
+    pub inbound_paused: bool,
+    /// Greetings longer than this many bytes are held as
+    /// `PendingTranslation` until the moderation oracle submits a
+    /// translation. Zero disables translation.
+    pub translate_above_len: u16,
+    /// Greetings a wallet may send per quota epoch. Zero disables the quota.
+    /// The owner and wallets on the bypass list are exempt.
+    pub send_quota: u32,
+    /// Length of a quota epoch in seconds.
+    pub quota_epoch_seconds: u32,
+    /// Greetings published by the program, including automatic replies.
+    pub sent_count: u64,
+    /// Messages received from all peers, delivered or not.
+    pub received_count: u64,
+    /// Wormhole sequence of the latest greeting published.
+    pub last_sent_sequence: u64,
+    /// Omit greeting and payload contents from events and logs, which then
+    /// carry only their keccak256 hash and length.
+    pub redact_events: bool,
+    /// Do not store received messages in `Received` records. Records held
+    /// for translation keep the greeting until it is translated, since the
+    /// moderation oracle reads it from there.
+    pub redact_storage: bool,
+    /// Greetings a wallet may send in a burst (its `RateLimit` bucket
+    /// size). Zero disables rate limiting. The owner and wallets on the
+    /// bypass list are exempt.
+    pub rate_limit_capacity: u16,
+    /// Seconds for a wallet's bucket to regain one greeting.
+    pub rate_limit_refill_seconds: u32,
+    /// Bit flags for [Config::resolver_derives_posted_vaa],
+    /// [Config::event_mode] and [Config::ignore_duplicate_deliveries]. The
+    /// first byte of the reserved region until it was named, so existing
+    /// accounts keep their flags.
+    pub flags: u8,
+    /// Most instruction groups the resolver may return; zero means
+    /// [Config::DEFAULT_RESOLVER_MAX_GROUPS]. Set by `set_resolver_limits`.
+    pub resolver_max_groups: u8,
+    /// Most accounts one resolved instruction may name; zero means
+    /// [Config::DEFAULT_RESOLVER_MAX_ACCOUNTS].
+    pub resolver_max_accounts: u8,
+    /// Wormhole Core Bridge the resolver names in the instructions it
+    /// returns; zero means the one the program was built for. Set by
+    /// `update_wormhole_config`.
+    pub wormhole_program: Pubkey,
+    /// Executor program relays are requested from; zero means
+    /// [ExecutorProgram]'s built-in ID. Set by `set_executor_program`.
+    pub executor_program: Pubkey,
+    /// Zeroed headroom for future fields, so they can be added without
+    /// reallocating already-initialized deployments. See
+    /// [Config::RESERVED_LEN].
+    pub reserved: [u8; 191],
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            owner: Pubkey::default(),
+            chain_id: 0,
+            wormhole: WormholeAddresses::default(),
+            batch_id: 0,
+            finality: 0,
+            bump: 0,
+            quote_signers: [[0; 20]; Config::MAX_QUOTE_SIGNERS],
+            timelock_seconds: 0,
+            next_admin_action_id: 0,
+            payer_buffer_lamports: 0,
+            salt: [0; 8],
+            moderation_oracle: Pubkey::default(),
+            resolver_requires_peer: false,
+            enforce_min_exec_amount: false,
+            sla_seconds: 0,
+            delivery_oracle: Pubkey::default(),
+            pending_owner: Pubkey::default(),
+            outbound_paused: false,
+            inbound_paused: false,
+            translate_above_len: 0,
+            send_quota: 0,
+            quota_epoch_seconds: 0,
+            sent_count: 0,
+            received_count: 0,
+            last_sent_sequence: 0,
+            redact_events: false,
+            redact_storage: false,
+            rate_limit_capacity: 0,
+            rate_limit_refill_seconds: 0,
+            flags: 0,
+            resolver_max_groups: 0,
+            resolver_max_accounts: 0,
+            wormhole_program: Pubkey::default(),
+            executor_program: Pubkey::default(),
+            reserved: [0; Config::RESERVED_LEN],
+        }
+    }
 }
 
 impl Config {
+    /// Size of [Config::reserved].
+    ///
+    /// A new field is added by declaring it before `reserved` and shrinking
+    /// both this and the `reserved` array literal (kept literal for the IDL)
+    /// by the field's serialized size, so `MAXIMUM_SIZE` never changes.
+    /// Existing accounts hold zeroes there, so the field must treat zero as
+    /// "unset" (or be read through [Config::reserved_field] with a default).
+    pub const RESERVED_LEN: usize = 191;
+
+    /// Capacity of [Config::quote_signers].
+    pub const MAX_QUOTE_SIGNERS: usize = 4;
+
+    /// Instruction groups the resolver may return by default. Each group is
+    /// a transaction the Executor sends.
+    pub const DEFAULT_RESOLVER_MAX_GROUPS: u8 = 2;
+
+    /// Accounts a resolved instruction may name by default: what fits in a
+    /// legacy transaction next to the Executor's own accounts, without a
+    /// lookup table.
+    pub const DEFAULT_RESOLVER_MAX_ACCOUNTS: u8 = 32;
+
+    /// Largest `resolver_max_accounts`: Solana's limit on the accounts one
+    /// transaction may lock.
+    pub const RESOLVER_MAX_ACCOUNTS_LIMIT: u8 = 64;
+
     pub const MAXIMUM_SIZE: usize = 8 // discriminator
         + 32 // owner
         + 2 // chain_id
         + WormholeAddresses::LEN // wormhole addresses
         + 4 // batch_id
         + 1 // finality
+        + 1 // bump
+        + 20 * Config::MAX_QUOTE_SIGNERS // quote_signers
+        + 4 // timelock_seconds
+        + 8 // next_admin_action_id
+        + 8 // payer_buffer_lamports
+        + 8 // salt
+        + 32 // moderation_oracle
+        + 1 // resolver_requires_peer
+        + 1 // enforce_min_exec_amount
+        + 4 // sla_seconds
+        + 32 // delivery_oracle
+        + 32 // pending_owner
+        + 1 // outbound_paused
+        + 1 // inbound_paused
+        + 2 // translate_above_len
+        + 4 // send_quota
+        + 4 // quota_epoch_seconds
+        + 8 // sent_count
+        + 8 // received_count
+        + 8 // last_sent_sequence
+        + 1 // redact_events
+        + 1 // redact_storage
+        + 2 // rate_limit_capacity
+        + 4 // rate_limit_refill_seconds
+        + 1 // flags
+        + 1 // resolver_max_groups
+        + 1 // resolver_max_accounts
+        + 32 // wormhole_program
+        + 32 // executor_program
+        + Config::RESERVED_LEN // reserved
     ;
 
     /// Seed prefix for deriving the Config PDA.
     pub const SEED_PREFIX: &'static [u8; 6] = b"config";
+
+    /// Salt as used in Peer and Received seeds (empty when unset).
+    pub fn salt_seed(&self) -> &[u8] {
+        crate::pda::salt_seed(&self.salt)
+    }
+
+    /// ID of the Executor program relays are requested from.
+    pub fn executor_program_id(&self) -> Pubkey {
+        if self.executor_program == Pubkey::default() {
+            ExecutorProgram::id()
+        } else {
+            self.executor_program
+        }
+    }
+
+    /// ID of the Wormhole Core Bridge the resolver resolves against.
+    pub fn wormhole_program_id(&self) -> Pubkey {
+        if self.wormhole_program == Pubkey::default() {
+            wormhole::program::ID
+        } else {
+            self.wormhole_program
+        }
+    }
+
+    /// Most instruction groups the resolver may return.
+    pub fn resolver_max_groups(&self) -> usize {
+        usize::from(match self.resolver_max_groups {
+            0 => Config::DEFAULT_RESOLVER_MAX_GROUPS,
+            max => max,
+        })
+    }
+
+    /// Most accounts one resolved instruction may name.
+    pub fn resolver_max_accounts(&self) -> usize {
+        usize::from(match self.resolver_max_accounts {
+            0 => Config::DEFAULT_RESOLVER_MAX_ACCOUNTS,
+            max => max,
+        })
+    }
+
+    /// Whether quote signers are configured, i.e. quotes must be checked.
+    pub fn checks_quotes(&self) -> bool {
+        self.quote_signers.iter().any(|signer| *signer != [0; 20])
+    }
+
+    /// Whether `signer` is an allowed quote signer.
+    pub fn is_quote_signer(&self, signer: &[u8; 20]) -> bool {
+        *signer != [0; 20] && self.quote_signers.contains(signer)
+    }
+
+    /// Quota epoch containing unix time `now`. Always zero while
+    /// `quota_epoch_seconds` is unset.
+    pub fn quota_epoch(&self, now: i64) -> u64 {
+        match self.quota_epoch_seconds {
+            0 => 0,
+            seconds => (now.max(0) as u64) / u64::from(seconds),
+        }
+    }
+
+    /// `content` as it may appear in an event: the default (empty) value
+    /// while `redact_events` is set.
+    pub fn event_content<T: Default>(&self, content: T) -> T {
+        if self.redact_events {
+            T::default()
+        } else {
+            content
+        }
+    }
+
+    /// Message bytes to store in a `Received` record: none while
+    /// `redact_storage` is set.
+    pub fn stored_message(&self, message: &[u8]) -> Vec<u8> {
+        if self.redact_storage {
+            Vec::new()
+        } else {
+            message.to_vec()
+        }
+    }
+
+    /// Whether the resolver names the Wormhole PostedVAA account itself
+    /// instead of the Executor's `RESOLVER_PUBKEY_POSTED_VAA` placeholder.
+    /// Kept in [Config::flags] (bit 0).
+    pub fn resolver_derives_posted_vaa(&self) -> bool {
+        self.flags & Config::RESOLVER_DERIVES_POSTED_VAA_FLAG != 0
+    }
+
+    /// Set [Config::resolver_derives_posted_vaa].
+    pub fn set_resolver_derives_posted_vaa(&mut self, enabled: bool) {
+        let flags = self.flags & !Config::RESOLVER_DERIVES_POSTED_VAA_FLAG;
+        let flag = if enabled {
+            Config::RESOLVER_DERIVES_POSTED_VAA_FLAG
+        } else {
+            0
+        };
+        self.flags = flags | flag;
+    }
+
+    /// Where events are emitted. Kept in [Config::flags] (bits 1-2); zero,
+    /// as in existing accounts, is [EventMode::Legacy].
+    pub fn event_mode(&self) -> EventMode {
+        match (self.flags & Config::EVENT_MODE_MASK) >> Config::EVENT_MODE_SHIFT {
+            1 => EventMode::Dual,
+            2 => EventMode::Cpi,
+            _ => EventMode::Legacy,
+        }
+    }
+
+    /// Set [Config::event_mode].
+    pub fn set_event_mode(&mut self, mode: EventMode) {
+        let bits = match mode {
+            EventMode::Legacy => 0,
+            EventMode::Dual => 1,
+            EventMode::Cpi => 2,
+        };
+        let flags = self.flags & !Config::EVENT_MODE_MASK;
+        self.flags = flags | (bits << Config::EVENT_MODE_SHIFT);
+    }
+
+    /// Whether a message delivered again after it was received (e.g. by a
+    /// self-relay racing the Executor) is ignored with a
+    /// `DuplicateDeliveryIgnored` event instead of reverting. Kept in
+    /// [Config::flags] (bit 3).
+    pub fn ignore_duplicate_deliveries(&self) -> bool {
+        self.flags & Config::IGNORE_DUPLICATE_DELIVERIES_FLAG != 0
+    }
+
+    /// Set [Config::ignore_duplicate_deliveries].
+    pub fn set_ignore_duplicate_deliveries(&mut self, enabled: bool) {
+        let flags = self.flags & !Config::IGNORE_DUPLICATE_DELIVERIES_FLAG;
+        let flag = if enabled {
+            Config::IGNORE_DUPLICATE_DELIVERIES_FLAG
+        } else {
+            0
+        };
+        self.flags = flags | flag;
+    }
+
+    /// Flag bit of [Config::resolver_derives_posted_vaa]. Bit 0, so accounts
+    /// that stored it as a whole byte read the same.
+    const RESOLVER_DERIVES_POSTED_VAA_FLAG: u8 = 0b001;
+
+    /// Flag bits of [Config::event_mode].
+    const EVENT_MODE_MASK: u8 = 0b110;
+    const EVENT_MODE_SHIFT: u8 = 1;
+
+    /// Flag bit of [Config::ignore_duplicate_deliveries].
+    const IGNORE_DUPLICATE_DELIVERIES_FLAG: u8 = 0b1000;
+
+    /// Read `N` bytes at `offset` in the reserved region.
+    ///
+    /// Lets a feature store a small value without a layout change before it
+    /// graduates to a named field.
+    pub fn reserved_field<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.reserved[offset..offset + N].try_into().unwrap()
+    }
+
+    /// Write `N` bytes at `offset` in the reserved region.
+    pub fn set_reserved_field<const N: usize>(&mut self, offset: usize, value: [u8; N]) {
+        self.reserved[offset..offset + N].copy_from_slice(&value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_serialized_size_matches_maximum_size() {
+        let mut data = Vec::new();
+        Config::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Config::MAXIMUM_SIZE);
+    }
+
+    #[test]
+    fn test_redaction() {
+        let mut config = Config::default();
+        assert_eq!(config.event_content("gm".to_string()), "gm");
+        assert_eq!(config.stored_message(b"gm"), b"gm");
+
+        config.redact_events = true;
+        assert_eq!(config.event_content("gm".to_string()), "");
+        assert_eq!(config.event_content(b"gm".to_vec()), b"");
+        assert_eq!(config.stored_message(b"gm"), b"gm");
+
+        config.redact_storage = true;
+        assert!(config.stored_message(b"gm").is_empty());
+    }
+
+    #[test]
+    fn test_resolver_derives_posted_vaa() {
+        let mut config = Config::default();
+        assert!(!config.resolver_derives_posted_vaa());
+        config.set_resolver_derives_posted_vaa(true);
+        assert!(config.resolver_derives_posted_vaa());
+        assert_eq!(config.flags, 1);
+        config.set_resolver_derives_posted_vaa(false);
+        assert!(!config.resolver_derives_posted_vaa());
+    }
+
+    #[test]
+    fn test_event_mode() {
+        let mut config = Config::default();
+        assert_eq!(config.event_mode(), EventMode::Legacy);
+
+        // Shares the flags byte with resolver_derives_posted_vaa.
+        config.set_resolver_derives_posted_vaa(true);
+        for mode in [EventMode::Dual, EventMode::Cpi, EventMode::Legacy] {
+            config.set_event_mode(mode);
+            assert_eq!(config.event_mode(), mode);
+            assert!(config.resolver_derives_posted_vaa());
+        }
+        config.set_event_mode(EventMode::Cpi);
+        config.set_resolver_derives_posted_vaa(false);
+        assert_eq!(config.event_mode(), EventMode::Cpi);
+        assert_eq!(config.flags, 0b100);
+    }
+
+    #[test]
+    fn test_ignore_duplicate_deliveries() {
+        let mut config = Config::default();
+        assert!(!config.ignore_duplicate_deliveries());
+
+        config.set_event_mode(EventMode::Cpi);
+        config.set_ignore_duplicate_deliveries(true);
+        assert!(config.ignore_duplicate_deliveries());
+        assert_eq!(config.event_mode(), EventMode::Cpi);
+        assert_eq!(config.flags, 0b1100);
+
+        config.set_ignore_duplicate_deliveries(false);
+        assert!(!config.ignore_duplicate_deliveries());
+        assert_eq!(config.event_mode(), EventMode::Cpi);
+    }
+
+    #[test]
+    fn test_executor_program_id() {
+        let mut config = Config::default();
+        assert_eq!(config.executor_program_id(), ExecutorProgram::id());
+
+        let executor = Pubkey::new_unique();
+        config.executor_program = executor;
+        assert_eq!(config.executor_program_id(), executor);
+    }
+
+    #[test]
+    fn test_wormhole_program_id() {
+        let mut config = Config::default();
+        assert_eq!(config.wormhole_program_id(), wormhole::program::ID);
+
+        let core_bridge = Pubkey::new_unique();
+        config.wormhole_program = core_bridge;
+        assert_eq!(config.wormhole_program_id(), core_bridge);
+    }
+
+    #[test]
+    fn test_resolver_limits() {
+        let mut config = Config::default();
+        assert_eq!(config.resolver_max_groups(), 2);
+        assert_eq!(config.resolver_max_accounts(), 32);
+
+        config.resolver_max_groups = 1;
+        config.resolver_max_accounts = 64;
+        assert_eq!(config.resolver_max_groups(), 1);
+        assert_eq!(config.resolver_max_accounts(), 64);
+    }
+
+    #[test]
+    fn test_quote_signers() {
+        let mut config = Config::default();
+        assert!(!config.checks_quotes());
+        assert!(!config.is_quote_signer(&[0; 20]));
+
+        config.quote_signers[1] = [7; 20];
+        assert!(config.checks_quotes());
+        assert!(config.is_quote_signer(&[7; 20]));
+        assert!(!config.is_quote_signer(&[8; 20]));
+        assert!(!config.is_quote_signer(&[0; 20]));
+    }
+
+    #[test]
+    fn test_reserved_field_round_trip() {
+        let mut config = Config::default();
+        config.set_reserved_field(0, [42]);
+        assert_eq!(config.reserved_field::<1>(0), [42]);
+        assert_eq!(config.reserved[0], 42);
+    }
+
+    #[test]
+    fn test_quota_epoch() {
+        let mut config = Config::default();
+        assert_eq!(config.quota_epoch(1_700_000_000), 0);
+
+        config.quota_epoch_seconds = 3_600;
+        assert_eq!(config.quota_epoch(3_599), 0);
+        assert_eq!(config.quota_epoch(3_600), 1);
+        assert_eq!(config.quota_epoch(-1), 0);
+    }
 }
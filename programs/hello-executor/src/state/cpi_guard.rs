@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+/// Programs allowed to call `send_greeting` through CPI.
+///
+/// Created by the first `set_cpi_callers`; until then, or while
+/// `restricted` is unset, any program may call it. Top-level calls are never
+/// restricted.
+#[account]
+#[derive(Default)]
+pub struct CpiGuard {
+    /// Whether only `callers` may call `send_greeting` through CPI.
+    pub restricted: bool,
+    /// Allowed calling programs. Unused slots are the default key.
+    pub callers: [Pubkey; 4],
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl CpiGuard {
+    /// Capacity of [CpiGuard::callers].
+    pub const MAX_CALLERS: usize = 4;
+
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 1 // restricted
+        + 32 * CpiGuard::MAX_CALLERS // callers
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the CpiGuard PDA.
+    pub const SEED_PREFIX: &'static [u8; 9] = b"cpi_guard";
+
+    /// Whether `program` may call `send_greeting` through CPI.
+    pub fn allows(&self, program: &Pubkey) -> bool {
+        !self.restricted || (*program != Pubkey::default() && self.callers.contains(program))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        let caller = Pubkey::new_unique();
+        let mut guard = CpiGuard::default();
+        guard.callers[0] = caller;
+        assert!(guard.allows(&Pubkey::new_unique()));
+
+        guard.restricted = true;
+        assert!(guard.allows(&caller));
+        assert!(!guard.allows(&Pubkey::new_unique()));
+        assert!(!guard.allows(&Pubkey::default()));
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Emitter whose VAAs `receive_governance` applies.
+///
+/// Created by the first `set_governance`; until then no governance VAA is
+/// accepted.
+#[account]
+#[derive(Default)]
+pub struct Governance {
+    /// Wormhole chain ID of the governance emitter.
+    pub emitter_chain: u16,
+    /// Universal address of the governance emitter.
+    pub emitter_address: [u8; 32],
+    /// Lowest sequence still accepted. Each applied VAA raises it past its
+    /// own, so VAAs cannot be replayed or applied out of order.
+    pub next_sequence: u64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Governance {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // emitter_chain
+        + 32 // emitter_address
+        + 8 // next_sequence
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the Governance PDA.
+    pub const SEED_PREFIX: &'static [u8; 10] = b"governance";
+
+    /// Whether a VAA from `(chain, address)` comes from the governance
+    /// emitter.
+    pub fn verify(&self, chain: u16, address: &[u8; 32]) -> bool {
+        self.emitter_chain == chain && self.emitter_address == *address
+    }
+
+    /// Apply the VAA at `sequence`, refusing it unless it is newer than the
+    /// last one applied.
+    pub fn apply(&mut self, sequence: u64) -> Result<()> {
+        require!(
+            sequence >= self.next_sequence,
+            HelloExecutorError::GovernanceReplay,
+        );
+        self.next_sequence = sequence.saturating_add(1);
+        Ok(())
+    }
+
+    /// Whether the VAA at `sequence` is still the last one applied, i.e. no
+    /// governance VAA has been applied since.
+    pub fn is_last_applied(&self, sequence: u64) -> bool {
+        self.next_sequence == sequence.saturating_add(1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let mut data = Vec::new();
+        Governance::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Governance::MAXIMUM_SIZE);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut governance = Governance::default();
+        assert!(governance.apply(3).is_ok());
+        assert!(governance.is_last_applied(3));
+
+        // Replays and older VAAs are refused.
+        assert!(governance.apply(3).is_err());
+        assert!(governance.apply(2).is_err());
+        assert!(governance.apply(5).is_ok());
+        assert_eq!(governance.next_sequence, 6);
+    }
+
+    #[test]
+    fn test_register_peer_between_register_all_chunks() {
+        let mut governance = Governance::default();
+
+        // The first RegisterAll chunk applies its VAA; later chunks
+        // continue while it is still the last one applied.
+        governance.apply(7).unwrap();
+        assert!(governance.is_last_applied(7));
+
+        // A RegisterPeer VAA applied between chunks supersedes the rest of
+        // the broadcast, so its entries cannot overwrite the newer peer.
+        governance.apply(8).unwrap();
+        assert!(!governance.is_last_applied(7));
+        assert!(governance.is_last_applied(8));
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+/// A greeting delivered to an [Inbox]: the `Received` record's VAA ID.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InboxEntry {
+    /// Wormhole chain ID of the sender.
+    pub chain: u16,
+    /// Sequence number of the Wormhole message.
+    pub sequence: u64,
+}
+
+/// Greetings addressed to a Solana wallet (`@<pubkey> ...`), as delivered
+/// by `receive_greeting_v2`.
+///
+/// Opened by its owner with `open_inbox`; greetings to a wallet without one
+/// are delivered without an inbox entry.
+#[account]
+#[derive(Default)]
+pub struct Inbox {
+    /// Wallet the greetings are addressed to.
+    pub owner: Pubkey,
+    /// Greetings delivered to this inbox.
+    pub delivered_count: u64,
+    /// The most recent greetings, as a ring buffer.
+    pub recent: [InboxEntry; Inbox::RECENT_LEN],
+    /// Next slot to overwrite in `recent`.
+    pub cursor: u8,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Inbox {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // owner
+        + 8 // delivered_count
+        + (2 + 8) * Inbox::RECENT_LEN // recent
+        + 1 // cursor
+        + 1 // bump
+    ;
+
+    /// Number of recent greetings kept.
+    pub const RECENT_LEN: usize = 8;
+
+    /// Seed prefix for deriving Inbox PDAs.
+    pub const SEED_PREFIX: &'static [u8; 5] = b"inbox";
+
+    /// Record a delivered greeting, overwriting the oldest entry when full.
+    pub fn push(&mut self, chain: u16, sequence: u64) {
+        self.recent[self.cursor as usize] = InboxEntry { chain, sequence };
+        self.cursor = ((self.cursor as usize + 1) % Inbox::RECENT_LEN) as u8;
+        self.delivered_count += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut inbox = Inbox::default();
+        for sequence in 0..Inbox::RECENT_LEN as u64 + 2 {
+            inbox.push(2, sequence);
+        }
+        assert_eq!(inbox.delivered_count, Inbox::RECENT_LEN as u64 + 2);
+        assert_eq!(inbox.cursor, 2);
+        // The two oldest entries were overwritten.
+        assert_eq!(
+            inbox.recent[0],
+            InboxEntry {
+                chain: 2,
+                sequence: 8
+            }
+        );
+        assert_eq!(
+            inbox.recent[2],
+            InboxEntry {
+                chain: 2,
+                sequence: 2
+            }
+        );
+
+        let mut data = Vec::new();
+        inbox.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Inbox::MAXIMUM_SIZE);
+    }
+}
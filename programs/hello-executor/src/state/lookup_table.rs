@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// The program's address lookup table, created by `create_lookup_table`.
+///
+/// This PDA is also the table's authority, so only the program can extend
+/// it. The resolver names the table in its result, letting the Executor
+/// reference the fixed accounts of `receive_greeting` by index.
+#[account]
+#[derive(Default)]
+pub struct LookupTable {
+    /// Address of the lookup table.
+    pub address: Pubkey,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl LookupTable {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // address
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the LookupTable PDA.
+    pub const SEED_PREFIX: &'static [u8; 12] = b"lookup_table";
+}
@@ -1,9 +1,53 @@
+pub use admin_action::*;
+pub use backfill_request::*;
 pub use config::*;
+pub use cpi_guard::*;
+pub use governance::*;
+pub use inbox::*;
+pub use lookup_table::*;
 pub use peer::*;
+pub use pending_peer::*;
+pub use publisher::*;
+pub use query_result::*;
+pub use quota_bypass::*;
+pub use rate_limit::*;
 pub use received::*;
+pub use received_index::*;
+pub use registration_cursor::*;
+pub use relay_request::*;
+pub use relay_request_index::*;
+pub use retention_policy::*;
+pub use send_quota::*;
+pub use sent::*;
+pub use spend_cap::*;
+#[cfg(feature = "integration-test")]
+pub use test_vaa::*;
+pub use treasury::*;
 pub use wormhole_emitter::*;
 
+pub mod admin_action;
+pub mod backfill_request;
 pub mod config;
+pub mod cpi_guard;
+pub mod governance;
+pub mod inbox;
+pub mod lookup_table;
 pub mod peer;
+pub mod pending_peer;
+pub mod publisher;
+pub mod query_result;
+pub mod quota_bypass;
+pub mod rate_limit;
 pub mod received;
+pub mod received_index;
+pub mod registration_cursor;
+pub mod relay_request;
+pub mod relay_request_index;
+pub mod retention_policy;
+pub mod send_quota;
+pub mod sent;
+pub mod spend_cap;
+#[cfg(feature = "integration-test")]
+pub mod test_vaa;
+pub mod treasury;
 pub mod wormhole_emitter;
@@ -1,4 +1,50 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// How inbound payloads from a peer are interpreted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PayloadMode {
+    /// UTF-8 greetings (structured Solana or raw EVM format).
+    #[default]
+    Greeting,
+    /// Arbitrary bytes stored verbatim, without UTF-8 validation.
+    Raw,
+}
+
+/// Instruction the resolver has the Executor run for a peer's messages.
+///
+/// An allowlist: only the program's receive instructions can be targeted,
+/// never `receive_greeting_test` or anything outside the program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceiveTarget {
+    /// `receive_and_reply` when the peer has a reply and outbound messaging
+    /// is not paused, `receive_greeting` otherwise.
+    #[default]
+    Auto,
+    /// Always `receive_greeting`, even if the peer has a reply.
+    ReceiveGreeting,
+    /// Always `receive_and_reply`, which skips the reply when there is none.
+    ReceiveAndReply,
+    /// Always `receive_greeting_shim`, verifying the VAA through the verify
+    /// VAA shim instead of posting it. Never replies.
+    ReceiveGreetingShim,
+    /// Always `receive_greeting_v2` (canary). Never replies.
+    ReceiveGreetingV2,
+    /// Always `receive_greeting_v2` in shadow mode, with `receive_greeting`'s
+    /// outcomes. Never replies.
+    ShadowV2,
+}
+
+/// Inclusive range of sequences from a peer that were skipped: later
+/// sequences arrived, these have not (yet).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceGap {
+    /// First missing sequence.
+    pub from: u64,
+    /// Last missing sequence.
+    pub to: u64,
+}
 
 /// Registered peer contract on another chain.
 #[account]
@@ -8,19 +54,334 @@ pub struct Peer {
     pub chain: u16,
     /// Universal address (32 bytes) of the peer contract.
     pub address: [u8; 32],
+    /// Maximum accepted VAA age in seconds. Zero disables the check.
+    pub max_age_seconds: u32,
+    /// Reject expired messages instead of recording them as `Expired`.
+    pub reject_expired: bool,
+    /// How inbound payloads are interpreted.
+    pub payload_mode: PayloadMode,
+    /// Messages received from this peer.
+    pub received_count: u64,
+    /// Sequence of the latest message received from this peer.
+    pub last_received_sequence: u64,
+    /// When stats for this peer were last published.
+    pub stats_published_at: i64,
+    /// Flag payloads identical to a recently received one as `Duplicate`.
+    pub dedup: bool,
+    /// Keccak256 hashes of the most recent payloads, as a ring buffer.
+    pub recent_payload_hashes: [[u8; 32]; 8],
+    /// Next slot to overwrite in `recent_payload_hashes`.
+    pub recent_payload_cursor: u8,
+    /// Largest total gas drop-off a relay to this peer may request, in the
+    /// destination's native base units. Zero disables drop-off.
+    pub max_gas_drop_off: u128,
+    /// Trust in the peer, set by the owner or the moderation oracle.
+    /// High-impact payloads need at least [Peer::HIGH_TRUST_SCORE]; plain
+    /// greetings are accepted at any score.
+    pub trust_score: u8,
+    /// Greeting posted back to the peer by `receive_and_reply` after each
+    /// delivered greeting. Empty disables replies.
+    pub reply_text: String,
+    /// Messages sent to this peer: relays requested to its chain and
+    /// automatic replies.
+    pub sent_count: u64,
+    /// Sequence of the latest message sent to this peer.
+    pub last_sent_sequence: u64,
+    /// Protocol version the peer last reported (see
+    /// [crate::message::PROTOCOL_VERSION]). Zero until it reports one;
+    /// unknown versions are not gated.
+    pub reported_version: u16,
+    /// Sequence of a version probe from the peer that `report_version` has
+    /// not answered yet.
+    pub pending_probe: Option<u64>,
+    /// Highest sequence received from this peer; `None` before the first
+    /// message.
+    pub highest_received_sequence: Option<u64>,
+    /// Known gaps below `highest_received_sequence`, oldest first, at most
+    /// [Peer::MAX_SEQUENCE_GAPS].
+    pub sequence_gaps: Vec<SequenceGap>,
+    /// Instruction the resolver targets for this peer's messages.
+    pub receive_target: ReceiveTarget,
+    /// Running hash of every message recorded from this peer, in arrival
+    /// order: `keccak256(history_hash | keccak256(payload))`, starting
+    /// from zero. Published by `publish_checkpoint`.
+    pub history_hash: [u8; 32],
+    /// When a history checkpoint for this peer was last published.
+    pub checkpoint_published_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
 }
 
 impl Peer {
     pub const MAXIMUM_SIZE: usize = 8 // discriminator
         + 2 // chain
         + 32 // address
+        + 4 // max_age_seconds
+        + 1 // reject_expired
+        + 1 // payload_mode
+        + 8 // received_count
+        + 8 // last_received_sequence
+        + 8 // stats_published_at
+        + 1 // dedup
+        + 32 * Peer::DEDUP_WINDOW // recent_payload_hashes
+        + 1 // recent_payload_cursor
+        + 16 // max_gas_drop_off
+        + 1 // trust_score
+        + 4 + Peer::REPLY_MAX_LENGTH // reply_text
+        + 8 // sent_count
+        + 8 // last_sent_sequence
+        + 2 // reported_version
+        + 1 + 8 // pending_probe
+        + 1 + 8 // highest_received_sequence
+        + 4 + 16 * Peer::MAX_SEQUENCE_GAPS // sequence_gaps
+        + 1 // receive_target
+        + 32 // history_hash
+        + 8 // checkpoint_published_at
+        + 1 // bump
     ;
 
+    /// Number of recent payload hashes kept for duplicate detection.
+    pub const DEDUP_WINDOW: usize = 8;
+
+    /// Minimum `trust_score` for high-impact payloads: raw payloads
+    /// forwarded to other programs, and greetings that mint a receipt token.
+    pub const HIGH_TRUST_SCORE: u8 = 128;
+
+    /// Number of sequence gaps kept per peer. Older gaps are dropped first.
+    pub const MAX_SEQUENCE_GAPS: usize = 8;
+
+    /// Maximum length of [Peer::reply_text] in bytes.
+    pub const REPLY_MAX_LENGTH: usize = 64;
+
     /// Seed prefix for deriving Peer PDAs.
     pub const SEED_PREFIX: &'static [u8; 4] = b"peer";
 
+    /// Whether the peer is known to be too old for a payload starting with
+    /// `payload_id`.
+    pub fn too_old_for(&self, payload_id: u8) -> bool {
+        self.reported_version != 0
+            && self.reported_version < crate::message::min_protocol_version(payload_id)
+    }
+
     /// Verify that the given address matches this peer.
     pub fn verify(&self, address: &[u8; 32]) -> bool {
         *address == self.address
     }
+
+    /// Whether a VAA with the given timestamp is too old to deliver at `now`.
+    pub fn is_expired(&self, vaa_timestamp: u32, now: i64) -> bool {
+        self.max_age_seconds > 0
+            && now.saturating_sub(i64::from(vaa_timestamp)) > i64::from(self.max_age_seconds)
+    }
+
+    /// Whether the peer may send high-impact payloads.
+    pub fn is_high_trust(&self) -> bool {
+        self.trust_score >= Peer::HIGH_TRUST_SCORE
+    }
+
+    /// Whether the resolver targets `receive_and_reply` rather than
+    /// `receive_greeting` for this peer's messages.
+    pub fn targets_reply(&self, outbound_paused: bool) -> bool {
+        match self.receive_target {
+            ReceiveTarget::Auto => !self.reply_text.is_empty() && !outbound_paused,
+            ReceiveTarget::ReceiveGreeting
+            | ReceiveTarget::ReceiveGreetingShim
+            | ReceiveTarget::ReceiveGreetingV2
+            | ReceiveTarget::ShadowV2 => false,
+            ReceiveTarget::ReceiveAndReply => true,
+        }
+    }
+
+    /// Record the arrival of `sequence`. Returns the gap it opens if it
+    /// skips past the highest sequence so far; a late arrival instead
+    /// closes (or splits) the gap it falls in.
+    pub fn record_sequence(&mut self, sequence: u64) -> Option<SequenceGap> {
+        let Some(highest) = self.highest_received_sequence else {
+            // Nothing is known about earlier sequences.
+            self.highest_received_sequence = Some(sequence);
+            return None;
+        };
+
+        if sequence > highest {
+            self.highest_received_sequence = Some(sequence);
+            if sequence == highest + 1 {
+                return None;
+            }
+            let gap = SequenceGap {
+                from: highest + 1,
+                to: sequence - 1,
+            };
+            self.sequence_gaps.push(gap);
+            self.drop_oldest_gaps();
+            return Some(gap);
+        }
+
+        let index = self
+            .sequence_gaps
+            .iter()
+            .position(|gap| gap.from <= sequence && sequence <= gap.to)?;
+        let gap = self.sequence_gaps[index];
+        let below = (gap.from < sequence).then(|| SequenceGap {
+            from: gap.from,
+            to: sequence - 1,
+        });
+        let above = (sequence < gap.to).then(|| SequenceGap {
+            from: sequence + 1,
+            to: gap.to,
+        });
+        self.sequence_gaps
+            .splice(index..=index, below.into_iter().chain(above));
+        self.drop_oldest_gaps();
+        None
+    }
+
+    fn drop_oldest_gaps(&mut self) {
+        let excess = self
+            .sequence_gaps
+            .len()
+            .saturating_sub(Peer::MAX_SEQUENCE_GAPS);
+        self.sequence_gaps.drain(..excess);
+    }
+
+    /// Record `payload_hash` and return whether it was already among the
+    /// recent payloads.
+    pub fn check_duplicate(&mut self, payload_hash: &[u8; 32]) -> bool {
+        if self.recent_payload_hashes.contains(payload_hash) {
+            return true;
+        }
+
+        let cursor = usize::from(self.recent_payload_cursor) % Peer::DEDUP_WINDOW;
+        self.recent_payload_hashes[cursor] = *payload_hash;
+        self.recent_payload_cursor = ((cursor + 1) % Peer::DEDUP_WINDOW) as u8;
+        false
+    }
+
+    /// Extend [Peer::history_hash] with a recorded message.
+    pub fn record_history(&mut self, payload_hash: &[u8; 32]) {
+        self.history_hash = keccak::hashv(&[&self.history_hash, payload_hash]).to_bytes();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_duplicate() {
+        let mut peer = Peer::default();
+
+        assert!(!peer.check_duplicate(&[1; 32]));
+        assert!(peer.check_duplicate(&[1; 32]));
+
+        // Fill the window; the oldest hash falls out.
+        for i in 2..=Peer::DEDUP_WINDOW as u8 + 1 {
+            assert!(!peer.check_duplicate(&[i; 32]));
+        }
+        assert!(!peer.check_duplicate(&[1; 32]));
+        assert!(peer.check_duplicate(&[Peer::DEDUP_WINDOW as u8 + 1; 32]));
+    }
+
+    #[test]
+    fn test_record_history() {
+        let mut peer = Peer::default();
+        peer.record_history(&[1; 32]);
+        assert_eq!(
+            peer.history_hash,
+            keccak::hashv(&[&[0; 32], &[1; 32]]).to_bytes()
+        );
+
+        // Order matters, so a peer can tell a reordered history apart.
+        let mut reordered = Peer::default();
+        peer.record_history(&[2; 32]);
+        reordered.record_history(&[2; 32]);
+        reordered.record_history(&[1; 32]);
+        assert_ne!(peer.history_hash, reordered.history_hash);
+    }
+
+    #[test]
+    fn test_is_high_trust() {
+        let mut peer = Peer::default();
+        assert!(!peer.is_high_trust());
+
+        peer.trust_score = Peer::HIGH_TRUST_SCORE - 1;
+        assert!(!peer.is_high_trust());
+
+        peer.trust_score = Peer::HIGH_TRUST_SCORE;
+        assert!(peer.is_high_trust());
+    }
+
+    #[test]
+    fn test_too_old_for() {
+        // HelloV2 (payload ID 4) needs protocol version 3.
+        let mut peer = Peer::default();
+        assert!(!peer.too_old_for(4));
+
+        peer.reported_version = 2;
+        assert!(peer.too_old_for(4));
+        assert!(!peer.too_old_for(1));
+        // Raw payloads are not gated.
+        assert!(!peer.too_old_for(b'g'));
+
+        peer.reported_version = 3;
+        assert!(!peer.too_old_for(4));
+    }
+
+    #[test]
+    fn test_record_sequence() {
+        let mut peer = Peer::default();
+        assert_eq!(peer.record_sequence(5), None);
+        assert_eq!(peer.record_sequence(6), None);
+        assert_eq!(
+            peer.record_sequence(10),
+            Some(SequenceGap { from: 7, to: 9 })
+        );
+        assert_eq!(peer.highest_received_sequence, Some(10));
+
+        // A late arrival splits its gap; a replayed old sequence is ignored.
+        assert_eq!(peer.record_sequence(8), None);
+        assert_eq!(
+            peer.sequence_gaps,
+            [
+                SequenceGap { from: 7, to: 7 },
+                SequenceGap { from: 9, to: 9 }
+            ]
+        );
+        assert_eq!(peer.record_sequence(7), None);
+        assert_eq!(peer.record_sequence(3), None);
+        assert_eq!(peer.sequence_gaps, [SequenceGap { from: 9, to: 9 }]);
+        assert_eq!(peer.highest_received_sequence, Some(10));
+    }
+
+    #[test]
+    fn test_sequence_gaps_are_bounded() {
+        let mut peer = Peer::default();
+        peer.record_sequence(0);
+        for i in 1..=Peer::MAX_SEQUENCE_GAPS as u64 + 1 {
+            assert!(peer.record_sequence(2 * i).is_some());
+        }
+        assert_eq!(peer.sequence_gaps.len(), Peer::MAX_SEQUENCE_GAPS);
+        // The oldest gap (sequence 1) was dropped.
+        assert_eq!(peer.sequence_gaps[0], SequenceGap { from: 3, to: 3 });
+
+        let mut data = Vec::new();
+        peer.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= Peer::MAXIMUM_SIZE);
+    }
+
+    #[test]
+    fn test_targets_reply() {
+        let mut peer = Peer::default();
+        assert!(!peer.targets_reply(false));
+
+        peer.reply_text = "gm back".to_string();
+        assert!(peer.targets_reply(false));
+        assert!(!peer.targets_reply(true));
+
+        peer.receive_target = ReceiveTarget::ReceiveGreeting;
+        assert!(!peer.targets_reply(false));
+
+        peer.receive_target = ReceiveTarget::ReceiveAndReply;
+        peer.reply_text.clear();
+        assert!(peer.targets_reply(true));
+    }
 }
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// A contract that announced itself with a `PeerHandshake`, waiting for
+/// the owner to confirm it as the peer for its chain or reject it.
+///
+/// Keyed by both chain and address, so a spoofed handshake cannot displace
+/// the genuine one; the owner picks which to confirm.
+#[account]
+#[derive(Default)]
+pub struct PendingPeer {
+    /// Wormhole chain ID of the announcing contract.
+    pub chain: u16,
+    /// Universal address of the announcing contract (the VAA's emitter).
+    pub address: [u8; 32],
+    /// Protocol version the contract announced.
+    pub protocol_version: u16,
+    /// Sequence of the handshake VAA.
+    pub sequence: u64,
+    /// When the handshake was received.
+    pub received_at: i64,
+    /// Who paid for this account. Refunded on confirmation; a rejected
+    /// handshake's rent goes to the owner instead.
+    pub payer: Pubkey,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl PendingPeer {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // chain
+        + 32 // address
+        + 2 // protocol_version
+        + 8 // sequence
+        + 8 // received_at
+        + 32 // payer
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving PendingPeer PDAs.
+    pub const SEED_PREFIX: &'static [u8; 12] = b"pending_peer";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let mut data = Vec::new();
+        PendingPeer::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PendingPeer::MAXIMUM_SIZE);
+    }
+}
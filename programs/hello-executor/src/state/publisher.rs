@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Allowlist entry permitting a non-owner to call `publish_payload`.
+#[account]
+#[derive(Default)]
+pub struct Publisher {
+    /// Account allowed to publish.
+    pub authority: Pubkey,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Publisher {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // authority
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving Publisher PDAs.
+    pub const SEED_PREFIX: &'static [u8; 9] = b"publisher";
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::query::QUERY_RESULT_MAX_LENGTH;
+
+/// Latest verified Wormhole Queries (CCQ) result read from a peer contract.
+///
+/// Unlike [`Received`](crate::state::Received), which records pushed VAAs,
+/// this account is written by `submit_query_response` from a pulled,
+/// guardian-signed `eth_call` response.
+#[account]
+#[derive(Default)]
+pub struct QueryResult {
+    /// Wormhole chain ID of the peer that was queried.
+    pub chain: u16,
+    /// Block number the `eth_call` was executed at.
+    pub block_number: u64,
+    /// Block time of the `eth_call` in microseconds.
+    pub block_time_us: u64,
+    /// Keccak256 hash of the signed query response.
+    pub response_hash: [u8; 32],
+    /// Raw return data of the `eth_call` (e.g. an ABI-encoded greeting count).
+    pub result: Vec<u8>,
+    /// Solana timestamp when the response was submitted.
+    pub submitted_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl QueryResult {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // chain
+        + 8 // block_number
+        + 8 // block_time_us
+        + 32 // response_hash
+        + 4 // Vec length prefix
+        + QUERY_RESULT_MAX_LENGTH // result
+        + 8 // submitted_at
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving QueryResult PDAs.
+    pub const SEED_PREFIX: &'static [u8; 5] = b"query";
+}
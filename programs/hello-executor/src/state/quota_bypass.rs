@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Bypass list entry exempting a wallet from the send quota.
+#[account]
+#[derive(Default)]
+pub struct QuotaBypass {
+    /// Exempt wallet.
+    pub wallet: Pubkey,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl QuotaBypass {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // wallet
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving QuotaBypass PDAs.
+    pub const SEED_PREFIX: &'static [u8; 12] = b"quota_bypass";
+}
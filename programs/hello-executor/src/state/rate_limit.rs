@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Token bucket limiting how fast a payer can send greetings.
+///
+/// Holds up to `config.rate_limit_capacity` tokens and regains one every
+/// `config.rate_limit_refill_seconds`; each greeting takes one. Unlike
+/// [SendQuota](crate::state::SendQuota) it allows bursts without letting a
+/// wallet spend a whole epoch's quota at once. Created by `send_greeting`
+/// with `init_if_needed`, full, so no bump is stored.
+#[account]
+#[derive(Default)]
+pub struct RateLimit {
+    /// Wallet the bucket applies to.
+    pub payer: Pubkey,
+    /// Tokens left in the bucket.
+    pub tokens: u16,
+    /// Unix time up to which refills have been credited.
+    pub refilled_at: i64,
+}
+
+impl RateLimit {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // payer
+        + 2 // tokens
+        + 8 // refilled_at
+    ;
+
+    /// Seed prefix for deriving RateLimit PDAs.
+    pub const SEED_PREFIX: &'static [u8; 10] = b"rate_limit";
+
+    /// Credit the tokens refilled up to `now`, at one per `refill_seconds`,
+    /// without exceeding `capacity`.
+    pub fn refill(&mut self, capacity: u16, refill_seconds: u32, now: i64) {
+        let elapsed = now.saturating_sub(self.refilled_at).max(0) as u64;
+        let refilled = elapsed / u64::from(refill_seconds.max(1));
+        let tokens = u64::from(self.tokens) + refilled;
+        if tokens >= u64::from(capacity) {
+            // Full: time spent full earns nothing.
+            self.tokens = capacity;
+            self.refilled_at = now;
+        } else {
+            // Keep the part of a refill period already elapsed.
+            self.tokens = tokens as u16;
+            self.refilled_at += (refilled * u64::from(refill_seconds)) as i64;
+        }
+    }
+
+    /// Take one token for a send at `now`.
+    pub fn take(&mut self, capacity: u16, refill_seconds: u32, now: i64) -> Result<()> {
+        self.refill(capacity, refill_seconds, now);
+        require!(self.tokens > 0, HelloExecutorError::RateLimited);
+        self.tokens -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_take() {
+        let mut bucket = RateLimit {
+            tokens: 2,
+            refilled_at: 1_000,
+            ..Default::default()
+        };
+        bucket.take(2, 60, 1_000).unwrap();
+        bucket.take(2, 60, 1_010).unwrap();
+        assert_eq!(
+            bucket.take(2, 60, 1_059).unwrap_err(),
+            Error::from(HelloExecutorError::RateLimited)
+        );
+
+        // One token back after a full period; the partial one carries over.
+        bucket.take(2, 60, 1_090).unwrap();
+        assert_eq!(bucket.refilled_at, 1_060);
+        assert!(bucket.take(2, 60, 1_119).is_err());
+        bucket.take(2, 60, 1_120).unwrap();
+    }
+
+    #[test]
+    fn test_refill_caps_at_capacity() {
+        let mut bucket = RateLimit::default();
+        bucket.refill(3, 10, 1_000_000);
+        assert_eq!(bucket.tokens, 3);
+        assert_eq!(bucket.refilled_at, 1_000_000);
+
+        // A lowered capacity applies immediately.
+        bucket.refill(1, 10, 1_000_001);
+        assert_eq!(bucket.tokens, 1);
+    }
+}
@@ -1,6 +1,38 @@
 use anchor_lang::prelude::*;
 
-use crate::message::GREETING_MAX_LENGTH;
+use crate::message::{ContentType, GREETING_MAX_LENGTH};
+
+/// Outcome of processing a received message.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceivedStatus {
+    /// The greeting was delivered.
+    #[default]
+    Delivered,
+    /// The VAA was older than the peer's `max_age_seconds`; recorded but not delivered.
+    Expired,
+    /// Same payload as a recent message from the peer (e.g. a sender-side
+    /// retry); recorded but not delivered.
+    Duplicate,
+    /// Held for the moderation oracle's translation; delivered by
+    /// `submit_translation`.
+    PendingTranslation,
+    /// Rejected by `receive_greeting_v2`, which records the failure (see
+    /// [Received::error_code]) instead of reverting; not delivered.
+    Failed,
+}
+
+/// Moderation verdict attested by the moderation oracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModerationVerdict {
+    /// Not attested yet.
+    #[default]
+    Pending,
+    /// Content approved.
+    Approved,
+    /// Content flagged.
+    Flagged,
+}
 
 /// Received message account for replay protection.
 ///
@@ -13,18 +45,81 @@ pub struct Received {
     pub batch_id: u32,
     /// Keccak256 hash of the verified VAA.
     pub wormhole_message_hash: [u8; 32],
+    /// Moderation verdict. Kept ahead of `message` at a fixed offset, see
+    /// [Received::VERDICT_OFFSET].
+    pub verdict: ModerationVerdict,
     /// The received greeting message.
     pub message: Vec<u8>,
+    /// Processing outcome.
+    pub status: ReceivedStatus,
+    /// Trace ID, from the payload or derived from the VAA ID.
+    pub trace_id: [u8; 16],
+    /// Wallet that sent the greeting, from a `HelloV2` payload. Zero when
+    /// the payload does not carry it (EVM and older Solana senders).
+    pub origin_sender: [u8; 32],
+    /// Unix timestamp at which the greeting was sent, from a `HelloV2`
+    /// payload. Zero when unknown.
+    pub sent_at: u64,
+    /// Receive instruction version that recorded the message: 1 for
+    /// `receive_greeting` and its variants, 2 for `receive_greeting_v2`.
+    pub receiver_version: u8,
+    /// Error code of a `Failed` message; zero otherwise.
+    pub error_code: u32,
+    /// How `message` is to be interpreted: the tag of a `TypedHello`
+    /// payload, `Binary` from a raw-mode peer, otherwise `Text`.
+    pub content_type: ContentType,
+    /// Whether a `DeliveryAck` was sent back to the peer by `acknowledge`.
+    pub acknowledged: bool,
+    /// Unix timestamp at which the message was received, for the
+    /// [RetentionPolicy](crate::state::RetentionPolicy) age limit.
+    pub received_at: i64,
+    /// PDA bump seed.
+    pub bump: u8,
 }
 
 impl Received {
     pub const MAXIMUM_SIZE: usize = 8 // discriminator
         + 4 // batch_id
         + 32 // wormhole_message_hash
+        + 1 // verdict
         + 4 // Vec length prefix
         + GREETING_MAX_LENGTH // message
+        + 1 // status
+        + 16 // trace_id
+        + 32 // origin_sender
+        + 8 // sent_at
+        + 1 // receiver_version
+        + 4 // error_code
+        + 1 // content_type
+        + 1 // acknowledged
+        + 8 // received_at
+        + 1 // bump
+    ;
+
+    /// Byte offset of `verdict` in the account data, for filtering records
+    /// with a `getProgramAccounts` memcmp.
+    pub const VERDICT_OFFSET: usize = 8 // discriminator
+        + 4 // batch_id
+        + 32 // wormhole_message_hash
     ;
 
     /// Seed prefix for deriving Received PDAs.
     pub const SEED_PREFIX: &'static [u8; 8] = b"received";
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verdict_offset() {
+        let received = Received {
+            verdict: ModerationVerdict::Flagged,
+            message: b"gm".to_vec(),
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        received.try_serialize(&mut data).unwrap();
+        assert_eq!(data[Received::VERDICT_OFFSET], ModerationVerdict::Flagged as u8);
+    }
+}
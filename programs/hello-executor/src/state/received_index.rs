@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Secondary index from a VAA hash to its Received record.
+///
+/// Executor logs identify deliveries by VAA hash; this lets a consumer find
+/// the `(chain, sequence)` that keys the Received PDA from the hash alone.
+#[account]
+#[derive(Default)]
+pub struct ReceivedIndex {
+    /// Emitter chain of the received VAA.
+    pub chain: u16,
+    /// Sequence of the received VAA.
+    pub sequence: u64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl ReceivedIndex {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // chain
+        + 8 // sequence
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving ReceivedIndex PDAs.
+    pub const SEED_PREFIX: &'static [u8; 14] = b"received_index";
+}
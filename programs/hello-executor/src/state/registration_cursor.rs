@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+/// Progress through a `RegisterAll` broadcast, which `register_all`
+/// applies a chunk at a time.
+///
+/// Created by the first chunk, which claims the VAA's governance sequence,
+/// and closed by the last one.
+#[account]
+#[derive(Default)]
+pub struct RegistrationCursor {
+    /// Sequence of the broadcast VAA.
+    pub sequence: u64,
+    /// Index of the next peer to apply.
+    pub next_index: u8,
+    /// Number of peers in the broadcast. Zero until the first chunk.
+    pub total: u8,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl RegistrationCursor {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // sequence
+        + 1 // next_index
+        + 1 // total
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving RegistrationCursor PDAs.
+    pub const SEED_PREFIX: &'static [u8; 19] = b"registration_cursor";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let mut data = Vec::new();
+        RegistrationCursor::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), RegistrationCursor::MAXIMUM_SIZE);
+    }
+}
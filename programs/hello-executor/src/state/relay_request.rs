@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+/// Delivery status of a relay request.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RelayStatus {
+    /// Requested from the Executor; no outcome known yet.
+    #[default]
+    Requested,
+    /// Delivered, as reported by the delivery oracle or acknowledged by the
+    /// peer.
+    Delivered,
+    /// The peer acknowledged the message but rejected it.
+    Failed,
+    /// Published by `send_greeting`, with no relay requested yet. Only
+    /// [Sent](super::Sent) records use it. Declared last so existing records
+    /// keep their encoding.
+    Published,
+}
+
+/// Record of an Executor relay request made through `request_relay`.
+///
+/// Keyed by the VAA sequence and destination chain, so relaying one message
+/// to several chains keeps a record for each. The request ID —
+/// `keccak256(request_bytes)` of the ERV1 request sent to the Executor — is
+/// stored and indexed by [RelayRequestIndex](super::RelayRequestIndex), so
+/// off-chain Executor status can be joined with on-chain state without
+/// scraping explorer data.
+#[account]
+#[derive(Default)]
+pub struct RelayRequest {
+    /// Keccak256 hash of the Executor request bytes.
+    pub request_id: [u8; 32],
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// Sequence of the relayed VAA.
+    pub sequence: u64,
+    /// Amount paid to the Executor (lamports).
+    pub exec_amount: u64,
+    /// Executor payee from the signed quote.
+    pub payee: Pubkey,
+    /// Account that paid for the (latest) request.
+    pub payer: Pubkey,
+    /// Timestamp of the latest request.
+    pub requested_at: i64,
+    /// Delivery timestamp reported by the delivery oracle, or of the peer's
+    /// acknowledgment; zero while undelivered.
+    pub delivered_at: i64,
+    /// Trace ID of the relayed greeting.
+    pub trace_id: [u8; 16],
+    /// Delivery status. A request is only repeated while `Requested`.
+    pub status: RelayStatus,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl RelayRequest {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // request_id
+        + 2 // dst_chain
+        + 8 // sequence
+        + 8 // exec_amount
+        + 32 // payee
+        + 32 // payer
+        + 8 // requested_at
+        + 8 // delivered_at
+        + 16 // trace_id
+        + 1 // status
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving RelayRequest PDAs.
+    pub const SEED_PREFIX: &'static [u8; 13] = b"relay_request";
+
+    /// Seconds between request and delivery (or `now`, while undelivered)
+    /// if they exceed `sla_seconds`. `None` when within the SLA or SLA
+    /// tracking is disabled.
+    pub fn sla_breach(&self, sla_seconds: u32, now: i64) -> Option<i64> {
+        if sla_seconds == 0 {
+            return None;
+        }
+        let end = if self.delivered_at != 0 {
+            self.delivered_at
+        } else {
+            now
+        };
+        let elapsed = end.saturating_sub(self.requested_at);
+        (elapsed > i64::from(sla_seconds)).then_some(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_serialized_size_matches_maximum_size() {
+        let mut data = Vec::new();
+        RelayRequest::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), RelayRequest::MAXIMUM_SIZE);
+    }
+
+    #[test]
+    fn test_sla_breach() {
+        let mut request = RelayRequest {
+            requested_at: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(request.sla_breach(0, 5_000), None);
+        assert_eq!(request.sla_breach(600, 1_600), None);
+        assert_eq!(request.sla_breach(600, 1_601), Some(601));
+
+        // Once delivered, the delivery time counts, not the current time.
+        request.delivered_at = 1_300;
+        assert_eq!(request.sla_breach(600, 5_000), None);
+        request.delivered_at = 2_000;
+        assert_eq!(request.sla_breach(600, 5_000), Some(1_000));
+    }
+}
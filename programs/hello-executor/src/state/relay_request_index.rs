@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// Secondary index from an Executor request ID to its RelayRequest record.
+///
+/// Executor status APIs identify requests by ID; this lets a consumer find
+/// the sequence that keys the RelayRequest PDA from the ID. The ID does not
+/// cover the destination, so the index is keyed by the destination chain as
+/// well, like the record, and relaying one message to several chains keeps
+/// an entry for each.
+#[account]
+#[derive(Default)]
+pub struct RelayRequestIndex {
+    /// Sequence of the relayed VAA.
+    pub sequence: u64,
+    /// Wormhole chain ID of the destination chain.
+    pub dst_chain: u16,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl RelayRequestIndex {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // sequence
+        + 2 // dst_chain
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving RelayRequestIndex PDAs.
+    pub const SEED_PREFIX: &'static [u8; 19] = b"relay_request_index";
+}
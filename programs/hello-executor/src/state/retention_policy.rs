@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Received, ReceivedStatus};
+
+/// How long `Received` records are kept before `prune` may close them.
+///
+/// Created by the first `set_retention_policy`; until then nothing is
+/// pruned. A record is eligible once either limit passes it.
+#[account]
+#[derive(Default)]
+pub struct RetentionPolicy {
+    /// Records kept per peer: a record is eligible once this many newer
+    /// sequences have been received from its peer. Zero disables the limit.
+    pub max_records_per_peer: u32,
+    /// Seconds a record is kept after it was received. Zero disables the
+    /// limit.
+    pub max_age_seconds: u32,
+    /// Share of each closed record's rent paid to the pruner, in basis
+    /// points; the rest goes to the owner. At most
+    /// [RetentionPolicy::MAX_TIP_BPS].
+    pub pruner_tip_bps: u16,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl RetentionPolicy {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 4 // max_records_per_peer
+        + 4 // max_age_seconds
+        + 2 // pruner_tip_bps
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the RetentionPolicy PDA.
+    pub const SEED_PREFIX: &'static [u8; 16] = b"retention_policy";
+
+    /// Largest pruner tip: 10% of the reclaimed rent.
+    pub const MAX_TIP_BPS: u16 = 1_000;
+
+    /// Whether `received`, the record of `sequence` from a peer whose highest
+    /// received sequence is `highest`, may be pruned at unix time `now`.
+    ///
+    /// Records held for translation are kept. Records from before
+    /// `received_at` existed count as old enough.
+    pub fn is_eligible(
+        &self,
+        received: &Received,
+        sequence: u64,
+        highest: Option<u64>,
+        now: i64,
+    ) -> bool {
+        if received.status == ReceivedStatus::PendingTranslation {
+            return false;
+        }
+        let over_count = self.max_records_per_peer > 0
+            && highest.is_some_and(|highest| {
+                highest.saturating_sub(sequence) >= u64::from(self.max_records_per_peer)
+            });
+        let too_old = self.max_age_seconds > 0
+            && received
+                .received_at
+                .saturating_add(i64::from(self.max_age_seconds))
+                <= now;
+        over_count || too_old
+    }
+
+    /// Pruner's share of `lamports` reclaimed.
+    pub fn tip(&self, lamports: u64) -> u64 {
+        (u128::from(lamports) * u128::from(self.pruner_tip_bps) / 10_000) as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_eligible() {
+        let received = Received {
+            received_at: 1_000,
+            ..Default::default()
+        };
+        let policy = RetentionPolicy::default();
+        assert!(!policy.is_eligible(&received, 1, Some(100), i64::MAX));
+
+        let policy = RetentionPolicy {
+            max_records_per_peer: 10,
+            ..Default::default()
+        };
+        assert!(policy.is_eligible(&received, 90, Some(100), 0));
+        assert!(!policy.is_eligible(&received, 91, Some(100), 0));
+        assert!(!policy.is_eligible(&received, 90, None, 0));
+
+        let policy = RetentionPolicy {
+            max_age_seconds: 60,
+            ..Default::default()
+        };
+        assert!(!policy.is_eligible(&received, 1, Some(1), 1_059));
+        assert!(policy.is_eligible(&received, 1, Some(1), 1_060));
+
+        let pending = Received {
+            status: ReceivedStatus::PendingTranslation,
+            ..Default::default()
+        };
+        assert!(!policy.is_eligible(&pending, 1, Some(1), i64::MAX));
+    }
+
+    #[test]
+    fn test_tip() {
+        let policy = RetentionPolicy {
+            pruner_tip_bps: RetentionPolicy::MAX_TIP_BPS,
+            ..Default::default()
+        };
+        assert_eq!(policy.tip(2_000_000), 200_000);
+        assert_eq!(RetentionPolicy::default().tip(2_000_000), 0);
+
+        let mut data = Vec::new();
+        policy.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), RetentionPolicy::MAXIMUM_SIZE);
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Greetings a payer has sent in one quota epoch.
+///
+/// Keyed by `(payer, epoch)`, so each epoch starts from a fresh account and
+/// no reset is needed. Past epochs can be closed by the payer with
+/// `close_send_quota`. Created by `send_greeting` with `init_if_needed`, so
+/// no bump is stored.
+#[account]
+#[derive(Default)]
+pub struct SendQuota {
+    /// Wallet the count applies to.
+    pub payer: Pubkey,
+    /// Quota epoch, see [Config::quota_epoch](crate::state::Config::quota_epoch).
+    pub epoch: u64,
+    /// Greetings sent in the epoch.
+    pub sent: u32,
+}
+
+impl SendQuota {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // payer
+        + 8 // epoch
+        + 4 // sent
+    ;
+
+    /// Seed prefix for deriving SendQuota PDAs.
+    pub const SEED_PREFIX: &'static [u8; 10] = b"send_quota";
+
+    /// Count one send against a quota of `limit` per epoch.
+    pub fn record(&mut self, limit: u32) -> Result<()> {
+        require!(self.sent < limit, HelloExecutorError::QuotaExceeded);
+        self.sent += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut quota = SendQuota::default();
+        quota.record(2).unwrap();
+        quota.record(2).unwrap();
+        assert_eq!(quota.sent, 2);
+
+        assert_eq!(quota.record(2).unwrap_err(), Error::from(HelloExecutorError::QuotaExceeded));
+        assert_eq!(quota.sent, 2);
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use super::RelayStatus;
+
+/// Record of a greeting published by `send_greeting`, the outbound
+/// counterpart of [Received](super::Received).
+///
+/// Keyed by the VAA sequence, so clients can enumerate outbound greetings
+/// on-chain instead of scraping events. Other messages the emitter posts
+/// (replies, stats, payloads) have no record.
+#[account]
+#[derive(Default)]
+pub struct Sent {
+    /// Sequence of the Wormhole message.
+    pub sequence: u64,
+    /// Wormhole chain ID of the latest relay the sender requested for the
+    /// message. Zero until the sender calls `request_relay` with this
+    /// record: a Wormhole message has no destination until it is relayed.
+    pub dst_chain: u16,
+    /// Keccak256 hash of the published payload.
+    pub payload_hash: [u8; 32],
+    /// Trace ID of the greeting (see [trace_id](crate::message::trace_id)),
+    /// also embedded in traced payloads and `GreetingSent`.
+    pub trace_id: [u8; 16],
+    /// Unix timestamp of the send.
+    pub timestamp: i64,
+    /// Wallet that sent the greeting.
+    pub sender: Pubkey,
+    /// Program whose top-level instruction called `send_greeting` through
+    /// CPI, or the default key for a top-level call.
+    pub caller_program: Pubkey,
+    /// CPI depth of the call: 0 at top level, 1 when called directly by
+    /// `caller_program`, more through intermediate programs.
+    pub cpi_depth: u8,
+    /// `Published` until the sender requests a relay, then `Requested`
+    /// until `mark_delivered` records the outcome the destination peer
+    /// acknowledged.
+    pub status: RelayStatus,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Sent {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // sequence
+        + 2 // dst_chain
+        + 32 // payload_hash
+        + 16 // trace_id
+        + 8 // timestamp
+        + 32 // sender
+        + 32 // caller_program
+        + 1 // cpi_depth
+        + 1 // status
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving Sent PDAs. Distinct from the `"sent"`
+    /// prefix of Wormhole message accounts.
+    pub const SEED_PREFIX: &'static [u8; 11] = b"sent_record";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_serialized_size_matches_maximum_size() {
+        let mut data = Vec::new();
+        Sent::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Sent::MAXIMUM_SIZE);
+    }
+}
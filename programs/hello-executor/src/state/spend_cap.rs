@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::error::HelloExecutorError;
+
+/// Limit on what the program's outbound paths spend per epoch.
+///
+/// Counts the `exec_amount` of every relay request and the Wormhole fee of
+/// every greeting, whoever pays them, so a keeper running off a treasury
+/// cannot spend more than `max_lamports` in an epoch. Created by the first
+/// `set_spend_cap`; until then nothing is limited.
+#[account]
+#[derive(Default)]
+pub struct SpendCap {
+    /// Lamports spendable per epoch. Zero disables the cap.
+    pub max_lamports: u64,
+    /// Length of an epoch. Zero makes the cap a lifetime one.
+    pub epoch_seconds: u32,
+    /// Epoch `spent_lamports` counts toward.
+    pub epoch: u64,
+    /// Lamports spent in `epoch`.
+    pub spent_lamports: u64,
+    /// Whether `SpendCapWarning` was emitted in `epoch`.
+    pub warned: bool,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl SpendCap {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // max_lamports
+        + 4 // epoch_seconds
+        + 8 // epoch
+        + 8 // spent_lamports
+        + 1 // warned
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the SpendCap PDA.
+    pub const SEED_PREFIX: &'static [u8; 9] = b"spend_cap";
+
+    /// Share of the cap, in percent, whose spending emits `SpendCapWarning`.
+    pub const WARN_PERCENT: u64 = 80;
+
+    /// Epoch containing unix time `now`. Always 0 while `epoch_seconds` is
+    /// unset.
+    pub fn epoch_at(&self, now: i64) -> u64 {
+        match self.epoch_seconds {
+            0 => 0,
+            seconds => now.max(0) as u64 / u64::from(seconds),
+        }
+    }
+
+    /// Count `amount` lamports spent at `now`, starting over in a new epoch.
+    /// Returns whether this spend crossed [Self::WARN_PERCENT] of the cap
+    /// for the first time in the epoch.
+    pub fn record(&mut self, amount: u64, now: i64) -> Result<bool> {
+        if self.max_lamports == 0 {
+            return Ok(false);
+        }
+
+        let epoch = self.epoch_at(now);
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.spent_lamports = 0;
+            self.warned = false;
+        }
+
+        let spent = self.spent_lamports.saturating_add(amount);
+        require!(
+            spent <= self.max_lamports,
+            HelloExecutorError::SpendCapExceeded
+        );
+        self.spent_lamports = spent;
+
+        let threshold = u128::from(self.max_lamports) * u128::from(Self::WARN_PERCENT) / 100;
+        let warn = !self.warned && u128::from(spent) >= threshold;
+        self.warned |= warn;
+        Ok(warn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut cap = SpendCap {
+            max_lamports: 1_000,
+            epoch_seconds: 60,
+            ..Default::default()
+        };
+        assert!(!cap.record(700, 10).unwrap());
+        // Warns once, on crossing 80%.
+        assert!(cap.record(100, 20).unwrap());
+        assert!(!cap.record(100, 30).unwrap());
+        assert_eq!(
+            cap.record(101, 40).unwrap_err(),
+            Error::from(HelloExecutorError::SpendCapExceeded)
+        );
+        assert_eq!(cap.spent_lamports, 900);
+
+        // A new epoch starts over.
+        assert!(!cap.record(101, 60).unwrap());
+        assert_eq!((cap.epoch, cap.spent_lamports), (1, 101));
+        assert!(cap.record(899, 61).unwrap());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut cap = SpendCap::default();
+        assert!(!cap.record(u64::MAX, 0).unwrap());
+        assert_eq!(cap.spent_lamports, 0);
+    }
+}
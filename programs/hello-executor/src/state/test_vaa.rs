@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Message injected by the trusted poster (the owner) for
+/// `receive_greeting_test` (`integration-test` feature).
+///
+/// Owned by this program with its own discriminator, so it can never pass
+/// for a VAA posted by the Wormhole Core Bridge. Consumed by
+/// `receive_greeting_test`, and only within [TestVaa::TTL_SLOTS] of being
+/// posted; `sweep_test_vaas` purges the rest.
+#[account]
+#[derive(Default)]
+pub struct TestVaa {
+    /// Wallet that posted the message, refunded when it is consumed.
+    pub poster: Pubkey,
+    /// Wormhole chain ID the message claims to come from.
+    pub emitter_chain: u16,
+    /// Emitter address the message claims to come from.
+    pub emitter_address: [u8; 32],
+    /// Sequence the message claims.
+    pub sequence: u64,
+    /// Last slot at which the message can be consumed.
+    pub expires_at_slot: u64,
+    /// Message payload.
+    pub payload: Vec<u8>,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl TestVaa {
+    /// Slots a test VAA stays valid after being posted (about a minute).
+    pub const TTL_SLOTS: u64 = 150;
+
+    /// Seed prefix for deriving TestVaa PDAs.
+    pub const SEED_PREFIX: &'static [u8; 8] = b"test_vaa";
+
+    /// Account size for a payload of `payload_len` bytes.
+    pub fn space(payload_len: usize) -> usize {
+        8 // discriminator
+        + 32 // poster
+        + 2 // emitter_chain
+        + 32 // emitter_address
+        + 8 // sequence
+        + 8 // expires_at_slot
+        + 4 + payload_len // payload
+        + 1 // bump
+    }
+
+    /// Whether the message can no longer be consumed at `slot`.
+    pub fn is_expired(&self, slot: u64) -> bool {
+        slot > self.expires_at_slot
+    }
+
+    /// Stand-in for the VAA hash, which keys the message's ReceivedIndex:
+    /// `keccak256(emitter_chain | emitter_address | sequence | payload)`,
+    /// integers big-endian.
+    pub fn message_hash(&self) -> [u8; 32] {
+        keccak::hashv(&[
+            &self.emitter_chain.to_be_bytes(),
+            &self.emitter_address,
+            &self.sequence.to_be_bytes(),
+            &self.payload,
+        ])
+        .to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_space() {
+        let test_vaa = TestVaa {
+            payload: b"gm".to_vec(),
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        test_vaa.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), TestVaa::space(2));
+        assert!(!test_vaa.is_expired(0));
+        assert!(test_vaa.is_expired(1));
+    }
+
+    #[test]
+    fn test_message_hash() {
+        let test_vaa = TestVaa {
+            emitter_chain: 2,
+            sequence: 5,
+            payload: b"gm".to_vec(),
+            ..Default::default()
+        };
+        let next = TestVaa {
+            sequence: 6,
+            ..test_vaa.clone()
+        };
+        assert_ne!(test_vaa.message_hash(), next.message_hash());
+        assert_ne!(test_vaa.message_hash(), [0; 32]);
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Program-owned vault collecting the protocol fee.
+///
+/// Its lamports above rent-exemption are the collected fees, withdrawn by
+/// the owner with `withdraw_treasury`. Created by the first
+/// `set_protocol_fee`; until then `send_greeting` charges no fee.
+#[account]
+#[derive(Default)]
+pub struct Treasury {
+    /// Lamports `send_greeting` charges per greeting. Zero charges nothing.
+    pub protocol_fee_lamports: u64,
+    /// Lamports collected since creation.
+    pub collected_lamports: u64,
+    /// Lamports withdrawn since creation.
+    pub withdrawn_lamports: u64,
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // protocol_fee_lamports
+        + 8 // collected_lamports
+        + 8 // withdrawn_lamports
+        + 1 // bump
+    ;
+
+    /// Seed prefix for deriving the Treasury PDA.
+    pub const SEED_PREFIX: &'static [u8; 8] = b"treasury";
+}
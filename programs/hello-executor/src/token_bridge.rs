@@ -0,0 +1,206 @@
+//! Wormhole Token Bridge transfers carrying a greeting (payload 3).
+//!
+//! A peer sends tokens with a `TransferWithPayload` addressed to this
+//! program's redeemer PDA, attaching a
+//! [`TokenHello`](crate::message::HelloExecutorMessage::TokenHello).
+//! `receive_token_greeting` completes the transfer through the Token Bridge
+//! into a temporary account, forwards the tokens to the greeting's
+//! recipient and emits the greeting.
+//!
+//! Layout of the VAA payload (all integers big-endian):
+//! `payload_id(1) = 3 | amount(32) | token_address(32) | token_chain(2) |
+//!  to(32) | to_chain(2) | from_address(32) | payload`
+//!
+//! The Token Bridge accounts are derived here, so the instruction and the
+//! resolver agree on them.
+
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::token_bridge::program::TokenBridge;
+
+use crate::chains::CHAIN_ID_SOLANA;
+
+/// Token Bridge payload ID of a transfer with payload.
+pub const PAYLOAD_ID_TRANSFER_WITH_PAYLOAD: u8 = 3;
+
+/// Length of the `TransferWithPayload` fields before the payload.
+pub const TRANSFER_WITH_PAYLOAD_HEADER_LEN: usize = 1 + 32 + 32 + 2 + 32 + 2 + 32;
+
+/// Seed for this program's redeemer PDA, which transfers must be addressed
+/// to. It signs the Token Bridge redemption and owns the temporary account.
+pub const SEED_PREFIX_REDEEMER: &[u8] = b"redeemer";
+
+/// Seed prefix for the temporary token account a transfer of `mint` is
+/// redeemed into: `["tmp", mint]`.
+pub const SEED_PREFIX_TMP: &[u8] = b"tmp";
+
+/// Token Bridge `TransferWithPayload` fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferWithPayload<'a> {
+    /// Amount, normalized to at most 8 decimals.
+    pub amount: [u8; 32],
+    /// Universal address of the token on its origin chain.
+    pub token_address: [u8; 32],
+    /// Origin chain of the token.
+    pub token_chain: u16,
+    /// Redeemer the transfer is addressed to.
+    pub to: [u8; 32],
+    /// Destination chain.
+    pub to_chain: u16,
+    /// Sending contract on the source chain.
+    pub from_address: [u8; 32],
+    /// Attached payload.
+    pub payload: &'a [u8],
+}
+
+impl TransferWithPayload<'_> {
+    /// Whether the token originates on Solana, and is held in Token Bridge
+    /// custody rather than minted as a wrapped token.
+    pub fn is_native(&self) -> bool {
+        self.token_chain == CHAIN_ID_SOLANA
+    }
+
+    /// Mint of the transferred token on Solana.
+    pub fn mint(&self) -> Pubkey {
+        if self.is_native() {
+            Pubkey::from(self.token_address)
+        } else {
+            wrapped_mint(self.token_chain, &self.token_address).0
+        }
+    }
+}
+
+/// Parse a Token Bridge `TransferWithPayload`. `None` for any other
+/// payload.
+pub fn parse_transfer_with_payload(payload: &[u8]) -> Option<TransferWithPayload<'_>> {
+    if payload.first() != Some(&PAYLOAD_ID_TRANSFER_WITH_PAYLOAD)
+        || payload.len() < TRANSFER_WITH_PAYLOAD_HEADER_LEN
+    {
+        return None;
+    }
+
+    Some(TransferWithPayload {
+        amount: payload[1..33].try_into().unwrap(),
+        token_address: payload[33..65].try_into().unwrap(),
+        token_chain: u16::from_be_bytes(payload[65..67].try_into().unwrap()),
+        to: payload[67..99].try_into().unwrap(),
+        to_chain: u16::from_be_bytes(payload[99..101].try_into().unwrap()),
+        from_address: payload[101..133].try_into().unwrap(),
+        payload: &payload[TRANSFER_WITH_PAYLOAD_HEADER_LEN..],
+    })
+}
+
+/// Whether `transfer` is addressed to this program on Solana.
+pub fn is_for_this_program(transfer: &TransferWithPayload) -> bool {
+    transfer.to_chain == CHAIN_ID_SOLANA && transfer.to == redeemer().0.to_bytes()
+}
+
+/// This program's redeemer.
+pub fn redeemer() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], &crate::ID)
+}
+
+/// Temporary token account a transfer of `mint` is redeemed into.
+pub fn tmp_token_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_PREFIX_TMP, mint.as_ref()], &crate::ID)
+}
+
+fn token_bridge_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &TokenBridge::id())
+}
+
+/// Token Bridge config.
+pub fn config() -> (Pubkey, u8) {
+    token_bridge_address(&[b"config"])
+}
+
+/// Token Bridge claim for the VAA `(emitter_chain, emitter_address,
+/// sequence)`, which prevents redeeming it twice.
+pub fn claim(emitter_chain: u16, emitter_address: &[u8; 32], sequence: u64) -> (Pubkey, u8) {
+    token_bridge_address(&[
+        emitter_address,
+        &emitter_chain.to_be_bytes(),
+        &sequence.to_be_bytes(),
+    ])
+}
+
+/// Token Bridge registration of the foreign Token Bridge on
+/// `emitter_chain`. Only exists for the real Token Bridge emitter.
+pub fn foreign_endpoint(emitter_chain: u16, emitter_address: &[u8; 32]) -> (Pubkey, u8) {
+    token_bridge_address(&[&emitter_chain.to_be_bytes(), emitter_address])
+}
+
+/// Token Bridge wrapped mint of the foreign token `(token_chain,
+/// token_address)`.
+pub fn wrapped_mint(token_chain: u16, token_address: &[u8; 32]) -> (Pubkey, u8) {
+    token_bridge_address(&[b"wrapped", &token_chain.to_be_bytes(), token_address])
+}
+
+/// Token Bridge accounts specific to the kind of token:
+/// `[custody, custody_signer]` for native tokens,
+/// `[wrapped_meta, mint_authority]` for wrapped ones.
+pub fn asset_accounts(mint: &Pubkey, native: bool) -> [Pubkey; 2] {
+    if native {
+        [
+            token_bridge_address(&[mint.as_ref()]).0,
+            token_bridge_address(&[b"custody_signer"]).0,
+        ]
+    } else {
+        [
+            token_bridge_address(&[b"meta", mint.as_ref()]).0,
+            token_bridge_address(&[b"mint_signer"]).0,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transfer(token_chain: u16, to: [u8; 32], payload: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![PAYLOAD_ID_TRANSFER_WITH_PAYLOAD];
+        encoded.extend_from_slice(&[0; 31]);
+        encoded.push(42); // amount
+        encoded.extend_from_slice(&[7; 32]); // token_address
+        encoded.extend_from_slice(&token_chain.to_be_bytes());
+        encoded.extend_from_slice(&to);
+        encoded.extend_from_slice(&CHAIN_ID_SOLANA.to_be_bytes());
+        encoded.extend_from_slice(&[9; 32]); // from_address
+        encoded.extend_from_slice(payload);
+        encoded
+    }
+
+    #[test]
+    fn test_parse_transfer_with_payload() {
+        let to = redeemer().0.to_bytes();
+        let encoded = transfer(2, to, b"gm");
+        let parsed = parse_transfer_with_payload(&encoded).unwrap();
+        assert_eq!(parsed.amount[31], 42);
+        assert_eq!(parsed.token_address, [7; 32]);
+        assert_eq!(parsed.token_chain, 2);
+        assert_eq!(parsed.from_address, [9; 32]);
+        assert_eq!(parsed.payload, b"gm");
+        assert!(is_for_this_program(&parsed));
+
+        // Wrapped tokens are minted by the Token Bridge; native ones are the
+        // token address itself.
+        assert!(!parsed.is_native());
+        assert_eq!(parsed.mint(), wrapped_mint(2, &[7; 32]).0);
+        let native = transfer(CHAIN_ID_SOLANA, to, b"gm");
+        let native = parse_transfer_with_payload(&native).unwrap();
+        assert_eq!(native.mint(), Pubkey::from([7; 32]));
+        assert_ne!(
+            asset_accounts(&native.mint(), true),
+            asset_accounts(&native.mint(), false)
+        );
+
+        // Other redeemers, other payloads, truncated headers.
+        let other = transfer(2, [1; 32], b"gm");
+        assert!(!is_for_this_program(
+            &parse_transfer_with_payload(&other).unwrap()
+        ));
+        assert!(parse_transfer_with_payload(b"Hello from EVM").is_none());
+        assert!(
+            parse_transfer_with_payload(&encoded[..TRANSFER_WITH_PAYLOAD_HEADER_LEN - 1]).is_none()
+        );
+    }
+}
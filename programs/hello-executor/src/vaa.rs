@@ -0,0 +1,355 @@
+//! Shared Wormhole VAA parser.
+//!
+//! The resolver accepts either a VAA body or a full signed VAA, which
+//! [strip_header] reduces to its body; flows that handle full signed VAAs
+//! (self-relay, governance) also need the header. Both are parsed here with
+//! strict bounds checks, so no caller slices raw VAA bytes itself.
+//!
+//! Layout (all integers big-endian):
+//! `version(1) | guardian_set_index(4) | num_signatures(1) |
+//!  [guardian_index(1) | signature(65)] * num_signatures | body`
+//!
+//! Body: `timestamp(4) | nonce(4) | emitter_chain(2) | emitter_address(32) |
+//!  sequence(8) | consistency_level(1) | payload`
+
+use anchor_lang::prelude::*;
+
+use crate::{error::VaaError, query::GuardianSetData};
+
+/// The only VAA version in use.
+pub const VAA_VERSION: u8 = 1;
+
+/// Length of the header before the signatures.
+pub const VAA_HEADER_LENGTH: usize = 1 + 4 + 1;
+
+/// Length of one guardian signature entry: index + `r | s | v`.
+pub const GUARDIAN_SIGNATURE_LENGTH: usize = 1 + 65;
+
+/// Length of the body before the payload.
+pub const VAA_BODY_HEADER_LENGTH: usize = 4 + 4 + 2 + 32 + 8 + 1;
+
+/// Largest guardian set Wormhole supports.
+pub const MAX_GUARDIANS: usize = 19;
+
+/// One guardian's signature over the body digest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuardianSignature {
+    /// Index of the guardian in the guardian set.
+    pub guardian_index: u8,
+    /// Secp256k1 signature, `r | s | v`.
+    pub signature: [u8; 65],
+}
+
+/// Signed VAA header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VaaHeader {
+    /// VAA version, always [VAA_VERSION].
+    pub version: u8,
+    /// Guardian set that signed the VAA.
+    pub guardian_set_index: u32,
+    /// Signatures, by strictly increasing guardian index.
+    pub signatures: Vec<GuardianSignature>,
+}
+
+impl VaaHeader {
+    /// Sanity-check the header against the Core Bridge guardian set that
+    /// signed it: the same index, still active at `now`, every guardian
+    /// index within the set and at least a quorum of signatures.
+    ///
+    /// Signatures themselves are not recovered; the Core Bridge (or the
+    /// verify VAA shim) does that when the VAA is posted.
+    pub fn check_guardian_set(&self, guardian_set: &GuardianSetData, now: i64) -> Result<()> {
+        require!(
+            guardian_set.index == self.guardian_set_index,
+            VaaError::GuardianSetMismatch,
+        );
+        require!(guardian_set.is_active(now), VaaError::GuardianSetExpired);
+        require!(
+            self.signatures
+                .iter()
+                .all(|signature| usize::from(signature.guardian_index) < guardian_set.keys.len()),
+            VaaError::GuardianIndexOutOfBounds,
+        );
+        require!(
+            self.signatures.len() >= guardian_set.quorum(),
+            VaaError::NoQuorum,
+        );
+
+        Ok(())
+    }
+}
+
+/// VAA body: the part guardians sign (via its double keccak digest).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VaaBody<'a> {
+    /// Message timestamp.
+    pub timestamp: u32,
+    /// Batch ID (nonce).
+    pub nonce: u32,
+    /// Wormhole chain ID of the emitter.
+    pub emitter_chain: u16,
+    /// Universal address of the emitter.
+    pub emitter_address: [u8; 32],
+    /// Sequence number of the message.
+    pub sequence: u64,
+    /// Consistency level the message was published with.
+    pub consistency_level: u8,
+    /// Message payload.
+    pub payload: &'a [u8],
+}
+
+/// Parse a VAA body.
+pub fn parse_body(body: &[u8]) -> Result<VaaBody<'_>> {
+    require!(body.len() >= VAA_BODY_HEADER_LENGTH, VaaError::BodyTooShort);
+
+    Ok(VaaBody {
+        timestamp: u32::from_be_bytes(body[0..4].try_into().unwrap()),
+        nonce: u32::from_be_bytes(body[4..8].try_into().unwrap()),
+        emitter_chain: u16::from_be_bytes(body[8..10].try_into().unwrap()),
+        emitter_address: body[10..42].try_into().unwrap(),
+        sequence: u64::from_be_bytes(body[42..50].try_into().unwrap()),
+        consistency_level: body[50],
+        payload: &body[VAA_BODY_HEADER_LENGTH..],
+    })
+}
+
+/// Parse the header of a signed VAA, returning it and the body bytes.
+///
+/// With `guardian_set_size`, every guardian index must be within the set.
+pub fn parse_header(vaa: &[u8], guardian_set_size: Option<usize>) -> Result<(VaaHeader, &[u8])> {
+    require!(vaa.len() >= VAA_HEADER_LENGTH, VaaError::HeaderTooShort);
+
+    let version = vaa[0];
+    require!(version == VAA_VERSION, VaaError::UnsupportedVersion);
+
+    let guardian_set_index = u32::from_be_bytes(vaa[1..5].try_into().unwrap());
+    let num_signatures = usize::from(vaa[5]);
+    require!(num_signatures <= MAX_GUARDIANS, VaaError::TooManySignatures);
+
+    let body_start = VAA_HEADER_LENGTH + num_signatures * GUARDIAN_SIGNATURE_LENGTH;
+    require!(vaa.len() >= body_start, VaaError::SignaturesTruncated);
+
+    let max_index = guardian_set_size.unwrap_or(MAX_GUARDIANS);
+    let mut signatures: Vec<GuardianSignature> = Vec::with_capacity(num_signatures);
+    for entry in vaa[VAA_HEADER_LENGTH..body_start].chunks_exact(GUARDIAN_SIGNATURE_LENGTH) {
+        let guardian_index = entry[0];
+        require!(
+            usize::from(guardian_index) < max_index,
+            VaaError::GuardianIndexOutOfBounds,
+        );
+        // Strictly increasing, so no guardian is counted twice.
+        if let Some(last) = signatures.last() {
+            require!(
+                guardian_index > last.guardian_index,
+                VaaError::GuardianIndexOutOfOrder,
+            );
+        }
+        signatures.push(GuardianSignature {
+            guardian_index,
+            signature: entry[1..].try_into().unwrap(),
+        });
+    }
+
+    Ok((
+        VaaHeader {
+            version,
+            guardian_set_index,
+            signatures,
+        },
+        &vaa[body_start..],
+    ))
+}
+
+/// Reduce a full signed VAA to its body; a bare body is returned as is.
+///
+/// A signed VAA starts with [VAA_VERSION] (1). A body starts with its
+/// timestamp, whose first byte is 1 only for messages from 1970, so the
+/// first byte tells the two apart. A malformed signed VAA is an error, not
+/// a body.
+pub fn strip_header(vaa: &[u8]) -> Result<(Option<VaaHeader>, &[u8])> {
+    if vaa.first() != Some(&VAA_VERSION) {
+        return Ok((None, vaa));
+    }
+    let (header, body) = parse_header(vaa, None)?;
+    Ok((Some(header), body))
+}
+
+/// Parse a full signed VAA.
+pub fn parse_vaa(
+    vaa: &[u8],
+    guardian_set_size: Option<usize>,
+) -> Result<(VaaHeader, VaaBody<'_>)> {
+    let (header, body) = parse_header(vaa, guardian_set_size)?;
+    Ok((header, parse_body(body)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::error::Error;
+
+    fn body(payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&1_700_000_000u32.to_be_bytes());
+        out.extend_from_slice(&0u32.to_be_bytes());
+        out.extend_from_slice(&10002u16.to_be_bytes());
+        out.extend_from_slice(&[7; 32]);
+        out.extend_from_slice(&42u64.to_be_bytes());
+        out.push(1);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn signed(indices: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut out = vec![VAA_VERSION];
+        out.extend_from_slice(&4u32.to_be_bytes());
+        out.push(indices.len() as u8);
+        for &index in indices {
+            out.push(index);
+            out.extend_from_slice(&[index; 65]);
+        }
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn test_parse_body() {
+        let bytes = body(b"gm");
+        let parsed = parse_body(&bytes).unwrap();
+        assert_eq!(parsed.emitter_chain, 10002);
+        assert_eq!(parsed.emitter_address, [7; 32]);
+        assert_eq!(parsed.sequence, 42);
+        assert_eq!(parsed.consistency_level, 1);
+        assert_eq!(parsed.payload, b"gm");
+
+        assert!(parse_body(&bytes[..VAA_BODY_HEADER_LENGTH - 1]).is_err());
+    }
+
+    #[test]
+    fn test_parse_vaa() {
+        let body = body(b"gm");
+        let (header, parsed) = parse_vaa(&signed(&[0, 3, 18], &body), Some(19)).unwrap();
+        assert_eq!(header.guardian_set_index, 4);
+        assert_eq!(header.signatures.len(), 3);
+        assert_eq!(header.signatures[1].guardian_index, 3);
+        assert_eq!(header.signatures[1].signature, [3; 65]);
+        assert_eq!(parsed.payload, b"gm");
+
+        // No signatures is structurally valid; quorum is the verifier's job.
+        assert!(parse_vaa(&signed(&[], &body), None).is_ok());
+    }
+
+    #[test]
+    fn test_strip_header() {
+        let body = body(b"gm");
+        let (header, stripped) = strip_header(&body).unwrap();
+        assert!(header.is_none());
+        assert_eq!(stripped, &body[..]);
+
+        let vaa = signed(&[0, 1], &body);
+        let (header, stripped) = strip_header(&vaa).unwrap();
+        assert_eq!(header.unwrap().signatures.len(), 2);
+        assert_eq!(stripped, &body[..]);
+
+        assert_eq!(
+            strip_header(&vaa[..VAA_HEADER_LENGTH + 1]).unwrap_err(),
+            Error::from(VaaError::SignaturesTruncated)
+        );
+    }
+
+    #[test]
+    fn test_check_guardian_set() {
+        let guardian_set = GuardianSetData {
+            index: 4,
+            keys: vec![[0; 20]; 4],
+            creation_time: 0,
+            expiration_time: 0,
+        };
+        let header = |indices: &[u8]| {
+            parse_header(&signed(indices, &body(b"gm")), None)
+                .unwrap()
+                .0
+        };
+
+        assert!(header(&[0, 1, 3])
+            .check_guardian_set(&guardian_set, 0)
+            .is_ok());
+        assert_eq!(
+            header(&[0, 1])
+                .check_guardian_set(&guardian_set, 0)
+                .unwrap_err(),
+            Error::from(VaaError::NoQuorum)
+        );
+        assert_eq!(
+            header(&[0, 1, 4])
+                .check_guardian_set(&guardian_set, 0)
+                .unwrap_err(),
+            Error::from(VaaError::GuardianIndexOutOfBounds)
+        );
+
+        let previous = GuardianSetData {
+            index: 3,
+            ..guardian_set.clone()
+        };
+        assert_eq!(
+            header(&[0, 1, 3])
+                .check_guardian_set(&previous, 0)
+                .unwrap_err(),
+            Error::from(VaaError::GuardianSetMismatch)
+        );
+
+        let expiring = GuardianSetData {
+            expiration_time: 100,
+            ..guardian_set
+        };
+        assert!(header(&[0, 1, 3]).check_guardian_set(&expiring, 99).is_ok());
+        assert_eq!(
+            header(&[0, 1, 3])
+                .check_guardian_set(&expiring, 100)
+                .unwrap_err(),
+            Error::from(VaaError::GuardianSetExpired)
+        );
+    }
+
+    fn err(vaa: &[u8], guardian_set_size: Option<usize>) -> Error {
+        parse_header(vaa, guardian_set_size).unwrap_err()
+    }
+
+    #[test]
+    fn test_parse_header_bounds() {
+        let body = body(b"gm");
+
+        assert_eq!(err(&[VAA_VERSION, 0, 0], None), Error::from(VaaError::HeaderTooShort));
+
+        let mut wrong_version = signed(&[0], &body);
+        wrong_version[0] = 2;
+        assert_eq!(err(&wrong_version, None), Error::from(VaaError::UnsupportedVersion));
+
+        let mut too_many = signed(&[], &body);
+        too_many[5] = MAX_GUARDIANS as u8 + 1;
+        assert_eq!(err(&too_many, None), Error::from(VaaError::TooManySignatures));
+
+        let truncated = signed(&[0, 1], &[]);
+        assert_eq!(
+            err(&truncated[..truncated.len() - 1], None),
+            Error::from(VaaError::SignaturesTruncated)
+        );
+
+        // Repeated or descending indices would count a guardian twice.
+        for indices in [[1, 1], [2, 1]] {
+            assert_eq!(
+                err(&signed(&indices, &body), None),
+                Error::from(VaaError::GuardianIndexOutOfOrder)
+            );
+        }
+
+        assert_eq!(
+            err(&signed(&[0, 13], &body), Some(13)),
+            Error::from(VaaError::GuardianIndexOutOfBounds)
+        );
+        assert_eq!(
+            err(&signed(&[19], &body), None),
+            Error::from(VaaError::GuardianIndexOutOfBounds)
+        );
+    }
+}
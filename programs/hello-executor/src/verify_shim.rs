@@ -0,0 +1,86 @@
+//! CPI into the Wormhole verify VAA shim.
+//!
+//! The shim checks guardian signatures over a VAA digest without a
+//! `PostedVAA` account: the signatures are posted to a temporary
+//! `GuardianSignatures` account (which the poster closes afterwards), and
+//! `verify_hash` succeeds only if they are a quorum of the guardian set over
+//! the digest. `receive_greeting_shim` calls it with the digest of the VAA
+//! body it was given, so no rent-bearing account is left per message.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use std::str::FromStr;
+
+use crate::{error::HelloExecutorError, query::SEED_PREFIX_GUARDIAN_SET};
+
+#[derive(Clone)]
+pub struct VerifyVaaShim;
+
+impl Id for VerifyVaaShim {
+    fn id() -> Pubkey {
+        Pubkey::from_str("EFaNWErqAtVWufdNb7yofSHHfWFos843DFpu4JBw24at")
+            .expect("invalid verify VAA shim program id")
+    }
+}
+
+/// Anchor discriminator of the shim's `verify_hash` instruction:
+/// `sha256("global:verify_hash")[..8]`. Checked by `tools/discriminators`.
+pub const VERIFY_HASH_DISCRIMINATOR: [u8; 8] = [22, 152, 160, 69, 241, 148, 14, 124];
+
+/// Offset of `guardian_set_index_be` in a `GuardianSignatures` account:
+/// after the discriminator and `refund_recipient`.
+const GUARDIAN_SET_INDEX_OFFSET: usize = 8 + 32;
+
+/// Digest guardians sign: `keccak256(keccak256(body))`.
+pub fn vaa_digest(vaa_body: &[u8]) -> [u8; 32] {
+    let hash = solana_program::keccak::hash(vaa_body);
+    solana_program::keccak::hash(&hash.0).0
+}
+
+/// Guardian set a `GuardianSignatures` account was posted for.
+pub fn guardian_set_index(guardian_signatures: &AccountInfo) -> Result<u32> {
+    let data = guardian_signatures.try_borrow_data()?;
+    let index = data
+        .get(GUARDIAN_SET_INDEX_OFFSET..GUARDIAN_SET_INDEX_OFFSET + 4)
+        .ok_or(HelloExecutorError::InvalidGuardianSignatures)?;
+    Ok(u32::from_be_bytes(index.try_into().unwrap()))
+}
+
+/// Have the shim verify `guardian_signatures` over `digest`. Fails unless
+/// `guardian_set` is the Core Bridge guardian set they were posted for.
+pub fn verify_hash<'info>(
+    verify_vaa_shim: &AccountInfo<'info>,
+    wormhole_program: &Pubkey,
+    guardian_set: &AccountInfo<'info>,
+    guardian_signatures: &AccountInfo<'info>,
+    digest: [u8; 32],
+) -> Result<()> {
+    let index = guardian_set_index(guardian_signatures)?;
+    let (expected, guardian_set_bump) = Pubkey::find_program_address(
+        &[SEED_PREFIX_GUARDIAN_SET, &index.to_be_bytes()],
+        wormhole_program,
+    );
+    require_keys_eq!(
+        *guardian_set.key,
+        expected,
+        HelloExecutorError::InvalidGuardianSet
+    );
+
+    let mut data = Vec::with_capacity(8 + 1 + 32);
+    data.extend_from_slice(&VERIFY_HASH_DISCRIMINATOR);
+    data.push(guardian_set_bump);
+    data.extend_from_slice(&digest);
+
+    let ix = Instruction {
+        program_id: *verify_vaa_shim.key,
+        accounts: vec![
+            AccountMeta::new_readonly(*guardian_set.key, false),
+            AccountMeta::new_readonly(*guardian_signatures.key, false),
+        ],
+        data,
+    };
+
+    invoke(&ix, &[guardian_set.clone(), guardian_signatures.clone()])?;
+    Ok(())
+}
@@ -0,0 +1,221 @@
+//! Raw Wormhole Core Bridge `post_message` CPI.
+//!
+//! Shared by every outbound path that publishes under the program emitter
+//! (`send_greeting`, `publish_payload`, ...), so the fee payment, sequence
+//! tracking and message-PDA conventions live in one place.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+
+use wormhole_raw::{BridgeData, PostedMessageHeader, SequenceTracker};
+
+use crate::{
+    error::HelloExecutorError, funds, instructions::SEED_PREFIX_SENT, message::PAYLOAD_ID_ALIVE,
+    pda, state::WormholeEmitter,
+};
+
+/// Accounts required by the Core Bridge `post_message` instruction.
+pub struct PostMessage<'info> {
+    /// Payer for the Wormhole fee and message account.
+    pub payer: AccountInfo<'info>,
+    /// Wormhole Core Bridge program.
+    pub wormhole_program: AccountInfo<'info>,
+    /// Wormhole bridge data (config).
+    pub wormhole_bridge: AccountInfo<'info>,
+    /// Wormhole fee collector.
+    pub wormhole_fee_collector: AccountInfo<'info>,
+    /// Program's emitter account.
+    pub wormhole_emitter: AccountInfo<'info>,
+    /// Emitter's sequence account.
+    pub wormhole_sequence: AccountInfo<'info>,
+    /// Message account, a `[SEED_PREFIX_SENT, sequence + 1]` PDA.
+    pub wormhole_message: AccountInfo<'info>,
+    /// System program.
+    pub system_program: AccountInfo<'info>,
+    /// Clock sysvar.
+    pub clock: AccountInfo<'info>,
+    /// Rent sysvar.
+    pub rent: AccountInfo<'info>,
+}
+
+/// Read the emitter's Wormhole sequence tracker.
+///
+/// The tracker stores the sequence number Wormhole will assign to the NEXT
+/// post_message call — i.e. the actual VAA sequence for the next message.
+/// A tracker not created yet (the emitter has never posted) reads as zero.
+pub fn next_sequence(wormhole_sequence: &AccountInfo) -> Result<u64> {
+    let seq_data = wormhole_sequence.try_borrow_data()?;
+    if seq_data.is_empty() {
+        return Ok(0);
+    }
+    let tracker = SequenceTracker::read(&seq_data).map_err(|e| {
+        msg!("{}", e);
+        HelloExecutorError::InvalidWormholeSequence
+    })?;
+    Ok(tracker.sequence)
+}
+
+/// Payload length of the message the program posted as VAA `sequence`.
+///
+/// Fails unless `wormhole_message` is that message's account.
+pub fn posted_payload_len(wormhole_message: &AccountInfo, sequence: u64) -> Result<usize> {
+    Ok(read_posted_header(wormhole_message, sequence)?.payload_len as usize)
+}
+
+/// First payload byte (the payload ID, for Hello Executor messages) of the
+/// message the program posted as VAA `sequence`. `None` for an empty
+/// payload.
+///
+/// Fails unless `wormhole_message` is that message's account.
+pub fn posted_payload_id(wormhole_message: &AccountInfo, sequence: u64) -> Result<Option<u8>> {
+    let header = read_posted_header(wormhole_message, sequence)?;
+    if header.payload_len == 0 {
+        return Ok(None);
+    }
+    let data = wormhole_message.try_borrow_data()?;
+    Ok(data.get(PostedMessageHeader::LEN).copied())
+}
+
+fn read_posted_header(wormhole_message: &AccountInfo, sequence: u64) -> Result<PostedMessageHeader> {
+    let (expected, _) = pda::sent(sequence + 1);
+    require_keys_eq!(
+        *wormhole_message.key,
+        expected,
+        HelloExecutorError::InvalidWormholeMessage
+    );
+
+    let header = PostedMessageHeader::read(&wormhole_message.try_borrow_data()?).map_err(|e| {
+        msg!("{}", e);
+        HelloExecutorError::InvalidWormholeMessage
+    })?;
+    Ok(header)
+}
+
+/// Wormhole fee for posting a message, read from the bridge account.
+pub fn message_fee(wormhole_bridge: &AccountInfo) -> Result<u64> {
+    let bridge = BridgeData::read(&wormhole_bridge.try_borrow_data()?).map_err(|e| {
+        msg!("{}", e);
+        HelloExecutorError::InvalidWormholeConfig
+    })?;
+    Ok(bridge.fee)
+}
+
+/// Pay the Wormhole fee and publish `payload` under the program emitter.
+///
+/// `wormhole_program_id` is the Core Bridge from
+/// [Config::wormhole_program_id](crate::state::Config::wormhole_program_id).
+/// The CPI signs for the emitter, so any other program is refused.
+///
+/// Returns the VAA sequence Wormhole assigned to the message.
+pub fn post_message(
+    accounts: PostMessage,
+    wormhole_program_id: Pubkey,
+    emitter_bump: u8,
+    batch_id: u32,
+    finality: u8,
+    payer_buffer: u64,
+    payload: &[u8],
+) -> Result<u64> {
+    require_keys_eq!(
+        *accounts.wormhole_program.key,
+        wormhole_program_id,
+        HelloExecutorError::InvalidWormholeProgram
+    );
+
+    // Sequence 0 is reserved for the Alive message posted by `announce`;
+    // request_relay and the EVM side never treat it as a greeting.
+    let vaa_sequence = next_sequence(&accounts.wormhole_sequence)?;
+    require!(
+        vaa_sequence > 0 || payload.first() == Some(&PAYLOAD_ID_ALIVE),
+        HelloExecutorError::NotAnnounced,
+    );
+
+    let fee = message_fee(&accounts.wormhole_bridge)?;
+
+    // The payer funds the fee and the message account, and must stay
+    // rent-exempt afterwards.
+    let message_rent = Rent::get()?.minimum_balance(PostedMessageHeader::account_size(payload.len()));
+    funds::require_payer_can_afford(&accounts.payer, fee.saturating_add(message_rent), payer_buffer)?;
+
+    // Pay Wormhole fee if required
+    if fee > 0 {
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, accounts.wormhole_fee_collector.key, fee),
+            &[
+                accounts.payer.clone(),
+                accounts.wormhole_fee_collector.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
+    // The tracker holds the actual VAA sequence for THIS message.
+    //
+    // The message PDA uses `vaa_sequence + 1`, so the Alive message (sequence
+    // 0) lands at `wormhole::INITIAL_SEQUENCE`, where announce expects it.
+    let pda_sequence = vaa_sequence + 1;
+
+    // Build wormhole post_message instruction (raw CPI)
+    // Wormhole uses 1-byte instruction discriminator: PostMessage = 1
+    // Data format: [discriminator(1) | nonce(4) | payload_len(4) | payload | consistency(1)]
+    let mut ix_data = Vec::with_capacity(1 + 4 + 4 + payload.len() + 1);
+    ix_data.push(0x01); // PostMessage instruction
+    ix_data.extend_from_slice(&batch_id.to_le_bytes()); // nonce (u32)
+    ix_data.extend_from_slice(&(payload.len() as u32).to_le_bytes()); // payload length
+    ix_data.extend_from_slice(payload);
+    ix_data.push(finality); // consistency level
+
+    let ix = Instruction {
+        program_id: *accounts.wormhole_program.key,
+        accounts: vec![
+            AccountMeta::new(*accounts.wormhole_bridge.key, false),
+            AccountMeta::new(*accounts.wormhole_message.key, true),
+            AccountMeta::new_readonly(*accounts.wormhole_emitter.key, true),
+            AccountMeta::new(*accounts.wormhole_sequence.key, false),
+            AccountMeta::new(*accounts.payer.key, true),
+            AccountMeta::new(*accounts.wormhole_fee_collector.key, false),
+            AccountMeta::new_readonly(*accounts.clock.key, false),
+            AccountMeta::new_readonly(*accounts.rent.key, false),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+        ],
+        data: ix_data,
+    };
+
+    // Derive the message PDA bump using pda_sequence.
+    // Unlike config/peer/emitter, this PDA is keyed by a fresh sequence on every
+    // send and has no program-owned account to store its bump in, so it is found
+    // once here and the derived address is checked against the passed account.
+    let pda_seq_buf = pda_sequence.to_le_bytes();
+    let (message_pda, message_bump) = pda::sent(pda_sequence);
+    require_keys_eq!(
+        *accounts.wormhole_message.key,
+        message_pda,
+        HelloExecutorError::InvalidWormholeMessage
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.wormhole_bridge,
+            accounts.wormhole_message,
+            accounts.wormhole_emitter,
+            accounts.wormhole_sequence,
+            accounts.payer,
+            accounts.wormhole_fee_collector,
+            accounts.clock,
+            accounts.rent,
+            accounts.system_program,
+            accounts.wormhole_program,
+        ],
+        &[
+            &[SEED_PREFIX_SENT, &pda_seq_buf, &[message_bump]],
+            &[WormholeEmitter::SEED_PREFIX, &[emitter_bump]],
+        ],
+    )?;
+
+    Ok(vaa_sequence)
+}
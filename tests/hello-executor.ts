@@ -105,6 +105,7 @@ describe('hello-executor', () => {
         console.log('Instructions:', instructionNames);
 
         expect(instructionNames).to.include('initialize');
+        expect(instructionNames).to.include('announce');
         expect(instructionNames).to.include('registerPeer');
         expect(instructionNames).to.include('sendGreeting');
         expect(instructionNames).to.include('receiveGreeting');
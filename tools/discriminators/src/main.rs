@@ -0,0 +1,331 @@
+//! Derive and check the discriminators Hello Executor hard-codes for raw
+//! (non-Anchor-generated) interfaces.
+//!
+//! ```text
+//! cargo run -p discriminators                     # check every known constant
+//! cargo run -p discriminators -- global:foo_bar   # derive one from its preimage
+//! cargo run -p discriminators -- --output json    # either, machine-readable
+//! ```
+//!
+//! A discriminator is the first 8 bytes of `sha256("<namespace>:<name>")`;
+//! Anchor instructions use the `global` namespace. Add new raw interfaces to
+//! [`KNOWN`] so their constants are checked by `cargo test`.
+//!
+//! `--output` (`-o`) takes `table` (the default), `json` or `yaml`. JSON
+//! and YAML list one record per discriminator with the fields `constant`
+//! (null when derived from an argument), `preimage`, `derived`, `expected`
+//! (null likewise) and `ok`; discriminators are lowercase hex. These names
+//! are stable for scripts. Exit codes:
+//!
+//! | Code | Meaning |
+//! |---|---|
+//! | 0 | every check passed, or discriminators derived |
+//! | 1 | a known constant does not match its preimage |
+//! | 2 | invalid arguments |
+
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use hello_executor::{executor_cpi, fallback, verify_shim};
+use solana_program::hash::hash;
+
+/// A hard-coded discriminator and the preimage it is derived from.
+struct Known {
+    /// Where the constant lives.
+    constant: &'static str,
+    /// `<namespace>:<name>` the discriminator is derived from.
+    preimage: &'static str,
+    /// The constant's value.
+    value: [u8; 8],
+}
+
+const KNOWN: &[Known] = &[
+    Known {
+        constant: "fallback::EXECUTE_VAA_V1_DISCRIMINATOR",
+        preimage: "executor-account-resolver:execute-vaa-v1",
+        value: fallback::EXECUTE_VAA_V1_DISCRIMINATOR,
+    },
+    Known {
+        constant: "executor_cpi::REQUEST_FOR_EXECUTION_DISCRIMINATOR",
+        preimage: "global:request_for_execution",
+        value: executor_cpi::REQUEST_FOR_EXECUTION_DISCRIMINATOR,
+    },
+    Known {
+        constant: "verify_shim::VERIFY_HASH_DISCRIMINATOR",
+        preimage: "global:verify_hash",
+        value: verify_shim::VERIFY_HASH_DISCRIMINATOR,
+    },
+];
+
+/// First 8 bytes of `sha256(preimage)`.
+fn derive(preimage: &str) -> [u8; 8] {
+    hash(preimage.as_bytes()).to_bytes()[..8].try_into().unwrap()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Exit code: every check passed, or discriminators derived.
+const EXIT_OK: u8 = 0;
+
+/// Exit code: a known constant does not match its preimage.
+const EXIT_MISMATCH: u8 = 1;
+
+/// Exit code: invalid arguments.
+const EXIT_USAGE: u8 = 2;
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Output {
+    Table,
+    Json,
+    Yaml,
+}
+
+impl Output {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "table" => Some(Output::Table),
+            "json" => Some(Output::Json),
+            "yaml" => Some(Output::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed command line.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    output: Output,
+    preimages: Vec<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut output = Output::Table;
+    let mut preimages = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let name = match arg.as_str() {
+            "-o" | "--output" => args.next().ok_or("--output needs a format")?,
+            _ => match arg.strip_prefix("--output=") {
+                Some(name) => name.to_string(),
+                None => {
+                    preimages.push(arg);
+                    continue;
+                }
+            },
+        };
+        output = Output::parse(&name)
+            .ok_or_else(|| format!("unknown output format {name:?}; use table, json or yaml"))?;
+    }
+    Ok(Args { output, preimages })
+}
+
+/// One derived (and, for known constants, checked) discriminator.
+struct Record {
+    constant: Option<&'static str>,
+    preimage: String,
+    derived: [u8; 8],
+    expected: Option<[u8; 8]>,
+}
+
+impl Record {
+    fn ok(&self) -> bool {
+        self.expected.map_or(true, |expected| expected == self.derived)
+    }
+
+    /// `(field, JSON value)` pairs, in output order.
+    fn fields(&self) -> [(&'static str, String); 5] {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+        [
+            ("constant", optional(self.constant.map(json_string))),
+            ("preimage", json_string(&self.preimage)),
+            ("derived", json_string(&to_hex(&self.derived))),
+            ("expected", optional(self.expected.map(|v| json_string(&to_hex(&v))))),
+            ("ok", self.ok().to_string()),
+        ]
+    }
+}
+
+/// Quote `value` as a JSON string, which is also a YAML scalar.
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn render(records: &[Record], output: Output) -> String {
+    let mut out = String::new();
+    match output {
+        Output::Table => {
+            for record in records {
+                let derived = to_hex(&record.derived);
+                let line = match (record.constant, record.expected) {
+                    (None, _) => format!("{}: {} {:?}", record.preimage, derived, record.derived),
+                    (Some(constant), _) if record.ok() => {
+                        format!("ok        {constant} = {derived} ({})", record.preimage)
+                    }
+                    (Some(constant), expected) => format!(
+                        "MISMATCH  {constant} = {}, derived {derived} from {}",
+                        to_hex(&expected.unwrap_or_default()),
+                        record.preimage
+                    ),
+                };
+                writeln!(out, "{line}").unwrap();
+            }
+        }
+        Output::Json => {
+            let objects: Vec<String> = records
+                .iter()
+                .map(|record| {
+                    let fields: Vec<String> = record
+                        .fields()
+                        .iter()
+                        .map(|(name, value)| format!("    \"{name}\": {value}"))
+                        .collect();
+                    format!("  {{\n{}\n  }}", fields.join(",\n"))
+                })
+                .collect();
+            if objects.is_empty() {
+                out.push_str("[]\n");
+            } else {
+                writeln!(out, "[\n{}\n]", objects.join(",\n")).unwrap();
+            }
+        }
+        Output::Yaml => {
+            if records.is_empty() {
+                out.push_str("[]\n");
+            }
+            for record in records {
+                for (i, (name, value)) in record.fields().iter().enumerate() {
+                    let indent = if i == 0 { "- " } else { "  " };
+                    writeln!(out, "{indent}{name}: {value}").unwrap();
+                }
+            }
+        }
+    }
+    out
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}");
+            eprintln!("usage: discriminators [--output table|json|yaml] [<namespace>:<name>...]");
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let records: Vec<Record> = if args.preimages.is_empty() {
+        KNOWN
+            .iter()
+            .map(|known| Record {
+                constant: Some(known.constant),
+                preimage: known.preimage.to_string(),
+                derived: derive(known.preimage),
+                expected: Some(known.value),
+            })
+            .collect()
+    } else {
+        args.preimages
+            .into_iter()
+            .map(|preimage| Record {
+                constant: None,
+                derived: derive(&preimage),
+                preimage,
+                expected: None,
+            })
+            .collect()
+    };
+
+    print!("{}", render(&records, args.output));
+
+    if records.iter().all(Record::ok) {
+        ExitCode::from(EXIT_OK)
+    } else {
+        ExitCode::from(EXIT_MISMATCH)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_known_discriminators() {
+        for known in KNOWN {
+            assert_eq!(derive(known.preimage), known.value, "{}", known.constant);
+        }
+    }
+
+    #[test]
+    fn test_execute_vaa_v1_hex() {
+        assert_eq!(to_hex(&fallback::EXECUTE_VAA_V1_DISCRIMINATOR), "94b8a9decf089a7f");
+    }
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args(&["global:foo"]).unwrap(),
+            Args {
+                output: Output::Table,
+                preimages: vec!["global:foo".to_string()],
+            }
+        );
+        assert_eq!(args(&["-o", "json"]).unwrap().output, Output::Json);
+        assert_eq!(args(&["--output=yaml", "a:b"]).unwrap().output, Output::Yaml);
+        assert!(args(&["--output", "xml"]).is_err());
+        assert!(args(&["--output"]).is_err());
+    }
+
+    #[test]
+    fn test_render_json_and_yaml() {
+        let records = [Record {
+            constant: None,
+            preimage: "global:\"quoted\"".to_string(),
+            derived: [0xab; 8],
+            expected: None,
+        }];
+        assert_eq!(
+            render(&records, Output::Json),
+            "[\n  {\n    \"constant\": null,\n    \"preimage\": \"global:\\\"quoted\\\"\",\n    \
+             \"derived\": \"abababababababab\",\n    \"expected\": null,\n    \"ok\": true\n  }\n]\n"
+        );
+        assert_eq!(
+            render(&records, Output::Yaml),
+            "- constant: null\n  preimage: \"global:\\\"quoted\\\"\"\n  \
+             derived: \"abababababababab\"\n  expected: null\n  ok: true\n"
+        );
+        assert_eq!(render(&[], Output::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_render_mismatch() {
+        let record = Record {
+            constant: Some("X"),
+            preimage: "global:x".to_string(),
+            derived: [1; 8],
+            expected: Some([2; 8]),
+        };
+        assert!(!record.ok());
+        assert_eq!(
+            render(&[record], Output::Table),
+            "MISMATCH  X = 0202020202020202, derived 0101010101010101 from global:x\n"
+        );
+    }
+}
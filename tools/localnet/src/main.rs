@@ -0,0 +1,482 @@
+//! Start a local validator with Hello Executor deployed and configured.
+//!
+//! ```text
+//! anchor build
+//! cargo run -p localnet                                   # Sepolia peer, devnet Executor
+//! cargo run -p localnet -- --peer 10002:0x15cE...8841     # choose the peers
+//! cargo run -p localnet -- --executor-so mock_executor.so # load a mock Executor
+//! ```
+//!
+//! Runs `solana-test-validator` (from the Solana CLI, on `PATH`) with:
+//!
+//! - `target/deploy/hello_executor.so` at the program ID,
+//! - the devnet Wormhole Core Bridge and its bridge and fee collector
+//!   accounts, cloned from devnet,
+//! - the Executor, cloned from devnet or loaded from `--executor-so`,
+//! - `Config`, the Wormhole emitter and one `Peer` per `--peer`, written
+//!   into genesis as `initialize` and `register_peer` would have left them.
+//!
+//! The wallet (`--wallet`, default `~/.config/solana/id.json`) owns the
+//! config. Each `--peer` is `<chain>:<address>`, the address as 20-byte
+//! (EVM, left-padded) or 32-byte hex; without any, the Sepolia
+//! HelloWormhole contract from `e2e/config.ts` is registered. The program
+//! must be built for Solana devnet (the default feature), which the cloned
+//! Core Bridge belongs to.
+//!
+//! The ledger and generated account files go under `--dir` (default
+//! `target/localnet`), and the ledger is reset on every run. Once the
+//! validator is up, run `announce` (as `e2e/initialize.ts` does) before
+//! sending greetings. Exit codes:
+//!
+//! | Code | Meaning |
+//! |---|---|
+//! | 0 | the validator exited cleanly |
+//! | 1 | the validator failed to start or exited with an error |
+//! | 2 | invalid arguments, or a missing program or wallet file |
+
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anchor_lang::{AccountSerialize, Id};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hello_executor::{
+    executor_cpi::ExecutorProgram,
+    finality::DEFAULT_FINALITY,
+    pda,
+    state::{Config, Peer, WormholeAddresses, WormholeEmitter},
+};
+use solana_program::{pubkey::Pubkey, rent::Rent};
+
+/// Wormhole Core Bridge on Solana devnet.
+const CORE_BRIDGE: &str = "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5";
+
+/// Wormhole chain ID of Solana.
+const CHAIN_ID_SOLANA: u16 = 1;
+
+/// Peer registered when no `--peer` is given: HelloWormhole on Sepolia.
+const DEFAULT_PEER: &str = "10002:0x15cEeB2C089D19E754463e1697d69Ad11A6e8841";
+
+/// JSON-RPC port of `solana-test-validator`.
+const RPC_PORT: u16 = 8899;
+
+/// How long to wait for the validator to report healthy.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Exit code: the validator exited cleanly.
+const EXIT_OK: u8 = 0;
+
+/// Exit code: the validator failed to start or exited with an error.
+const EXIT_VALIDATOR: u8 = 1;
+
+/// Exit code: invalid arguments, or a missing program or wallet file.
+const EXIT_USAGE: u8 = 2;
+
+/// Parsed command line.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    program_so: PathBuf,
+    executor_so: Option<PathBuf>,
+    wallet: PathBuf,
+    dir: PathBuf,
+    peers: Vec<(u16, [u8; 32])>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut program_so = PathBuf::from("target/deploy/hello_executor.so");
+    let mut executor_so = None;
+    let mut wallet = None;
+    let mut dir = PathBuf::from("target/localnet");
+    let mut peers = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), value.to_string()),
+            None => {
+                let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+                (arg, value)
+            }
+        };
+        match name.as_str() {
+            "--program-so" => program_so = value.into(),
+            "--executor-so" => executor_so = Some(value.into()),
+            "--wallet" => wallet = Some(value.into()),
+            "--dir" => dir = value.into(),
+            "--peer" => peers.push(parse_peer(&value)?),
+            _ => return Err(format!("unknown option {name:?}")),
+        }
+    }
+    if peers.is_empty() {
+        peers.push(parse_peer(DEFAULT_PEER)?);
+    }
+    let wallet = match wallet {
+        Some(wallet) => wallet,
+        None => {
+            let home = std::env::var("HOME").map_err(|_| "HOME is not set; pass --wallet")?;
+            Path::new(&home).join(".config/solana/id.json")
+        }
+    };
+    Ok(Args {
+        program_so,
+        executor_so,
+        wallet,
+        dir,
+        peers,
+    })
+}
+
+/// Parse `<chain>:<hex address>`, left-padding 20-byte addresses.
+fn parse_peer(value: &str) -> Result<(u16, [u8; 32]), String> {
+    let invalid = || format!("invalid peer {value:?}; use <chain>:<hex address>");
+    let (chain, address) = value.split_once(':').ok_or_else(invalid)?;
+    let chain = chain.parse().map_err(|_| invalid())?;
+    let bytes = from_hex(address.trim_start_matches("0x")).ok_or_else(invalid)?;
+    let mut padded = [0; 32];
+    match bytes.len() {
+        20 | 32 => padded[32 - bytes.len()..].copy_from_slice(&bytes),
+        _ => return Err(invalid()),
+    }
+    Ok((chain, padded))
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Public key of a keypair file: the last 32 of its 64 bytes.
+fn keypair_pubkey(json: &str) -> Result<Pubkey, String> {
+    let bytes: Vec<u8> = json
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|byte| byte.trim().parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| "keypair file is not a JSON byte array")?;
+    if bytes.len() != 64 {
+        return Err(format!("keypair file has {} bytes, not 64", bytes.len()));
+    }
+    Ok(Pubkey::try_from(&bytes[32..]).unwrap())
+}
+
+/// An account written into genesis, owned by the program.
+struct GenesisAccount {
+    name: String,
+    address: Pubkey,
+    data: Vec<u8>,
+}
+
+impl GenesisAccount {
+    /// Serialize `account`, zero-padded to the `space` it is created with.
+    fn new(
+        name: impl Into<String>,
+        address: Pubkey,
+        account: &impl AccountSerialize,
+        space: usize,
+    ) -> Self {
+        let mut data = Vec::with_capacity(space);
+        account.try_serialize(&mut data).unwrap();
+        data.resize(space.max(data.len()), 0);
+        Self {
+            name: name.into(),
+            address,
+            data,
+        }
+    }
+
+    /// The account in `solana account --output json` form, which
+    /// `solana-test-validator --account` reads.
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{{").unwrap();
+        writeln!(out, "  \"pubkey\": \"{}\",", self.address).unwrap();
+        writeln!(out, "  \"account\": {{").unwrap();
+        writeln!(
+            out,
+            "    \"lamports\": {},",
+            Rent::default().minimum_balance(self.data.len())
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    \"data\": [\"{}\", \"base64\"],",
+            STANDARD.encode(&self.data)
+        )
+        .unwrap();
+        writeln!(out, "    \"owner\": \"{}\",", hello_executor::ID).unwrap();
+        writeln!(out, "    \"executable\": false,").unwrap();
+        writeln!(out, "    \"rentEpoch\": 0,").unwrap();
+        writeln!(out, "    \"space\": {}", self.data.len()).unwrap();
+        writeln!(out, "  }}").unwrap();
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+/// Core Bridge accounts `initialize` stores in the config.
+fn wormhole_addresses() -> WormholeAddresses {
+    let core_bridge = Pubkey::from_str(CORE_BRIDGE).unwrap();
+    let emitter = pda::emitter().0;
+    WormholeAddresses {
+        bridge: Pubkey::find_program_address(&[b"Bridge"], &core_bridge).0,
+        fee_collector: Pubkey::find_program_address(&[b"fee_collector"], &core_bridge).0,
+        sequence: Pubkey::find_program_address(&[b"Sequence", emitter.as_ref()], &core_bridge).0,
+    }
+}
+
+/// Config, emitter and peer accounts as `initialize` and `register_peer`
+/// would leave them.
+fn genesis_accounts(owner: Pubkey, peers: &[(u16, [u8; 32])]) -> Vec<GenesisAccount> {
+    let (emitter, emitter_bump) = pda::emitter();
+    let (config, config_bump) = pda::config();
+
+    let config_account = Config {
+        owner,
+        chain_id: CHAIN_ID_SOLANA,
+        wormhole: wormhole_addresses(),
+        finality: DEFAULT_FINALITY,
+        bump: config_bump,
+        enforce_min_exec_amount: true,
+        executor_program: ExecutorProgram::id(),
+        wormhole_program: Pubkey::from_str(CORE_BRIDGE).unwrap(),
+        ..Default::default()
+    };
+
+    let mut accounts = vec![
+        GenesisAccount::new("config", config, &config_account, Config::MAXIMUM_SIZE),
+        GenesisAccount::new(
+            "emitter",
+            emitter,
+            &WormholeEmitter { bump: emitter_bump },
+            WormholeEmitter::MAXIMUM_SIZE,
+        ),
+    ];
+    for &(chain, address) in peers {
+        let (peer, bump) = pda::peer(&config_account.salt, chain);
+        let peer_account = Peer {
+            chain,
+            address,
+            bump,
+            ..Default::default()
+        };
+        accounts.push(GenesisAccount::new(
+            format!("peer-{chain}"),
+            peer,
+            &peer_account,
+            Peer::MAXIMUM_SIZE,
+        ));
+    }
+    accounts
+}
+
+/// Whether the validator's RPC answers `/health` with `ok`.
+fn healthy() -> bool {
+    let Ok(mut stream) = TcpStream::connect(("127.0.0.1", RPC_PORT)) else {
+        return false;
+    };
+    let request = "GET /health HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n";
+    let mut response = String::new();
+    stream.write_all(request.as_bytes()).is_ok()
+        && stream.read_to_string(&mut response).is_ok()
+        && response.trim_end().ends_with("ok")
+}
+
+fn run(args: Args) -> Result<ExitCode, (u8, String)> {
+    let usage = |error: String| (EXIT_USAGE, error);
+    let validator = |error: String| (EXIT_VALIDATOR, error);
+
+    if !args.program_so.is_file() {
+        return Err(usage(format!(
+            "{} not found; run `anchor build` first",
+            args.program_so.display()
+        )));
+    }
+    let wallet = std::fs::read_to_string(&args.wallet)
+        .map_err(|e| usage(format!("cannot read {}: {e}", args.wallet.display())))?;
+    let owner = keypair_pubkey(&wallet).map_err(usage)?;
+
+    let accounts_dir = args.dir.join("accounts");
+    std::fs::create_dir_all(&accounts_dir)
+        .map_err(|e| usage(format!("cannot create {}: {e}", accounts_dir.display())))?;
+
+    let mut command = Command::new("solana-test-validator");
+    command
+        .arg("--reset")
+        .arg("--ledger")
+        .arg(args.dir.join("ledger"))
+        .args(["--url", "devnet"])
+        .arg("--bpf-program")
+        .arg(hello_executor::ID.to_string())
+        .arg(&args.program_so)
+        .args(["--clone-upgradeable-program", CORE_BRIDGE]);
+
+    let wormhole = wormhole_addresses();
+    command
+        .args(["--clone", &wormhole.bridge.to_string()])
+        .args(["--clone", &wormhole.fee_collector.to_string()]);
+
+    match &args.executor_so {
+        Some(executor_so) => command
+            .arg("--bpf-program")
+            .arg(ExecutorProgram::id().to_string())
+            .arg(executor_so),
+        None => command.args([
+            "--clone-upgradeable-program",
+            &ExecutorProgram::id().to_string(),
+        ]),
+    };
+
+    let accounts = genesis_accounts(owner, &args.peers);
+    for account in &accounts {
+        let path = accounts_dir.join(format!("{}.json", account.name));
+        std::fs::write(&path, account.to_json())
+            .map_err(|e| usage(format!("cannot write {}: {e}", path.display())))?;
+        command
+            .arg("--account")
+            .arg(account.address.to_string())
+            .arg(path);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| validator(format!("cannot run solana-test-validator: {e}")))?;
+
+    let started = Instant::now();
+    while !healthy() {
+        if let Some(status) = child.try_wait().map_err(|e| validator(e.to_string()))? {
+            return Err(validator(format!("solana-test-validator exited: {status}")));
+        }
+        if started.elapsed() > STARTUP_TIMEOUT {
+            let _ = child.kill();
+            return Err(validator(
+                "solana-test-validator did not become healthy".into(),
+            ));
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+
+    println!("Localnet ready at http://127.0.0.1:{RPC_PORT}");
+    println!("  program   {}", hello_executor::ID);
+    println!("  owner     {owner}");
+    println!("  executor  {}", ExecutorProgram::id());
+    for account in &accounts {
+        println!("  {:<9} {}", account.name, account.address);
+    }
+    println!("Run `announce` before sending greetings. Ctrl-C stops the validator.");
+
+    let status = child.wait().map_err(|e| validator(e.to_string()))?;
+    if status.success() {
+        Ok(ExitCode::from(EXIT_OK))
+    } else {
+        Err(validator(format!("solana-test-validator exited: {status}")))
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}");
+            eprintln!(
+                "usage: localnet [--peer <chain>:<address>...] [--executor-so <path>] \
+                 [--program-so <path>] [--wallet <path>] [--dir <path>]"
+            );
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    match run(args) {
+        Ok(code) => code,
+        Err((code, error)) => {
+            eprintln!("error: {error}");
+            ExitCode::from(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::AccountDeserialize;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(
+            ["--wallet", "id.json"]
+                .iter()
+                .chain(args)
+                .map(|arg| arg.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&[]).unwrap();
+        assert_eq!(parsed.peers, vec![parse_peer(DEFAULT_PEER).unwrap()]);
+        assert_eq!(parsed.executor_so, None);
+        assert_eq!(parsed.wallet, PathBuf::from("id.json"));
+
+        let parsed = args(&["--peer", "2:0x01", "--executor-so=mock.so"]);
+        assert!(parsed.is_err());
+        let parsed = args(&[
+            "--peer",
+            &format!("2:{}", "ab".repeat(32)),
+            "--executor-so=mock.so",
+        ])
+        .unwrap();
+        assert_eq!(parsed.peers, vec![(2, [0xab; 32])]);
+        assert_eq!(parsed.executor_so, Some(PathBuf::from("mock.so")));
+
+        assert!(args(&["--dir"]).is_err());
+        assert!(args(&["--bogus", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_peer_pads_evm_address() {
+        let (chain, address) = parse_peer(DEFAULT_PEER).unwrap();
+        assert_eq!(chain, 10002);
+        assert_eq!(address[..12], [0; 12]);
+        assert_eq!(address[12..14], [0x15, 0xce]);
+        assert!(parse_peer("10002:0x15ce").is_err());
+        assert!(parse_peer("sepolia:0x15ce").is_err());
+    }
+
+    #[test]
+    fn test_keypair_pubkey() {
+        let bytes: Vec<String> = (0..64u8).map(|byte| byte.to_string()).collect();
+        let pubkey = keypair_pubkey(&format!("[{}]\n", bytes.join(","))).unwrap();
+        assert_eq!(pubkey.to_bytes()[0], 32);
+        assert!(keypair_pubkey("[1,2,3]").is_err());
+    }
+
+    #[test]
+    fn test_genesis_accounts() {
+        let owner = Pubkey::new_unique();
+        let accounts = genesis_accounts(owner, &[(10002, [7; 32])]);
+        assert_eq!(accounts.len(), 3);
+
+        let config = Config::try_deserialize(&mut accounts[0].data.as_slice()).unwrap();
+        assert_eq!(accounts[0].address, pda::config().0);
+        assert_eq!(accounts[0].data.len(), Config::MAXIMUM_SIZE);
+        assert_eq!(config.owner, owner);
+        assert_eq!(config.executor_program_id(), ExecutorProgram::id());
+        assert_eq!(config.wormhole_program_id().to_string(), CORE_BRIDGE);
+
+        let peer = Peer::try_deserialize(&mut accounts[2].data.as_slice()).unwrap();
+        assert_eq!(accounts[2].address, pda::peer(&config.salt, 10002).0);
+        assert_eq!(accounts[2].data.len(), Peer::MAXIMUM_SIZE);
+        assert_eq!(peer.address, [7; 32]);
+        assert!(accounts[2]
+            .to_json()
+            .contains(&hello_executor::ID.to_string()));
+    }
+}